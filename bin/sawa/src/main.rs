@@ -1,9 +1,10 @@
 use sawa_api::create_app;
 use sawa_application::Service;
 use sawa_infra_memory::{
-    InMemoryMediaRepository, InMemoryProductInstanceRepository, InMemoryProductRepository,
-    InMemoryProductVariantRepository, InMemoryPurchaseOrderRepository, InMemoryTagRepository,
-    InMemoryUserRepository, InMemoryUserTransactionRepository,
+    InMemoryCartRepository, InMemoryMediaRepository, InMemoryProductInstanceRepository,
+    InMemoryProductRepository, InMemoryProductVariantRepository, InMemoryPurchaseOrderRepository,
+    InMemoryStatisticsRepository, InMemoryStorageLocationRepository, InMemoryTagRepository,
+    InMemoryUserRepository, InMemoryUserTransactionRepository, InMemoryWishlistRepository,
 };
 use std::net::SocketAddr;
 use tokio::net::TcpListener;
@@ -26,6 +27,16 @@ async fn main() {
     let user = InMemoryUserRepository::new();
     let tag = InMemoryTagRepository::new();
     let media = InMemoryMediaRepository::new();
+    let cart = InMemoryCartRepository::new();
+    let storage_location = InMemoryStorageLocationRepository::new();
+    let wishlist = InMemoryWishlistRepository::new();
+    let statistics = InMemoryStatisticsRepository::new(
+        order.clone(),
+        product_instance.clone(),
+        product_variant.clone(),
+        product.clone(),
+        tag.clone(),
+    );
 
     // Create service
     let service = Service {
@@ -37,6 +48,10 @@ async fn main() {
         user,
         tag,
         media,
+        cart,
+        storage_location,
+        wishlist,
+        statistics,
     };
 
     // Create the app
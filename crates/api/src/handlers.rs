@@ -1,7 +1,15 @@
 pub mod auth;
+pub mod cart;
+pub mod collection;
 pub mod health;
 pub mod media;
 pub mod product;
 pub mod product_instance;
 pub mod purchase_order;
+pub mod settlement;
+pub mod statistics;
+pub mod storage_location;
 pub mod tag;
+pub mod trade_match;
+pub mod transaction;
+pub mod wishlist;
@@ -4,9 +4,13 @@ use axum_login::AuthUser;
 use sawa_core::{
     models::{
         misc::{MediaId, NonEmptyString},
-        user::{Email, UserId, Username},
+        user::{Email, User, UserId, Username},
+    },
+    services::{
+        ClaimPlaceholderError, ClaimPlaceholderRequest, CreatePlaceholderRequest, CreateUserError,
+        CreateUserRequest, GetUserRequest, ListPlaceholdersRequest, ResetCalendarTokenRequest,
+        RevokeCalendarTokenRequest, UserService,
     },
-    services::{CreateUserRequest, GetUserRequest, UserService},
 };
 
 use crate::{
@@ -71,6 +75,10 @@ pub struct RegisterBody {
     pub email: Email,
     pub username: Username,
     pub password: NonEmptyString,
+
+    /// Invite code of a placeholder to claim with the new account
+    #[serde(default)]
+    pub invite_code: Option<String>,
 }
 
 #[derive(serde::Serialize, schemars::JsonSchema)]
@@ -94,13 +102,15 @@ where
         password: body.password.clone(),
         email: body.email,
         avatar: None,
+        invite_code: body.invite_code,
     };
 
-    let user = state
-        .service
-        .create_user(req)
-        .await
-        .map_err(|_| AppError::InternalServerError)?;
+    let user = state.service.create_user(req).await.map_err(|e| match e {
+        CreateUserError::InvalidInviteCode => {
+            AppError::BadRequest("Invalid invite code".to_string())
+        }
+        _ => AppError::InternalServerError,
+    })?;
 
     auth_session
         .login(&user.clone().into())
@@ -154,3 +164,217 @@ pub fn create_me_docs(op: TransformOperation) -> TransformOperation {
         .tag("User")
         .response::<200, Json<PublicUser>>()
 }
+
+#[derive(serde::Serialize, schemars::JsonSchema)]
+pub struct PlaceholderUser {
+    pub id: UserId,
+    pub display_name: String,
+    pub invite_code: String,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl PlaceholderUser {
+    fn from_user(user: User) -> Option<Self> {
+        let placeholder = user.placeholder?;
+        Some(Self {
+            id: user.id,
+            display_name: placeholder.display_name,
+            invite_code: placeholder.invite_code,
+            created_at: user.created_at,
+        })
+    }
+}
+
+#[derive(serde::Deserialize, schemars::JsonSchema)]
+pub struct CreatePlaceholderBody {
+    /// The name shown for the placeholder
+    pub display_name: NonEmptyString,
+}
+
+/// Create a placeholder for a participant without an account.
+pub async fn create_placeholder<S>(
+    auth_session: AuthSession<S>,
+    State(state): State<AppState<S>>,
+    Json(body): Json<CreatePlaceholderBody>,
+) -> Result<impl IntoApiResponse, AppError>
+where
+    S: Clone + UserService,
+{
+    let user = auth_session.user.as_ref().ok_or(AppError::Unauthorized)?;
+
+    let placeholder = state
+        .service
+        .create_placeholder(CreatePlaceholderRequest {
+            user_id: user.id(),
+            display_name: body.display_name,
+        })
+        .await
+        .map_err(|_| AppError::InternalServerError)?;
+
+    Ok((
+        StatusCode::CREATED,
+        Json(PlaceholderUser::from_user(placeholder).ok_or(AppError::InternalServerError)?),
+    ))
+}
+
+pub fn create_create_placeholder_docs(op: TransformOperation) -> TransformOperation {
+    op.summary("Create placeholder")
+        .description(
+            "Create a placeholder user for a participant without an account. \
+            The placeholder can own line items and instances, \
+            and can be claimed later with its invite code.",
+        )
+        .tag("User")
+        .response::<201, Json<PlaceholderUser>>()
+}
+
+/// List the placeholders managed by the current user.
+pub async fn list_placeholders<S>(
+    auth_session: AuthSession<S>,
+    State(state): State<AppState<S>>,
+) -> Result<impl IntoApiResponse, AppError>
+where
+    S: Clone + UserService,
+{
+    let user = auth_session.user.as_ref().ok_or(AppError::Unauthorized)?;
+
+    let placeholders = state
+        .service
+        .list_placeholders(ListPlaceholdersRequest { user_id: user.id() })
+        .await
+        .map_err(|_| AppError::InternalServerError)?;
+
+    Ok(Json(
+        placeholders
+            .into_iter()
+            .filter_map(PlaceholderUser::from_user)
+            .collect::<Vec<_>>(),
+    ))
+}
+
+pub fn create_list_placeholders_docs(op: TransformOperation) -> TransformOperation {
+    op.summary("List placeholders")
+        .description("List the placeholder users managed by the current user.")
+        .tag("User")
+        .response::<200, Json<Vec<PlaceholderUser>>>()
+}
+
+#[derive(serde::Deserialize, schemars::JsonSchema)]
+pub struct ClaimPlaceholderBody {
+    pub invite_code: String,
+}
+
+/// Claim a placeholder with its invite code.
+pub async fn claim_placeholder<S>(
+    auth_session: AuthSession<S>,
+    State(state): State<AppState<S>>,
+    Json(body): Json<ClaimPlaceholderBody>,
+) -> Result<impl IntoApiResponse, AppError>
+where
+    S: Clone + UserService,
+{
+    let user = auth_session.user.as_ref().ok_or(AppError::Unauthorized)?;
+
+    let user = claim(&state, user.id(), body.invite_code).await?;
+
+    Ok(Json(PublicUser {
+        id: user.id,
+        username: user.username,
+        email: Some(user.email),
+        avatar: user.avatar,
+    }))
+}
+
+pub fn create_claim_placeholder_docs(op: TransformOperation) -> TransformOperation {
+    op.summary("Claim placeholder")
+        .description(
+            "Merge a placeholder into the current user, \
+            transferring all its instances, orders and transactions.",
+        )
+        .tag("User")
+        .response::<200, Json<PublicUser>>()
+}
+
+#[derive(serde::Serialize, schemars::JsonSchema)]
+pub struct CalendarToken {
+    /// The secret to pass as the `token` query parameter of calendar feeds
+    pub token: String,
+}
+
+/// Issue a new calendar feed token, revoking the previous one.
+pub async fn reset_calendar_token<S>(
+    auth_session: AuthSession<S>,
+    State(state): State<AppState<S>>,
+) -> Result<impl IntoApiResponse, AppError>
+where
+    S: Clone + UserService,
+{
+    let user = auth_session.user.as_ref().ok_or(AppError::Unauthorized)?;
+
+    let token = state
+        .service
+        .reset_calendar_token(ResetCalendarTokenRequest { user_id: user.id() })
+        .await
+        .map_err(|_| AppError::InternalServerError)?;
+
+    Ok(Json(CalendarToken { token }))
+}
+
+pub fn create_reset_calendar_token_docs(op: TransformOperation) -> TransformOperation {
+    op.summary("Reset calendar token")
+        .description(
+            "Issue a new secret token for subscribing to calendar feeds, \
+            revoking the previous one.",
+        )
+        .tag("User")
+        .response::<200, Json<CalendarToken>>()
+}
+
+/// Revoke the calendar feed token.
+pub async fn revoke_calendar_token<S>(
+    auth_session: AuthSession<S>,
+    State(state): State<AppState<S>>,
+) -> Result<impl IntoApiResponse, AppError>
+where
+    S: Clone + UserService,
+{
+    let user = auth_session.user.as_ref().ok_or(AppError::Unauthorized)?;
+
+    state
+        .service
+        .revoke_calendar_token(RevokeCalendarTokenRequest { user_id: user.id() })
+        .await
+        .map_err(|_| AppError::InternalServerError)?;
+
+    Ok(StatusCode::OK)
+}
+
+pub fn create_revoke_calendar_token_docs(op: TransformOperation) -> TransformOperation {
+    op.summary("Revoke calendar token")
+        .description("Revoke the calendar feed token, so subscribed feeds stop working.")
+        .tag("User")
+        .response::<200, ()>()
+}
+
+async fn claim<S>(
+    state: &AppState<S>,
+    user_id: UserId,
+    invite_code: String,
+) -> Result<User, AppError>
+where
+    S: Clone + UserService,
+{
+    state
+        .service
+        .claim_placeholder(ClaimPlaceholderRequest {
+            user_id,
+            invite_code,
+        })
+        .await
+        .map_err(|e| match e {
+            ClaimPlaceholderError::InvalidInviteCode => {
+                AppError::BadRequest("Invalid invite code".to_string())
+            }
+            _ => AppError::InternalServerError,
+        })
+}
@@ -0,0 +1,225 @@
+use crate::{auth::AuthSession, error::AppError, state::AppState};
+use aide::{axum::IntoApiResponse, transform::TransformOperation};
+use axum::{
+    Json,
+    extract::{Path, State},
+    http::StatusCode,
+};
+use axum_login::AuthUser;
+use sawa_core::{
+    models::{
+        cart::{Cart, CartLineId},
+        misc::{Address, Price},
+        product::ProductVariantId,
+        purchase::PurchaseOrder,
+        user::UserId,
+    },
+    services::{
+        AddCartLineRequest, CartService, CheckoutCartError, CheckoutCartRequest, GetCartRequest,
+        RemoveCartLineRequest, UpdateCartLineRequest, UserService,
+    },
+};
+use schemars::JsonSchema;
+use serde::Deserialize;
+use std::num::NonZeroU32;
+
+#[derive(Deserialize, JsonSchema)]
+pub struct AddCartLineBody {
+    pub variant_id: ProductVariantId,
+    pub quantity: NonZeroU32,
+    pub owner_id: Option<UserId>,
+    pub unit_price: Option<Price>,
+}
+
+#[derive(Deserialize, JsonSchema)]
+pub struct UpdateCartLineBody {
+    pub quantity: Option<NonZeroU32>,
+    pub owner_id: Option<UserId>,
+    pub unit_price: Option<Price>,
+}
+
+#[derive(Deserialize, JsonSchema)]
+pub struct CheckoutCartBody {
+    pub receiver_id: Option<UserId>,
+    pub shipping_address: Option<Address>,
+    pub total_price: Option<Price>,
+}
+
+#[derive(Deserialize, JsonSchema)]
+pub struct CartLineIdPath {
+    pub line_id: CartLineId,
+}
+
+/// GET /cart
+pub async fn get_cart<S>(
+    State(state): State<AppState<S>>,
+    auth_session: AuthSession<S>,
+) -> Result<impl IntoApiResponse, AppError>
+where
+    S: CartService + UserService + Clone,
+{
+    let user = auth_session.user.as_ref().ok_or(AppError::Unauthorized)?;
+
+    let cart = state
+        .service
+        .get_cart(GetCartRequest { user_id: user.id() })
+        .await
+        .map_err(|_| AppError::InternalServerError)?;
+
+    Ok((StatusCode::OK, Json(cart)))
+}
+
+pub fn create_get_cart_docs(op: TransformOperation) -> TransformOperation {
+    op.summary("Get cart")
+        .description("Get the cart of the current user.")
+        .tag("Cart")
+        .response::<200, Json<Cart>>()
+}
+
+/// POST /cart/lines
+pub async fn add_cart_line<S>(
+    State(state): State<AppState<S>>,
+    auth_session: AuthSession<S>,
+    Json(body): Json<AddCartLineBody>,
+) -> Result<impl IntoApiResponse, AppError>
+where
+    S: CartService + UserService + Clone,
+{
+    let user = auth_session.user.as_ref().ok_or(AppError::Unauthorized)?;
+
+    let req = AddCartLineRequest {
+        user_id: user.id(),
+        variant_id: body.variant_id,
+        quantity: body.quantity,
+        owner_id: body.owner_id,
+        unit_price: body.unit_price,
+    };
+
+    let cart = state
+        .service
+        .add_cart_line(req)
+        .await
+        .map_err(|_| AppError::InternalServerError)?;
+
+    Ok((StatusCode::OK, Json(cart)))
+}
+
+pub fn create_add_cart_line_docs(op: TransformOperation) -> TransformOperation {
+    op.summary("Add to cart")
+        .description(
+            "Add a variant to the cart. \
+            Adding a variant already in the cart for the same owner increases its quantity.",
+        )
+        .tag("Cart")
+        .response::<200, Json<Cart>>()
+}
+
+/// PATCH /cart/lines/{line_id}
+pub async fn update_cart_line<S>(
+    State(state): State<AppState<S>>,
+    auth_session: AuthSession<S>,
+    Path(CartLineIdPath { line_id }): Path<CartLineIdPath>,
+    Json(body): Json<UpdateCartLineBody>,
+) -> Result<impl IntoApiResponse, AppError>
+where
+    S: CartService + UserService + Clone,
+{
+    let user = auth_session.user.as_ref().ok_or(AppError::Unauthorized)?;
+
+    let req = UpdateCartLineRequest {
+        user_id: user.id(),
+        line_id,
+        quantity: body.quantity,
+        owner_id: body.owner_id,
+        unit_price: body.unit_price,
+    };
+
+    let cart = state
+        .service
+        .update_cart_line(req)
+        .await
+        .map_err(|_| AppError::InternalServerError)?;
+
+    Ok((StatusCode::OK, Json(cart)))
+}
+
+pub fn create_update_cart_line_docs(op: TransformOperation) -> TransformOperation {
+    op.summary("Update cart line")
+        .description("Update the quantity, owner or price of a cart line.")
+        .tag("Cart")
+        .response::<200, Json<Cart>>()
+}
+
+/// DELETE /cart/lines/{line_id}
+pub async fn remove_cart_line<S>(
+    State(state): State<AppState<S>>,
+    auth_session: AuthSession<S>,
+    Path(CartLineIdPath { line_id }): Path<CartLineIdPath>,
+) -> Result<impl IntoApiResponse, AppError>
+where
+    S: CartService + UserService + Clone,
+{
+    let user = auth_session.user.as_ref().ok_or(AppError::Unauthorized)?;
+
+    let req = RemoveCartLineRequest {
+        user_id: user.id(),
+        line_id,
+    };
+
+    let cart = state
+        .service
+        .remove_cart_line(req)
+        .await
+        .map_err(|_| AppError::InternalServerError)?;
+
+    Ok((StatusCode::OK, Json(cart)))
+}
+
+pub fn create_remove_cart_line_docs(op: TransformOperation) -> TransformOperation {
+    op.summary("Remove cart line")
+        .description("Remove a line from the cart.")
+        .tag("Cart")
+        .response::<200, Json<Cart>>()
+}
+
+/// POST /cart/checkout
+pub async fn checkout_cart<S>(
+    State(state): State<AppState<S>>,
+    auth_session: AuthSession<S>,
+    Json(body): Json<CheckoutCartBody>,
+) -> Result<impl IntoApiResponse, AppError>
+where
+    S: CartService + UserService + Clone,
+{
+    let user = auth_session.user.as_ref().ok_or(AppError::Unauthorized)?;
+
+    let req = CheckoutCartRequest {
+        user_id: user.id(),
+        receiver_id: body.receiver_id,
+        shipping_address: body.shipping_address,
+        total_price: body.total_price,
+    };
+
+    let order = state
+        .service
+        .checkout_cart(req)
+        .await
+        .map_err(|e| match e {
+            CheckoutCartError::CartEmpty | CheckoutCartError::VariantsNotFound { .. } => {
+                AppError::BadRequest(e.to_string())
+            }
+            _ => AppError::InternalServerError,
+        })?;
+
+    Ok((StatusCode::CREATED, Json(order)))
+}
+
+pub fn create_checkout_cart_docs(op: TransformOperation) -> TransformOperation {
+    op.summary("Checkout cart")
+        .description(
+            "Create a purchase order from the cart and clear the cart. \
+            Fails if a variant in the cart no longer exists.",
+        )
+        .tag("Cart")
+        .response::<201, Json<PurchaseOrder>>()
+}
@@ -0,0 +1,157 @@
+use crate::{auth::AuthSession, error::AppError, state::AppState};
+use aide::{axum::IntoApiResponse, transform::TransformOperation};
+use axum::{
+    Json,
+    extract::{Query, State},
+    http::StatusCode,
+};
+use axum_login::AuthUser;
+use sawa_core::{
+    models::{
+        collection::{CollectionCompleteness, CollectionScope, DuplicateVariant},
+        misc::TagId,
+        product::ProductId,
+    },
+    services::{
+        CollectionScopeError, CollectionService, GetCollectionCompletenessError,
+        GetCollectionCompletenessRequest, GetDuplicatesError, GetDuplicatesRequest, UserService,
+    },
+};
+use schemars::JsonSchema;
+use serde::Deserialize;
+
+/// Exactly one of `product_id` and `tag_id` must be given.
+#[derive(Deserialize, JsonSchema)]
+pub struct CollectionScopeQuery {
+    pub product_id: Option<ProductId>,
+    pub tag_id: Option<TagId>,
+    /// Also include variants with any tag nested under `tag_id`
+    #[serde(default)]
+    pub include_descendants: bool,
+}
+
+impl TryFrom<CollectionScopeQuery> for CollectionScope {
+    type Error = AppError;
+
+    fn try_from(query: CollectionScopeQuery) -> Result<Self, Self::Error> {
+        optional_scope(query.product_id, query.tag_id, query.include_descendants)?.ok_or_else(
+            || AppError::BadRequest("Exactly one of product_id and tag_id is required".to_string()),
+        )
+    }
+}
+
+fn optional_scope(
+    product_id: Option<ProductId>,
+    tag_id: Option<TagId>,
+    include_descendants: bool,
+) -> Result<Option<CollectionScope>, AppError> {
+    match (product_id, tag_id) {
+        (None, None) => Ok(None),
+        (Some(product_id), None) => Ok(Some(CollectionScope::Product { product_id })),
+        (None, Some(tag_id)) => Ok(Some(CollectionScope::Tag {
+            tag_id,
+            include_descendants,
+        })),
+        (Some(_), Some(_)) => Err(AppError::BadRequest(
+            "Only one of product_id and tag_id can be given".to_string(),
+        )),
+    }
+}
+
+/// At most one of `product_id` and `tag_id` may be given.
+#[derive(Deserialize, JsonSchema)]
+pub struct DuplicatesQuery {
+    /// Report variants owned more than this many times, 1 by default
+    pub threshold: Option<u32>,
+    pub product_id: Option<ProductId>,
+    pub tag_id: Option<TagId>,
+    /// Also include variants with any tag nested under `tag_id`
+    #[serde(default)]
+    pub include_descendants: bool,
+}
+
+fn scope_error(e: CollectionScopeError) -> AppError {
+    match e {
+        CollectionScopeError::ProductNotFound { .. } | CollectionScopeError::TagNotFound { .. } => {
+            AppError::NotFound
+        }
+        CollectionScopeError::Repository(_) => AppError::InternalServerError,
+    }
+}
+
+/// GET /collection/completeness
+pub async fn get_collection_completeness<S>(
+    State(state): State<AppState<S>>,
+    auth_session: AuthSession<S>,
+    Query(query): Query<CollectionScopeQuery>,
+) -> Result<impl IntoApiResponse, AppError>
+where
+    S: CollectionService + UserService + Clone,
+{
+    let user = auth_session.user.as_ref().ok_or(AppError::Unauthorized)?;
+
+    let req = GetCollectionCompletenessRequest {
+        user_id: user.id(),
+        scope: query.try_into()?,
+    };
+
+    let completeness = state
+        .service
+        .get_collection_completeness(req)
+        .await
+        .map_err(|e| match e {
+            GetCollectionCompletenessError::Scope(e) => scope_error(e),
+            _ => AppError::InternalServerError,
+        })?;
+
+    Ok((StatusCode::OK, Json(completeness)))
+}
+
+pub fn create_get_collection_completeness_docs(op: TransformOperation) -> TransformOperation {
+    op.summary("Collection completeness")
+        .description(
+            "List every variant of a product or tag with how many the current user owns, \
+            the missing ones, and the completion percentage.",
+        )
+        .tag("Collection")
+        .response::<200, Json<CollectionCompleteness>>()
+}
+
+/// GET /collection/duplicates
+pub async fn get_duplicates<S>(
+    State(state): State<AppState<S>>,
+    auth_session: AuthSession<S>,
+    Query(query): Query<DuplicatesQuery>,
+) -> Result<impl IntoApiResponse, AppError>
+where
+    S: CollectionService + UserService + Clone,
+{
+    let user = auth_session.user.as_ref().ok_or(AppError::Unauthorized)?;
+
+    let req = GetDuplicatesRequest {
+        user_id: user.id(),
+        threshold: query.threshold.unwrap_or(1),
+        scope: optional_scope(query.product_id, query.tag_id, query.include_descendants)?,
+    };
+
+    let duplicates = state
+        .service
+        .get_duplicates(req)
+        .await
+        .map_err(|e| match e {
+            GetDuplicatesError::Scope(e) => scope_error(e),
+            _ => AppError::InternalServerError,
+        })?;
+
+    Ok((StatusCode::OK, Json(duplicates)))
+}
+
+pub fn create_get_duplicates_docs(op: TransformOperation) -> TransformOperation {
+    op.summary("Duplicates report")
+        .description(
+            "List the variants the current user owns more `Active` instances of than the \
+            threshold, with the spare instances to offer in trades.",
+        )
+        .tag("Collection")
+        .response::<200, Json<Vec<DuplicateVariant>>>()
+}
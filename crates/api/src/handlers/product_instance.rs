@@ -4,13 +4,29 @@ use axum::{
     Json,
     extract::{Path, Query, State},
     http::StatusCode,
+    response::IntoResponse,
 };
 use axum_login::AuthUser;
 use sawa_core::{
-    models::product::{ProductInstance, ProductInstanceStatus, ProductVariantId},
+    models::{
+        misc::MediaId,
+        product::{
+            ProductInstance, ProductInstanceCondition, ProductInstanceId, ProductInstanceStatus,
+            ProductVariantId,
+        },
+        storage::StorageLocationId,
+        user::UserId,
+    },
     services::{
-        ListProductInstancesQueryBy, ListProductInstancesRequest, ProductInstanceService,
-        UserService,
+        BulkChangeInstanceStatusError, BulkChangeInstanceStatusRequest, BulkInstanceResult,
+        BulkInstanceSelection, BulkTransferInstancesError, BulkTransferInstancesRequest,
+        BulkTransferResponse, ConsumeProductInstanceError, ConsumeProductInstanceRequest,
+        ListProductInstancesQueryBy, ListProductInstancesRequest,
+        MarkProductInstanceDestroyedError, MarkProductInstanceDestroyedRequest,
+        MarkProductInstanceLostError, MarkProductInstanceLostRequest, ProductInstanceService,
+        RecoverProductInstanceError, RecoverProductInstanceRequest, UnconsumeProductInstanceError,
+        UnconsumeProductInstanceRequest, UpdateProductInstanceDetailsError,
+        UpdateProductInstanceDetailsRequest, UserService,
     },
 };
 use schemars::JsonSchema;
@@ -21,17 +37,86 @@ pub struct QueryByPath {
     pub query_by: ListProductInstancesQueryBy,
 }
 
+#[derive(Deserialize, JsonSchema)]
+pub struct InstanceIdPath {
+    pub instance_id: ProductInstanceId,
+}
+
+#[derive(Deserialize, JsonSchema)]
+pub struct StatusChangeBody {
+    /// Why the status changed, recorded in the status history
+    #[serde(default)]
+    pub reason: Option<String>,
+}
+
 #[derive(Deserialize, JsonSchema)]
 pub struct ListProductInstanceQuery {
     pub status: Option<ProductInstanceStatus>,
     pub variant_id: Option<ProductVariantId>,
+    /// Only goods kept in this storage location, or any location nested inside it
+    pub location_id: Option<StorageLocationId>,
+    pub condition: Option<ProductInstanceCondition>,
+}
+
+#[derive(Deserialize, JsonSchema)]
+pub struct BulkStatusChangeBody {
+    /// The goods to change. Either `ids` or `filter` must be given.
+    pub ids: Option<Vec<ProductInstanceId>>,
+    /// Select the owned goods matching all criteria instead of listing them
+    pub filter: Option<ListProductInstanceQuery>,
+    pub status: ProductInstanceStatus,
+    /// Why the status changed, recorded in the status history of every instance
+    #[serde(default)]
+    pub reason: Option<String>,
+}
+
+#[derive(Deserialize, JsonSchema)]
+pub struct BulkTransferBody {
+    /// The goods to transfer. Either `ids` or `filter` must be given.
+    pub ids: Option<Vec<ProductInstanceId>>,
+    /// Select the owned goods matching all criteria instead of listing them
+    pub filter: Option<ListProductInstanceQuery>,
+    pub to_user_id: UserId,
+}
+
+fn bulk_selection(
+    ids: Option<Vec<ProductInstanceId>>,
+    filter: Option<ListProductInstanceQuery>,
+) -> Result<BulkInstanceSelection, AppError> {
+    match (ids, filter) {
+        (Some(ids), None) => Ok(BulkInstanceSelection::Ids(ids)),
+        (None, Some(filter)) => Ok(BulkInstanceSelection::Filter {
+            variant_id: filter.variant_id,
+            status: filter.status,
+            location_id: filter.location_id,
+            condition: filter.condition,
+        }),
+        _ => Err(AppError::BadRequest(
+            "Either ids or filter must be given".to_string(),
+        )),
+    }
+}
+
+#[derive(Deserialize, JsonSchema)]
+pub struct UpdateProductInstanceDetailsBody {
+    pub condition: Option<ProductInstanceCondition>,
+    /// Private note, only visible to the owner
+    #[serde(default)]
+    pub note: String,
+    #[serde(default)]
+    pub medias: Vec<MediaId>,
 }
 
 /// GET /goods/{query_by}
 pub async fn list_product_instances<S>(
     State(state): State<AppState<S>>,
     Path(QueryByPath { query_by }): Path<QueryByPath>,
-    Query(ListProductInstanceQuery { status, variant_id }): Query<ListProductInstanceQuery>,
+    Query(ListProductInstanceQuery {
+        status,
+        variant_id,
+        location_id,
+        condition,
+    }): Query<ListProductInstanceQuery>,
     auth_session: AuthSession<S>,
 ) -> Result<impl IntoApiResponse, AppError>
 where
@@ -44,6 +129,8 @@ where
         query_by,
         variant_id,
         status,
+        location_id,
+        condition,
     };
 
     let instances = state
@@ -57,7 +144,342 @@ where
 
 pub fn create_list_product_instances_docs(op: TransformOperation) -> TransformOperation {
     op.summary("List goods")
-        .description("List product instances owned or held by a user.")
+        .description(
+            "List product instances owned, held, lent out or borrowed by a user. \
+            `query_by` is one of `owned`, `held`, `lent_out` and `borrowed`.",
+        )
         .tag("Goods")
         .response::<200, Json<Vec<ProductInstance>>>()
 }
+
+/// POST /goods/instances/{instance_id}/consume
+pub async fn consume_product_instance<S>(
+    State(state): State<AppState<S>>,
+    auth_session: AuthSession<S>,
+    Path(InstanceIdPath { instance_id }): Path<InstanceIdPath>,
+) -> Result<impl IntoApiResponse, AppError>
+where
+    S: ProductInstanceService + UserService + Clone,
+{
+    let user = auth_session.user.as_ref().ok_or(AppError::Unauthorized)?;
+
+    let req = ConsumeProductInstanceRequest {
+        id: instance_id,
+        user_id: user.id(),
+    };
+
+    let instance = state
+        .service
+        .consume_product_instance(req)
+        .await
+        .map_err(|e| match e {
+            ConsumeProductInstanceError::NotFound
+            | ConsumeProductInstanceError::PermissionDenied => AppError::NotFound,
+            e @ (ConsumeProductInstanceError::InvalidTransition(_)
+            | ConsumeProductInstanceError::NotHeldByOwner) => AppError::BadRequest(e.to_string()),
+            _ => AppError::InternalServerError,
+        })?;
+
+    Ok((StatusCode::OK, Json(instance)))
+}
+
+pub fn create_consume_product_instance_docs(op: TransformOperation) -> TransformOperation {
+    op.summary("Consume goods")
+        .description("Mark a product instance as consumed.")
+        .tag("Goods")
+        .response::<200, Json<ProductInstance>>()
+}
+
+/// POST /goods/instances/{instance_id}/unconsume
+pub async fn unconsume_product_instance<S>(
+    State(state): State<AppState<S>>,
+    auth_session: AuthSession<S>,
+    Path(InstanceIdPath { instance_id }): Path<InstanceIdPath>,
+) -> Result<impl IntoApiResponse, AppError>
+where
+    S: ProductInstanceService + UserService + Clone,
+{
+    let user = auth_session.user.as_ref().ok_or(AppError::Unauthorized)?;
+
+    let req = UnconsumeProductInstanceRequest {
+        id: instance_id,
+        user_id: user.id(),
+    };
+
+    let instance = state
+        .service
+        .unconsume_product_instance(req)
+        .await
+        .map_err(|e| match e {
+            UnconsumeProductInstanceError::NotFound
+            | UnconsumeProductInstanceError::PermissionDenied => AppError::NotFound,
+            e @ (UnconsumeProductInstanceError::InvalidTransition(_)
+            | UnconsumeProductInstanceError::NotHeldByOwner) => AppError::BadRequest(e.to_string()),
+            _ => AppError::InternalServerError,
+        })?;
+
+    Ok((StatusCode::OK, Json(instance)))
+}
+
+pub fn create_unconsume_product_instance_docs(op: TransformOperation) -> TransformOperation {
+    op.summary("Undo consuming goods")
+        .description(
+            "Make a consumed product instance active again, e.g. when it was consumed by mistake.",
+        )
+        .tag("Goods")
+        .response::<200, Json<ProductInstance>>()
+}
+
+/// POST /goods/instances/{instance_id}/lost
+pub async fn mark_product_instance_lost<S>(
+    State(state): State<AppState<S>>,
+    auth_session: AuthSession<S>,
+    Path(InstanceIdPath { instance_id }): Path<InstanceIdPath>,
+    Json(body): Json<StatusChangeBody>,
+) -> Result<impl IntoApiResponse, AppError>
+where
+    S: ProductInstanceService + UserService + Clone,
+{
+    let user = auth_session.user.as_ref().ok_or(AppError::Unauthorized)?;
+
+    let req = MarkProductInstanceLostRequest {
+        id: instance_id,
+        user_id: user.id(),
+        reason: body.reason,
+    };
+
+    let instance = state
+        .service
+        .mark_product_instance_lost(req)
+        .await
+        .map_err(|e| match e {
+            MarkProductInstanceLostError::NotFound
+            | MarkProductInstanceLostError::PermissionDenied => AppError::NotFound,
+            e @ (MarkProductInstanceLostError::InvalidTransition(_)
+            | MarkProductInstanceLostError::NotHeldByOwner) => AppError::BadRequest(e.to_string()),
+            _ => AppError::InternalServerError,
+        })?;
+
+    Ok((StatusCode::OK, Json(instance)))
+}
+
+pub fn create_mark_product_instance_lost_docs(op: TransformOperation) -> TransformOperation {
+    op.summary("Mark goods as lost")
+        .description("Mark a product instance as lost. It can be recovered later if found again.")
+        .tag("Goods")
+        .response::<200, Json<ProductInstance>>()
+}
+
+/// POST /goods/instances/{instance_id}/recover
+pub async fn recover_product_instance<S>(
+    State(state): State<AppState<S>>,
+    auth_session: AuthSession<S>,
+    Path(InstanceIdPath { instance_id }): Path<InstanceIdPath>,
+    Json(body): Json<StatusChangeBody>,
+) -> Result<impl IntoApiResponse, AppError>
+where
+    S: ProductInstanceService + UserService + Clone,
+{
+    let user = auth_session.user.as_ref().ok_or(AppError::Unauthorized)?;
+
+    let req = RecoverProductInstanceRequest {
+        id: instance_id,
+        user_id: user.id(),
+        reason: body.reason,
+    };
+
+    let instance = state
+        .service
+        .recover_product_instance(req)
+        .await
+        .map_err(|e| match e {
+            RecoverProductInstanceError::NotFound
+            | RecoverProductInstanceError::PermissionDenied => AppError::NotFound,
+            e @ (RecoverProductInstanceError::InvalidTransition(_)
+            | RecoverProductInstanceError::NotHeldByOwner) => AppError::BadRequest(e.to_string()),
+            _ => AppError::InternalServerError,
+        })?;
+
+    Ok((StatusCode::OK, Json(instance)))
+}
+
+pub fn create_recover_product_instance_docs(op: TransformOperation) -> TransformOperation {
+    op.summary("Recover lost goods")
+        .description("Mark a lost product instance as found again.")
+        .tag("Goods")
+        .response::<200, Json<ProductInstance>>()
+}
+
+/// POST /goods/instances/{instance_id}/destroy
+pub async fn mark_product_instance_destroyed<S>(
+    State(state): State<AppState<S>>,
+    auth_session: AuthSession<S>,
+    Path(InstanceIdPath { instance_id }): Path<InstanceIdPath>,
+    Json(body): Json<StatusChangeBody>,
+) -> Result<impl IntoApiResponse, AppError>
+where
+    S: ProductInstanceService + UserService + Clone,
+{
+    let user = auth_session.user.as_ref().ok_or(AppError::Unauthorized)?;
+
+    let req = MarkProductInstanceDestroyedRequest {
+        id: instance_id,
+        user_id: user.id(),
+        reason: body.reason,
+    };
+
+    let instance = state
+        .service
+        .mark_product_instance_destroyed(req)
+        .await
+        .map_err(|e| match e {
+            MarkProductInstanceDestroyedError::NotFound
+            | MarkProductInstanceDestroyedError::PermissionDenied => AppError::NotFound,
+            e @ (MarkProductInstanceDestroyedError::InvalidTransition(_)
+            | MarkProductInstanceDestroyedError::NotHeldByOwner) => {
+                AppError::BadRequest(e.to_string())
+            }
+            _ => AppError::InternalServerError,
+        })?;
+
+    Ok((StatusCode::OK, Json(instance)))
+}
+
+pub fn create_mark_product_instance_destroyed_docs(op: TransformOperation) -> TransformOperation {
+    op.summary("Mark goods as destroyed")
+        .description("Mark an active or lost product instance as destroyed.")
+        .tag("Goods")
+        .response::<200, Json<ProductInstance>>()
+}
+
+/// PUT /goods/instances/{instance_id}
+pub async fn update_product_instance_details<S>(
+    State(state): State<AppState<S>>,
+    auth_session: AuthSession<S>,
+    Path(InstanceIdPath { instance_id }): Path<InstanceIdPath>,
+    Json(body): Json<UpdateProductInstanceDetailsBody>,
+) -> Result<impl IntoApiResponse, AppError>
+where
+    S: ProductInstanceService + UserService + Clone,
+{
+    let user = auth_session.user.as_ref().ok_or(AppError::Unauthorized)?;
+
+    let req = UpdateProductInstanceDetailsRequest {
+        id: instance_id,
+        user_id: user.id(),
+        condition: body.condition,
+        note: body.note,
+        medias: body.medias,
+    };
+
+    let instance = state
+        .service
+        .update_product_instance_details(req)
+        .await
+        .map_err(|e| match e {
+            UpdateProductInstanceDetailsError::NotFound
+            | UpdateProductInstanceDetailsError::PermissionDenied => AppError::NotFound,
+            UpdateProductInstanceDetailsError::MediaNotFound { .. } => {
+                AppError::BadRequest(e.to_string())
+            }
+            _ => AppError::InternalServerError,
+        })?;
+
+    Ok((StatusCode::OK, Json(instance)))
+}
+
+pub fn create_update_product_instance_details_docs(op: TransformOperation) -> TransformOperation {
+    op.summary("Update goods details")
+        .description("Update the condition, private note and photos of a product instance.")
+        .tag("Goods")
+        .response::<200, Json<ProductInstance>>()
+}
+
+/// POST /goods/bulk/status
+pub async fn bulk_change_instance_status<S>(
+    State(state): State<AppState<S>>,
+    auth_session: AuthSession<S>,
+    Json(body): Json<BulkStatusChangeBody>,
+) -> Result<impl IntoApiResponse, AppError>
+where
+    S: ProductInstanceService + UserService + Clone,
+{
+    let user = auth_session.user.as_ref().ok_or(AppError::Unauthorized)?;
+
+    let req = BulkChangeInstanceStatusRequest {
+        user_id: user.id(),
+        selection: bulk_selection(body.ids, body.filter)?,
+        status: body.status,
+        reason: body.reason,
+    };
+
+    match state.service.bulk_change_instance_status(req).await {
+        Ok(results) => Ok((StatusCode::OK, Json(results))),
+        Err(BulkChangeInstanceStatusError::Rejected { results }) => {
+            Ok((StatusCode::UNPROCESSABLE_ENTITY, Json(results)))
+        }
+        Err(
+            e @ (BulkChangeInstanceStatusError::NoInstances
+            | BulkChangeInstanceStatusError::UnsupportedStatus { .. }),
+        ) => Err(AppError::BadRequest(e.to_string())),
+        Err(BulkChangeInstanceStatusError::Repository(_)) => Err(AppError::InternalServerError),
+    }
+}
+
+pub fn create_bulk_change_instance_status_docs(op: TransformOperation) -> TransformOperation {
+    op.summary("Change the status of many goods")
+        .description(
+            "Change the status of the listed goods, or of all owned goods matching a filter, \
+            e.g. to mark everything missing after a move as lost. \
+            Either all goods are changed, or none: if any of them cannot be changed, \
+            the response lists the result of every instance with status 422.",
+        )
+        .tag("Goods")
+        .response::<200, Json<Vec<BulkInstanceResult>>>()
+        .response::<422, Json<Vec<BulkInstanceResult>>>()
+}
+
+/// POST /goods/bulk/transfer
+pub async fn bulk_transfer_instances<S>(
+    State(state): State<AppState<S>>,
+    auth_session: AuthSession<S>,
+    Json(body): Json<BulkTransferBody>,
+) -> Result<impl IntoApiResponse, AppError>
+where
+    S: ProductInstanceService + UserService + Clone,
+{
+    let user = auth_session.user.as_ref().ok_or(AppError::Unauthorized)?;
+
+    let req = BulkTransferInstancesRequest {
+        user_id: user.id(),
+        selection: bulk_selection(body.ids, body.filter)?,
+        to_user_id: body.to_user_id,
+    };
+
+    match state.service.bulk_transfer_instances(req).await {
+        Ok(response) => Ok((StatusCode::CREATED, Json(response)).into_response()),
+        Err(BulkTransferInstancesError::Rejected { results }) => {
+            Ok((StatusCode::UNPROCESSABLE_ENTITY, Json(results)).into_response())
+        }
+        Err(
+            e @ (BulkTransferInstancesError::NoInstances
+            | BulkTransferInstancesError::RecipientNotFound
+            | BulkTransferInstancesError::SameUser
+            | BulkTransferInstancesError::CreateTransaction(_)),
+        ) => Err(AppError::BadRequest(e.to_string())),
+        Err(BulkTransferInstancesError::Repository(_)) => Err(AppError::InternalServerError),
+    }
+}
+
+pub fn create_bulk_transfer_instances_docs(op: TransformOperation) -> TransformOperation {
+    op.summary("Transfer many goods")
+        .description(
+            "Start transferring the listed goods, or all owned goods matching a filter, \
+            to another user in a single pending transaction. \
+            Either all goods are included, or none: if any of them cannot be transferred, \
+            the response lists the result of every instance with status 422.",
+        )
+        .tag("Goods")
+        .response::<201, Json<BulkTransferResponse>>()
+        .response::<422, Json<Vec<BulkInstanceResult>>>()
+}
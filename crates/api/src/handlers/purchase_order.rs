@@ -3,23 +3,33 @@ use aide::{axum::IntoApiResponse, transform::TransformOperation};
 use axum::{
     Json,
     extract::{Path, Query, State},
-    http::StatusCode,
+    http::{StatusCode, header},
 };
 use axum_login::AuthUser;
+use chrono::{Days, NaiveDate, Utc};
 use sawa_core::{
     models::{
-        misc::{Address, Price},
+        misc::{Address, MediaId, Price},
         product::ProductVariantId,
         purchase::{
-            OrderRoleFilter, PurchaseOrder, PurchaseOrderId, PurchaseOrderItemId,
-            PurchaseOrderStatus,
+            AdjustmentAllocation, OrderRoleFilter, PurchaseOrder, PurchaseOrderAdjustmentId,
+            PurchaseOrderAdjustmentKind, PurchaseOrderAttachment, PurchaseOrderAttachmentId,
+            PurchaseOrderAttachmentKind, PurchaseOrderEvent, PurchaseOrderId, PurchaseOrderItemId,
+            PurchaseOrderLineItemId, PurchaseOrderSchedule, PurchaseOrderStatus, PurchaseOrderView,
         },
         user::UserId,
     },
     services::{
+        AddMysteryBoxResultsRequest, AddOrderAdjustmentRequest, AddOrderAttachmentRequest,
         AddOrderItemRequest, CancelOrderRequest, CreateOrderItemRequest, CreateOrderRequest,
-        FulfillOrderRequest, GetOrderRequest, ListOrdersRequest, PurchaseOrderLifecycleService,
-        PurchaseOrderService, SubmitMysteryBoxResultsRequest, UserService,
+        FulfillOrderRequest, GetOrderEventsRequest, GetOrderRequest, GetUserError, GetUserRequest,
+        LineItemOwnerAssignment, ListOrderArrivalsRequest, ListOrdersRequest, MysteryBoxResult,
+        MysteryBoxResultsResponse, OrderArrivalsResponse, OwnerShare,
+        PurchaseOrderLifecycleService, PurchaseOrderService, ReassignLineItemOwnersRequest,
+        RemoveMysteryBoxResultRequest, RemoveOrderAdjustmentRequest, RemoveOrderAttachmentRequest,
+        RemoveOrderItemRequest, ReturnOrderItemsRequest, SplitOrderItemOwnersRequest,
+        SubmitMysteryBoxResultsRequest, UpdateMysteryBoxResultRequest, UpdateOrderItemRequest,
+        UpdateOrderRequest, UpdateOrderScheduleRequest, UserService,
     },
 };
 use schemars::JsonSchema;
@@ -50,12 +60,49 @@ pub struct AddOrderItemBody {
     pub unit_price: Option<Price>,
 }
 
+#[derive(Deserialize, JsonSchema)]
+pub struct UpdateOrderBody {
+    pub receiver_id: Option<UserId>,
+    pub shipping_address: Option<Address>,
+}
+
+#[derive(Deserialize, JsonSchema)]
+pub struct UpdateOrderItemBody {
+    pub quantity: Option<NonZeroU32>,
+    pub unit_price: Option<Price>,
+}
+
+#[derive(Deserialize, JsonSchema)]
+pub struct AddOrderAdjustmentBody {
+    pub kind: PurchaseOrderAdjustmentKind,
+    pub amount: Price,
+    pub allocation: AdjustmentAllocation,
+    pub note: Option<String>,
+}
+
 #[derive(Deserialize, JsonSchema)]
 pub struct SubmitMysteryBoxResultsBody {
     pub owner_id: UserId,
     pub received_variants: Vec<ProductVariantId>,
 }
 
+#[derive(Deserialize, JsonSchema)]
+pub struct AddMysteryBoxResultsBody {
+    pub results: Vec<MysteryBoxResultBody>,
+}
+
+#[derive(Deserialize, JsonSchema)]
+pub struct MysteryBoxResultBody {
+    pub variant_id: ProductVariantId,
+    pub owner_id: Option<UserId>,
+}
+
+#[derive(Deserialize, JsonSchema)]
+pub struct UpdateMysteryBoxResultBody {
+    pub variant_id: Option<ProductVariantId>,
+    pub owner_id: Option<UserId>,
+}
+
 #[derive(Deserialize, JsonSchema)]
 pub struct ListOrdersQuery {
     pub role: OrderRoleFilter,
@@ -136,7 +183,170 @@ pub fn create_list_orders_docs(op: TransformOperation) -> TransformOperation {
     op.summary("List orders")
         .description("List purchase orders for the authenticated user.")
         .tag("Purchase Order")
-        .response::<200, Json<Vec<PurchaseOrder>>>()
+        .response::<200, Json<Vec<PurchaseOrderView>>>()
+}
+
+#[derive(Deserialize, JsonSchema)]
+pub struct ListOrderArrivalsQuery {
+    /// The reference date, defaults to today (UTC).
+    pub today: Option<NaiveDate>,
+    /// How many days ahead count as arriving soon. All upcoming arrivals if omitted.
+    pub within_days: Option<u32>,
+}
+
+/// GET /orders/arrivals
+pub async fn list_order_arrivals<S>(
+    State(state): State<AppState<S>>,
+    auth_session: AuthSession<S>,
+    Query(query): Query<ListOrderArrivalsQuery>,
+) -> Result<impl IntoApiResponse, AppError>
+where
+    S: PurchaseOrderService + UserService + Clone,
+{
+    let user = auth_session.user.as_ref().ok_or(AppError::Unauthorized)?;
+
+    let req = ListOrderArrivalsRequest {
+        user_id: user.id(),
+        today: query.today.unwrap_or_else(|| Utc::now().date_naive()),
+        within_days: query.within_days,
+    };
+
+    let arrivals = state
+        .service
+        .list_order_arrivals(req)
+        .await
+        .map_err(|_| AppError::InternalServerError)?;
+
+    Ok((StatusCode::OK, Json(arrivals)))
+}
+
+pub fn create_list_order_arrivals_docs(op: TransformOperation) -> TransformOperation {
+    op.summary("List order arrivals")
+        .description("List pending purchase orders that are overdue or expected to arrive soon.")
+        .tag("Purchase Order")
+        .response::<200, Json<OrderArrivalsResponse>>()
+}
+
+#[derive(Deserialize, JsonSchema)]
+pub struct CalendarFeedQuery {
+    /// The user's calendar feed token
+    pub token: String,
+}
+
+/// GET /orders/arrivals.ics
+///
+/// Calendar apps can't log in, so the feed is authenticated by the user's calendar token.
+pub async fn get_order_arrivals_calendar<S>(
+    State(state): State<AppState<S>>,
+    Query(CalendarFeedQuery { token }): Query<CalendarFeedQuery>,
+) -> Result<impl IntoApiResponse, AppError>
+where
+    S: PurchaseOrderService + UserService + Clone,
+{
+    let user = state
+        .service
+        .get_user(GetUserRequest::ByCalendarToken(token))
+        .await
+        .map_err(|e| match e {
+            GetUserError::NotFound => AppError::Unauthorized,
+            _ => AppError::InternalServerError,
+        })?;
+
+    let req = ListOrderArrivalsRequest {
+        user_id: user.id,
+        today: Utc::now().date_naive(),
+        within_days: None,
+    };
+
+    let arrivals = state
+        .service
+        .list_order_arrivals(req)
+        .await
+        .map_err(|_| AppError::InternalServerError)?;
+
+    let orders = arrivals.overdue.iter().chain(&arrivals.arriving_soon);
+    Ok((
+        StatusCode::OK,
+        [(header::CONTENT_TYPE, "text/calendar; charset=utf-8")],
+        render_arrivals_calendar(orders),
+    ))
+}
+
+pub fn create_get_order_arrivals_calendar_docs(op: TransformOperation) -> TransformOperation {
+    op.summary("Get order arrivals calendar")
+        .description(
+            "Get an iCalendar feed with an all-day event on the expected arrival date of every pending purchase order. \
+            Authenticated by the `token` query parameter, see `POST /user/calendar-token`.",
+        )
+        .tag("Purchase Order")
+        .response::<200, String>()
+}
+
+/// Render pending orders as an iCalendar (RFC 5545) document.
+pub fn render_arrivals_calendar<'a>(orders: impl Iterator<Item = &'a PurchaseOrderView>) -> String {
+    let stamp = Utc::now().format("%Y%m%dT%H%M%SZ");
+
+    let mut lines = vec![
+        "BEGIN:VCALENDAR".to_string(),
+        "VERSION:2.0".to_string(),
+        "PRODID:-//sawa//order arrivals//EN".to_string(),
+        "CALSCALE:GREGORIAN".to_string(),
+    ];
+    for order in orders {
+        let Some(date) = order.pending_arrival_date() else {
+            continue;
+        };
+        let order_id = uuid::Uuid::from(order.id());
+
+        let mut description = format!("Order {order_id}");
+        if let Some(tracking) = &order.schedule().tracking {
+            description.push_str(&format!(
+                "\\nTracking: {} {}",
+                escape_ical_text(&tracking.carrier),
+                escape_ical_text(&tracking.tracking_number)
+            ));
+        }
+
+        lines.push("BEGIN:VEVENT".to_string());
+        lines.push(format!("UID:order-arrival-{order_id}@sawa"));
+        lines.push(format!("DTSTAMP:{stamp}"));
+        lines.push(format!("DTSTART;VALUE=DATE:{}", date.format("%Y%m%d")));
+        lines.push(format!(
+            "DTEND;VALUE=DATE:{}",
+            (date + Days::new(1)).format("%Y%m%d")
+        ));
+        lines.push("SUMMARY:Order arrival".to_string());
+        lines.push(format!("DESCRIPTION:{description}"));
+        lines.push("TRANSP:TRANSPARENT".to_string());
+        lines.push("END:VEVENT".to_string());
+    }
+    lines.push("END:VCALENDAR".to_string());
+
+    lines.iter().map(|line| fold_ical_line(line)).collect()
+}
+
+/// Escape a value of a TEXT property.
+pub fn escape_ical_text(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace('\n', "\\n")
+}
+
+/// Fold a content line into chunks of at most 75 octets, each terminated by CRLF.
+pub fn fold_ical_line(line: &str) -> String {
+    let mut folded = String::with_capacity(line.len() + 2);
+    let mut width = 0;
+    for c in line.chars() {
+        if width + c.len_utf8() > 75 {
+            folded.push_str("\r\n ");
+            width = 1;
+        }
+        folded.push(c);
+        width += c.len_utf8();
+    }
+    folded.push_str("\r\n");
+    folded
 }
 
 #[derive(Deserialize, JsonSchema)]
@@ -171,7 +381,112 @@ where
 
 pub fn create_get_order_docs(op: TransformOperation) -> TransformOperation {
     op.summary("Get order")
-        .description("Get a purchase order by ID.")
+        .description(
+            "Get a purchase order by ID. Participants other than the creator and receiver only see their own line items and share.",
+        )
+        .tag("Purchase Order")
+        .response::<200, Json<PurchaseOrderView>>()
+}
+
+/// GET /orders/{order_id}/events
+pub async fn get_order_events<S>(
+    State(state): State<AppState<S>>,
+    auth_session: AuthSession<S>,
+    Path(OrderIdPath { order_id }): Path<OrderIdPath>,
+) -> Result<impl IntoApiResponse, AppError>
+where
+    S: PurchaseOrderService + UserService + Clone,
+{
+    let user = auth_session.user.as_ref().ok_or(AppError::Unauthorized)?;
+
+    let req = GetOrderEventsRequest {
+        user_id: user.id(),
+        order_id,
+    };
+
+    let events = state
+        .service
+        .get_order_events(req)
+        .await
+        .map_err(|_| AppError::InternalServerError)?;
+
+    Ok((StatusCode::OK, Json(events)))
+}
+
+pub fn create_get_order_events_docs(op: TransformOperation) -> TransformOperation {
+    op.summary("Get order events")
+        .description("Get the append-only event log of a purchase order, oldest first.")
+        .tag("Purchase Order")
+        .response::<200, Json<Vec<PurchaseOrderEvent>>>()
+}
+
+/// PATCH /orders/{order_id}
+pub async fn update_order<S>(
+    State(state): State<AppState<S>>,
+    auth_session: AuthSession<S>,
+    Path(OrderIdPath { order_id }): Path<OrderIdPath>,
+    Json(body): Json<UpdateOrderBody>,
+) -> Result<impl IntoApiResponse, AppError>
+where
+    S: PurchaseOrderService + UserService + Clone,
+{
+    let user = auth_session.user.as_ref().ok_or(AppError::Unauthorized)?;
+
+    let req = UpdateOrderRequest {
+        user_id: user.id(),
+        order_id,
+        receiver_id: body.receiver_id,
+        shipping_address: body.shipping_address,
+    };
+
+    let order = state
+        .service
+        .update_order(req)
+        .await
+        .map_err(|_| AppError::InternalServerError)?;
+
+    Ok((StatusCode::OK, Json(order)))
+}
+
+pub fn create_update_order_docs(op: TransformOperation) -> TransformOperation {
+    op.summary("Update order")
+        .description("Update the receiver or shipping address of an incomplete purchase order.")
+        .tag("Purchase Order")
+        .response::<200, Json<PurchaseOrder>>()
+}
+
+/// PUT /orders/{order_id}/schedule
+pub async fn update_order_schedule<S>(
+    State(state): State<AppState<S>>,
+    auth_session: AuthSession<S>,
+    Path(OrderIdPath { order_id }): Path<OrderIdPath>,
+    Json(schedule): Json<PurchaseOrderSchedule>,
+) -> Result<impl IntoApiResponse, AppError>
+where
+    S: PurchaseOrderService + UserService + Clone,
+{
+    let user = auth_session.user.as_ref().ok_or(AppError::Unauthorized)?;
+
+    let req = UpdateOrderScheduleRequest {
+        user_id: user.id(),
+        order_id,
+        schedule,
+    };
+
+    let order = state
+        .service
+        .update_order_schedule(req)
+        .await
+        .map_err(|_| AppError::InternalServerError)?;
+
+    Ok((StatusCode::OK, Json(order)))
+}
+
+pub fn create_update_order_schedule_docs(op: TransformOperation) -> TransformOperation {
+    op.summary("Update order schedule")
+        .description(
+            "Replace the expected release, ship and arrival dates and the shipment tracking of a pending purchase order.",
+        )
         .tag("Purchase Order")
         .response::<200, Json<PurchaseOrder>>()
 }
@@ -219,105 +534,667 @@ pub struct OrderIdPathItemIdPath {
     pub item_id: PurchaseOrderItemId,
 }
 
-/// POST /orders/{order_id}/items/{item_id}/mystery-box
-pub async fn submit_mystery_box_results<S>(
+/// PATCH /orders/{order_id}/items/{item_id}
+pub async fn update_order_item<S>(
     State(state): State<AppState<S>>,
     auth_session: AuthSession<S>,
     Path(OrderIdPathItemIdPath { order_id, item_id }): Path<OrderIdPathItemIdPath>,
-    Json(body): Json<SubmitMysteryBoxResultsBody>,
+    Json(body): Json<UpdateOrderItemBody>,
 ) -> Result<impl IntoApiResponse, AppError>
 where
     S: PurchaseOrderService + UserService + Clone,
 {
     let user = auth_session.user.as_ref().ok_or(AppError::Unauthorized)?;
 
-    let req = SubmitMysteryBoxResultsRequest {
+    let req = UpdateOrderItemRequest {
         user_id: user.id(),
         order_id,
         order_item_id: item_id,
-        owner_id: body.owner_id,
-        received_variants: body.received_variants,
+        quantity: body.quantity,
+        unit_price: body.unit_price,
     };
 
-    state
+    let order = state
         .service
-        .submit_mystery_box_results(req)
+        .update_order_item(req)
         .await
         .map_err(|_| AppError::InternalServerError)?;
 
-    Ok(StatusCode::OK)
+    Ok((StatusCode::OK, Json(order)))
 }
 
-pub fn create_submit_mystery_box_results_docs(op: TransformOperation) -> TransformOperation {
-    op.summary("Submit mystery box results")
-        .description("Submit results for a mystery box item.")
+pub fn create_update_order_item_docs(op: TransformOperation) -> TransformOperation {
+    op.summary("Update order item")
+        .description("Update the quantity or unit price of an item in an incomplete order.")
         .tag("Purchase Order")
-        .response::<200, ()>()
+        .response::<200, Json<PurchaseOrder>>()
 }
 
-pub async fn fulfill_order<S>(
+#[derive(Deserialize, JsonSchema)]
+pub struct ReassignLineItemOwnersBody {
+    pub assignments: Vec<LineItemOwnerAssignmentBody>,
+}
+
+#[derive(Deserialize, JsonSchema)]
+pub struct LineItemOwnerAssignmentBody {
+    pub line_item_id: PurchaseOrderLineItemId,
+    pub owner_id: UserId,
+}
+
+/// PUT /orders/{order_id}/line-items/owners
+pub async fn reassign_line_item_owners<S>(
     State(state): State<AppState<S>>,
     auth_session: AuthSession<S>,
     Path(OrderIdPath { order_id }): Path<OrderIdPath>,
+    Json(body): Json<ReassignLineItemOwnersBody>,
 ) -> Result<impl IntoApiResponse, AppError>
 where
-    S: PurchaseOrderLifecycleService + UserService + Clone,
+    S: PurchaseOrderService + UserService + Clone,
 {
     let user = auth_session.user.as_ref().ok_or(AppError::Unauthorized)?;
 
-    let req = FulfillOrderRequest {
+    let req = ReassignLineItemOwnersRequest {
         user_id: user.id(),
         order_id,
+        assignments: body
+            .assignments
+            .into_iter()
+            .map(|assignment| LineItemOwnerAssignment {
+                line_item_id: assignment.line_item_id,
+                owner_id: assignment.owner_id,
+            })
+            .collect(),
     };
 
     let order = state
         .service
-        .fulfill_order(&req)
+        .reassign_line_item_owners(req)
         .await
         .map_err(|_| AppError::InternalServerError)?;
 
     Ok((StatusCode::OK, Json(order)))
 }
 
-pub fn create_fulfill_order_docs(op: TransformOperation) -> TransformOperation {
-    op.summary("Fulfill order")
-        .description("Fulfill a purchase order.")
+pub fn create_reassign_line_item_owners_docs(op: TransformOperation) -> TransformOperation {
+    op.summary("Reassign line item owners")
+        .description("Change the owners of individual line items of an incomplete purchase order.")
         .tag("Purchase Order")
         .response::<200, Json<PurchaseOrder>>()
 }
 
 #[derive(Deserialize, JsonSchema)]
-pub struct CancelOrderBody {
-    pub reason: Option<String>,
+pub struct SplitOrderItemOwnersBody {
+    pub shares: Vec<OwnerShareBody>,
 }
 
-pub async fn cancel_order<S>(
+#[derive(Deserialize, JsonSchema)]
+pub struct OwnerShareBody {
+    pub owner_id: UserId,
+    pub quantity: NonZeroU32,
+}
+
+/// PUT /orders/{order_id}/items/{item_id}/owners
+pub async fn split_order_item_owners<S>(
     State(state): State<AppState<S>>,
     auth_session: AuthSession<S>,
-    Path(OrderIdPath { order_id }): Path<OrderIdPath>,
-    Json(CancelOrderBody { reason }): Json<CancelOrderBody>,
+    Path(OrderIdPathItemIdPath { order_id, item_id }): Path<OrderIdPathItemIdPath>,
+    Json(body): Json<SplitOrderItemOwnersBody>,
 ) -> Result<impl IntoApiResponse, AppError>
 where
-    S: PurchaseOrderLifecycleService + UserService + Clone,
+    S: PurchaseOrderService + UserService + Clone,
 {
     let user = auth_session.user.as_ref().ok_or(AppError::Unauthorized)?;
-    let req = CancelOrderRequest {
+
+    let req = SplitOrderItemOwnersRequest {
         user_id: user.id(),
         order_id,
-        reason,
+        order_item_id: item_id,
+        shares: body
+            .shares
+            .into_iter()
+            .map(|share| OwnerShare {
+                owner_id: share.owner_id,
+                quantity: share.quantity,
+            })
+            .collect(),
     };
+
     let order = state
         .service
-        .cancel_order(&req)
+        .split_order_item_owners(req)
         .await
         .map_err(|_| AppError::InternalServerError)?;
 
     Ok((StatusCode::OK, Json(order)))
 }
 
-pub fn create_cancel_order_docs(op: TransformOperation) -> TransformOperation {
-    op.summary("Cancel order")
-        .description("Cancel a purchase order.")
+pub fn create_split_order_item_owners_docs(op: TransformOperation) -> TransformOperation {
+    op.summary("Split order item owners")
+        .description(
+            "Split the quantity of an item in an incomplete purchase order among several owners.",
+        )
+        .tag("Purchase Order")
+        .response::<200, Json<PurchaseOrder>>()
+}
+
+/// DELETE /orders/{order_id}/items/{item_id}
+pub async fn remove_order_item<S>(
+    State(state): State<AppState<S>>,
+    auth_session: AuthSession<S>,
+    Path(OrderIdPathItemIdPath { order_id, item_id }): Path<OrderIdPathItemIdPath>,
+) -> Result<impl IntoApiResponse, AppError>
+where
+    S: PurchaseOrderService + UserService + Clone,
+{
+    let user = auth_session.user.as_ref().ok_or(AppError::Unauthorized)?;
+
+    let req = RemoveOrderItemRequest {
+        user_id: user.id(),
+        order_id,
+        order_item_id: item_id,
+    };
+
+    let order = state
+        .service
+        .remove_order_item(req)
+        .await
+        .map_err(|_| AppError::InternalServerError)?;
+
+    Ok((StatusCode::OK, Json(order)))
+}
+
+pub fn create_remove_order_item_docs(op: TransformOperation) -> TransformOperation {
+    op.summary("Remove order item")
+        .description("Remove an item from an incomplete purchase order.")
+        .tag("Purchase Order")
+        .response::<200, Json<PurchaseOrder>>()
+}
+
+/// POST /orders/{order_id}/items/{item_id}/mystery-box
+pub async fn submit_mystery_box_results<S>(
+    State(state): State<AppState<S>>,
+    auth_session: AuthSession<S>,
+    Path(OrderIdPathItemIdPath { order_id, item_id }): Path<OrderIdPathItemIdPath>,
+    Json(body): Json<SubmitMysteryBoxResultsBody>,
+) -> Result<impl IntoApiResponse, AppError>
+where
+    S: PurchaseOrderService + UserService + Clone,
+{
+    let user = auth_session.user.as_ref().ok_or(AppError::Unauthorized)?;
+
+    let req = SubmitMysteryBoxResultsRequest {
+        user_id: user.id(),
+        order_id,
+        order_item_id: item_id,
+        owner_id: body.owner_id,
+        received_variants: body.received_variants,
+    };
+
+    let response = state
+        .service
+        .submit_mystery_box_results(req)
+        .await
+        .map_err(|_| AppError::InternalServerError)?;
+
+    Ok((StatusCode::OK, Json(response)))
+}
+
+pub fn create_submit_mystery_box_results_docs(op: TransformOperation) -> TransformOperation {
+    op.summary("Submit mystery box results")
+        .description(
+            "Submit results for a mystery box item. \
+             Variants outside the box's possible variants are rejected or reported as warnings, \
+             depending on its validation policy.",
+        )
+        .tag("Purchase Order")
+        .response::<200, Json<MysteryBoxResultsResponse>>()
+}
+
+/// POST /orders/{order_id}/items/{item_id}/mystery-box/results
+pub async fn add_mystery_box_results<S>(
+    State(state): State<AppState<S>>,
+    auth_session: AuthSession<S>,
+    Path(OrderIdPathItemIdPath { order_id, item_id }): Path<OrderIdPathItemIdPath>,
+    Json(body): Json<AddMysteryBoxResultsBody>,
+) -> Result<impl IntoApiResponse, AppError>
+where
+    S: PurchaseOrderService + UserService + Clone,
+{
+    let user = auth_session.user.as_ref().ok_or(AppError::Unauthorized)?;
+
+    let req = AddMysteryBoxResultsRequest {
+        user_id: user.id(),
+        order_id,
+        order_item_id: item_id,
+        results: body
+            .results
+            .into_iter()
+            .map(|result| MysteryBoxResult {
+                variant_id: result.variant_id,
+                owner_id: result.owner_id,
+            })
+            .collect(),
+    };
+
+    let response = state
+        .service
+        .add_mystery_box_results(req)
+        .await
+        .map_err(|_| AppError::InternalServerError)?;
+
+    Ok((StatusCode::OK, Json(response)))
+}
+
+pub fn create_add_mystery_box_results_docs(op: TransformOperation) -> TransformOperation {
+    op.summary("Add mystery box results")
+        .description(
+            "Add results to a mystery box item as boxes are opened. \
+             The item becomes pending once all results are in.",
+        )
+        .tag("Purchase Order")
+        .response::<200, Json<MysteryBoxResultsResponse>>()
+}
+
+#[derive(Deserialize, JsonSchema)]
+pub struct MysteryBoxResultPath {
+    pub order_id: PurchaseOrderId,
+    pub item_id: PurchaseOrderItemId,
+    pub line_item_id: PurchaseOrderLineItemId,
+}
+
+/// PATCH /orders/{order_id}/items/{item_id}/mystery-box/results/{line_item_id}
+pub async fn update_mystery_box_result<S>(
+    State(state): State<AppState<S>>,
+    auth_session: AuthSession<S>,
+    Path(MysteryBoxResultPath {
+        order_id,
+        item_id,
+        line_item_id,
+    }): Path<MysteryBoxResultPath>,
+    Json(body): Json<UpdateMysteryBoxResultBody>,
+) -> Result<impl IntoApiResponse, AppError>
+where
+    S: PurchaseOrderService + UserService + Clone,
+{
+    let user = auth_session.user.as_ref().ok_or(AppError::Unauthorized)?;
+
+    let req = UpdateMysteryBoxResultRequest {
+        user_id: user.id(),
+        order_id,
+        order_item_id: item_id,
+        line_item_id,
+        variant_id: body.variant_id,
+        owner_id: body.owner_id,
+    };
+
+    let response = state
+        .service
+        .update_mystery_box_result(req)
+        .await
+        .map_err(|_| AppError::InternalServerError)?;
+
+    Ok((StatusCode::OK, Json(response)))
+}
+
+pub fn create_update_mystery_box_result_docs(op: TransformOperation) -> TransformOperation {
+    op.summary("Update mystery box result")
+        .description(
+            "Correct the variant or reassign the owner of an unfulfilled mystery box result.",
+        )
+        .tag("Purchase Order")
+        .response::<200, Json<MysteryBoxResultsResponse>>()
+}
+
+/// DELETE /orders/{order_id}/items/{item_id}/mystery-box/results/{line_item_id}
+pub async fn remove_mystery_box_result<S>(
+    State(state): State<AppState<S>>,
+    auth_session: AuthSession<S>,
+    Path(MysteryBoxResultPath {
+        order_id,
+        item_id,
+        line_item_id,
+    }): Path<MysteryBoxResultPath>,
+) -> Result<impl IntoApiResponse, AppError>
+where
+    S: PurchaseOrderService + UserService + Clone,
+{
+    let user = auth_session.user.as_ref().ok_or(AppError::Unauthorized)?;
+
+    let req = RemoveMysteryBoxResultRequest {
+        user_id: user.id(),
+        order_id,
+        order_item_id: item_id,
+        line_item_id,
+    };
+
+    let order = state
+        .service
+        .remove_mystery_box_result(req)
+        .await
+        .map_err(|_| AppError::InternalServerError)?;
+
+    Ok((StatusCode::OK, Json(order)))
+}
+
+pub fn create_remove_mystery_box_result_docs(op: TransformOperation) -> TransformOperation {
+    op.summary("Remove mystery box result")
+        .description("Remove an unfulfilled mystery box result.")
+        .tag("Purchase Order")
+        .response::<200, Json<PurchaseOrder>>()
+}
+
+/// POST /orders/{order_id}/adjustments
+pub async fn add_order_adjustment<S>(
+    State(state): State<AppState<S>>,
+    auth_session: AuthSession<S>,
+    Path(OrderIdPath { order_id }): Path<OrderIdPath>,
+    Json(body): Json<AddOrderAdjustmentBody>,
+) -> Result<impl IntoApiResponse, AppError>
+where
+    S: PurchaseOrderService + UserService + Clone,
+{
+    let user = auth_session.user.as_ref().ok_or(AppError::Unauthorized)?;
+
+    let req = AddOrderAdjustmentRequest {
+        user_id: user.id(),
+        order_id,
+        kind: body.kind,
+        amount: body.amount,
+        allocation: body.allocation,
+        note: body.note,
+    };
+
+    let adjustment_id = state
+        .service
+        .add_order_adjustment(req)
+        .await
+        .map_err(|_| AppError::InternalServerError)?;
+
+    Ok((StatusCode::CREATED, Json(adjustment_id)))
+}
+
+pub fn create_add_order_adjustment_docs(op: TransformOperation) -> TransformOperation {
+    op.summary("Add order adjustment")
+        .description("Add a shipping, fee, tax or discount adjustment to a purchase order.")
+        .tag("Purchase Order")
+        .response::<201, Json<PurchaseOrderAdjustmentId>>()
+}
+
+#[derive(Deserialize, JsonSchema)]
+pub struct OrderIdPathAdjustmentIdPath {
+    pub order_id: PurchaseOrderId,
+    pub adjustment_id: PurchaseOrderAdjustmentId,
+}
+
+/// DELETE /orders/{order_id}/adjustments/{adjustment_id}
+pub async fn remove_order_adjustment<S>(
+    State(state): State<AppState<S>>,
+    auth_session: AuthSession<S>,
+    Path(OrderIdPathAdjustmentIdPath {
+        order_id,
+        adjustment_id,
+    }): Path<OrderIdPathAdjustmentIdPath>,
+) -> Result<impl IntoApiResponse, AppError>
+where
+    S: PurchaseOrderService + UserService + Clone,
+{
+    let user = auth_session.user.as_ref().ok_or(AppError::Unauthorized)?;
+
+    let req = RemoveOrderAdjustmentRequest {
+        user_id: user.id(),
+        order_id,
+        adjustment_id,
+    };
+
+    let order = state
+        .service
+        .remove_order_adjustment(req)
+        .await
+        .map_err(|_| AppError::InternalServerError)?;
+
+    Ok((StatusCode::OK, Json(order)))
+}
+
+pub fn create_remove_order_adjustment_docs(op: TransformOperation) -> TransformOperation {
+    op.summary("Remove order adjustment")
+        .description("Remove an adjustment from a purchase order.")
+        .tag("Purchase Order")
+        .response::<200, Json<PurchaseOrder>>()
+}
+
+#[derive(Deserialize, JsonSchema)]
+pub struct AddOrderAttachmentBody {
+    pub media_id: MediaId,
+    pub kind: PurchaseOrderAttachmentKind,
+    pub note: Option<String>,
+}
+
+/// POST /orders/{order_id}/attachments
+pub async fn add_order_attachment<S>(
+    State(state): State<AppState<S>>,
+    auth_session: AuthSession<S>,
+    Path(OrderIdPath { order_id }): Path<OrderIdPath>,
+    Json(body): Json<AddOrderAttachmentBody>,
+) -> Result<impl IntoApiResponse, AppError>
+where
+    S: PurchaseOrderService + UserService + Clone,
+{
+    let user = auth_session.user.as_ref().ok_or(AppError::Unauthorized)?;
+
+    let req = AddOrderAttachmentRequest {
+        user_id: user.id(),
+        order_id,
+        media_id: body.media_id,
+        kind: body.kind,
+        note: body.note,
+    };
+
+    let attachment = state
+        .service
+        .add_order_attachment(req)
+        .await
+        .map_err(|_| AppError::InternalServerError)?;
+
+    Ok((StatusCode::CREATED, Json(attachment)))
+}
+
+pub fn create_add_order_attachment_docs(op: TransformOperation) -> TransformOperation {
+    op.summary("Add order attachment")
+        .description("Attach an uploaded receipt, invoice or photo to a purchase order.")
+        .tag("Purchase Order")
+        .response::<201, Json<PurchaseOrderAttachment>>()
+}
+
+#[derive(Deserialize, JsonSchema)]
+pub struct OrderIdPathAttachmentIdPath {
+    pub order_id: PurchaseOrderId,
+    pub attachment_id: PurchaseOrderAttachmentId,
+}
+
+/// DELETE /orders/{order_id}/attachments/{attachment_id}
+pub async fn remove_order_attachment<S>(
+    State(state): State<AppState<S>>,
+    auth_session: AuthSession<S>,
+    Path(OrderIdPathAttachmentIdPath {
+        order_id,
+        attachment_id,
+    }): Path<OrderIdPathAttachmentIdPath>,
+) -> Result<impl IntoApiResponse, AppError>
+where
+    S: PurchaseOrderService + UserService + Clone,
+{
+    let user = auth_session.user.as_ref().ok_or(AppError::Unauthorized)?;
+
+    let req = RemoveOrderAttachmentRequest {
+        user_id: user.id(),
+        order_id,
+        attachment_id,
+    };
+
+    let order = state
+        .service
+        .remove_order_attachment(req)
+        .await
+        .map_err(|_| AppError::InternalServerError)?;
+
+    Ok((StatusCode::OK, Json(order)))
+}
+
+pub fn create_remove_order_attachment_docs(op: TransformOperation) -> TransformOperation {
+    op.summary("Remove order attachment")
+        .description("Remove an attachment from a purchase order.")
+        .tag("Purchase Order")
+        .response::<200, Json<PurchaseOrder>>()
+}
+
+pub async fn fulfill_order<S>(
+    State(state): State<AppState<S>>,
+    auth_session: AuthSession<S>,
+    Path(OrderIdPath { order_id }): Path<OrderIdPath>,
+) -> Result<impl IntoApiResponse, AppError>
+where
+    S: PurchaseOrderLifecycleService + UserService + Clone,
+{
+    let user = auth_session.user.as_ref().ok_or(AppError::Unauthorized)?;
+
+    let req = FulfillOrderRequest {
+        user_id: user.id(),
+        order_id,
+    };
+
+    let order = state
+        .service
+        .fulfill_order(&req)
+        .await
+        .map_err(|_| AppError::InternalServerError)?;
+
+    Ok((StatusCode::OK, Json(order)))
+}
+
+pub fn create_fulfill_order_docs(op: TransformOperation) -> TransformOperation {
+    op.summary("Fulfill order")
+        .description("Fulfill a purchase order.")
+        .tag("Purchase Order")
+        .response::<200, Json<PurchaseOrder>>()
+}
+
+#[derive(Deserialize, JsonSchema)]
+pub struct FulfillOrderItemsBody {
+    pub item_ids: Vec<PurchaseOrderItemId>,
+    pub line_item_ids: Vec<PurchaseOrderLineItemId>,
+}
+
+pub async fn fulfill_order_items<S>(
+    State(state): State<AppState<S>>,
+    auth_session: AuthSession<S>,
+    Path(OrderIdPath { order_id }): Path<OrderIdPath>,
+    Json(body): Json<FulfillOrderItemsBody>,
+) -> Result<impl IntoApiResponse, AppError>
+where
+    S: PurchaseOrderLifecycleService + UserService + Clone,
+{
+    let user = auth_session.user.as_ref().ok_or(AppError::Unauthorized)?;
+
+    let req = FulfillOrderItemsRequest {
+        user_id: user.id(),
+        order_id,
+        item_ids: body.item_ids,
+        line_item_ids: body.line_item_ids,
+    };
+
+    let order = state
+        .service
+        .fulfill_order_items(&req)
+        .await
+        .map_err(|_| AppError::InternalServerError)?;
+
+    Ok((StatusCode::OK, Json(order)))
+}
+
+pub fn create_fulfill_order_items_docs(op: TransformOperation) -> TransformOperation {
+    op.summary("Fulfill order items")
+        .description("Fulfill selected items or line items of a purchase order.")
+        .tag("Purchase Order")
+        .response::<200, Json<PurchaseOrder>>()
+}
+
+#[derive(Deserialize, JsonSchema)]
+pub struct CancelOrderBody {
+    pub reason: Option<String>,
+}
+
+pub async fn cancel_order<S>(
+    State(state): State<AppState<S>>,
+    auth_session: AuthSession<S>,
+    Path(OrderIdPath { order_id }): Path<OrderIdPath>,
+    Json(CancelOrderBody { reason }): Json<CancelOrderBody>,
+) -> Result<impl IntoApiResponse, AppError>
+where
+    S: PurchaseOrderLifecycleService + UserService + Clone,
+{
+    let user = auth_session.user.as_ref().ok_or(AppError::Unauthorized)?;
+    let req = CancelOrderRequest {
+        user_id: user.id(),
+        order_id,
+        reason,
+    };
+    let order = state
+        .service
+        .cancel_order(&req)
+        .await
+        .map_err(|_| AppError::InternalServerError)?;
+
+    Ok((StatusCode::OK, Json(order)))
+}
+
+pub fn create_cancel_order_docs(op: TransformOperation) -> TransformOperation {
+    op.summary("Cancel order")
+        .description("Cancel a purchase order.")
+        .tag("Purchase Order")
+        .response::<200, Json<PurchaseOrder>>()
+}
+
+#[derive(Deserialize, JsonSchema)]
+pub struct ReturnOrderItemsBody {
+    pub line_item_ids: Vec<PurchaseOrderLineItemId>,
+    pub refund: Option<Price>,
+    pub reason: Option<String>,
+    pub replace: bool,
+}
+
+/// POST /orders/{order_id}/returns
+pub async fn return_order_items<S>(
+    State(state): State<AppState<S>>,
+    auth_session: AuthSession<S>,
+    Path(OrderIdPath { order_id }): Path<OrderIdPath>,
+    Json(body): Json<ReturnOrderItemsBody>,
+) -> Result<impl IntoApiResponse, AppError>
+where
+    S: PurchaseOrderLifecycleService + UserService + Clone,
+{
+    let user = auth_session.user.as_ref().ok_or(AppError::Unauthorized)?;
+    let req = ReturnOrderItemsRequest {
+        user_id: user.id(),
+        order_id,
+        line_item_ids: body.line_item_ids,
+        refund: body.refund,
+        reason: body.reason,
+        replace: body.replace,
+    };
+    let order = state
+        .service
+        .return_order_items(&req)
+        .await
+        .map_err(|_| AppError::InternalServerError)?;
+
+    Ok((StatusCode::OK, Json(order)))
+}
+
+pub fn create_return_order_items_docs(op: TransformOperation) -> TransformOperation {
+    op.summary("Return order items")
+        .description(
+            "Return fulfilled line items to the seller, with optional refund and replacement.",
+        )
         .tag("Purchase Order")
         .response::<200, Json<PurchaseOrder>>()
 }
@@ -0,0 +1,197 @@
+use crate::{
+    auth::AuthSession, error::AppError, handlers::purchase_order::OrderIdPath, state::AppState,
+};
+use aide::{axum::IntoApiResponse, transform::TransformOperation};
+use axum::{
+    Json,
+    extract::{Path, State},
+    http::StatusCode,
+};
+use axum_login::AuthUser;
+use sawa_core::{
+    models::{
+        misc::Price,
+        purchase::{PurchaseOrderId, PurchaseOrderPayment, PurchaseOrderPaymentId},
+        settlement::{Balance, OrderSettlement},
+        user::UserId,
+    },
+    services::{
+        GetNettingRequest, GetOrderSettlementRequest, ListBalancesRequest, RecordPaymentRequest,
+        RemovePaymentRequest, SettlementService, UserService,
+    },
+};
+use schemars::JsonSchema;
+use serde::Deserialize;
+
+#[derive(Deserialize, JsonSchema)]
+pub struct RecordPaymentBody {
+    pub payer_id: UserId,
+    pub amount: Price,
+    pub note: Option<String>,
+}
+
+/// GET /orders/{order_id}/settlement
+pub async fn get_order_settlement<S>(
+    State(state): State<AppState<S>>,
+    auth_session: AuthSession<S>,
+    Path(OrderIdPath { order_id }): Path<OrderIdPath>,
+) -> Result<impl IntoApiResponse, AppError>
+where
+    S: SettlementService + UserService + Clone,
+{
+    let user = auth_session.user.as_ref().ok_or(AppError::Unauthorized)?;
+
+    let req = GetOrderSettlementRequest {
+        user_id: user.id(),
+        order_id,
+    };
+
+    let settlement = state
+        .service
+        .get_order_settlement(req)
+        .await
+        .map_err(|_| AppError::InternalServerError)?;
+
+    Ok((StatusCode::OK, Json(settlement)))
+}
+
+pub fn create_get_order_settlement_docs(op: TransformOperation) -> TransformOperation {
+    op.summary("Get order settlement")
+        .description("Get what each participant of a group-buy order owes the creator.")
+        .tag("Settlement")
+        .response::<200, Json<OrderSettlement>>()
+}
+
+/// POST /orders/{order_id}/payments
+pub async fn record_payment<S>(
+    State(state): State<AppState<S>>,
+    auth_session: AuthSession<S>,
+    Path(OrderIdPath { order_id }): Path<OrderIdPath>,
+    Json(body): Json<RecordPaymentBody>,
+) -> Result<impl IntoApiResponse, AppError>
+where
+    S: SettlementService + UserService + Clone,
+{
+    let user = auth_session.user.as_ref().ok_or(AppError::Unauthorized)?;
+
+    let req = RecordPaymentRequest {
+        user_id: user.id(),
+        order_id,
+        payer_id: body.payer_id,
+        amount: body.amount,
+        note: body.note,
+    };
+
+    let payment = state
+        .service
+        .record_payment(req)
+        .await
+        .map_err(|_| AppError::InternalServerError)?;
+
+    Ok((StatusCode::CREATED, Json(payment)))
+}
+
+pub fn create_record_payment_docs(op: TransformOperation) -> TransformOperation {
+    op.summary("Record payment")
+        .description("Record a payment from a participant to the order creator.")
+        .tag("Settlement")
+        .response::<201, Json<PurchaseOrderPayment>>()
+}
+
+#[derive(Deserialize, JsonSchema)]
+pub struct OrderIdPathPaymentIdPath {
+    pub order_id: PurchaseOrderId,
+    pub payment_id: PurchaseOrderPaymentId,
+}
+
+/// DELETE /orders/{order_id}/payments/{payment_id}
+pub async fn remove_payment<S>(
+    State(state): State<AppState<S>>,
+    auth_session: AuthSession<S>,
+    Path(OrderIdPathPaymentIdPath {
+        order_id,
+        payment_id,
+    }): Path<OrderIdPathPaymentIdPath>,
+) -> Result<impl IntoApiResponse, AppError>
+where
+    S: SettlementService + UserService + Clone,
+{
+    let user = auth_session.user.as_ref().ok_or(AppError::Unauthorized)?;
+
+    let req = RemovePaymentRequest {
+        user_id: user.id(),
+        order_id,
+        payment_id,
+    };
+
+    let settlement = state
+        .service
+        .remove_payment(req)
+        .await
+        .map_err(|_| AppError::InternalServerError)?;
+
+    Ok((StatusCode::OK, Json(settlement)))
+}
+
+pub fn create_remove_payment_docs(op: TransformOperation) -> TransformOperation {
+    op.summary("Remove payment")
+        .description("Remove a recorded payment from a purchase order.")
+        .tag("Settlement")
+        .response::<200, Json<OrderSettlement>>()
+}
+
+/// GET /settlements/balances
+pub async fn list_balances<S>(
+    State(state): State<AppState<S>>,
+    auth_session: AuthSession<S>,
+) -> Result<impl IntoApiResponse, AppError>
+where
+    S: SettlementService + UserService + Clone,
+{
+    let user = auth_session.user.as_ref().ok_or(AppError::Unauthorized)?;
+
+    let req = ListBalancesRequest { user_id: user.id() };
+
+    let balances = state
+        .service
+        .list_balances(req)
+        .await
+        .map_err(|_| AppError::InternalServerError)?;
+
+    Ok((StatusCode::OK, Json(balances)))
+}
+
+pub fn create_list_balances_docs(op: TransformOperation) -> TransformOperation {
+    op.summary("List balances")
+        .description("List outstanding balances with other users across all orders.")
+        .tag("Settlement")
+        .response::<200, Json<Vec<Balance>>>()
+}
+
+/// GET /settlements/netting
+pub async fn get_netting<S>(
+    State(state): State<AppState<S>>,
+    auth_session: AuthSession<S>,
+) -> Result<impl IntoApiResponse, AppError>
+where
+    S: SettlementService + UserService + Clone,
+{
+    let user = auth_session.user.as_ref().ok_or(AppError::Unauthorized)?;
+
+    let req = GetNettingRequest { user_id: user.id() };
+
+    let balances = state
+        .service
+        .get_netting(req)
+        .await
+        .map_err(|_| AppError::InternalServerError)?;
+
+    Ok((StatusCode::OK, Json(balances)))
+}
+
+pub fn create_get_netting_docs(op: TransformOperation) -> TransformOperation {
+    op.summary("Get netting")
+        .description("Net the balances of all shared orders into the fewest transfers.")
+        .tag("Settlement")
+        .response::<200, Json<Vec<Balance>>>()
+}
@@ -0,0 +1,254 @@
+use crate::{auth::AuthSession, error::AppError, state::AppState};
+use aide::{axum::IntoApiResponse, transform::TransformOperation};
+use axum::{
+    Json,
+    extract::{Query, State},
+    http::StatusCode,
+};
+use axum_login::AuthUser;
+use chrono::NaiveDate;
+use sawa_core::{
+    models::statistics::{
+        DateRange, MonthlySpending, MysteryBoxStatistics, ProductStatistics, SeriesPurchaseCount,
+        StatusCount, TagStatistics,
+    },
+    services::{
+        GetMostBoughtSeriesRequest, GetStatisticsError, GetStatisticsRequest, StatisticsService,
+        UserService,
+    },
+};
+use schemars::JsonSchema;
+use serde::Deserialize;
+
+#[derive(Deserialize, JsonSchema)]
+pub struct MostBoughtSeriesQuery {
+    /// The first day to include
+    pub from: Option<NaiveDate>,
+
+    /// The last day to include
+    pub to: Option<NaiveDate>,
+
+    /// The maximum number of series to return, 10 by default
+    pub limit: Option<usize>,
+}
+
+fn map_statistics_error(e: GetStatisticsError) -> AppError {
+    match e {
+        GetStatisticsError::InvalidRange => AppError::BadRequest(e.to_string()),
+        GetStatisticsError::Repository(_) => AppError::InternalServerError,
+    }
+}
+
+/// GET /statistics/spending
+pub async fn get_monthly_spending<S>(
+    State(state): State<AppState<S>>,
+    auth_session: AuthSession<S>,
+    Query(range): Query<DateRange>,
+) -> Result<impl IntoApiResponse, AppError>
+where
+    S: StatisticsService + UserService + Clone,
+{
+    let user = auth_session.user.as_ref().ok_or(AppError::Unauthorized)?;
+
+    let req = GetStatisticsRequest {
+        user_id: user.id(),
+        range,
+    };
+
+    let spending = state
+        .service
+        .get_monthly_spending(req)
+        .await
+        .map_err(map_statistics_error)?;
+
+    Ok((StatusCode::OK, Json(spending)))
+}
+
+pub fn create_get_monthly_spending_docs(op: TransformOperation) -> TransformOperation {
+    op.summary("Get monthly spending")
+        .description(
+            "Get what the current user spent per month and currency on items received from \
+            fulfilled orders, using their landed costs. Returned items are left out.",
+        )
+        .tag("Statistics")
+        .response::<200, Json<Vec<MonthlySpending>>>()
+}
+
+/// GET /statistics/statuses
+pub async fn get_status_counts<S>(
+    State(state): State<AppState<S>>,
+    auth_session: AuthSession<S>,
+    Query(range): Query<DateRange>,
+) -> Result<impl IntoApiResponse, AppError>
+where
+    S: StatisticsService + UserService + Clone,
+{
+    let user = auth_session.user.as_ref().ok_or(AppError::Unauthorized)?;
+
+    let req = GetStatisticsRequest {
+        user_id: user.id(),
+        range,
+    };
+
+    let counts = state
+        .service
+        .get_status_counts(req)
+        .await
+        .map_err(map_statistics_error)?;
+
+    Ok((StatusCode::OK, Json(counts)))
+}
+
+pub fn create_get_status_counts_docs(op: TransformOperation) -> TransformOperation {
+    op.summary("Get item counts by status")
+        .description(
+            "Count the product instances the current user owns in each status, \
+            for instances created within the range.",
+        )
+        .tag("Statistics")
+        .response::<200, Json<Vec<StatusCount>>>()
+}
+
+/// GET /statistics/tags
+pub async fn get_tag_statistics<S>(
+    State(state): State<AppState<S>>,
+    auth_session: AuthSession<S>,
+    Query(range): Query<DateRange>,
+) -> Result<impl IntoApiResponse, AppError>
+where
+    S: StatisticsService + UserService + Clone,
+{
+    let user = auth_session.user.as_ref().ok_or(AppError::Unauthorized)?;
+
+    let req = GetStatisticsRequest {
+        user_id: user.id(),
+        range,
+    };
+
+    let statistics = state
+        .service
+        .get_tag_statistics(req)
+        .await
+        .map_err(map_statistics_error)?;
+
+    Ok((StatusCode::OK, Json(statistics)))
+}
+
+pub fn create_get_tag_statistics_docs(op: TransformOperation) -> TransformOperation {
+    op.summary("Get statistics per tag")
+        .description(
+            "Count the product instances the current user owns per tag of their variant, \
+            and sum their landed costs per currency. Most owned tags come first.",
+        )
+        .tag("Statistics")
+        .response::<200, Json<Vec<TagStatistics>>>()
+}
+
+/// GET /statistics/products
+pub async fn get_product_statistics<S>(
+    State(state): State<AppState<S>>,
+    auth_session: AuthSession<S>,
+    Query(range): Query<DateRange>,
+) -> Result<impl IntoApiResponse, AppError>
+where
+    S: StatisticsService + UserService + Clone,
+{
+    let user = auth_session.user.as_ref().ok_or(AppError::Unauthorized)?;
+
+    let req = GetStatisticsRequest {
+        user_id: user.id(),
+        range,
+    };
+
+    let statistics = state
+        .service
+        .get_product_statistics(req)
+        .await
+        .map_err(map_statistics_error)?;
+
+    Ok((StatusCode::OK, Json(statistics)))
+}
+
+pub fn create_get_product_statistics_docs(op: TransformOperation) -> TransformOperation {
+    op.summary("Get statistics per product")
+        .description(
+            "Count the product instances the current user owns per product, \
+            and sum their landed costs per currency. Most owned products come first.",
+        )
+        .tag("Statistics")
+        .response::<200, Json<Vec<ProductStatistics>>>()
+}
+
+/// GET /statistics/series
+pub async fn get_most_bought_series<S>(
+    State(state): State<AppState<S>>,
+    auth_session: AuthSession<S>,
+    Query(query): Query<MostBoughtSeriesQuery>,
+) -> Result<impl IntoApiResponse, AppError>
+where
+    S: StatisticsService + UserService + Clone,
+{
+    let user = auth_session.user.as_ref().ok_or(AppError::Unauthorized)?;
+
+    let req = GetMostBoughtSeriesRequest {
+        user_id: user.id(),
+        range: DateRange {
+            from: query.from,
+            to: query.to,
+        },
+        limit: query.limit.unwrap_or(10),
+    };
+
+    let series = state
+        .service
+        .get_most_bought_series(req)
+        .await
+        .map_err(map_statistics_error)?;
+
+    Ok((StatusCode::OK, Json(series)))
+}
+
+pub fn create_get_most_bought_series_docs(op: TransformOperation) -> TransformOperation {
+    op.summary("Get most-bought series")
+        .description(
+            "Get the products the current user received the most items of from fulfilled \
+            orders. Items drawn from mystery boxes count for the product they belong to.",
+        )
+        .tag("Statistics")
+        .response::<200, Json<Vec<SeriesPurchaseCount>>>()
+}
+
+/// GET /statistics/mystery-boxes
+pub async fn get_mystery_box_statistics<S>(
+    State(state): State<AppState<S>>,
+    auth_session: AuthSession<S>,
+    Query(range): Query<DateRange>,
+) -> Result<impl IntoApiResponse, AppError>
+where
+    S: StatisticsService + UserService + Clone,
+{
+    let user = auth_session.user.as_ref().ok_or(AppError::Unauthorized)?;
+
+    let req = GetStatisticsRequest {
+        user_id: user.id(),
+        range,
+    };
+
+    let statistics = state
+        .service
+        .get_mystery_box_statistics(req)
+        .await
+        .map_err(map_statistics_error)?;
+
+    Ok((StatusCode::OK, Json(statistics)))
+}
+
+pub fn create_get_mystery_box_statistics_docs(op: TransformOperation) -> TransformOperation {
+    op.summary("Get mystery-box statistics")
+        .description(
+            "Get how many items and different variants the current user drew from each \
+            mystery-box variant, what they cost, and the average cost per variant obtained.",
+        )
+        .tag("Statistics")
+        .response::<200, Json<Vec<MysteryBoxStatistics>>>()
+}
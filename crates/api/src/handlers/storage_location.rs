@@ -0,0 +1,264 @@
+use crate::{auth::AuthSession, error::AppError, state::AppState};
+use aide::{axum::IntoApiResponse, transform::TransformOperation};
+use axum::{
+    Json,
+    extract::{Path, Query, State},
+    http::StatusCode,
+};
+use axum_login::AuthUser;
+use sawa_core::{
+    models::{
+        misc::NonEmptyString,
+        product::{ProductInstance, ProductInstanceId},
+        storage::{LocatedProductInstance, StorageLocation, StorageLocationId},
+    },
+    services::{
+        CreateStorageLocationError, CreateStorageLocationRequest, DeleteStorageLocationError,
+        DeleteStorageLocationRequest, ListStorageLocationsRequest, LocateProductInstancesRequest,
+        MoveProductInstancesError, MoveProductInstancesRequest, StorageLocationService,
+        UpdateStorageLocationError, UpdateStorageLocationRequest, UserService,
+    },
+};
+use schemars::JsonSchema;
+use serde::Deserialize;
+
+#[derive(Deserialize, JsonSchema)]
+pub struct StorageLocationBody {
+    pub name: NonEmptyString,
+    /// The location containing this one, or `null` for a top-level location
+    pub parent_id: Option<StorageLocationId>,
+}
+
+#[derive(Deserialize, JsonSchema)]
+pub struct LocationIdPath {
+    pub location_id: StorageLocationId,
+}
+
+#[derive(Deserialize, JsonSchema)]
+pub struct MoveProductInstancesBody {
+    pub instance_ids: Vec<ProductInstanceId>,
+    /// The target location, or `null` to clear the location
+    pub location_id: Option<StorageLocationId>,
+}
+
+#[derive(Deserialize, JsonSchema)]
+pub struct LocateProductInstancesQuery {
+    /// Part of the variant name to search for
+    pub q: String,
+}
+
+/// GET /locations
+pub async fn list_storage_locations<S>(
+    State(state): State<AppState<S>>,
+    auth_session: AuthSession<S>,
+) -> Result<impl IntoApiResponse, AppError>
+where
+    S: StorageLocationService + UserService + Clone,
+{
+    let user = auth_session.user.as_ref().ok_or(AppError::Unauthorized)?;
+
+    let locations = state
+        .service
+        .list_storage_locations(ListStorageLocationsRequest { user_id: user.id() })
+        .await
+        .map_err(|_| AppError::InternalServerError)?;
+
+    Ok((StatusCode::OK, Json(locations)))
+}
+
+pub fn create_list_storage_locations_docs(op: TransformOperation) -> TransformOperation {
+    op.summary("List storage locations")
+        .description("List all storage locations of the current user.")
+        .tag("Storage")
+        .response::<200, Json<Vec<StorageLocation>>>()
+}
+
+/// POST /locations
+pub async fn create_storage_location<S>(
+    State(state): State<AppState<S>>,
+    auth_session: AuthSession<S>,
+    Json(body): Json<StorageLocationBody>,
+) -> Result<impl IntoApiResponse, AppError>
+where
+    S: StorageLocationService + UserService + Clone,
+{
+    let user = auth_session.user.as_ref().ok_or(AppError::Unauthorized)?;
+
+    let req = CreateStorageLocationRequest {
+        user_id: user.id(),
+        name: body.name,
+        parent_id: body.parent_id,
+    };
+
+    let location = state
+        .service
+        .create_storage_location(req)
+        .await
+        .map_err(|e| match e {
+            CreateStorageLocationError::ParentNotFound { .. } => {
+                AppError::BadRequest(e.to_string())
+            }
+            _ => AppError::InternalServerError,
+        })?;
+
+    Ok((StatusCode::CREATED, Json(location)))
+}
+
+pub fn create_create_storage_location_docs(op: TransformOperation) -> TransformOperation {
+    op.summary("Create storage location")
+        .description("Create a storage location, such as a room, shelf or box.")
+        .tag("Storage")
+        .response::<201, Json<StorageLocation>>()
+}
+
+/// PUT /locations/{location_id}
+pub async fn update_storage_location<S>(
+    State(state): State<AppState<S>>,
+    auth_session: AuthSession<S>,
+    Path(LocationIdPath { location_id }): Path<LocationIdPath>,
+    Json(body): Json<StorageLocationBody>,
+) -> Result<impl IntoApiResponse, AppError>
+where
+    S: StorageLocationService + UserService + Clone,
+{
+    let user = auth_session.user.as_ref().ok_or(AppError::Unauthorized)?;
+
+    let req = UpdateStorageLocationRequest {
+        id: location_id,
+        user_id: user.id(),
+        name: body.name,
+        parent_id: body.parent_id,
+    };
+
+    let location = state
+        .service
+        .update_storage_location(req)
+        .await
+        .map_err(|e| match e {
+            UpdateStorageLocationError::NotFound => AppError::NotFound,
+            UpdateStorageLocationError::ParentNotFound { .. }
+            | UpdateStorageLocationError::Cycle => AppError::BadRequest(e.to_string()),
+            _ => AppError::InternalServerError,
+        })?;
+
+    Ok((StatusCode::OK, Json(location)))
+}
+
+pub fn create_update_storage_location_docs(op: TransformOperation) -> TransformOperation {
+    op.summary("Update storage location")
+        .description("Rename a storage location or move it into another one.")
+        .tag("Storage")
+        .response::<200, Json<StorageLocation>>()
+}
+
+/// DELETE /locations/{location_id}
+pub async fn delete_storage_location<S>(
+    State(state): State<AppState<S>>,
+    auth_session: AuthSession<S>,
+    Path(LocationIdPath { location_id }): Path<LocationIdPath>,
+) -> Result<impl IntoApiResponse, AppError>
+where
+    S: StorageLocationService + UserService + Clone,
+{
+    let user = auth_session.user.as_ref().ok_or(AppError::Unauthorized)?;
+
+    let req = DeleteStorageLocationRequest {
+        id: location_id,
+        user_id: user.id(),
+    };
+
+    state
+        .service
+        .delete_storage_location(req)
+        .await
+        .map_err(|e| match e {
+            DeleteStorageLocationError::NotFound => AppError::NotFound,
+            DeleteStorageLocationError::NotEmpty => AppError::BadRequest(e.to_string()),
+            _ => AppError::InternalServerError,
+        })?;
+
+    Ok(StatusCode::OK)
+}
+
+pub fn create_delete_storage_location_docs(op: TransformOperation) -> TransformOperation {
+    op.summary("Delete storage location")
+        .description("Delete a storage location that contains no other locations or goods.")
+        .tag("Storage")
+        .response::<200, ()>()
+}
+
+/// POST /goods/move
+pub async fn move_product_instances<S>(
+    State(state): State<AppState<S>>,
+    auth_session: AuthSession<S>,
+    Json(body): Json<MoveProductInstancesBody>,
+) -> Result<impl IntoApiResponse, AppError>
+where
+    S: StorageLocationService + UserService + Clone,
+{
+    let user = auth_session.user.as_ref().ok_or(AppError::Unauthorized)?;
+
+    let req = MoveProductInstancesRequest {
+        user_id: user.id(),
+        instance_ids: body.instance_ids,
+        location_id: body.location_id,
+    };
+
+    let instances = state
+        .service
+        .move_product_instances(req)
+        .await
+        .map_err(|e| match e {
+            MoveProductInstancesError::LocationNotFound
+            | MoveProductInstancesError::InstanceNotFound { .. }
+            | MoveProductInstancesError::NotHeld { .. } => AppError::BadRequest(e.to_string()),
+            _ => AppError::InternalServerError,
+        })?;
+
+    Ok((StatusCode::OK, Json(instances)))
+}
+
+pub fn create_move_product_instances_docs(op: TransformOperation) -> TransformOperation {
+    op.summary("Move goods")
+        .description(
+            "Move goods held by the current user into a storage location. \
+            Either all goods are moved, or none.",
+        )
+        .tag("Storage")
+        .response::<200, Json<Vec<ProductInstance>>>()
+}
+
+/// GET /goods/search
+pub async fn locate_product_instances<S>(
+    State(state): State<AppState<S>>,
+    auth_session: AuthSession<S>,
+    Query(LocateProductInstancesQuery { q }): Query<LocateProductInstancesQuery>,
+) -> Result<impl IntoApiResponse, AppError>
+where
+    S: StorageLocationService + UserService + Clone,
+{
+    let user = auth_session.user.as_ref().ok_or(AppError::Unauthorized)?;
+
+    let req = LocateProductInstancesRequest {
+        user_id: user.id(),
+        query: q,
+    };
+
+    let instances = state
+        .service
+        .locate_product_instances(req)
+        .await
+        .map_err(|_| AppError::InternalServerError)?;
+
+    Ok((StatusCode::OK, Json(instances)))
+}
+
+pub fn create_locate_product_instances_docs(op: TransformOperation) -> TransformOperation {
+    op.summary("Find goods")
+        .description(
+            "Find the goods of the current user by variant name, \
+            with the storage location each of them is kept in.",
+        )
+        .tag("Storage")
+        .response::<200, Json<Vec<LocatedProductInstance>>>()
+}
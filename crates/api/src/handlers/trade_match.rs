@@ -0,0 +1,152 @@
+use crate::{auth::AuthSession, error::AppError, state::AppState};
+use aide::{axum::IntoApiResponse, transform::TransformOperation};
+use axum::{
+    Json,
+    extract::{Query, State},
+    http::StatusCode,
+};
+use axum_login::AuthUser;
+use sawa_core::{
+    models::{
+        transfer::{TradeMatch, UserTransactionId},
+        wishlist::Wishlist,
+    },
+    services::{
+        FindTradeMatchesError, FindTradeMatchesRequest, ProposeTradeMatchError,
+        ProposeTradeMatchRequest, SetTradeOptInRequest, TradeMatchService, UserService,
+    },
+};
+use schemars::JsonSchema;
+use serde::Deserialize;
+
+#[derive(Deserialize, JsonSchema)]
+pub struct SetTradeOptInBody {
+    pub open_to_trades: bool,
+}
+
+#[derive(Deserialize, JsonSchema)]
+pub struct FindTradeMatchesQuery {
+    /// The most users in a match, 3 by default. Use 2 for pairs only.
+    pub max_cycle_length: Option<usize>,
+}
+
+/// PUT /trades/opt-in
+pub async fn set_trade_opt_in<S>(
+    State(state): State<AppState<S>>,
+    auth_session: AuthSession<S>,
+    Json(body): Json<SetTradeOptInBody>,
+) -> Result<impl IntoApiResponse, AppError>
+where
+    S: TradeMatchService + UserService + Clone,
+{
+    let user = auth_session.user.as_ref().ok_or(AppError::Unauthorized)?;
+
+    let req = SetTradeOptInRequest {
+        user_id: user.id(),
+        open_to_trades: body.open_to_trades,
+    };
+
+    let wishlist = state
+        .service
+        .set_trade_opt_in(req)
+        .await
+        .map_err(|_| AppError::InternalServerError)?;
+
+    Ok((StatusCode::OK, Json(wishlist)))
+}
+
+pub fn create_set_trade_opt_in_docs(op: TransformOperation) -> TransformOperation {
+    op.summary("Opt in to trade matching")
+        .description(
+            "Take part in trade matching, or stop taking part. \
+            The unsatisfied items of the wishlist are the variants wanted in exchange.",
+        )
+        .tag("Trade")
+        .response::<200, Json<Wishlist>>()
+}
+
+/// GET /trades/matches
+pub async fn find_trade_matches<S>(
+    State(state): State<AppState<S>>,
+    auth_session: AuthSession<S>,
+    Query(query): Query<FindTradeMatchesQuery>,
+) -> Result<impl IntoApiResponse, AppError>
+where
+    S: TradeMatchService + UserService + Clone,
+{
+    let user = auth_session.user.as_ref().ok_or(AppError::Unauthorized)?;
+
+    let req = FindTradeMatchesRequest {
+        user_id: user.id(),
+        max_cycle_length: query.max_cycle_length.unwrap_or(3),
+    };
+
+    let matches = state
+        .service
+        .find_trade_matches(req)
+        .await
+        .map_err(|e| match e {
+            FindTradeMatchesError::NotOptedIn => AppError::BadRequest(e.to_string()),
+            _ => AppError::InternalServerError,
+        })?;
+
+    Ok((StatusCode::OK, Json(matches)))
+}
+
+pub fn create_find_trade_matches_docs(op: TransformOperation) -> TransformOperation {
+    op.summary("Find trade matches")
+        .description(
+            "Find pairs and cycles of users, starting with the current user, in which everyone \
+            gives spares and receives wanted variants. \
+            Matches satisfying the most wants come first.",
+        )
+        .tag("Trade")
+        .response::<200, Json<Vec<TradeMatch>>>()
+}
+
+/// POST /trades/proposals
+pub async fn propose_trade_match<S>(
+    State(state): State<AppState<S>>,
+    auth_session: AuthSession<S>,
+    Json(body): Json<TradeMatch>,
+) -> Result<impl IntoApiResponse, AppError>
+where
+    S: TradeMatchService + UserService + Clone,
+{
+    let user = auth_session.user.as_ref().ok_or(AppError::Unauthorized)?;
+
+    let req = ProposeTradeMatchRequest {
+        user_id: user.id(),
+        trade_match: body,
+    };
+
+    let transactions = state
+        .service
+        .propose_trade_match(req)
+        .await
+        .map_err(|e| match e {
+            e @ (ProposeTradeMatchError::NotOptedIn
+            | ProposeTradeMatchError::MatchNotAvailable
+            | ProposeTradeMatchError::CreateTransaction(_)) => AppError::BadRequest(e.to_string()),
+            _ => AppError::InternalServerError,
+        })?;
+
+    let transaction_ids: Vec<UserTransactionId> = transactions
+        .into_iter()
+        .map(|transaction| transaction.id)
+        .collect();
+
+    Ok((StatusCode::CREATED, Json(transaction_ids)))
+}
+
+pub fn create_propose_trade_match_docs(op: TransformOperation) -> TransformOperation {
+    op.summary("Propose trade match")
+        .description(
+            "Create a transaction for every leg of a match found for the current user. \
+            The current user's spares are locked right away, the other senders must accept \
+            their legs before their spares are locked. \
+            Returns the ids of the created transactions.",
+        )
+        .tag("Trade")
+        .response::<201, Json<Vec<UserTransactionId>>>()
+}
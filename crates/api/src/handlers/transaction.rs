@@ -0,0 +1,167 @@
+use crate::{auth::AuthSession, error::AppError, state::AppState};
+use aide::{axum::IntoApiResponse, transform::TransformOperation};
+use axum::{
+    Json,
+    extract::{Path, State},
+    http::StatusCode,
+};
+use axum_login::AuthUser;
+use chrono::NaiveDate;
+use sawa_core::{
+    models::{
+        product::ProductInstanceId,
+        transfer::{UserTransaction, UserTransactionId},
+        user::UserId,
+    },
+    services::{
+        CancelTransactionError, CancelTransactionRequest, CompleteTransactionError,
+        CompleteTransactionRequest, CreateLendingTransactionRequest, CreateTransactionError,
+        TransactionLifecycleService, UserService,
+    },
+};
+use schemars::JsonSchema;
+use serde::Deserialize;
+
+#[derive(Deserialize, JsonSchema)]
+pub struct TransactionIdPath {
+    pub transaction_id: UserTransactionId,
+}
+
+#[derive(Deserialize, JsonSchema)]
+pub struct CreateLendingTransactionBody {
+    /// The user to leave the goods with
+    pub to_user_id: UserId,
+    pub items: Vec<ProductInstanceId>,
+    /// When the goods should be given back
+    pub return_by: Option<NaiveDate>,
+}
+
+/// POST /transactions/lending
+pub async fn create_lending_transaction<S>(
+    State(state): State<AppState<S>>,
+    auth_session: AuthSession<S>,
+    Json(body): Json<CreateLendingTransactionBody>,
+) -> Result<impl IntoApiResponse, AppError>
+where
+    S: TransactionLifecycleService + UserService + Clone,
+{
+    let user = auth_session.user.as_ref().ok_or(AppError::Unauthorized)?;
+
+    let req = CreateLendingTransactionRequest {
+        from_user_id: user.id(),
+        to_user_id: body.to_user_id,
+        items: body.items,
+        return_by: body.return_by,
+    };
+
+    let transaction = state
+        .service
+        .create_lending_transaction(req)
+        .await
+        .map_err(|e| match e {
+            e @ (CreateTransactionError::ItemNotFound
+            | CreateTransactionError::ItemNotOwned
+            | CreateTransactionError::ItemNotActive
+            | CreateTransactionError::ItemNotHeld) => AppError::BadRequest(e.to_string()),
+            _ => AppError::InternalServerError,
+        })?;
+
+    Ok((StatusCode::CREATED, Json(transaction)))
+}
+
+pub fn create_create_lending_transaction_docs(op: TransformOperation) -> TransformOperation {
+    op.summary("Lend goods")
+        .description(
+            "Leave goods owned and held by the current user with another user, e.g. until the \
+            next meetup. The goods are locked until the receiver confirms receipt, which makes \
+            them the holder while the current user keeps ownership.",
+        )
+        .tag("Transaction")
+        .response::<201, Json<UserTransaction>>()
+}
+
+/// POST /transactions/{transaction_id}/complete
+pub async fn complete_transaction<S>(
+    State(state): State<AppState<S>>,
+    auth_session: AuthSession<S>,
+    Path(TransactionIdPath { transaction_id }): Path<TransactionIdPath>,
+) -> Result<impl IntoApiResponse, AppError>
+where
+    S: TransactionLifecycleService + UserService + Clone,
+{
+    let user = auth_session.user.as_ref().ok_or(AppError::Unauthorized)?;
+
+    let req = CompleteTransactionRequest {
+        transaction_id,
+        user_id: user.id(),
+    };
+
+    let transaction = state
+        .service
+        .complete_transaction(req)
+        .await
+        .map_err(|e| match e {
+            CompleteTransactionError::NotFound | CompleteTransactionError::PermissionDenied => {
+                AppError::NotFound
+            }
+            e @ (CompleteTransactionError::NotAccepted
+            | CompleteTransactionError::AlreadyCompleted
+            | CompleteTransactionError::Cancelled) => AppError::BadRequest(e.to_string()),
+            _ => AppError::InternalServerError,
+        })?;
+
+    Ok((StatusCode::OK, Json(transaction)))
+}
+
+pub fn create_complete_transaction_docs(op: TransformOperation) -> TransformOperation {
+    op.summary("Confirm receipt")
+        .description(
+            "Confirm receipt of the goods of a pending transaction as its receiver. \
+            The receiver becomes the holder of the goods, and their owner unless the goods \
+            are delivered or lent.",
+        )
+        .tag("Transaction")
+        .response::<200, Json<UserTransaction>>()
+}
+
+/// POST /transactions/{transaction_id}/cancel
+pub async fn cancel_transaction<S>(
+    State(state): State<AppState<S>>,
+    auth_session: AuthSession<S>,
+    Path(TransactionIdPath { transaction_id }): Path<TransactionIdPath>,
+) -> Result<impl IntoApiResponse, AppError>
+where
+    S: TransactionLifecycleService + UserService + Clone,
+{
+    let user = auth_session.user.as_ref().ok_or(AppError::Unauthorized)?;
+
+    let req = CancelTransactionRequest {
+        transaction_id,
+        user_id: user.id(),
+    };
+
+    let transaction = state
+        .service
+        .cancel_transaction(req)
+        .await
+        .map_err(|e| match e {
+            CancelTransactionError::NotFound | CancelTransactionError::PermissionDenied => {
+                AppError::NotFound
+            }
+            e @ (CancelTransactionError::AlreadyCompleted
+            | CancelTransactionError::AlreadyCancelled) => AppError::BadRequest(e.to_string()),
+            _ => AppError::InternalServerError,
+        })?;
+
+    Ok((StatusCode::OK, Json(transaction)))
+}
+
+pub fn create_cancel_transaction_docs(op: TransformOperation) -> TransformOperation {
+    op.summary("Cancel transaction")
+        .description(
+            "Cancel a proposed or pending transaction as its sender or receiver. \
+            Locked goods become active again.",
+        )
+        .tag("Transaction")
+        .response::<200, Json<UserTransaction>>()
+}
@@ -0,0 +1,265 @@
+use crate::{auth::AuthSession, error::AppError, state::AppState};
+use aide::{axum::IntoApiResponse, transform::TransformOperation};
+use axum::{
+    Json,
+    extract::{Path, State},
+    http::StatusCode,
+};
+use axum_login::AuthUser;
+use sawa_core::{
+    models::{
+        misc::Price,
+        product::ProductVariantId,
+        user::UserId,
+        wishlist::{Wishlist, WishlistItemId, WishlistPriority},
+    },
+    services::{
+        AddWishlistItemError, AddWishlistItemRequest, GetWishlistError, GetWishlistRequest,
+        RemoveWishlistItemError, RemoveWishlistItemRequest, SetWishlistVisibilityRequest,
+        UpdateWishlistItemError, UpdateWishlistItemRequest, UserService, WishlistService,
+    },
+};
+use schemars::JsonSchema;
+use serde::Deserialize;
+use std::num::NonZeroU32;
+
+#[derive(Deserialize, JsonSchema)]
+pub struct AddWishlistItemBody {
+    pub variant_id: ProductVariantId,
+    #[serde(default)]
+    pub priority: WishlistPriority,
+    pub desired_quantity: NonZeroU32,
+    pub max_price: Option<Price>,
+    #[serde(default)]
+    pub note: String,
+}
+
+#[derive(Deserialize, JsonSchema)]
+pub struct UpdateWishlistItemBody {
+    pub priority: Option<WishlistPriority>,
+    pub desired_quantity: Option<NonZeroU32>,
+    pub max_price: Option<Price>,
+    pub note: Option<String>,
+}
+
+#[derive(Deserialize, JsonSchema)]
+pub struct SetWishlistVisibilityBody {
+    pub is_public: bool,
+}
+
+#[derive(Deserialize, JsonSchema)]
+pub struct WishlistItemIdPath {
+    pub item_id: WishlistItemId,
+}
+
+#[derive(Deserialize, JsonSchema)]
+pub struct UserIdPath {
+    pub user_id: UserId,
+}
+
+/// GET /wishlist
+pub async fn get_wishlist<S>(
+    State(state): State<AppState<S>>,
+    auth_session: AuthSession<S>,
+) -> Result<impl IntoApiResponse, AppError>
+where
+    S: WishlistService + UserService + Clone,
+{
+    let user = auth_session.user.as_ref().ok_or(AppError::Unauthorized)?;
+
+    let wishlist = state
+        .service
+        .get_wishlist(GetWishlistRequest {
+            user_id: user.id(),
+            viewer_id: Some(user.id()),
+        })
+        .await
+        .map_err(|_| AppError::InternalServerError)?;
+
+    Ok((StatusCode::OK, Json(wishlist)))
+}
+
+pub fn create_get_wishlist_docs(op: TransformOperation) -> TransformOperation {
+    op.summary("Get wishlist")
+        .description("Get the wishlist of the current user.")
+        .tag("Wishlist")
+        .response::<200, Json<Wishlist>>()
+}
+
+/// PUT /wishlist
+pub async fn set_wishlist_visibility<S>(
+    State(state): State<AppState<S>>,
+    auth_session: AuthSession<S>,
+    Json(body): Json<SetWishlistVisibilityBody>,
+) -> Result<impl IntoApiResponse, AppError>
+where
+    S: WishlistService + UserService + Clone,
+{
+    let user = auth_session.user.as_ref().ok_or(AppError::Unauthorized)?;
+
+    let req = SetWishlistVisibilityRequest {
+        user_id: user.id(),
+        is_public: body.is_public,
+    };
+
+    let wishlist = state
+        .service
+        .set_wishlist_visibility(req)
+        .await
+        .map_err(|_| AppError::InternalServerError)?;
+
+    Ok((StatusCode::OK, Json(wishlist)))
+}
+
+pub fn create_set_wishlist_visibility_docs(op: TransformOperation) -> TransformOperation {
+    op.summary("Share wishlist")
+        .description("Share the wishlist of the current user publicly, or make it private again.")
+        .tag("Wishlist")
+        .response::<200, Json<Wishlist>>()
+}
+
+/// GET /wishlists/{user_id}
+pub async fn get_user_wishlist<S>(
+    State(state): State<AppState<S>>,
+    auth_session: AuthSession<S>,
+    Path(UserIdPath { user_id }): Path<UserIdPath>,
+) -> Result<impl IntoApiResponse, AppError>
+where
+    S: WishlistService + UserService + Clone,
+{
+    let req = GetWishlistRequest {
+        user_id,
+        viewer_id: auth_session.user.as_ref().map(|user| user.id()),
+    };
+
+    let wishlist = state.service.get_wishlist(req).await.map_err(|e| match e {
+        GetWishlistError::NotFound => AppError::NotFound,
+        _ => AppError::InternalServerError,
+    })?;
+
+    Ok((StatusCode::OK, Json(wishlist)))
+}
+
+pub fn create_get_user_wishlist_docs(op: TransformOperation) -> TransformOperation {
+    op.summary("Get shared wishlist")
+        .description("Get the wishlist of a user. Only shared wishlists can be viewed by others.")
+        .tag("Wishlist")
+        .response::<200, Json<Wishlist>>()
+}
+
+/// POST /wishlist/items
+pub async fn add_wishlist_item<S>(
+    State(state): State<AppState<S>>,
+    auth_session: AuthSession<S>,
+    Json(body): Json<AddWishlistItemBody>,
+) -> Result<impl IntoApiResponse, AppError>
+where
+    S: WishlistService + UserService + Clone,
+{
+    let user = auth_session.user.as_ref().ok_or(AppError::Unauthorized)?;
+
+    let req = AddWishlistItemRequest {
+        user_id: user.id(),
+        variant_id: body.variant_id,
+        priority: body.priority,
+        desired_quantity: body.desired_quantity,
+        max_price: body.max_price,
+        note: body.note,
+    };
+
+    let wishlist = state
+        .service
+        .add_wishlist_item(req)
+        .await
+        .map_err(|e| match e {
+            e @ (AddWishlistItemError::VariantNotFound { .. }
+            | AddWishlistItemError::AlreadyWishlisted { .. }) => {
+                AppError::BadRequest(e.to_string())
+            }
+            _ => AppError::InternalServerError,
+        })?;
+
+    Ok((StatusCode::OK, Json(wishlist)))
+}
+
+pub fn create_add_wishlist_item_docs(op: TransformOperation) -> TransformOperation {
+    op.summary("Add to wishlist")
+        .description("Add a variant to the wishlist of the current user.")
+        .tag("Wishlist")
+        .response::<200, Json<Wishlist>>()
+}
+
+/// PATCH /wishlist/items/{item_id}
+pub async fn update_wishlist_item<S>(
+    State(state): State<AppState<S>>,
+    auth_session: AuthSession<S>,
+    Path(WishlistItemIdPath { item_id }): Path<WishlistItemIdPath>,
+    Json(body): Json<UpdateWishlistItemBody>,
+) -> Result<impl IntoApiResponse, AppError>
+where
+    S: WishlistService + UserService + Clone,
+{
+    let user = auth_session.user.as_ref().ok_or(AppError::Unauthorized)?;
+
+    let req = UpdateWishlistItemRequest {
+        user_id: user.id(),
+        item_id,
+        priority: body.priority,
+        desired_quantity: body.desired_quantity,
+        max_price: body.max_price,
+        note: body.note,
+    };
+
+    let wishlist = state
+        .service
+        .update_wishlist_item(req)
+        .await
+        .map_err(|e| match e {
+            UpdateWishlistItemError::ItemNotFound { .. } => AppError::NotFound,
+            _ => AppError::InternalServerError,
+        })?;
+
+    Ok((StatusCode::OK, Json(wishlist)))
+}
+
+pub fn create_update_wishlist_item_docs(op: TransformOperation) -> TransformOperation {
+    op.summary("Update wishlist item")
+        .description("Update the priority, quantity, price or notes of a wishlist item.")
+        .tag("Wishlist")
+        .response::<200, Json<Wishlist>>()
+}
+
+/// DELETE /wishlist/items/{item_id}
+pub async fn remove_wishlist_item<S>(
+    State(state): State<AppState<S>>,
+    auth_session: AuthSession<S>,
+    Path(WishlistItemIdPath { item_id }): Path<WishlistItemIdPath>,
+) -> Result<impl IntoApiResponse, AppError>
+where
+    S: WishlistService + UserService + Clone,
+{
+    let user = auth_session.user.as_ref().ok_or(AppError::Unauthorized)?;
+
+    let req = RemoveWishlistItemRequest {
+        user_id: user.id(),
+        item_id,
+    };
+
+    let wishlist = state
+        .service
+        .remove_wishlist_item(req)
+        .await
+        .map_err(|e| match e {
+            RemoveWishlistItemError::ItemNotFound { .. } => AppError::NotFound,
+            _ => AppError::InternalServerError,
+        })?;
+
+    Ok((StatusCode::OK, Json(wishlist)))
+}
+
+pub fn create_remove_wishlist_item_docs(op: TransformOperation) -> TransformOperation {
+    op.summary("Remove wishlist item")
+        .description("Remove an item from the wishlist of the current user.")
+        .tag("Wishlist")
+        .response::<200, Json<Wishlist>>()
+}
@@ -2,7 +2,7 @@ use crate::auth::AuthBackend;
 use aide::{
     axum::{
         ApiRouter,
-        routing::{get, get_with, post_with},
+        routing::{delete_with, get, get_with, patch_with, post_with, put_with},
     },
     openapi::OpenApi,
 };
@@ -12,8 +12,10 @@ use axum_login::{
     tower_sessions::{Expiry, SessionManagerLayer, SessionStore},
 };
 use sawa_core::services::{
-    MediaService, ProductInstanceService, ProductService, PurchaseOrderLifecycleService,
-    PurchaseOrderService, TagService, UserService,
+    CartService, CollectionService, MediaService, ProductInstanceService, ProductService,
+    PurchaseOrderLifecycleService, PurchaseOrderService, SettlementService, StatisticsService,
+    StorageLocationService, TagService, TradeMatchService, TransactionLifecycleService,
+    UserService, WishlistService,
 };
 use state::AppState;
 
@@ -44,9 +46,17 @@ where
         + UserService
         + PurchaseOrderService
         + PurchaseOrderLifecycleService
+        + SettlementService
         + ProductInstanceService
         + MediaService
-        + TagService,
+        + TagService
+        + CartService
+        + StorageLocationService
+        + CollectionService
+        + WishlistService
+        + TradeMatchService
+        + TransactionLifecycleService
+        + StatisticsService,
     SS: Clone + SessionStore,
 {
     let mut api = OpenApi::default();
@@ -87,6 +97,38 @@ where
             get_with(handlers::auth::me::<S>, handlers::auth::create_me_docs)
                 .route_layer(ensure_login!()),
         )
+        .api_route(
+            "/user/placeholders",
+            post_with(
+                handlers::auth::create_placeholder::<S>,
+                handlers::auth::create_create_placeholder_docs,
+            )
+            .get_with(
+                handlers::auth::list_placeholders::<S>,
+                handlers::auth::create_list_placeholders_docs,
+            )
+            .route_layer(ensure_login!()),
+        )
+        .api_route(
+            "/user/calendar-token",
+            post_with(
+                handlers::auth::reset_calendar_token::<S>,
+                handlers::auth::create_reset_calendar_token_docs,
+            )
+            .delete_with(
+                handlers::auth::revoke_calendar_token::<S>,
+                handlers::auth::create_revoke_calendar_token_docs,
+            )
+            .route_layer(ensure_login!()),
+        )
+        .api_route(
+            "/user/placeholders/claim",
+            post_with(
+                handlers::auth::claim_placeholder::<S>,
+                handlers::auth::create_claim_placeholder_docs,
+            )
+            .route_layer(ensure_login!()),
+        )
         .api_route(
             "/products",
             post_with(
@@ -159,12 +201,47 @@ where
             )
             .route_layer(ensure_login!()),
         )
+        .api_route(
+            "/orders/arrivals",
+            get_with(
+                handlers::purchase_order::list_order_arrivals::<S>,
+                handlers::purchase_order::create_list_order_arrivals_docs,
+            )
+            .route_layer(ensure_login!()),
+        )
+        .api_route(
+            "/orders/arrivals.ics",
+            get_with(
+                handlers::purchase_order::get_order_arrivals_calendar::<S>,
+                handlers::purchase_order::create_get_order_arrivals_calendar_docs,
+            ),
+        )
         .api_route(
             "/orders/{order_id}",
             get_with(
                 handlers::purchase_order::get_order::<S>,
                 handlers::purchase_order::create_get_order_docs,
             )
+            .patch_with(
+                handlers::purchase_order::update_order::<S>,
+                handlers::purchase_order::create_update_order_docs,
+            )
+            .route_layer(ensure_login!()),
+        )
+        .api_route(
+            "/orders/{order_id}/events",
+            get_with(
+                handlers::purchase_order::get_order_events::<S>,
+                handlers::purchase_order::create_get_order_events_docs,
+            )
+            .route_layer(ensure_login!()),
+        )
+        .api_route(
+            "/orders/{order_id}/schedule",
+            put_with(
+                handlers::purchase_order::update_order_schedule::<S>,
+                handlers::purchase_order::create_update_order_schedule_docs,
+            )
             .route_layer(ensure_login!()),
         )
         .api_route(
@@ -175,6 +252,34 @@ where
             )
             .route_layer(ensure_login!()),
         )
+        .api_route(
+            "/orders/{order_id}/items/{item_id}",
+            patch_with(
+                handlers::purchase_order::update_order_item::<S>,
+                handlers::purchase_order::create_update_order_item_docs,
+            )
+            .delete_with(
+                handlers::purchase_order::remove_order_item::<S>,
+                handlers::purchase_order::create_remove_order_item_docs,
+            )
+            .route_layer(ensure_login!()),
+        )
+        .api_route(
+            "/orders/{order_id}/items/{item_id}/owners",
+            put_with(
+                handlers::purchase_order::split_order_item_owners::<S>,
+                handlers::purchase_order::create_split_order_item_owners_docs,
+            )
+            .route_layer(ensure_login!()),
+        )
+        .api_route(
+            "/orders/{order_id}/line-items/owners",
+            put_with(
+                handlers::purchase_order::reassign_line_item_owners::<S>,
+                handlers::purchase_order::create_reassign_line_item_owners_docs,
+            )
+            .route_layer(ensure_login!()),
+        )
         .api_route(
             "/orders/{order_id}/items/{item_id}/mystery-box",
             post_with(
@@ -183,6 +288,82 @@ where
             )
             .route_layer(ensure_login!()),
         )
+        .api_route(
+            "/orders/{order_id}/items/{item_id}/mystery-box/results",
+            post_with(
+                handlers::purchase_order::add_mystery_box_results::<S>,
+                handlers::purchase_order::create_add_mystery_box_results_docs,
+            )
+            .route_layer(ensure_login!()),
+        )
+        .api_route(
+            "/orders/{order_id}/items/{item_id}/mystery-box/results/{line_item_id}",
+            patch_with(
+                handlers::purchase_order::update_mystery_box_result::<S>,
+                handlers::purchase_order::create_update_mystery_box_result_docs,
+            )
+            .delete_with(
+                handlers::purchase_order::remove_mystery_box_result::<S>,
+                handlers::purchase_order::create_remove_mystery_box_result_docs,
+            )
+            .route_layer(ensure_login!()),
+        )
+        .api_route(
+            "/orders/{order_id}/adjustments",
+            post_with(
+                handlers::purchase_order::add_order_adjustment::<S>,
+                handlers::purchase_order::create_add_order_adjustment_docs,
+            )
+            .route_layer(ensure_login!()),
+        )
+        .api_route(
+            "/orders/{order_id}/adjustments/{adjustment_id}",
+            delete_with(
+                handlers::purchase_order::remove_order_adjustment::<S>,
+                handlers::purchase_order::create_remove_order_adjustment_docs,
+            )
+            .route_layer(ensure_login!()),
+        )
+        .api_route(
+            "/orders/{order_id}/attachments",
+            post_with(
+                handlers::purchase_order::add_order_attachment::<S>,
+                handlers::purchase_order::create_add_order_attachment_docs,
+            )
+            .route_layer(ensure_login!()),
+        )
+        .api_route(
+            "/orders/{order_id}/attachments/{attachment_id}",
+            delete_with(
+                handlers::purchase_order::remove_order_attachment::<S>,
+                handlers::purchase_order::create_remove_order_attachment_docs,
+            )
+            .route_layer(ensure_login!()),
+        )
+        .api_route(
+            "/orders/{order_id}/settlement",
+            get_with(
+                handlers::settlement::get_order_settlement::<S>,
+                handlers::settlement::create_get_order_settlement_docs,
+            )
+            .route_layer(ensure_login!()),
+        )
+        .api_route(
+            "/orders/{order_id}/payments",
+            post_with(
+                handlers::settlement::record_payment::<S>,
+                handlers::settlement::create_record_payment_docs,
+            )
+            .route_layer(ensure_login!()),
+        )
+        .api_route(
+            "/orders/{order_id}/payments/{payment_id}",
+            delete_with(
+                handlers::settlement::remove_payment::<S>,
+                handlers::settlement::create_remove_payment_docs,
+            )
+            .route_layer(ensure_login!()),
+        )
         .api_route(
             "/orders/{order_id}/fulfill",
             post_with(
@@ -191,6 +372,14 @@ where
             )
             .route_layer(ensure_login!()),
         )
+        .api_route(
+            "/orders/{order_id}/fulfill/items",
+            post_with(
+                handlers::purchase_order::fulfill_order_items::<S>,
+                handlers::purchase_order::create_fulfill_order_items_docs,
+            )
+            .route_layer(ensure_login!()),
+        )
         .api_route(
             "/orders/{order_id}/cancel",
             post_with(
@@ -199,6 +388,257 @@ where
             )
             .route_layer(ensure_login!()),
         )
+        .api_route(
+            "/orders/{order_id}/returns",
+            post_with(
+                handlers::purchase_order::return_order_items::<S>,
+                handlers::purchase_order::create_return_order_items_docs,
+            )
+            .route_layer(ensure_login!()),
+        )
+        .api_route(
+            "/settlements/balances",
+            get_with(
+                handlers::settlement::list_balances::<S>,
+                handlers::settlement::create_list_balances_docs,
+            )
+            .route_layer(ensure_login!()),
+        )
+        .api_route(
+            "/settlements/netting",
+            get_with(
+                handlers::settlement::get_netting::<S>,
+                handlers::settlement::create_get_netting_docs,
+            )
+            .route_layer(ensure_login!()),
+        )
+        .api_route(
+            "/cart",
+            get_with(
+                handlers::cart::get_cart::<S>,
+                handlers::cart::create_get_cart_docs,
+            )
+            .route_layer(ensure_login!()),
+        )
+        .api_route(
+            "/cart/lines",
+            post_with(
+                handlers::cart::add_cart_line::<S>,
+                handlers::cart::create_add_cart_line_docs,
+            )
+            .route_layer(ensure_login!()),
+        )
+        .api_route(
+            "/cart/lines/{line_id}",
+            patch_with(
+                handlers::cart::update_cart_line::<S>,
+                handlers::cart::create_update_cart_line_docs,
+            )
+            .delete_with(
+                handlers::cart::remove_cart_line::<S>,
+                handlers::cart::create_remove_cart_line_docs,
+            )
+            .route_layer(ensure_login!()),
+        )
+        .api_route(
+            "/cart/checkout",
+            post_with(
+                handlers::cart::checkout_cart::<S>,
+                handlers::cart::create_checkout_cart_docs,
+            )
+            .route_layer(ensure_login!()),
+        )
+        .api_route(
+            "/wishlist",
+            get_with(
+                handlers::wishlist::get_wishlist::<S>,
+                handlers::wishlist::create_get_wishlist_docs,
+            )
+            .put_with(
+                handlers::wishlist::set_wishlist_visibility::<S>,
+                handlers::wishlist::create_set_wishlist_visibility_docs,
+            )
+            .route_layer(ensure_login!()),
+        )
+        .api_route(
+            "/wishlist/items",
+            post_with(
+                handlers::wishlist::add_wishlist_item::<S>,
+                handlers::wishlist::create_add_wishlist_item_docs,
+            )
+            .route_layer(ensure_login!()),
+        )
+        .api_route(
+            "/wishlist/items/{item_id}",
+            patch_with(
+                handlers::wishlist::update_wishlist_item::<S>,
+                handlers::wishlist::create_update_wishlist_item_docs,
+            )
+            .delete_with(
+                handlers::wishlist::remove_wishlist_item::<S>,
+                handlers::wishlist::create_remove_wishlist_item_docs,
+            )
+            .route_layer(ensure_login!()),
+        )
+        .api_route(
+            "/wishlists/{user_id}",
+            get_with(
+                handlers::wishlist::get_user_wishlist::<S>,
+                handlers::wishlist::create_get_user_wishlist_docs,
+            ),
+        )
+        .api_route(
+            "/trades/opt-in",
+            put_with(
+                handlers::trade_match::set_trade_opt_in::<S>,
+                handlers::trade_match::create_set_trade_opt_in_docs,
+            )
+            .route_layer(ensure_login!()),
+        )
+        .api_route(
+            "/trades/matches",
+            get_with(
+                handlers::trade_match::find_trade_matches::<S>,
+                handlers::trade_match::create_find_trade_matches_docs,
+            )
+            .route_layer(ensure_login!()),
+        )
+        .api_route(
+            "/trades/proposals",
+            post_with(
+                handlers::trade_match::propose_trade_match::<S>,
+                handlers::trade_match::create_propose_trade_match_docs,
+            )
+            .route_layer(ensure_login!()),
+        )
+        .api_route(
+            "/transactions/lending",
+            post_with(
+                handlers::transaction::create_lending_transaction::<S>,
+                handlers::transaction::create_create_lending_transaction_docs,
+            )
+            .route_layer(ensure_login!()),
+        )
+        .api_route(
+            "/transactions/{transaction_id}/complete",
+            post_with(
+                handlers::transaction::complete_transaction::<S>,
+                handlers::transaction::create_complete_transaction_docs,
+            )
+            .route_layer(ensure_login!()),
+        )
+        .api_route(
+            "/transactions/{transaction_id}/cancel",
+            post_with(
+                handlers::transaction::cancel_transaction::<S>,
+                handlers::transaction::create_cancel_transaction_docs,
+            )
+            .route_layer(ensure_login!()),
+        )
+        .api_route(
+            "/collection/completeness",
+            get_with(
+                handlers::collection::get_collection_completeness::<S>,
+                handlers::collection::create_get_collection_completeness_docs,
+            )
+            .route_layer(ensure_login!()),
+        )
+        .api_route(
+            "/collection/duplicates",
+            get_with(
+                handlers::collection::get_duplicates::<S>,
+                handlers::collection::create_get_duplicates_docs,
+            )
+            .route_layer(ensure_login!()),
+        )
+        .api_route(
+            "/statistics/spending",
+            get_with(
+                handlers::statistics::get_monthly_spending::<S>,
+                handlers::statistics::create_get_monthly_spending_docs,
+            )
+            .route_layer(ensure_login!()),
+        )
+        .api_route(
+            "/statistics/statuses",
+            get_with(
+                handlers::statistics::get_status_counts::<S>,
+                handlers::statistics::create_get_status_counts_docs,
+            )
+            .route_layer(ensure_login!()),
+        )
+        .api_route(
+            "/statistics/tags",
+            get_with(
+                handlers::statistics::get_tag_statistics::<S>,
+                handlers::statistics::create_get_tag_statistics_docs,
+            )
+            .route_layer(ensure_login!()),
+        )
+        .api_route(
+            "/statistics/products",
+            get_with(
+                handlers::statistics::get_product_statistics::<S>,
+                handlers::statistics::create_get_product_statistics_docs,
+            )
+            .route_layer(ensure_login!()),
+        )
+        .api_route(
+            "/statistics/series",
+            get_with(
+                handlers::statistics::get_most_bought_series::<S>,
+                handlers::statistics::create_get_most_bought_series_docs,
+            )
+            .route_layer(ensure_login!()),
+        )
+        .api_route(
+            "/statistics/mystery-boxes",
+            get_with(
+                handlers::statistics::get_mystery_box_statistics::<S>,
+                handlers::statistics::create_get_mystery_box_statistics_docs,
+            )
+            .route_layer(ensure_login!()),
+        )
+        .api_route(
+            "/goods/search",
+            get_with(
+                handlers::storage_location::locate_product_instances::<S>,
+                handlers::storage_location::create_locate_product_instances_docs,
+            )
+            .route_layer(ensure_login!()),
+        )
+        .api_route(
+            "/goods/move",
+            post_with(
+                handlers::storage_location::move_product_instances::<S>,
+                handlers::storage_location::create_move_product_instances_docs,
+            )
+            .route_layer(ensure_login!()),
+        )
+        .api_route(
+            "/locations",
+            get_with(
+                handlers::storage_location::list_storage_locations::<S>,
+                handlers::storage_location::create_list_storage_locations_docs,
+            )
+            .post_with(
+                handlers::storage_location::create_storage_location::<S>,
+                handlers::storage_location::create_create_storage_location_docs,
+            )
+            .route_layer(ensure_login!()),
+        )
+        .api_route(
+            "/locations/{location_id}",
+            put_with(
+                handlers::storage_location::update_storage_location::<S>,
+                handlers::storage_location::create_update_storage_location_docs,
+            )
+            .delete_with(
+                handlers::storage_location::delete_storage_location::<S>,
+                handlers::storage_location::create_delete_storage_location_docs,
+            )
+            .route_layer(ensure_login!()),
+        )
         .api_route(
             "/goods/{query_by}",
             get_with(
@@ -207,6 +647,70 @@ where
             )
             .route_layer(ensure_login!()),
         )
+        .api_route(
+            "/goods/bulk/status",
+            post_with(
+                handlers::product_instance::bulk_change_instance_status::<S>,
+                handlers::product_instance::create_bulk_change_instance_status_docs,
+            )
+            .route_layer(ensure_login!()),
+        )
+        .api_route(
+            "/goods/bulk/transfer",
+            post_with(
+                handlers::product_instance::bulk_transfer_instances::<S>,
+                handlers::product_instance::create_bulk_transfer_instances_docs,
+            )
+            .route_layer(ensure_login!()),
+        )
+        .api_route(
+            "/goods/instances/{instance_id}",
+            put_with(
+                handlers::product_instance::update_product_instance_details::<S>,
+                handlers::product_instance::create_update_product_instance_details_docs,
+            )
+            .route_layer(ensure_login!()),
+        )
+        .api_route(
+            "/goods/instances/{instance_id}/consume",
+            post_with(
+                handlers::product_instance::consume_product_instance::<S>,
+                handlers::product_instance::create_consume_product_instance_docs,
+            )
+            .route_layer(ensure_login!()),
+        )
+        .api_route(
+            "/goods/instances/{instance_id}/unconsume",
+            post_with(
+                handlers::product_instance::unconsume_product_instance::<S>,
+                handlers::product_instance::create_unconsume_product_instance_docs,
+            )
+            .route_layer(ensure_login!()),
+        )
+        .api_route(
+            "/goods/instances/{instance_id}/lost",
+            post_with(
+                handlers::product_instance::mark_product_instance_lost::<S>,
+                handlers::product_instance::create_mark_product_instance_lost_docs,
+            )
+            .route_layer(ensure_login!()),
+        )
+        .api_route(
+            "/goods/instances/{instance_id}/recover",
+            post_with(
+                handlers::product_instance::recover_product_instance::<S>,
+                handlers::product_instance::create_recover_product_instance_docs,
+            )
+            .route_layer(ensure_login!()),
+        )
+        .api_route(
+            "/goods/instances/{instance_id}/destroy",
+            post_with(
+                handlers::product_instance::mark_product_instance_destroyed::<S>,
+                handlers::product_instance::create_mark_product_instance_destroyed_docs,
+            )
+            .route_layer(ensure_login!()),
+        )
         .api_route(
             "/media/batch",
             post_with(
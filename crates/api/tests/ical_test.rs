@@ -0,0 +1,133 @@
+use chrono::{NaiveDate, Utc};
+use sawa_api::handlers::purchase_order::{
+    escape_ical_text, fold_ical_line, render_arrivals_calendar,
+};
+use sawa_core::models::misc::{Currency, Price};
+use sawa_core::models::purchase::{
+    PurchaseOrder, PurchaseOrderId, PurchaseOrderSchedule, PurchaseOrderStatus, PurchaseOrderView,
+    ShipmentTracking,
+};
+use sawa_core::models::user::UserId;
+
+fn create_order(status: PurchaseOrderStatus, schedule: PurchaseOrderSchedule) -> PurchaseOrder {
+    let user_id = UserId::new();
+    PurchaseOrder {
+        id: PurchaseOrderId::new(),
+        creator_id: user_id,
+        receiver_id: user_id,
+        items: vec![],
+        adjustments: vec![],
+        payments: vec![],
+        returns: vec![],
+        attachments: vec![],
+        events: vec![],
+        shipping_address: None,
+        schedule,
+        total_price: Price {
+            currency: Currency::JPY,
+            amount: 0,
+        },
+        status,
+        created_at: Utc::now(),
+        completed_at: None,
+        cancelled_at: None,
+    }
+}
+
+#[test]
+fn test_escape_ical_text() {
+    assert_eq!(escape_ical_text("plain"), "plain");
+    assert_eq!(
+        escape_ical_text("a\\b;c,d\ne"),
+        "a\\\\b\\;c\\,d\\ne",
+        "Backslashes, separators and newlines are escaped"
+    );
+}
+
+#[test]
+fn test_fold_ical_line() {
+    assert_eq!(
+        fold_ical_line("SUMMARY:Order arrival"),
+        "SUMMARY:Order arrival\r\n"
+    );
+
+    // Exactly 75 octets stay on one line
+    let line = "X".repeat(75);
+    assert_eq!(fold_ical_line(&line), format!("{line}\r\n"));
+
+    // Continuation lines start with a space, which counts toward their 75 octets
+    let line = "X".repeat(160);
+    let folded = fold_ical_line(&line);
+    let chunks: Vec<&str> = folded.trim_end_matches("\r\n").split("\r\n").collect();
+    assert_eq!(chunks.len(), 3);
+    assert_eq!(chunks[0].len(), 75);
+    assert_eq!(chunks[1], format!(" {}", "X".repeat(74)));
+    assert_eq!(chunks[2], format!(" {}", "X".repeat(11)));
+
+    // Multi-byte characters are never split across lines
+    let line = "あ".repeat(30);
+    let folded = fold_ical_line(&line);
+    for chunk in folded.trim_end_matches("\r\n").split("\r\n") {
+        assert!(chunk.len() <= 75);
+    }
+    assert_eq!(folded.replace("\r\n ", "").trim_end_matches("\r\n"), line);
+}
+
+#[test]
+fn test_render_arrivals_calendar() {
+    let date = NaiveDate::from_ymd_opt(2025, 3, 14).unwrap();
+    let pending = create_order(
+        PurchaseOrderStatus::Incomplete,
+        PurchaseOrderSchedule {
+            expected_arrival_date: Some(date),
+            tracking: Some(ShipmentTracking {
+                carrier: "Yamato, Japan".to_string(),
+                tracking_number: "1234;5678".to_string(),
+            }),
+            ..Default::default()
+        },
+    );
+    let fulfilled = create_order(
+        PurchaseOrderStatus::Fulfilled,
+        PurchaseOrderSchedule {
+            expected_arrival_date: Some(date),
+            ..Default::default()
+        },
+    );
+    let unscheduled = create_order(
+        PurchaseOrderStatus::Incomplete,
+        PurchaseOrderSchedule::default(),
+    );
+    let pending_id = uuid::Uuid::from(pending.id);
+
+    let views = [
+        PurchaseOrderView::Full(pending),
+        PurchaseOrderView::Full(fulfilled),
+        PurchaseOrderView::Full(unscheduled),
+    ];
+    let calendar = render_arrivals_calendar(views.iter());
+
+    assert!(calendar.starts_with("BEGIN:VCALENDAR\r\n"));
+    assert!(calendar.ends_with("END:VCALENDAR\r\n"));
+    assert!(
+        calendar.split("\r\n").all(|line| line.len() <= 75),
+        "Every line is folded"
+    );
+    assert_eq!(
+        calendar.matches("BEGIN:VEVENT").count(),
+        1,
+        "Only pending orders with an arrival date get an event"
+    );
+
+    let unfolded = calendar.replace("\r\n ", "");
+    assert!(unfolded.contains(&format!("UID:order-arrival-{pending_id}@sawa\r\n")));
+    assert!(unfolded.contains("DTSTART;VALUE=DATE:20250314\r\n"));
+    assert!(unfolded.contains("DTEND;VALUE=DATE:20250315\r\n"));
+    assert!(unfolded.contains(&format!(
+        "DESCRIPTION:Order {pending_id}\\nTracking: Yamato\\, Japan 1234\\;5678\r\n"
+    )));
+    assert!(
+        !unfolded.contains("\nTracking"),
+        "The description has no raw line break"
+    );
+}
@@ -13,7 +13,7 @@
 //! All service traits are implemented by a single `Service` struct:
 //!
 //! ```ignore
-//! pub struct Service<P, V, I, O, T, U, Tg, M> {
+//! pub struct Service<P, V, I, O, T, U, Tg, M, C, L, W, S> {
 //!     // All repository dependencies injected
 //! }
 //!
@@ -1,6 +1,8 @@
 use sawa_core::repositories::{
-    MediaRepository, ProductInstanceRepository, ProductRepository, ProductVariantRepository,
-    PurchaseOrderRepository, TagRepository, UserRepository, UserTransactionRepository,
+    CartRepository, MediaRepository, ProductInstanceRepository, ProductRepository,
+    ProductVariantRepository, PurchaseOrderRepository, StatisticsRepository,
+    StorageLocationRepository, TagRepository, UserRepository, UserTransactionRepository,
+    WishlistRepository,
 };
 
 /// Unified service that implements all domain service traits.
@@ -13,7 +15,7 @@ use sawa_core::repositories::{
 /// - This struct is the ADAPTER that implements all ports
 /// - Repositories are injected dependencies (also ports)
 #[derive(Clone)]
-pub struct Service<P, PV, PI, PO, UT, U, T, M>
+pub struct Service<P, PV, PI, PO, UT, U, T, M, C, L, W, S>
 where
     P: ProductRepository,
     PV: ProductVariantRepository,
@@ -23,6 +25,10 @@ where
     U: UserRepository,
     T: TagRepository,
     M: MediaRepository,
+    C: CartRepository,
+    L: StorageLocationRepository,
+    W: WishlistRepository,
+    S: StatisticsRepository,
 {
     pub product: P,
     pub product_variant: PV,
@@ -32,15 +38,26 @@ where
     pub user: U,
     pub tag: T,
     pub media: M,
+    pub cart: C,
+    pub storage_location: L,
+    pub wishlist: W,
+    pub statistics: S,
 }
 
 // Service trait implementations (core flow only)
+mod cart_impl;
+mod collection_impl;
 mod media_impl;
 mod product_impl;
 mod product_instance_impl;
 mod purchase_order_impl;
 mod purchase_order_lifecycle_impl;
+mod settlement_impl;
+mod statistics_impl;
+mod storage_location_impl;
 mod tag_impl;
+mod trade_match_impl;
 mod transaction_impl;
 mod transaction_lifecycle_impl;
 mod user_impl;
+mod wishlist_impl;
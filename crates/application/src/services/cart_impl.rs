@@ -0,0 +1,215 @@
+use super::Service;
+use chrono::Utc;
+use sawa_core::{
+    errors::RepositoryError,
+    models::{
+        cart::{Cart, CartLine, CartLineId},
+        purchase::PurchaseOrder,
+        user::UserId,
+    },
+    repositories::*,
+    services::{
+        AddCartLineError, AddCartLineRequest, CartService, CheckoutCartError, CheckoutCartRequest,
+        CreateOrderItemRequest, CreateOrderRequest, GetCartError, GetCartRequest,
+        PurchaseOrderService, RemoveCartLineError, RemoveCartLineRequest, UpdateCartLineError,
+        UpdateCartLineRequest,
+    },
+};
+
+impl<P, PV, PI, PO, UT, U, T, M, C, L, W, S> Service<P, PV, PI, PO, UT, U, T, M, C, L, W, S>
+where
+    P: ProductRepository,
+    PV: ProductVariantRepository,
+    PI: ProductInstanceRepository,
+    PO: PurchaseOrderRepository,
+    UT: UserTransactionRepository,
+    U: UserRepository,
+    T: TagRepository,
+    M: MediaRepository,
+    C: CartRepository,
+    L: StorageLocationRepository,
+    W: WishlistRepository,
+    S: StatisticsRepository,
+{
+    async fn load_cart(&self, user_id: UserId) -> Result<Cart, RepositoryError> {
+        Ok(self
+            .cart
+            .find_by_user(&user_id)
+            .await?
+            .unwrap_or_else(|| Cart::empty(user_id)))
+    }
+}
+
+impl<P, PV, PI, PO, UT, U, T, M, C, L, W, S> CartService
+    for Service<P, PV, PI, PO, UT, U, T, M, C, L, W, S>
+where
+    P: ProductRepository,
+    PV: ProductVariantRepository,
+    PI: ProductInstanceRepository,
+    PO: PurchaseOrderRepository,
+    UT: UserTransactionRepository,
+    U: UserRepository,
+    T: TagRepository,
+    M: MediaRepository,
+    C: CartRepository,
+    L: StorageLocationRepository,
+    W: WishlistRepository,
+    S: StatisticsRepository,
+{
+    async fn get_cart(&self, req: GetCartRequest) -> Result<Cart, GetCartError> {
+        Ok(self.load_cart(req.user_id).await?)
+    }
+
+    async fn add_cart_line(&self, req: AddCartLineRequest) -> Result<Cart, AddCartLineError> {
+        if self
+            .product_variant
+            .find_by_id(&req.variant_id)
+            .await?
+            .is_none()
+        {
+            return Err(AddCartLineError::VariantNotFound {
+                variant_id: req.variant_id,
+            });
+        }
+        if let Some(owner_id) = req.owner_id
+            && self.user.find_by_id(&owner_id).await?.is_none()
+        {
+            return Err(AddCartLineError::UserNotFound { user_id: owner_id });
+        }
+
+        let mut cart = self.load_cart(req.user_id).await?;
+
+        // Adding the same variant for the same owner again increases the quantity
+        match cart
+            .lines
+            .iter_mut()
+            .find(|line| line.variant_id == req.variant_id && line.owner_id == req.owner_id)
+        {
+            Some(line) => {
+                line.quantity = line.quantity.saturating_add(req.quantity.get());
+                if req.unit_price.is_some() {
+                    line.unit_price = req.unit_price;
+                }
+            }
+            None => cart.lines.push(CartLine {
+                id: CartLineId::new(),
+                variant_id: req.variant_id,
+                quantity: req.quantity,
+                owner_id: req.owner_id,
+                unit_price: req.unit_price,
+                added_at: Utc::now(),
+            }),
+        }
+
+        cart.updated_at = Utc::now();
+        self.cart.save(&cart).await?;
+
+        Ok(cart)
+    }
+
+    async fn update_cart_line(
+        &self,
+        req: UpdateCartLineRequest,
+    ) -> Result<Cart, UpdateCartLineError> {
+        if let Some(owner_id) = req.owner_id
+            && self.user.find_by_id(&owner_id).await?.is_none()
+        {
+            return Err(UpdateCartLineError::UserNotFound { user_id: owner_id });
+        }
+
+        let mut cart = self.load_cart(req.user_id).await?;
+        let line = cart
+            .lines
+            .iter_mut()
+            .find(|line| line.id == req.line_id)
+            .ok_or(UpdateCartLineError::LineNotFound {
+                line_id: req.line_id,
+            })?;
+
+        if let Some(quantity) = req.quantity {
+            line.quantity = quantity;
+        }
+        if let Some(owner_id) = req.owner_id {
+            line.owner_id = Some(owner_id);
+        }
+        if let Some(unit_price) = req.unit_price {
+            line.unit_price = Some(unit_price);
+        }
+
+        cart.updated_at = Utc::now();
+        self.cart.save(&cart).await?;
+
+        Ok(cart)
+    }
+
+    async fn remove_cart_line(
+        &self,
+        req: RemoveCartLineRequest,
+    ) -> Result<Cart, RemoveCartLineError> {
+        let mut cart = self.load_cart(req.user_id).await?;
+        let index = cart
+            .lines
+            .iter()
+            .position(|line| line.id == req.line_id)
+            .ok_or(RemoveCartLineError::LineNotFound {
+                line_id: req.line_id,
+            })?;
+        cart.lines.remove(index);
+
+        cart.updated_at = Utc::now();
+        self.cart.save(&cart).await?;
+
+        Ok(cart)
+    }
+
+    async fn checkout_cart(
+        &self,
+        req: CheckoutCartRequest,
+    ) -> Result<PurchaseOrder, CheckoutCartError> {
+        let cart = self.load_cart(req.user_id).await?;
+        if cart.lines.is_empty() {
+            return Err(CheckoutCartError::CartEmpty);
+        }
+
+        // Report every variant deleted since it was added, so all of them can be fixed at once
+        let mut missing = Vec::new();
+        for line in &cart.lines {
+            if self
+                .product_variant
+                .find_by_id(&line.variant_id)
+                .await?
+                .is_none()
+            {
+                missing.push(line.variant_id);
+            }
+        }
+        if !missing.is_empty() {
+            return Err(CheckoutCartError::VariantsNotFound {
+                variant_ids: missing,
+            });
+        }
+
+        let order = self
+            .create_order(CreateOrderRequest {
+                user_id: req.user_id,
+                receiver_id: req.receiver_id,
+                shipping_address: req.shipping_address,
+                total_price: req.total_price,
+                items: cart
+                    .lines
+                    .into_iter()
+                    .map(|line| CreateOrderItemRequest {
+                        variant_id: line.variant_id,
+                        owner_id: line.owner_id,
+                        quantity: line.quantity,
+                        unit_price: line.unit_price,
+                    })
+                    .collect(),
+            })
+            .await?;
+
+        self.cart.delete(&req.user_id).await?;
+
+        Ok(order)
+    }
+}
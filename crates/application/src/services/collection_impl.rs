@@ -0,0 +1,252 @@
+use super::Service;
+use sawa_core::{
+    errors::RepositoryError,
+    models::{
+        collection::{
+            CollectionCompleteness, CollectionScope, DuplicateVariant, VariantCompleteness,
+        },
+        product::{ProductInstance, ProductInstanceStatus, ProductVariant, ProductVariantId},
+        user::UserId,
+    },
+    repositories::*,
+    services::{
+        CollectionScopeError, CollectionService, GetCollectionCompletenessError,
+        GetCollectionCompletenessRequest, GetDuplicatesError, GetDuplicatesRequest,
+    },
+};
+use std::{
+    cmp::Reverse,
+    collections::{HashMap, HashSet},
+};
+
+impl<P, PV, PI, PO, UT, U, T, M, C, L, W, S> Service<P, PV, PI, PO, UT, U, T, M, C, L, W, S>
+where
+    P: ProductRepository,
+    PV: ProductVariantRepository,
+    PI: ProductInstanceRepository,
+    PO: PurchaseOrderRepository,
+    UT: UserTransactionRepository,
+    U: UserRepository,
+    T: TagRepository,
+    M: MediaRepository,
+    C: CartRepository,
+    L: StorageLocationRepository,
+    W: WishlistRepository,
+    S: StatisticsRepository,
+{
+    /// The `Active` instances a user owns, grouped by variant, oldest first.
+    pub(super) async fn find_active_instances_by_variant(
+        &self,
+        user_id: &UserId,
+    ) -> Result<HashMap<ProductVariantId, Vec<ProductInstance>>, RepositoryError> {
+        let mut instances_by_variant: HashMap<_, Vec<_>> = HashMap::new();
+        for instance in self
+            .product_instance
+            .find_by_owner_and_status(user_id, ProductInstanceStatus::Active)
+            .await?
+        {
+            instances_by_variant
+                .entry(instance.variant_id)
+                .or_default()
+                .push(instance);
+        }
+        for instances in instances_by_variant.values_mut() {
+            instances.sort_by_key(|instance| instance.created_at);
+        }
+
+        Ok(instances_by_variant)
+    }
+
+    /// The collectible variants in a scope.
+    ///
+    /// Mystery-box variants are replaced by their possible variants, each variant appears once.
+    async fn find_scope_variants(
+        &self,
+        scope: CollectionScope,
+    ) -> Result<Vec<ProductVariant>, CollectionScopeError> {
+        let variants = match scope {
+            CollectionScope::Product { product_id } => {
+                if self.product.find_by_id(&product_id).await?.is_none() {
+                    return Err(CollectionScopeError::ProductNotFound { product_id });
+                }
+                let mut variants = self.product_variant.find_by_product_id(&product_id).await?;
+                variants.sort_by_key(|variant| variant.sort_order);
+                variants
+            }
+            CollectionScope::Tag {
+                tag_id,
+                include_descendants,
+            } => {
+                if self.tag.find_by_id(&tag_id).await?.is_none() {
+                    return Err(CollectionScopeError::TagNotFound { tag_id });
+                }
+                let mut tag_ids = vec![tag_id];
+                if include_descendants {
+                    let mut index = 0;
+                    while index < tag_ids.len() {
+                        for child in self.tag.find_by_parent(&tag_ids[index]).await? {
+                            // Guard against cycles in the tag tree
+                            if !tag_ids.contains(&child.id) {
+                                tag_ids.push(child.id);
+                            }
+                        }
+                        index += 1;
+                    }
+                }
+                self.product_variant.find_by_tags_any(&tag_ids).await?
+            }
+        };
+
+        // Expand mystery boxes, loading possible variants outside the scope as needed
+        let loaded: HashMap<_, _> = variants
+            .iter()
+            .map(|variant| (variant.id, variant.clone()))
+            .collect();
+        let mut ids = Vec::new();
+        for variant in &variants {
+            match &variant.mystery_box {
+                Some(mystery_box) => ids.extend(mystery_box.possible_variants.iter().copied()),
+                None => ids.push(variant.id),
+            }
+        }
+        let mut seen = HashSet::new();
+        ids.retain(|id| seen.insert(*id));
+
+        let unloaded: Vec<_> = ids
+            .iter()
+            .filter(|id| !loaded.contains_key(*id))
+            .copied()
+            .collect();
+        let mut extra: HashMap<_, _> = self
+            .product_variant
+            .load_by_ids(&unloaded)
+            .await?
+            .into_iter()
+            .flatten()
+            .map(|variant| (variant.id, variant))
+            .collect();
+
+        Ok(ids
+            .into_iter()
+            .filter_map(|id| loaded.get(&id).cloned().or_else(|| extra.remove(&id)))
+            // A mystery box listed as a possible variant is still not collectible
+            .filter(|variant| variant.mystery_box.is_none())
+            .collect())
+    }
+}
+
+impl<P, PV, PI, PO, UT, U, T, M, C, L, W, S> CollectionService
+    for Service<P, PV, PI, PO, UT, U, T, M, C, L, W, S>
+where
+    P: ProductRepository,
+    PV: ProductVariantRepository,
+    PI: ProductInstanceRepository,
+    PO: PurchaseOrderRepository,
+    UT: UserTransactionRepository,
+    U: UserRepository,
+    T: TagRepository,
+    M: MediaRepository,
+    C: CartRepository,
+    L: StorageLocationRepository,
+    W: WishlistRepository,
+    S: StatisticsRepository,
+{
+    async fn get_collection_completeness(
+        &self,
+        req: GetCollectionCompletenessRequest,
+    ) -> Result<CollectionCompleteness, GetCollectionCompletenessError> {
+        let variants = self.find_scope_variants(req.scope).await?;
+
+        let mut owned_counts: HashMap<_, u32> = HashMap::new();
+        for instance in self
+            .product_instance
+            .find_by_owner_and_status(&req.user_id, ProductInstanceStatus::Active)
+            .await?
+        {
+            *owned_counts.entry(instance.variant_id).or_default() += 1;
+        }
+
+        Ok(CollectionCompleteness::new(
+            variants
+                .into_iter()
+                .map(|variant| VariantCompleteness {
+                    owned_count: owned_counts.get(&variant.id).copied().unwrap_or(0),
+                    variant,
+                })
+                .collect(),
+        ))
+    }
+
+    async fn get_duplicates(
+        &self,
+        req: GetDuplicatesRequest,
+    ) -> Result<Vec<DuplicateVariant>, GetDuplicatesError> {
+        let scope_variants = match req.scope {
+            Some(scope) => Some(self.find_scope_variants(scope).await?),
+            None => None,
+        };
+
+        let mut instances_by_variant = self.find_active_instances_by_variant(&req.user_id).await?;
+        instances_by_variant.retain(|_, instances| instances.len() > req.threshold as usize);
+
+        let variants: Vec<_> = match scope_variants {
+            Some(variants) => variants
+                .into_iter()
+                .filter(|variant| instances_by_variant.contains_key(&variant.id))
+                .collect(),
+            None => {
+                let ids: Vec<_> = instances_by_variant.keys().copied().collect();
+                self.product_variant
+                    .load_by_ids(&ids)
+                    .await?
+                    .into_iter()
+                    .flatten()
+                    .collect()
+            }
+        };
+
+        let mut products = HashMap::new();
+        let mut tags = HashMap::new();
+        let mut duplicates = Vec::with_capacity(variants.len());
+        for variant in variants {
+            let Some(mut instances) = instances_by_variant.remove(&variant.id) else {
+                continue;
+            };
+
+            if !products.contains_key(&variant.product_id) {
+                let product = self.product.find_by_id(&variant.product_id).await?;
+                products.insert(variant.product_id, product);
+            }
+            let Some(product) = products[&variant.product_id].clone() else {
+                continue;
+            };
+
+            let mut variant_tags = Vec::with_capacity(variant.tags.len());
+            for tag_id in &variant.tags {
+                if !tags.contains_key(tag_id) {
+                    tags.insert(*tag_id, self.tag.find_by_id(tag_id).await?);
+                }
+                variant_tags.extend(tags[tag_id].clone());
+            }
+
+            let owned_count = instances.len() as u32;
+            let spare_instance_ids = instances
+                .split_off(req.threshold as usize)
+                .into_iter()
+                .map(|instance| instance.id)
+                .collect();
+
+            duplicates.push(DuplicateVariant {
+                variant,
+                product,
+                tags: variant_tags,
+                owned_count,
+                spare_instance_ids,
+            });
+        }
+
+        // The most duplicated variants first
+        duplicates.sort_by_key(|duplicate| Reverse(duplicate.owned_count));
+        Ok(duplicates)
+    }
+}
@@ -9,7 +9,8 @@ use sawa_core::{
 
 use super::Service;
 
-impl<P, PV, PI, PO, UT, U, T, M> MediaService for Service<P, PV, PI, PO, UT, U, T, M>
+impl<P, PV, PI, PO, UT, U, T, M, C, L, W, S> MediaService
+    for Service<P, PV, PI, PO, UT, U, T, M, C, L, W, S>
 where
     P: ProductRepository,
     PV: ProductVariantRepository,
@@ -19,6 +20,10 @@ where
     U: UserRepository,
     T: TagRepository,
     M: MediaRepository,
+    C: CartRepository,
+    L: StorageLocationRepository,
+    W: WishlistRepository,
+    S: StatisticsRepository,
 {
     async fn get_media(&self, req: GetMediaRequest) -> Result<Media, GetMediaError> {
         self.media
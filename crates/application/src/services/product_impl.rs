@@ -9,7 +9,8 @@ use sawa_core::{
 
 use super::Service;
 
-impl<P, PV, PI, PO, UT, U, T, M> ProductService for Service<P, PV, PI, PO, UT, U, T, M>
+impl<P, PV, PI, PO, UT, U, T, M, C, L, W, S> ProductService
+    for Service<P, PV, PI, PO, UT, U, T, M, C, L, W, S>
 where
     P: ProductRepository,
     PV: ProductVariantRepository,
@@ -19,6 +20,10 @@ where
     U: UserRepository,
     T: TagRepository,
     M: MediaRepository,
+    C: CartRepository,
+    L: StorageLocationRepository,
+    W: WishlistRepository,
+    S: StatisticsRepository,
 {
     async fn get_product(
         &self,
@@ -81,12 +86,14 @@ where
 
         // 2. Create variant
         let mut variant = if let Some(mb_config) = req.mystery_box {
-            ProductVariant::mystery_box(
+            let mut variant = ProductVariant::mystery_box(
                 req.product_id,
                 req.name,
                 mb_config.items_count,
                 mb_config.possible_variants,
-            )
+            );
+            variant.set_mystery_box_validation(mb_config.validation);
+            variant
         } else {
             ProductVariant::new(req.product_id, req.name)
         };
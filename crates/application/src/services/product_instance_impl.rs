@@ -1,20 +1,167 @@
-use chrono::Utc;
 use sawa_core::{
-    models::product::{
-        ProductInstance, ProductInstanceStatus, ProductInstanceStatusHistory,
-        ProductInstanceStatusHistoryId,
+    errors::RepositoryError,
+    models::{
+        product::{
+            InvalidStatusTransition, ProductInstance, ProductInstanceId, ProductInstanceStatus,
+        },
+        storage::StorageLocation,
+        user::UserId,
     },
     repositories::*,
     services::{
-        ConsumeProductInstanceError, GetProductInstanceError, ListProductInstancesError,
-        ListProductInstancesQueryBy, MarkProductInstanceDestroyedError,
-        MarkProductInstanceLostError, ProductInstanceService,
+        BulkChangeInstanceStatusError, BulkChangeInstanceStatusRequest, BulkInstanceItemError,
+        BulkInstanceResult, BulkInstanceSelection, BulkTransferInstancesError,
+        BulkTransferInstancesRequest, BulkTransferResponse, ConsumeProductInstanceError,
+        CreateTransactionRequest, GetProductInstanceError, ListProductInstancesError,
+        ListProductInstancesQueryBy, ListProductInstancesRequest,
+        MarkProductInstanceDestroyedError, MarkProductInstanceLostError, ProductInstanceService,
+        RecoverProductInstanceError, TransactionLifecycleService, UnconsumeProductInstanceError,
+        UpdateProductInstanceDetailsError,
     },
 };
+use std::collections::HashSet;
 
 use super::Service;
 
-impl<P, PV, PI, PO, UT, U, T, M> ProductInstanceService for Service<P, PV, PI, PO, UT, U, T, M>
+/// The errors shared by all status changes of a single instance.
+trait StatusChangeError: From<RepositoryError> + From<InvalidStatusTransition> + Send {
+    fn not_found() -> Self;
+    fn permission_denied() -> Self;
+    fn not_held_by_owner() -> Self;
+}
+
+macro_rules! impl_status_change_error {
+    ($($error:ident),* $(,)?) => {
+        $(
+            impl StatusChangeError for $error {
+                fn not_found() -> Self {
+                    $error::NotFound
+                }
+                fn permission_denied() -> Self {
+                    $error::PermissionDenied
+                }
+                fn not_held_by_owner() -> Self {
+                    $error::NotHeldByOwner
+                }
+            }
+        )*
+    };
+}
+
+impl_status_change_error!(
+    ConsumeProductInstanceError,
+    UnconsumeProductInstanceError,
+    MarkProductInstanceLostError,
+    RecoverProductInstanceError,
+    MarkProductInstanceDestroyedError,
+);
+
+/// Check that `user_id` may include an instance in a bulk operation.
+fn check_bulk_instance(
+    user_id: UserId,
+    instance: &ProductInstance,
+) -> Result<(), BulkInstanceItemError> {
+    if instance.owner_id != user_id {
+        return Err(BulkInstanceItemError::PermissionDenied);
+    }
+
+    if instance.owner_id != instance.holder_id {
+        return Err(BulkInstanceItemError::NotHeldByOwner);
+    }
+
+    Ok(())
+}
+
+impl<P, PV, PI, PO, UT, U, T, M, C, L, W, S> Service<P, PV, PI, PO, UT, U, T, M, C, L, W, S>
+where
+    P: ProductRepository,
+    PV: ProductVariantRepository,
+    PI: ProductInstanceRepository,
+    PO: PurchaseOrderRepository,
+    UT: UserTransactionRepository,
+    U: UserRepository,
+    T: TagRepository,
+    M: MediaRepository,
+    C: CartRepository,
+    L: StorageLocationRepository,
+    W: WishlistRepository,
+    S: StatisticsRepository,
+{
+    /// Change the status of an instance owned and held by `user_id`.
+    async fn change_instance_status<E: StatusChangeError>(
+        &self,
+        id: &ProductInstanceId,
+        user_id: UserId,
+        status: ProductInstanceStatus,
+        reason: Option<String>,
+    ) -> Result<ProductInstance, E> {
+        let mut instance = self
+            .product_instance
+            .find_by_id(id)
+            .await?
+            .ok_or_else(E::not_found)?;
+
+        if instance.owner_id != user_id {
+            return Err(E::permission_denied());
+        }
+
+        if instance.owner_id != instance.holder_id {
+            return Err(E::not_held_by_owner());
+        }
+
+        instance.transition_to(status, reason)?;
+        self.product_instance.save(&instance).await?;
+
+        Ok(instance)
+    }
+
+    /// Load the instances a bulk operation applies to, `None` for unknown IDs.
+    async fn select_instances(
+        &self,
+        user_id: UserId,
+        selection: BulkInstanceSelection,
+    ) -> Result<Vec<(ProductInstanceId, Option<ProductInstance>)>, RepositoryError> {
+        match selection {
+            BulkInstanceSelection::Ids(ids) => {
+                let mut seen = HashSet::new();
+                let mut selected = Vec::new();
+                for id in ids {
+                    if seen.insert(id) {
+                        selected.push((id, self.product_instance.find_by_id(&id).await?));
+                    }
+                }
+                Ok(selected)
+            }
+            BulkInstanceSelection::Filter {
+                variant_id,
+                status,
+                location_id,
+                condition,
+            } => {
+                let instances = self
+                    .list_product_instances(ListProductInstancesRequest {
+                        user_id,
+                        query_by: ListProductInstancesQueryBy::Owned,
+                        variant_id,
+                        status,
+                        location_id,
+                        condition,
+                    })
+                    .await
+                    .map_err(|e| match e {
+                        ListProductInstancesError::Repository(e) => e,
+                    })?;
+                Ok(instances
+                    .into_iter()
+                    .map(|instance| (instance.id, Some(instance)))
+                    .collect())
+            }
+        }
+    }
+}
+
+impl<P, PV, PI, PO, UT, U, T, M, C, L, W, S> ProductInstanceService
+    for Service<P, PV, PI, PO, UT, U, T, M, C, L, W, S>
 where
     P: ProductRepository,
     PV: ProductVariantRepository,
@@ -24,23 +171,34 @@ where
     U: UserRepository,
     T: TagRepository,
     M: MediaRepository,
+    C: CartRepository,
+    L: StorageLocationRepository,
+    W: WishlistRepository,
+    S: StatisticsRepository,
 {
     async fn get_product_instance(
         &self,
         req: sawa_core::services::GetProductInstanceRequest,
     ) -> Result<ProductInstance, GetProductInstanceError> {
-        self.product_instance
+        let mut instance = self
+            .product_instance
             .find_by_id(&req.id)
             .await?
-            .ok_or(GetProductInstanceError::NotFound)
+            .ok_or(GetProductInstanceError::NotFound)?;
+
+        // Notes are private to the owner
+        if instance.owner_id != req.user_id {
+            instance.note.clear();
+        }
+        Ok(instance)
     }
 
     async fn list_product_instances(
         &self,
         req: sawa_core::services::ListProductInstancesRequest,
     ) -> Result<Vec<ProductInstance>, ListProductInstancesError> {
-        let instances = match req.query_by {
-            ListProductInstancesQueryBy::Owned => {
+        let mut instances = match req.query_by {
+            ListProductInstancesQueryBy::Owned | ListProductInstancesQueryBy::LentOut => {
                 if let Some(variant_id) = req.variant_id {
                     let mut instances = self
                         .product_instance
@@ -59,7 +217,7 @@ where
                     self.product_instance.find_by_owner(&req.user_id).await?
                 }
             }
-            ListProductInstancesQueryBy::Held => {
+            ListProductInstancesQueryBy::Held | ListProductInstancesQueryBy::Borrowed => {
                 if let Some(variant_id) = req.variant_id {
                     let mut instances = self
                         .product_instance
@@ -79,111 +237,258 @@ where
                 }
             }
         };
+        match req.query_by {
+            ListProductInstancesQueryBy::LentOut => {
+                instances.retain(|i| i.holder_id != req.user_id);
+            }
+            ListProductInstancesQueryBy::Borrowed => {
+                instances.retain(|i| i.owner_id != req.user_id);
+            }
+            ListProductInstancesQueryBy::Owned | ListProductInstancesQueryBy::Held => {}
+        }
+        if let Some(location_id) = req.location_id {
+            let locations = self.storage_location.find_by_user(&req.user_id).await?;
+            let location_ids = StorageLocation::descendants(&locations, location_id);
+            instances.retain(|i| {
+                i.location_id
+                    .is_some_and(|location_id| location_ids.contains(&location_id))
+            });
+        }
+        if let Some(condition) = req.condition {
+            instances.retain(|i| i.condition == Some(condition));
+        }
+
+        // Notes are private to the owner
+        for instance in &mut instances {
+            if instance.owner_id != req.user_id {
+                instance.note.clear();
+            }
+        }
         Ok(instances)
     }
 
-    async fn consume_product_instance(
+    async fn update_product_instance_details(
         &self,
-        req: sawa_core::services::ConsumeProductInstanceRequest,
-    ) -> Result<ProductInstance, ConsumeProductInstanceError> {
+        req: sawa_core::services::UpdateProductInstanceDetailsRequest,
+    ) -> Result<ProductInstance, UpdateProductInstanceDetailsError> {
         let mut instance = self
             .product_instance
             .find_by_id(&req.id)
             .await?
-            .ok_or(ConsumeProductInstanceError::NotFound)?;
+            .ok_or(UpdateProductInstanceDetailsError::NotFound)?;
 
         if instance.owner_id != req.user_id {
-            return Err(ConsumeProductInstanceError::PermissionDenied);
+            return Err(UpdateProductInstanceDetailsError::PermissionDenied);
         }
 
-        if instance.status != ProductInstanceStatus::Active {
-            return Err(ConsumeProductInstanceError::NotActive);
-        }
-
-        if instance.owner_id != instance.holder_id {
-            return Err(ConsumeProductInstanceError::NotHeldByOwner);
+        for media_id in &req.medias {
+            if self.media.find_by_id(media_id).await?.is_none() {
+                return Err(UpdateProductInstanceDetailsError::MediaNotFound {
+                    media_id: *media_id,
+                });
+            }
         }
 
-        instance.status = ProductInstanceStatus::Consumed;
-        instance.status_history.push(ProductInstanceStatusHistory {
-            id: ProductInstanceStatusHistoryId::new(),
-            status: ProductInstanceStatus::Consumed,
-            changed_at: Utc::now(),
-            reason: None,
-        });
-
+        instance.condition = req.condition;
+        instance.note = req.note;
+        instance.medias = req.medias;
         self.product_instance.save(&instance).await?;
 
         Ok(instance)
     }
 
+    async fn consume_product_instance(
+        &self,
+        req: sawa_core::services::ConsumeProductInstanceRequest,
+    ) -> Result<ProductInstance, ConsumeProductInstanceError> {
+        self.change_instance_status(&req.id, req.user_id, ProductInstanceStatus::Consumed, None)
+            .await
+    }
+
+    async fn unconsume_product_instance(
+        &self,
+        req: sawa_core::services::UnconsumeProductInstanceRequest,
+    ) -> Result<ProductInstance, UnconsumeProductInstanceError> {
+        // Only consumed instances can be made active again this way
+        let instance = self
+            .product_instance
+            .find_by_id(&req.id)
+            .await?
+            .ok_or(UnconsumeProductInstanceError::NotFound)?;
+        if instance.status != ProductInstanceStatus::Consumed {
+            return Err(InvalidStatusTransition {
+                from: instance.status,
+                to: ProductInstanceStatus::Active,
+            }
+            .into());
+        }
+
+        self.change_instance_status(&req.id, req.user_id, ProductInstanceStatus::Active, None)
+            .await
+    }
+
     async fn mark_product_instance_lost(
         &self,
         req: sawa_core::services::MarkProductInstanceLostRequest,
     ) -> Result<ProductInstance, MarkProductInstanceLostError> {
-        let mut instance = self
+        self.change_instance_status(
+            &req.id,
+            req.user_id,
+            ProductInstanceStatus::NotFound,
+            req.reason,
+        )
+        .await
+    }
+
+    async fn recover_product_instance(
+        &self,
+        req: sawa_core::services::RecoverProductInstanceRequest,
+    ) -> Result<ProductInstance, RecoverProductInstanceError> {
+        // Only lost instances can be found again
+        let instance = self
             .product_instance
             .find_by_id(&req.id)
             .await?
-            .ok_or(MarkProductInstanceLostError::NotFound)?;
+            .ok_or(RecoverProductInstanceError::NotFound)?;
+        if instance.status != ProductInstanceStatus::NotFound {
+            return Err(InvalidStatusTransition {
+                from: instance.status,
+                to: ProductInstanceStatus::Active,
+            }
+            .into());
+        }
 
-        if instance.owner_id != req.user_id {
-            return Err(MarkProductInstanceLostError::PermissionDenied);
+        self.change_instance_status(
+            &req.id,
+            req.user_id,
+            ProductInstanceStatus::Active,
+            req.reason,
+        )
+        .await
+    }
+
+    async fn mark_product_instance_destroyed(
+        &self,
+        req: sawa_core::services::MarkProductInstanceDestroyedRequest,
+    ) -> Result<ProductInstance, MarkProductInstanceDestroyedError> {
+        self.change_instance_status(
+            &req.id,
+            req.user_id,
+            ProductInstanceStatus::Destroyed,
+            req.reason,
+        )
+        .await
+    }
+
+    async fn bulk_change_instance_status(
+        &self,
+        req: BulkChangeInstanceStatusRequest,
+    ) -> Result<Vec<BulkInstanceResult>, BulkChangeInstanceStatusError> {
+        // Locks and returns go through transactions and orders
+        if matches!(
+            req.status,
+            ProductInstanceStatus::Locked | ProductInstanceStatus::Returned
+        ) {
+            return Err(BulkChangeInstanceStatusError::UnsupportedStatus { status: req.status });
         }
 
-        if instance.status != ProductInstanceStatus::Active {
-            return Err(MarkProductInstanceLostError::NotActive);
+        let selected = self.select_instances(req.user_id, req.selection).await?;
+        if selected.is_empty() {
+            return Err(BulkChangeInstanceStatusError::NoInstances);
         }
 
-        if instance.owner_id != instance.holder_id {
-            return Err(MarkProductInstanceLostError::NotHeldByOwner);
+        // Check all instances before changing any of them
+        let mut results = Vec::with_capacity(selected.len());
+        let mut changed = Vec::new();
+        for (instance_id, instance) in selected {
+            let result = match instance {
+                None => Err(BulkInstanceItemError::NotFound),
+                Some(mut instance) => check_bulk_instance(req.user_id, &instance).and_then(|()| {
+                    // Only the transaction may unlock its items
+                    if instance.status == ProductInstanceStatus::Locked {
+                        return Err(BulkInstanceItemError::Locked);
+                    }
+                    if instance.status == req.status {
+                        return Ok(());
+                    }
+                    instance
+                        .transition_to(req.status, req.reason.clone())
+                        .map_err(|InvalidStatusTransition { from, to }| {
+                            BulkInstanceItemError::InvalidTransition { from, to }
+                        })?;
+                    changed.push(instance);
+                    Ok(())
+                }),
+            };
+            results.push(BulkInstanceResult {
+                instance_id,
+                error: result.err(),
+            });
         }
 
-        instance.status = ProductInstanceStatus::NotFound;
-        instance.status_history.push(ProductInstanceStatusHistory {
-            id: ProductInstanceStatusHistoryId::new(),
-            status: ProductInstanceStatus::NotFound,
-            changed_at: Utc::now(),
-            reason: req.reason,
-        });
+        if results.iter().any(|result| result.error.is_some()) {
+            return Err(BulkChangeInstanceStatusError::Rejected { results });
+        }
 
-        self.product_instance.save(&instance).await?;
+        self.product_instance.save_batch(&changed).await?;
 
-        Ok(instance)
+        Ok(results)
     }
 
-    async fn mark_product_instance_destroyed(
+    async fn bulk_transfer_instances(
         &self,
-        req: sawa_core::services::MarkProductInstanceDestroyedRequest,
-    ) -> Result<ProductInstance, MarkProductInstanceDestroyedError> {
-        let mut instance = self
-            .product_instance
-            .find_by_id(&req.id)
-            .await?
-            .ok_or(MarkProductInstanceDestroyedError::NotFound)?;
-
-        if instance.owner_id != req.user_id {
-            return Err(MarkProductInstanceDestroyedError::PermissionDenied);
+        req: BulkTransferInstancesRequest,
+    ) -> Result<BulkTransferResponse, BulkTransferInstancesError> {
+        if req.to_user_id == req.user_id {
+            return Err(BulkTransferInstancesError::SameUser);
+        }
+        if self.user.find_by_id(&req.to_user_id).await?.is_none() {
+            return Err(BulkTransferInstancesError::RecipientNotFound);
         }
 
-        if instance.status != ProductInstanceStatus::Active {
-            return Err(MarkProductInstanceDestroyedError::NotActive);
+        let selected = self.select_instances(req.user_id, req.selection).await?;
+        if selected.is_empty() {
+            return Err(BulkTransferInstancesError::NoInstances);
         }
 
-        if instance.owner_id != instance.holder_id {
-            return Err(MarkProductInstanceDestroyedError::NotHeldByOwner);
+        // Check all instances before locking any of them
+        let mut results = Vec::with_capacity(selected.len());
+        for (instance_id, instance) in selected {
+            let result = match instance {
+                None => Err(BulkInstanceItemError::NotFound),
+                Some(instance) => check_bulk_instance(req.user_id, &instance).and_then(|()| {
+                    if instance.status == ProductInstanceStatus::Active {
+                        Ok(())
+                    } else {
+                        Err(BulkInstanceItemError::InvalidTransition {
+                            from: instance.status,
+                            to: ProductInstanceStatus::Locked,
+                        })
+                    }
+                }),
+            };
+            results.push(BulkInstanceResult {
+                instance_id,
+                error: result.err(),
+            });
         }
 
-        instance.status = ProductInstanceStatus::Destroyed;
-        instance.status_history.push(ProductInstanceStatusHistory {
-            id: ProductInstanceStatusHistoryId::new(),
-            status: ProductInstanceStatus::Destroyed,
-            changed_at: Utc::now(),
-            reason: req.reason,
-        });
+        if results.iter().any(|result| result.error.is_some()) {
+            return Err(BulkTransferInstancesError::Rejected { results });
+        }
 
-        self.product_instance.save(&instance).await?;
+        // Locks all instances with a single batch save
+        let transaction = self
+            .create_transaction(CreateTransactionRequest {
+                from_user_id: req.user_id,
+                to_user_id: req.to_user_id,
+                items: results.iter().map(|result| result.instance_id).collect(),
+            })
+            .await?;
 
-        Ok(instance)
+        Ok(BulkTransferResponse {
+            transaction_id: transaction.id,
+            results,
+        })
     }
 }
@@ -1,25 +1,39 @@
 use super::Service;
-use chrono::Utc;
+use chrono::{Days, NaiveDate, Utc};
 use sawa_core::{
     models::{
         misc::{Currency, Price},
-        product::ProductVariantId,
+        product::{MysteryBoxConfig, MysteryBoxValidation, MysteryBoxWarning, ProductVariantId},
         purchase::{
-            PurchaseOrder, PurchaseOrderId, PurchaseOrderItem, PurchaseOrderItemId,
-            PurchaseOrderItemStatus, PurchaseOrderLineItem, PurchaseOrderStatus,
+            AdjustmentAllocation, OrderRoleFilter, PurchaseOrder, PurchaseOrderAdjustment,
+            PurchaseOrderAdjustmentId, PurchaseOrderAttachment, PurchaseOrderAttachmentId,
+            PurchaseOrderEvent, PurchaseOrderEventKind, PurchaseOrderId, PurchaseOrderItem,
+            PurchaseOrderItemId, PurchaseOrderItemStatus, PurchaseOrderLineItem,
+            PurchaseOrderLineItemId, PurchaseOrderSchedule, PurchaseOrderStatus, PurchaseOrderView,
         },
         user::UserId,
     },
     repositories::*,
     services::{
+        AddMysteryBoxResultsError, AddMysteryBoxResultsRequest, AddOrderAdjustmentError,
+        AddOrderAdjustmentRequest, AddOrderAttachmentError, AddOrderAttachmentRequest,
         AddOrderItemError, AddOrderItemRequest, CreateOrderError, CreateOrderRequest,
-        GetOrderError, GetOrderRequest, ListOrdersError, ListOrdersRequest, PurchaseOrderService,
-        SubmitMysteryBoxResultsError, SubmitMysteryBoxResultsRequest,
+        GetOrderError, GetOrderEventsError, GetOrderEventsRequest, GetOrderRequest,
+        ListOrderArrivalsError, ListOrderArrivalsRequest, ListOrdersError, ListOrdersRequest,
+        MysteryBoxResultsResponse, OrderArrivalsResponse, PurchaseOrderService,
+        ReassignLineItemOwnersError, ReassignLineItemOwnersRequest, RemoveMysteryBoxResultError,
+        RemoveMysteryBoxResultRequest, RemoveOrderAdjustmentError, RemoveOrderAdjustmentRequest,
+        RemoveOrderAttachmentError, RemoveOrderAttachmentRequest, RemoveOrderItemError,
+        RemoveOrderItemRequest, SplitOrderItemOwnersError, SplitOrderItemOwnersRequest,
+        SubmitMysteryBoxResultsError, SubmitMysteryBoxResultsRequest, UpdateMysteryBoxResultError,
+        UpdateMysteryBoxResultRequest, UpdateOrderError, UpdateOrderItemError,
+        UpdateOrderItemRequest, UpdateOrderRequest, UpdateOrderScheduleError,
+        UpdateOrderScheduleRequest,
     },
 };
-use std::num::NonZeroU32;
+use std::{collections::HashSet, num::NonZeroU32};
 
-impl<P, PV, PI, PO, UT, U, T, M> Service<P, PV, PI, PO, UT, U, T, M>
+impl<P, PV, PI, PO, UT, U, T, M, C, L, W, S> Service<P, PV, PI, PO, UT, U, T, M, C, L, W, S>
 where
     P: ProductRepository,
     PV: ProductVariantRepository,
@@ -29,6 +43,10 @@ where
     U: UserRepository,
     T: TagRepository,
     M: MediaRepository,
+    C: CartRepository,
+    L: StorageLocationRepository,
+    W: WishlistRepository,
+    S: StatisticsRepository,
 {
     async fn process_add_item(
         &self,
@@ -89,15 +107,65 @@ where
             }
 
             // Add to total: unit_price * quantity
-            let item_total = unit_price.amount as u64 * quantity.get() as u64;
-            order.total_price.amount = order.total_price.amount.saturating_add(item_total as u32);
+            order.total_price.amount = item_total(Some(unit_price), quantity)
+                .and_then(|item_total| order.total_price.amount.checked_add(item_total))
+                .ok_or(AddOrderItemError::PriceOverflow)?;
         }
 
         Ok(item_id)
     }
 }
 
-impl<P, PV, PI, PO, UT, U, T, M> PurchaseOrderService for Service<P, PV, PI, PO, UT, U, T, M>
+/// Check received mystery box variants against the box's validation policy.
+///
+/// Returns the warnings to report, or the first unexpected variant if the policy is strict.
+fn validate_mystery_box_results(
+    config: &MysteryBoxConfig,
+    received: &[ProductVariantId],
+) -> Result<Vec<MysteryBoxWarning>, ProductVariantId> {
+    let unexpected = config.unexpected_variants(received);
+    match config.validation {
+        MysteryBoxValidation::Lenient => Ok(vec![]),
+        MysteryBoxValidation::Warn => Ok(unexpected
+            .into_iter()
+            .map(|variant_id| MysteryBoxWarning::UnexpectedVariant { variant_id })
+            .collect()),
+        MysteryBoxValidation::Strict => match unexpected.first() {
+            Some(variant_id) => Err(*variant_id),
+            None => Ok(vec![]),
+        },
+    }
+}
+
+/// Calculate the total price of an item: unit_price * quantity.
+///
+/// Returns `None` if the total doesn't fit in a `Price`.
+fn item_total(unit_price: Option<Price>, quantity: NonZeroU32) -> Option<u32> {
+    match unit_price {
+        Some(price) => u32::try_from(price.amount as u64 * quantity.get() as u64).ok(),
+        None => Some(0),
+    }
+}
+
+/// Find a line item that an adjustment is manually allocated to.
+///
+/// Such line items can't be removed, or the allocation would point at nothing.
+fn manually_allocated(
+    adjustments: &[PurchaseOrderAdjustment],
+    line_items: &[PurchaseOrderLineItem],
+) -> Option<PurchaseOrderLineItemId> {
+    line_items
+        .iter()
+        .map(|line_item| line_item.id)
+        .find(|line_item_id| {
+            adjustments
+                .iter()
+                .any(|adjustment| adjustment.allocates_to(*line_item_id))
+        })
+}
+
+impl<P, PV, PI, PO, UT, U, T, M, C, L, W, S> PurchaseOrderService
+    for Service<P, PV, PI, PO, UT, U, T, M, C, L, W, S>
 where
     P: ProductRepository,
     PV: ProductVariantRepository,
@@ -107,6 +175,10 @@ where
     U: UserRepository,
     T: TagRepository,
     M: MediaRepository,
+    C: CartRepository,
+    L: StorageLocationRepository,
+    W: WishlistRepository,
+    S: StatisticsRepository,
 {
     async fn create_order(
         &self,
@@ -139,7 +211,13 @@ where
             creator_id: req.user_id,
             receiver_id,
             items: vec![],
+            adjustments: vec![],
+            payments: vec![],
+            returns: vec![],
+            attachments: vec![],
+            events: vec![],
             shipping_address: req.shipping_address,
+            schedule: PurchaseOrderSchedule::default(),
             total_price: req.total_price.unwrap_or_else(|| Price {
                 currency: Currency::JPY,
                 amount: 0,
@@ -149,29 +227,42 @@ where
             completed_at: None,
             cancelled_at: None,
         };
+        order.record_event(req.user_id, PurchaseOrderEventKind::Created);
 
         // Add initial items
         for item in req.items {
-            self.process_add_item(
-                &mut order,
-                item.variant_id,
-                item.owner_id.unwrap_or(receiver_id),
-                item.quantity,
-                item.unit_price,
-            )
-            .await
-            .map_err(|e| match e {
-                AddOrderItemError::VariantNotFound { variant_id } => {
-                    CreateOrderError::VariantNotFound { variant_id }
-                }
-                AddOrderItemError::CurrencyMismatch { expected, actual } => {
-                    CreateOrderError::CurrencyMismatch { expected, actual }
-                }
-                AddOrderItemError::Repository(e) => CreateOrderError::Repository(e),
-                _ => CreateOrderError::Repository(sawa_core::errors::RepositoryError::Internal(
-                    format!("Unexpected error adding item: {}", e),
-                )),
-            })?;
+            let order_item_id = self
+                .process_add_item(
+                    &mut order,
+                    item.variant_id,
+                    item.owner_id.unwrap_or(receiver_id),
+                    item.quantity,
+                    item.unit_price,
+                )
+                .await
+                .map_err(|e| match e {
+                    AddOrderItemError::VariantNotFound { variant_id } => {
+                        CreateOrderError::VariantNotFound { variant_id }
+                    }
+                    AddOrderItemError::CurrencyMismatch { expected, actual } => {
+                        CreateOrderError::CurrencyMismatch { expected, actual }
+                    }
+                    AddOrderItemError::PriceOverflow => CreateOrderError::PriceOverflow,
+                    AddOrderItemError::Repository(e) => CreateOrderError::Repository(e),
+                    _ => {
+                        CreateOrderError::Repository(sawa_core::errors::RepositoryError::Internal(
+                            format!("Unexpected error adding item: {}", e),
+                        ))
+                    }
+                })?;
+            order.record_event(
+                req.user_id,
+                PurchaseOrderEventKind::ItemAdded {
+                    order_item_id,
+                    variant_id: item.variant_id,
+                    quantity: item.quantity,
+                },
+            );
         }
 
         // Save order
@@ -214,6 +305,14 @@ where
                 req.unit_price,
             )
             .await?;
+        order.record_event(
+            req.user_id,
+            PurchaseOrderEventKind::ItemAdded {
+                order_item_id: item_id,
+                variant_id: req.variant_id,
+                quantity: req.quantity,
+            },
+        );
 
         // Save updated order
         self.order.save(&order).await?;
@@ -221,89 +320,1131 @@ where
         Ok(item_id)
     }
 
-    async fn submit_mystery_box_results(
+    async fn remove_order_item(
         &self,
-        req: SubmitMysteryBoxResultsRequest,
-    ) -> Result<(), SubmitMysteryBoxResultsError> {
+        req: RemoveOrderItemRequest,
+    ) -> Result<PurchaseOrder, RemoveOrderItemError> {
         // Load and verify order exists
         let mut order = self
             .order
             .find_by_id(&req.order_id, &req.user_id)
             .await?
-            .ok_or(SubmitMysteryBoxResultsError::OrderNotFound)?;
+            .ok_or(RemoveOrderItemError::OrderNotFound {
+                order_id: req.order_id,
+            })?;
 
         // Verify user has permission to modify this order
         if order.creator_id != req.user_id {
-            return Err(SubmitMysteryBoxResultsError::PermissionDenied {
+            return Err(RemoveOrderItemError::PermissionDenied {
                 user_id: req.user_id,
             });
         }
 
-        // Find the order item
+        if order.status != PurchaseOrderStatus::Incomplete {
+            return Err(RemoveOrderItemError::OrderNotEditable);
+        }
+
+        let index = order
+            .items
+            .iter()
+            .position(|item| item.id == req.order_item_id)
+            .ok_or(RemoveOrderItemError::OrderItemNotFound {
+                order_item_id: req.order_item_id,
+            })?;
+        if let Some(line_item_id) =
+            manually_allocated(&order.adjustments, &order.items[index].line_items)
+        {
+            return Err(RemoveOrderItemError::LineItemAllocated { line_item_id });
+        }
+        let item = order.items.remove(index);
+
+        // Update total price (the item's total was checked when it was added)
+        order.total_price.amount = order
+            .total_price
+            .amount
+            .saturating_sub(item_total(item.unit_price, item.quantity).unwrap_or(u32::MAX));
+        order.record_event(
+            req.user_id,
+            PurchaseOrderEventKind::ItemRemoved {
+                order_item_id: item.id,
+            },
+        );
+
+        // Save updated order
+        self.order.save(&order).await?;
+
+        Ok(order)
+    }
+
+    async fn update_order_item(
+        &self,
+        req: UpdateOrderItemRequest,
+    ) -> Result<PurchaseOrder, UpdateOrderItemError> {
+        // Load and verify order exists
+        let mut order = self
+            .order
+            .find_by_id(&req.order_id, &req.user_id)
+            .await?
+            .ok_or(UpdateOrderItemError::OrderNotFound {
+                order_id: req.order_id,
+            })?;
+
+        // Verify user has permission to modify this order
+        if order.creator_id != req.user_id {
+            return Err(UpdateOrderItemError::PermissionDenied {
+                user_id: req.user_id,
+            });
+        }
+
+        if order.status != PurchaseOrderStatus::Incomplete {
+            return Err(UpdateOrderItemError::OrderNotEditable);
+        }
+
+        // Ensure currency matches
+        if let Some(unit_price) = req.unit_price
+            && unit_price.currency != order.total_price.currency
+        {
+            return Err(UpdateOrderItemError::CurrencyMismatch {
+                expected: order.total_price.currency,
+                actual: unit_price.currency,
+            });
+        }
+
+        let receiver_id = order.receiver_id;
         let item = order
             .items
             .iter_mut()
             .find(|item| item.id == req.order_item_id)
-            .ok_or(SubmitMysteryBoxResultsError::OrderItemNotFound {
+            .ok_or(UpdateOrderItemError::OrderItemNotFound {
                 order_item_id: req.order_item_id,
             })?;
+        let old_total = item_total(item.unit_price, item.quantity).unwrap_or(u32::MAX);
 
-        // Verify it's a mystery box item
-        let variant = self
-            .product_variant
-            .find_by_id(&item.purchased_variant_id)
+        if let Some(quantity) = req.quantity
+            && quantity != item.quantity
+        {
+            let variant = self
+                .product_variant
+                .find_by_id(&item.purchased_variant_id)
+                .await?
+                .ok_or(UpdateOrderItemError::VariantNotFound {
+                    variant_id: item.purchased_variant_id,
+                })?;
+
+            // Line items that would be dropped must not be manually allocated
+            let dropped = if variant.mystery_box.is_some() {
+                &item.line_items[..]
+            } else {
+                &item.line_items[item.line_items.len().min(quantity.get() as usize)..]
+            };
+            if let Some(line_item_id) = manually_allocated(&order.adjustments, dropped) {
+                return Err(UpdateOrderItemError::LineItemAllocated { line_item_id });
+            }
+
+            if variant.mystery_box.is_some() {
+                // Submitted results no longer match the expected count
+                item.line_items.clear();
+                item.status = PurchaseOrderItemStatus::AwaitingInput;
+            } else {
+                // Regular item: keep existing line items and their owner, add or drop the rest
+                let owner_id = item
+                    .line_items
+                    .first()
+                    .map(|line_item| line_item.owner_id)
+                    .unwrap_or(receiver_id);
+                let count = quantity.get() as usize;
+                item.line_items.truncate(count);
+                while item.line_items.len() < count {
+                    item.line_items.push(PurchaseOrderLineItem::new(
+                        item.purchased_variant_id,
+                        item.id,
+                        owner_id,
+                    ));
+                }
+            }
+
+            item.quantity = quantity;
+        }
+
+        if let Some(unit_price) = req.unit_price {
+            item.unit_price = Some(unit_price);
+        }
+
+        // Update total price
+        order.total_price.amount = item_total(item.unit_price, item.quantity)
+            .and_then(|new_total| {
+                let total = order.total_price.amount as u64 + new_total as u64;
+                u32::try_from(total.saturating_sub(old_total as u64)).ok()
+            })
+            .ok_or(UpdateOrderItemError::PriceOverflow)?;
+        order.record_event(
+            req.user_id,
+            PurchaseOrderEventKind::ItemUpdated {
+                order_item_id: req.order_item_id,
+            },
+        );
+
+        // Save updated order
+        self.order.save(&order).await?;
+
+        Ok(order)
+    }
+
+    async fn reassign_line_item_owners(
+        &self,
+        req: ReassignLineItemOwnersRequest,
+    ) -> Result<PurchaseOrder, ReassignLineItemOwnersError> {
+        let mut order = self
+            .order
+            .find_by_id(&req.order_id, &req.user_id)
             .await?
-            .ok_or(SubmitMysteryBoxResultsError::VariantNotFound {
-                variant_id: item.purchased_variant_id,
+            .ok_or(ReassignLineItemOwnersError::OrderNotFound {
+                order_id: req.order_id,
             })?;
 
-        let mystery_box_config = variant
-            .mystery_box
-            .ok_or(SubmitMysteryBoxResultsError::NotMysteryBox)?;
+        if order.creator_id != req.user_id {
+            return Err(ReassignLineItemOwnersError::PermissionDenied {
+                user_id: req.user_id,
+            });
+        }
 
-        // Verify the number of variants matches expected count
-        let expected_count = item.quantity.get() * mystery_box_config.items_count.get();
-        if req.received_variants.len() as u32 != expected_count {
-            return Err(SubmitMysteryBoxResultsError::InvalidVariantCount {
-                expected: expected_count,
-                actual: req.received_variants.len() as u32,
+        if order.status != PurchaseOrderStatus::Incomplete {
+            return Err(ReassignLineItemOwnersError::OrderNotEditable);
+        }
+
+        // Validate all new owners before changing anything
+        let mut checked_owners: Vec<UserId> = vec![];
+        for assignment in &req.assignments {
+            if checked_owners.contains(&assignment.owner_id) {
+                continue;
+            }
+            self.user.find_by_id(&assignment.owner_id).await?.ok_or(
+                ReassignLineItemOwnersError::UserNotFound {
+                    user_id: assignment.owner_id,
+                },
+            )?;
+            checked_owners.push(assignment.owner_id);
+        }
+
+        let mut line_item_ids = vec![];
+        for assignment in &req.assignments {
+            let line_item = order
+                .items
+                .iter_mut()
+                .flat_map(|item| &mut item.line_items)
+                .find(|line_item| line_item.id == assignment.line_item_id)
+                .ok_or(ReassignLineItemOwnersError::LineItemNotFound {
+                    line_item_id: assignment.line_item_id,
+                })?;
+            line_item.owner_id = assignment.owner_id;
+            line_item_ids.push(line_item.id);
+        }
+        order.record_event(
+            req.user_id,
+            PurchaseOrderEventKind::OwnersReassigned { line_item_ids },
+        );
+
+        self.order.save(&order).await?;
+
+        Ok(order)
+    }
+
+    async fn split_order_item_owners(
+        &self,
+        req: SplitOrderItemOwnersRequest,
+    ) -> Result<PurchaseOrder, SplitOrderItemOwnersError> {
+        let mut order = self
+            .order
+            .find_by_id(&req.order_id, &req.user_id)
+            .await?
+            .ok_or(SplitOrderItemOwnersError::OrderNotFound {
+                order_id: req.order_id,
+            })?;
+
+        if order.creator_id != req.user_id {
+            return Err(SplitOrderItemOwnersError::PermissionDenied {
+                user_id: req.user_id,
             });
         }
 
-        // Create line items for each received variant
-        item.line_items = req
-            .received_variants
-            .into_iter()
-            .map(|variant_id| PurchaseOrderLineItem::new(variant_id, item.id, req.owner_id))
+        if order.status != PurchaseOrderStatus::Incomplete {
+            return Err(SplitOrderItemOwnersError::OrderNotEditable);
+        }
+
+        let mut checked_owners: Vec<UserId> = vec![];
+        for share in &req.shares {
+            if checked_owners.contains(&share.owner_id) {
+                continue;
+            }
+            self.user.find_by_id(&share.owner_id).await?.ok_or(
+                SplitOrderItemOwnersError::UserNotFound {
+                    user_id: share.owner_id,
+                },
+            )?;
+            checked_owners.push(share.owner_id);
+        }
+
+        let item = order
+            .items
+            .iter_mut()
+            .find(|item| item.id == req.order_item_id)
+            .ok_or(SplitOrderItemOwnersError::OrderItemNotFound {
+                order_item_id: req.order_item_id,
+            })?;
+        if item.status != PurchaseOrderItemStatus::Pending {
+            // Mystery boxes must have their results submitted first
+            return Err(SplitOrderItemOwnersError::OrderItemNotEditable {
+                order_item_id: item.id,
+            });
+        }
+
+        let requested: u32 = req.shares.iter().map(|share| share.quantity.get()).sum();
+        if requested as usize != item.line_items.len() {
+            return Err(SplitOrderItemOwnersError::QuantityMismatch {
+                expected: item.line_items.len() as u32,
+                actual: requested,
+            });
+        }
+
+        let owners = req
+            .shares
+            .iter()
+            .flat_map(|share| std::iter::repeat_n(share.owner_id, share.quantity.get() as usize));
+        for (line_item, owner_id) in item.line_items.iter_mut().zip(owners) {
+            line_item.owner_id = owner_id;
+        }
+        let line_item_ids = item
+            .line_items
+            .iter()
+            .map(|line_item| line_item.id)
             .collect();
+        order.record_event(
+            req.user_id,
+            PurchaseOrderEventKind::OwnersReassigned { line_item_ids },
+        );
 
-        // Update item status to Pending
-        item.status = PurchaseOrderItemStatus::Pending;
+        self.order.save(&order).await?;
+
+        Ok(order)
+    }
+
+    async fn update_order(
+        &self,
+        req: UpdateOrderRequest,
+    ) -> Result<PurchaseOrder, UpdateOrderError> {
+        // Load and verify order exists
+        let mut order = self
+            .order
+            .find_by_id(&req.order_id, &req.user_id)
+            .await?
+            .ok_or(UpdateOrderError::OrderNotFound {
+                order_id: req.order_id,
+            })?;
+
+        // Verify user has permission to modify this order
+        if order.creator_id != req.user_id {
+            return Err(UpdateOrderError::PermissionDenied {
+                user_id: req.user_id,
+            });
+        }
+
+        if order.status != PurchaseOrderStatus::Incomplete {
+            return Err(UpdateOrderError::OrderNotEditable);
+        }
+
+        if let Some(receiver_id) = req.receiver_id {
+            self.user
+                .find_by_id(&receiver_id)
+                .await?
+                .ok_or(UpdateOrderError::UserNotFound {
+                    user_id: receiver_id,
+                })?;
+
+            // Line items without a designated owner default to the receiver, so they follow it.
+            // Line items assigned by reassignment or a corrected result keep their owner.
+            let assigned: HashSet<PurchaseOrderLineItemId> = order
+                .events
+                .iter()
+                .flat_map(|event| match &event.kind {
+                    PurchaseOrderEventKind::OwnersReassigned { line_item_ids } => {
+                        line_item_ids.clone()
+                    }
+                    PurchaseOrderEventKind::MysteryBoxResultUpdated { line_item_id, .. } => {
+                        vec![*line_item_id]
+                    }
+                    _ => vec![],
+                })
+                .collect();
+            let previous_receiver_id = order.receiver_id;
+            for line_item in order.items.iter_mut().flat_map(|item| &mut item.line_items) {
+                if line_item.owner_id == previous_receiver_id && !assigned.contains(&line_item.id) {
+                    line_item.owner_id = receiver_id;
+                }
+            }
+            order.receiver_id = receiver_id;
+        }
+
+        if let Some(shipping_address) = req.shipping_address {
+            order.shipping_address = Some(shipping_address);
+        }
+        order.record_event(req.user_id, PurchaseOrderEventKind::Updated);
 
         // Save updated order
         self.order.save(&order).await?;
 
-        Ok(())
+        Ok(order)
     }
 
-    async fn get_order(&self, req: GetOrderRequest) -> Result<PurchaseOrder, GetOrderError> {
-        let order = self
+    async fn add_order_attachment(
+        &self,
+        req: AddOrderAttachmentRequest,
+    ) -> Result<PurchaseOrderAttachment, AddOrderAttachmentError> {
+        let mut order = self
             .order
             .find_by_id(&req.order_id, &req.user_id)
             .await?
-            .ok_or(GetOrderError::NotFound)?;
+            .ok_or(AddOrderAttachmentError::OrderNotFound {
+                order_id: req.order_id,
+            })?;
+
+        if order.creator_id != req.user_id && order.receiver_id != req.user_id {
+            return Err(AddOrderAttachmentError::PermissionDenied {
+                user_id: req.user_id,
+            });
+        }
+
+        self.media.find_by_id(&req.media_id).await?.ok_or(
+            AddOrderAttachmentError::MediaNotFound {
+                media_id: req.media_id,
+            },
+        )?;
+
+        let attachment = PurchaseOrderAttachment {
+            id: PurchaseOrderAttachmentId::new(),
+            media_id: req.media_id,
+            kind: req.kind,
+            note: req.note,
+            uploader_id: req.user_id,
+            created_at: Utc::now(),
+        };
+        order.attachments.push(attachment.clone());
+        order.record_event(
+            req.user_id,
+            PurchaseOrderEventKind::AttachmentAdded {
+                attachment_id: attachment.id,
+            },
+        );
+
+        self.order.save(&order).await?;
+
+        Ok(attachment)
+    }
+
+    async fn remove_order_attachment(
+        &self,
+        req: RemoveOrderAttachmentRequest,
+    ) -> Result<PurchaseOrder, RemoveOrderAttachmentError> {
+        let mut order = self
+            .order
+            .find_by_id(&req.order_id, &req.user_id)
+            .await?
+            .ok_or(RemoveOrderAttachmentError::OrderNotFound {
+                order_id: req.order_id,
+            })?;
+
+        if order.creator_id != req.user_id && order.receiver_id != req.user_id {
+            return Err(RemoveOrderAttachmentError::PermissionDenied {
+                user_id: req.user_id,
+            });
+        }
+
+        let index = order
+            .attachments
+            .iter()
+            .position(|attachment| attachment.id == req.attachment_id)
+            .ok_or(RemoveOrderAttachmentError::AttachmentNotFound {
+                attachment_id: req.attachment_id,
+            })?;
+        let attachment = order.attachments.remove(index);
+        order.record_event(
+            req.user_id,
+            PurchaseOrderEventKind::AttachmentRemoved {
+                attachment_id: attachment.id,
+            },
+        );
+
+        self.order.save(&order).await?;
 
         Ok(order)
     }
 
-    async fn list_orders(
+    async fn update_order_schedule(
         &self,
-        req: ListOrdersRequest,
-    ) -> Result<Vec<PurchaseOrder>, ListOrdersError> {
-        let orders = self
+        req: UpdateOrderScheduleRequest,
+    ) -> Result<PurchaseOrder, UpdateOrderScheduleError> {
+        let mut order = self
             .order
-            .find_by_user(&req.user_id, req.role, req.status)
-            .await?;
-        Ok(orders)
+            .find_by_id(&req.order_id, &req.user_id)
+            .await?
+            .ok_or(UpdateOrderScheduleError::OrderNotFound {
+                order_id: req.order_id,
+            })?;
+
+        // The receiver usually gets the tracking number, so they may edit the schedule too
+        if order.creator_id != req.user_id && order.receiver_id != req.user_id {
+            return Err(UpdateOrderScheduleError::PermissionDenied {
+                user_id: req.user_id,
+            });
+        }
+
+        if !matches!(
+            order.status,
+            PurchaseOrderStatus::Incomplete | PurchaseOrderStatus::PartiallyFulfilled
+        ) {
+            return Err(UpdateOrderScheduleError::OrderNotEditable);
+        }
+
+        order.schedule = req.schedule;
+        order.record_event(req.user_id, PurchaseOrderEventKind::ScheduleUpdated);
+
+        self.order.save(&order).await?;
+
+        Ok(order)
+    }
+
+    async fn add_order_adjustment(
+        &self,
+        req: AddOrderAdjustmentRequest,
+    ) -> Result<PurchaseOrderAdjustmentId, AddOrderAdjustmentError> {
+        // Load and verify order exists
+        let mut order = self
+            .order
+            .find_by_id(&req.order_id, &req.user_id)
+            .await?
+            .ok_or(AddOrderAdjustmentError::OrderNotFound {
+                order_id: req.order_id,
+            })?;
+
+        // Verify user has permission to modify this order
+        if order.creator_id != req.user_id {
+            return Err(AddOrderAdjustmentError::PermissionDenied {
+                user_id: req.user_id,
+            });
+        }
+
+        if order.status != PurchaseOrderStatus::Incomplete {
+            return Err(AddOrderAdjustmentError::OrderNotEditable);
+        }
+
+        // Ensure currency matches
+        if req.amount.currency != order.total_price.currency {
+            return Err(AddOrderAdjustmentError::CurrencyMismatch {
+                expected: order.total_price.currency,
+                actual: req.amount.currency,
+            });
+        }
+
+        // Manual allocations must target line items of this order and add up to the amount
+        if let AdjustmentAllocation::Manual { allocations } = &req.allocation {
+            for allocation in allocations {
+                let exists = order
+                    .items
+                    .iter()
+                    .flat_map(|item| &item.line_items)
+                    .any(|line_item| line_item.id == allocation.line_item_id);
+                if !exists {
+                    return Err(AddOrderAdjustmentError::LineItemNotFound {
+                        line_item_id: allocation.line_item_id,
+                    });
+                }
+            }
+
+            let allocated: u64 = allocations.iter().map(|a| a.amount as u64).sum();
+            if allocated != req.amount.amount as u64 {
+                return Err(AddOrderAdjustmentError::AllocationMismatch {
+                    expected: req.amount.amount as u64,
+                    actual: allocated,
+                });
+            }
+        }
+
+        let adjustment = PurchaseOrderAdjustment {
+            id: PurchaseOrderAdjustmentId::new(),
+            kind: req.kind,
+            amount: req.amount,
+            allocation: req.allocation,
+            note: req.note,
+        };
+        let adjustment_id = adjustment.id;
+
+        // Update total price
+        order.total_price.amount = if adjustment.kind.is_deduction() {
+            order
+                .total_price
+                .amount
+                .checked_sub(adjustment.amount.amount)
+                .ok_or(AddOrderAdjustmentError::ExceedsTotal)?
+        } else {
+            order
+                .total_price
+                .amount
+                .checked_add(adjustment.amount.amount)
+                .ok_or(AddOrderAdjustmentError::PriceOverflow)?
+        };
+        order.adjustments.push(adjustment);
+        order.record_event(
+            req.user_id,
+            PurchaseOrderEventKind::AdjustmentAdded { adjustment_id },
+        );
+
+        // Save updated order
+        self.order.save(&order).await?;
+
+        Ok(adjustment_id)
+    }
+
+    async fn remove_order_adjustment(
+        &self,
+        req: RemoveOrderAdjustmentRequest,
+    ) -> Result<PurchaseOrder, RemoveOrderAdjustmentError> {
+        // Load and verify order exists
+        let mut order = self
+            .order
+            .find_by_id(&req.order_id, &req.user_id)
+            .await?
+            .ok_or(RemoveOrderAdjustmentError::OrderNotFound {
+                order_id: req.order_id,
+            })?;
+
+        // Verify user has permission to modify this order
+        if order.creator_id != req.user_id {
+            return Err(RemoveOrderAdjustmentError::PermissionDenied {
+                user_id: req.user_id,
+            });
+        }
+
+        if order.status != PurchaseOrderStatus::Incomplete {
+            return Err(RemoveOrderAdjustmentError::OrderNotEditable);
+        }
+
+        let index = order
+            .adjustments
+            .iter()
+            .position(|adjustment| adjustment.id == req.adjustment_id)
+            .ok_or(RemoveOrderAdjustmentError::AdjustmentNotFound {
+                adjustment_id: req.adjustment_id,
+            })?;
+        let adjustment = order.adjustments.remove(index);
+
+        // Update total price
+        order.total_price.amount = if adjustment.kind.is_deduction() {
+            order
+                .total_price
+                .amount
+                .checked_add(adjustment.amount.amount)
+                .ok_or(RemoveOrderAdjustmentError::PriceOverflow)?
+        } else {
+            order
+                .total_price
+                .amount
+                .checked_sub(adjustment.amount.amount)
+                .ok_or(RemoveOrderAdjustmentError::ExceedsTotal)?
+        };
+        order.record_event(
+            req.user_id,
+            PurchaseOrderEventKind::AdjustmentRemoved {
+                adjustment_id: adjustment.id,
+            },
+        );
+
+        // Save updated order
+        self.order.save(&order).await?;
+
+        Ok(order)
+    }
+
+    async fn submit_mystery_box_results(
+        &self,
+        req: SubmitMysteryBoxResultsRequest,
+    ) -> Result<MysteryBoxResultsResponse, SubmitMysteryBoxResultsError> {
+        // Load and verify order exists
+        let mut order = self
+            .order
+            .find_by_id(&req.order_id, &req.user_id)
+            .await?
+            .ok_or(SubmitMysteryBoxResultsError::OrderNotFound)?;
+
+        // Verify user has permission to modify this order
+        if order.creator_id != req.user_id {
+            return Err(SubmitMysteryBoxResultsError::PermissionDenied {
+                user_id: req.user_id,
+            });
+        }
+
+        // Results can be edited until the order is completed
+        if !matches!(
+            order.status,
+            PurchaseOrderStatus::Incomplete | PurchaseOrderStatus::PartiallyFulfilled
+        ) {
+            return Err(SubmitMysteryBoxResultsError::OrderNotEditable);
+        }
+
+        // Find the order item
+        let item = order
+            .items
+            .iter_mut()
+            .find(|item| item.id == req.order_item_id)
+            .ok_or(SubmitMysteryBoxResultsError::OrderItemNotFound {
+                order_item_id: req.order_item_id,
+            })?;
+
+        // Replacing the results must not drop line items that were already received
+        if !matches!(
+            item.status,
+            PurchaseOrderItemStatus::AwaitingInput | PurchaseOrderItemStatus::Pending
+        ) || item
+            .line_items
+            .iter()
+            .any(|line_item| line_item.is_fulfilled())
+        {
+            return Err(SubmitMysteryBoxResultsError::OrderItemNotEditable {
+                order_item_id: item.id,
+            });
+        }
+
+        // Verify it's a mystery box item
+        let variant = self
+            .product_variant
+            .find_by_id(&item.purchased_variant_id)
+            .await?
+            .ok_or(SubmitMysteryBoxResultsError::VariantNotFound {
+                variant_id: item.purchased_variant_id,
+            })?;
+
+        let mystery_box_config = variant
+            .mystery_box
+            .ok_or(SubmitMysteryBoxResultsError::NotMysteryBox)?;
+
+        // Verify the number of variants matches expected count
+        let expected_count = item.quantity.get() * mystery_box_config.items_count.get();
+        if req.received_variants.len() as u32 != expected_count {
+            return Err(SubmitMysteryBoxResultsError::InvalidVariantCount {
+                expected: expected_count,
+                actual: req.received_variants.len() as u32,
+            });
+        }
+
+        // Check received variants against the possible ones
+        let warnings = validate_mystery_box_results(&mystery_box_config, &req.received_variants)
+            .map_err(|variant_id| SubmitMysteryBoxResultsError::UnexpectedVariant { variant_id })?;
+
+        // The previous results are replaced, so they must not be manually allocated
+        if let Some(line_item_id) = manually_allocated(&order.adjustments, &item.line_items) {
+            return Err(SubmitMysteryBoxResultsError::LineItemAllocated { line_item_id });
+        }
+
+        // Create line items for each received variant
+        item.line_items = req
+            .received_variants
+            .into_iter()
+            .map(|variant_id| PurchaseOrderLineItem::new(variant_id, item.id, req.owner_id))
+            .collect();
+
+        // Update item status to Pending
+        item.status = PurchaseOrderItemStatus::Pending;
+        order.record_event(
+            req.user_id,
+            PurchaseOrderEventKind::MysteryBoxResultsSubmitted {
+                order_item_id: req.order_item_id,
+            },
+        );
+
+        // Save updated order
+        self.order.save(&order).await?;
+
+        Ok(MysteryBoxResultsResponse { order, warnings })
+    }
+
+    async fn add_mystery_box_results(
+        &self,
+        req: AddMysteryBoxResultsRequest,
+    ) -> Result<MysteryBoxResultsResponse, AddMysteryBoxResultsError> {
+        // Load and verify order exists
+        let mut order = self
+            .order
+            .find_by_id(&req.order_id, &req.user_id)
+            .await?
+            .ok_or(AddMysteryBoxResultsError::OrderNotFound {
+                order_id: req.order_id,
+            })?;
+
+        // Verify user has permission to modify this order
+        if order.creator_id != req.user_id {
+            return Err(AddMysteryBoxResultsError::PermissionDenied {
+                user_id: req.user_id,
+            });
+        }
+
+        // Results can be edited until the order is completed
+        if !matches!(
+            order.status,
+            PurchaseOrderStatus::Incomplete | PurchaseOrderStatus::PartiallyFulfilled
+        ) {
+            return Err(AddMysteryBoxResultsError::OrderNotEditable);
+        }
+
+        // Validate all owners before changing anything
+        let mut checked_owners: Vec<UserId> = vec![];
+        for owner_id in req.results.iter().filter_map(|result| result.owner_id) {
+            if checked_owners.contains(&owner_id) {
+                continue;
+            }
+            self.user
+                .find_by_id(&owner_id)
+                .await?
+                .ok_or(AddMysteryBoxResultsError::UserNotFound { user_id: owner_id })?;
+            checked_owners.push(owner_id);
+        }
+
+        let receiver_id = order.receiver_id;
+        let item = order
+            .items
+            .iter_mut()
+            .find(|item| item.id == req.order_item_id)
+            .ok_or(AddMysteryBoxResultsError::OrderItemNotFound {
+                order_item_id: req.order_item_id,
+            })?;
+        if !matches!(
+            item.status,
+            PurchaseOrderItemStatus::AwaitingInput | PurchaseOrderItemStatus::Pending
+        ) {
+            return Err(AddMysteryBoxResultsError::OrderItemNotEditable {
+                order_item_id: item.id,
+            });
+        }
+
+        // Verify it's a mystery box item
+        let variant = self
+            .product_variant
+            .find_by_id(&item.purchased_variant_id)
+            .await?
+            .ok_or(AddMysteryBoxResultsError::VariantNotFound {
+                variant_id: item.purchased_variant_id,
+            })?;
+        let mystery_box_config = variant
+            .mystery_box
+            .ok_or(AddMysteryBoxResultsError::NotMysteryBox)?;
+
+        // Results may come in box by box, but never exceed the expected count
+        let expected_count = item.quantity.get() * mystery_box_config.items_count.get();
+        let actual_count = (item.line_items.len() + req.results.len()) as u32;
+        if actual_count > expected_count {
+            return Err(AddMysteryBoxResultsError::TooManyResults {
+                expected: expected_count,
+                actual: actual_count,
+            });
+        }
+
+        // Check received variants against the possible ones
+        let received: Vec<ProductVariantId> =
+            req.results.iter().map(|result| result.variant_id).collect();
+        let warnings = validate_mystery_box_results(&mystery_box_config, &received)
+            .map_err(|variant_id| AddMysteryBoxResultsError::UnexpectedVariant { variant_id })?;
+
+        // Create line items for each received variant
+        for result in req.results {
+            item.line_items.push(PurchaseOrderLineItem::new(
+                result.variant_id,
+                item.id,
+                result.owner_id.unwrap_or(receiver_id),
+            ));
+        }
+
+        // The item is ready for fulfillment once all results are in
+        if actual_count == expected_count {
+            item.status = PurchaseOrderItemStatus::Pending;
+        }
+        order.record_event(
+            req.user_id,
+            PurchaseOrderEventKind::MysteryBoxResultsSubmitted {
+                order_item_id: req.order_item_id,
+            },
+        );
+
+        // Save updated order
+        self.order.save(&order).await?;
+
+        Ok(MysteryBoxResultsResponse { order, warnings })
+    }
+
+    async fn update_mystery_box_result(
+        &self,
+        req: UpdateMysteryBoxResultRequest,
+    ) -> Result<MysteryBoxResultsResponse, UpdateMysteryBoxResultError> {
+        // Load and verify order exists
+        let mut order = self
+            .order
+            .find_by_id(&req.order_id, &req.user_id)
+            .await?
+            .ok_or(UpdateMysteryBoxResultError::OrderNotFound {
+                order_id: req.order_id,
+            })?;
+
+        // Verify user has permission to modify this order
+        if order.creator_id != req.user_id {
+            return Err(UpdateMysteryBoxResultError::PermissionDenied {
+                user_id: req.user_id,
+            });
+        }
+
+        // Results can be edited until the order is completed
+        if !matches!(
+            order.status,
+            PurchaseOrderStatus::Incomplete | PurchaseOrderStatus::PartiallyFulfilled
+        ) {
+            return Err(UpdateMysteryBoxResultError::OrderNotEditable);
+        }
+
+        if let Some(owner_id) = req.owner_id {
+            self.user
+                .find_by_id(&owner_id)
+                .await?
+                .ok_or(UpdateMysteryBoxResultError::UserNotFound { user_id: owner_id })?;
+        }
+
+        let item = order
+            .items
+            .iter_mut()
+            .find(|item| item.id == req.order_item_id)
+            .ok_or(UpdateMysteryBoxResultError::OrderItemNotFound {
+                order_item_id: req.order_item_id,
+            })?;
+        if !matches!(
+            item.status,
+            PurchaseOrderItemStatus::AwaitingInput | PurchaseOrderItemStatus::Pending
+        ) {
+            return Err(UpdateMysteryBoxResultError::OrderItemNotEditable {
+                order_item_id: item.id,
+            });
+        }
+
+        // Verify it's a mystery box item
+        let variant = self
+            .product_variant
+            .find_by_id(&item.purchased_variant_id)
+            .await?
+            .ok_or(UpdateMysteryBoxResultError::VariantNotFound {
+                variant_id: item.purchased_variant_id,
+            })?;
+        let mystery_box_config = variant
+            .mystery_box
+            .ok_or(UpdateMysteryBoxResultError::NotMysteryBox)?;
+
+        let index = item
+            .line_items
+            .iter()
+            .position(|line_item| line_item.id == req.line_item_id)
+            .ok_or(UpdateMysteryBoxResultError::LineItemNotFound {
+                line_item_id: req.line_item_id,
+            })?;
+        if item.line_items[index].is_fulfilled() {
+            return Err(UpdateMysteryBoxResultError::LineItemAlreadyFulfilled {
+                line_item_id: req.line_item_id,
+            });
+        }
+
+        // Check the corrected variant against the possible ones
+        let received: Vec<ProductVariantId> = req.variant_id.into_iter().collect();
+        let warnings = validate_mystery_box_results(&mystery_box_config, &received)
+            .map_err(|variant_id| UpdateMysteryBoxResultError::UnexpectedVariant { variant_id })?;
+
+        let line_item = &mut item.line_items[index];
+        if let Some(variant_id) = req.variant_id {
+            line_item.variant_id = variant_id;
+        }
+        if let Some(owner_id) = req.owner_id {
+            line_item.owner_id = owner_id;
+        }
+        order.record_event(
+            req.user_id,
+            PurchaseOrderEventKind::MysteryBoxResultUpdated {
+                order_item_id: req.order_item_id,
+                line_item_id: req.line_item_id,
+            },
+        );
+
+        // Save updated order
+        self.order.save(&order).await?;
+
+        Ok(MysteryBoxResultsResponse { order, warnings })
+    }
+
+    async fn remove_mystery_box_result(
+        &self,
+        req: RemoveMysteryBoxResultRequest,
+    ) -> Result<PurchaseOrder, RemoveMysteryBoxResultError> {
+        // Load and verify order exists
+        let mut order = self
+            .order
+            .find_by_id(&req.order_id, &req.user_id)
+            .await?
+            .ok_or(RemoveMysteryBoxResultError::OrderNotFound {
+                order_id: req.order_id,
+            })?;
+
+        // Verify user has permission to modify this order
+        if order.creator_id != req.user_id {
+            return Err(RemoveMysteryBoxResultError::PermissionDenied {
+                user_id: req.user_id,
+            });
+        }
+
+        // Results can be edited until the order is completed
+        if !matches!(
+            order.status,
+            PurchaseOrderStatus::Incomplete | PurchaseOrderStatus::PartiallyFulfilled
+        ) {
+            return Err(RemoveMysteryBoxResultError::OrderNotEditable);
+        }
+
+        let item = order
+            .items
+            .iter_mut()
+            .find(|item| item.id == req.order_item_id)
+            .ok_or(RemoveMysteryBoxResultError::OrderItemNotFound {
+                order_item_id: req.order_item_id,
+            })?;
+        if !matches!(
+            item.status,
+            PurchaseOrderItemStatus::AwaitingInput | PurchaseOrderItemStatus::Pending
+        ) {
+            return Err(RemoveMysteryBoxResultError::OrderItemNotEditable {
+                order_item_id: item.id,
+            });
+        }
+
+        // Verify it's a mystery box item
+        let variant = self
+            .product_variant
+            .find_by_id(&item.purchased_variant_id)
+            .await?
+            .ok_or(RemoveMysteryBoxResultError::VariantNotFound {
+                variant_id: item.purchased_variant_id,
+            })?;
+        if variant.mystery_box.is_none() {
+            return Err(RemoveMysteryBoxResultError::NotMysteryBox);
+        }
+
+        let index = item
+            .line_items
+            .iter()
+            .position(|line_item| line_item.id == req.line_item_id)
+            .ok_or(RemoveMysteryBoxResultError::LineItemNotFound {
+                line_item_id: req.line_item_id,
+            })?;
+        if item.line_items[index].is_fulfilled() {
+            return Err(RemoveMysteryBoxResultError::LineItemAlreadyFulfilled {
+                line_item_id: req.line_item_id,
+            });
+        }
+        if manually_allocated(&order.adjustments, &item.line_items[index..=index]).is_some() {
+            return Err(RemoveMysteryBoxResultError::LineItemAllocated {
+                line_item_id: req.line_item_id,
+            });
+        }
+
+        item.line_items.remove(index);
+
+        // The item is waiting for input again
+        item.status = PurchaseOrderItemStatus::AwaitingInput;
+        order.record_event(
+            req.user_id,
+            PurchaseOrderEventKind::MysteryBoxResultRemoved {
+                order_item_id: req.order_item_id,
+                line_item_id: req.line_item_id,
+            },
+        );
+
+        // Save updated order
+        self.order.save(&order).await?;
+
+        Ok(order)
+    }
+
+    async fn get_order(&self, req: GetOrderRequest) -> Result<PurchaseOrderView, GetOrderError> {
+        let order = self
+            .order
+            .find_by_id(&req.order_id, &req.user_id)
+            .await?
+            .ok_or(GetOrderError::NotFound)?;
+
+        Ok(order.into_view(req.user_id))
+    }
+
+    async fn get_order_events(
+        &self,
+        req: GetOrderEventsRequest,
+    ) -> Result<Vec<PurchaseOrderEvent>, GetOrderEventsError> {
+        let order = self
+            .order
+            .find_by_id(&req.order_id, &req.user_id)
+            .await?
+            .ok_or(GetOrderEventsError::NotFound)?;
+
+        Ok(order.events_visible_to(req.user_id))
+    }
+
+    async fn list_order_arrivals(
+        &self,
+        req: ListOrderArrivalsRequest,
+    ) -> Result<OrderArrivalsResponse, ListOrderArrivalsError> {
+        let orders = self
+            .order
+            .find_by_user(&req.user_id, OrderRoleFilter::Participant, None)
+            .await?;
+
+        let horizon = req
+            .within_days
+            .map(|days| req.today + Days::new(u64::from(days)));
+
+        let mut overdue = Vec::new();
+        let mut arriving_soon = Vec::new();
+        for order in orders {
+            let Some(date) = order.pending_arrival_date() else {
+                continue;
+            };
+            if date < req.today {
+                overdue.push((date, order));
+            } else if horizon.is_none_or(|horizon| date <= horizon) {
+                arriving_soon.push((date, order));
+            }
+        }
+        overdue.sort_by_key(|(date, _)| *date);
+        arriving_soon.sort_by_key(|(date, _)| *date);
+
+        let into_views = |orders: Vec<(NaiveDate, PurchaseOrder)>| {
+            orders
+                .into_iter()
+                .map(|(_, order)| order.into_view(req.user_id))
+                .collect()
+        };
+        Ok(OrderArrivalsResponse {
+            overdue: into_views(overdue),
+            arriving_soon: into_views(arriving_soon),
+        })
+    }
+
+    async fn list_orders(
+        &self,
+        req: ListOrdersRequest,
+    ) -> Result<Vec<PurchaseOrderView>, ListOrdersError> {
+        let orders = self
+            .order
+            .find_by_user(&req.user_id, req.role, req.status)
+            .await?;
+        Ok(orders
+            .into_iter()
+            .map(|order| order.into_view(req.user_id))
+            .collect())
     }
 }
@@ -1,15 +1,34 @@
 use chrono::Utc;
 use sawa_core::{
     errors::RepositoryError,
-    models::purchase::{PurchaseOrder, PurchaseOrderItemStatus, PurchaseOrderStatus},
+    models::{
+        misc::Price,
+        product::{ProductInstance, ProductInstanceStatus},
+        purchase::{
+            AdjustmentAllocation, PurchaseOrder, PurchaseOrderEventKind, PurchaseOrderItemStatus,
+            PurchaseOrderLineItemId, PurchaseOrderReturn, PurchaseOrderReturnId,
+            PurchaseOrderStatus,
+        },
+        user::UserId,
+    },
     repositories::*,
-    services::{CancelOrderError, FulfillOrderError, PurchaseOrderLifecycleService},
+    services::{
+        CancelOrderError, CreateDeliveryTransactionRequest, CreateTransactionError,
+        FulfillOrderError, FulfillOrderItemsError, FulfillOrderItemsRequest,
+        PurchaseOrderLifecycleService, ReturnOrderItemsError, ReturnOrderItemsRequest,
+        TransactionLifecycleService,
+    },
 };
+use std::{collections::HashSet, num::NonZeroU32};
 
 use super::Service;
 
-impl<P, PV, PI, PO, UT, U, T, M> PurchaseOrderLifecycleService
-    for Service<P, PV, PI, PO, UT, U, T, M>
+/// Subtract `amount` from `total`, stopping at zero.
+fn deduct(total: &mut Price, amount: u64) {
+    total.amount = u32::try_from((total.amount as u64).saturating_sub(amount)).unwrap_or(0);
+}
+
+impl<P, PV, PI, PO, UT, U, T, M, C, L, W, S> Service<P, PV, PI, PO, UT, U, T, M, C, L, W, S>
 where
     P: ProductRepository,
     PV: ProductVariantRepository,
@@ -19,53 +38,44 @@ where
     U: UserRepository,
     T: TagRepository,
     M: MediaRepository,
+    C: CartRepository,
+    L: StorageLocationRepository,
+    W: WishlistRepository,
+    S: StatisticsRepository,
 {
-    async fn fulfill_order(
+    /// Create ProductInstances for the given line items of Pending items,
+    /// then update item and order statuses accordingly.
+    ///
+    /// Returns the instances of the line items fulfilled by this call.
+    async fn fulfill_line_items(
         &self,
-        req: &sawa_core::services::FulfillOrderRequest,
-    ) -> Result<PurchaseOrder, FulfillOrderError> {
-        // Load order
-        let mut order = self
-            .order
-            .find_by_id(&req.order_id, &req.user_id)
-            .await?
-            .ok_or(FulfillOrderError::OrderNotFound)?;
-
-        // Validate order is ready for fulfillment
-        match order.status {
-            PurchaseOrderStatus::Incomplete => {}
-            PurchaseOrderStatus::Fulfilled => {
-                return Ok(order);
-            }
-            PurchaseOrderStatus::Cancelled => {
-                return Err(FulfillOrderError::OrderCancelled);
-            }
-        }
+        order: &mut PurchaseOrder,
+        line_item_ids: &HashSet<PurchaseOrderLineItemId>,
+    ) -> Result<Vec<ProductInstance>, RepositoryError> {
+        let receiver_id = order.receiver_id;
+        let landed_costs = order.landed_costs();
+        let mut instances = Vec::new();
 
-        // Validate all items are in Pending status
-        for item in &order.items {
+        for item in &mut order.items {
             if item.status != PurchaseOrderItemStatus::Pending {
-                return Err(FulfillOrderError::ItemNotPending);
+                continue;
             }
-        }
-
-        // Create ProductInstances for all line items
-        let receiver_id = order.receiver_id;
 
-        for item in &mut order.items {
             for line_item in &mut item.line_items {
-                // Skip if already fulfilled items
-                if line_item.is_fulfilled() {
+                // Skip if already fulfilled or not selected
+                if line_item.is_fulfilled() || !line_item_ids.contains(line_item.id()) {
                     continue;
                 }
 
                 // Create ProductInstance
                 // The instance is first assigned to the receiver
-                let instance = line_item.to_product_instance(receiver_id);
+                let instance = line_item
+                    .to_product_instance(receiver_id, landed_costs.get(line_item.id()).copied());
                 match self.product_instance.save(&instance).await {
                     Ok(_) => {
                         // Update line item on success
                         line_item.fulfill(&instance);
+                        instances.push(instance);
                     }
                     Err(RepositoryError::Duplicated(e)) => {
                         let instance = self
@@ -75,31 +85,267 @@ where
                         if let Some(instance) = instance {
                             // Update line item on success
                             line_item.fulfill(&instance);
+                            instances.push(instance);
                         } else {
                             // Unique constraint violated but instance not found.
                             // Populate error
-                            return Err(FulfillOrderError::Repository(
-                                RepositoryError::Duplicated(e),
-                            ));
+                            return Err(RepositoryError::Duplicated(e));
                         }
                     }
                     Err(e) => {
-                        return Err(FulfillOrderError::Repository(e));
+                        return Err(e);
                     }
                 };
             }
 
-            // Update item status
-            item.status = PurchaseOrderItemStatus::Fulfilled;
+            // Update item status once all line items are fulfilled
+            if item
+                .line_items
+                .iter()
+                .all(|line_item| line_item.is_fulfilled())
+            {
+                item.status = PurchaseOrderItemStatus::Fulfilled;
+            }
         }
 
         // Update order status
-        order.status = PurchaseOrderStatus::Fulfilled;
-        order.completed_at = Some(Utc::now());
+        let has_remaining = order.items.iter().any(|item| {
+            matches!(
+                item.status,
+                PurchaseOrderItemStatus::AwaitingInput | PurchaseOrderItemStatus::Pending
+            )
+        });
+        let has_fulfilled = order
+            .items
+            .iter()
+            .flat_map(|item| &item.line_items)
+            .any(|line_item| line_item.is_fulfilled());
+        if !has_remaining {
+            order.status = PurchaseOrderStatus::Fulfilled;
+            order.completed_at = Some(Utc::now());
+        } else if has_fulfilled {
+            order.status = PurchaseOrderStatus::PartiallyFulfilled;
+        }
+
+        Ok(instances)
+    }
+
+    /// Hand over instances owned by other users from the receiver to their owners.
+    ///
+    /// Creates one pending delivery transaction per owner.
+    async fn create_deliveries(
+        &self,
+        receiver_id: UserId,
+        instances: &[ProductInstance],
+    ) -> Result<(), CreateTransactionError> {
+        let mut deliveries: Vec<CreateDeliveryTransactionRequest> = Vec::new();
+        for instance in instances {
+            // Skip items kept by the receiver, or already being handed over
+            if instance.owner_id == receiver_id
+                || instance.holder_id != receiver_id
+                || instance.status != ProductInstanceStatus::Active
+            {
+                continue;
+            }
+
+            match deliveries
+                .iter_mut()
+                .find(|delivery| delivery.to_user_id == instance.owner_id)
+            {
+                Some(delivery) => delivery.items.push(instance.id),
+                None => deliveries.push(CreateDeliveryTransactionRequest {
+                    from_user_id: receiver_id,
+                    to_user_id: instance.owner_id,
+                    items: vec![instance.id],
+                }),
+            }
+        }
+
+        for delivery in deliveries {
+            self.create_delivery_transaction(delivery).await?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<P, PV, PI, PO, UT, U, T, M, C, L, W, S> PurchaseOrderLifecycleService
+    for Service<P, PV, PI, PO, UT, U, T, M, C, L, W, S>
+where
+    P: ProductRepository,
+    PV: ProductVariantRepository,
+    PI: ProductInstanceRepository,
+    PO: PurchaseOrderRepository,
+    UT: UserTransactionRepository,
+    U: UserRepository,
+    T: TagRepository,
+    M: MediaRepository,
+    C: CartRepository,
+    L: StorageLocationRepository,
+    W: WishlistRepository,
+    S: StatisticsRepository,
+{
+    async fn fulfill_order(
+        &self,
+        req: &sawa_core::services::FulfillOrderRequest,
+    ) -> Result<PurchaseOrder, FulfillOrderError> {
+        // Load order
+        let mut order = self
+            .order
+            .find_by_id(&req.order_id, &req.user_id)
+            .await?
+            .ok_or(FulfillOrderError::OrderNotFound)?;
+
+        // Only the creator or the receiver can record arrivals
+        if order.creator_id != req.user_id && order.receiver_id != req.user_id {
+            return Err(FulfillOrderError::PermissionDenied {
+                user_id: req.user_id,
+            });
+        }
+
+        // Validate order is ready for fulfillment
+        match order.status {
+            PurchaseOrderStatus::Incomplete | PurchaseOrderStatus::PartiallyFulfilled => {}
+            PurchaseOrderStatus::Fulfilled => {
+                return Ok(order);
+            }
+            PurchaseOrderStatus::Cancelled => {
+                return Err(FulfillOrderError::OrderCancelled);
+            }
+        }
+
+        // Validate all unfulfilled items are in Pending status
+        for item in &order.items {
+            if !matches!(
+                item.status,
+                PurchaseOrderItemStatus::Pending | PurchaseOrderItemStatus::Fulfilled
+            ) {
+                return Err(FulfillOrderError::ItemNotPending);
+            }
+        }
+
+        // Fulfill all line items
+        let line_item_ids: HashSet<_> = order
+            .items
+            .iter()
+            .flat_map(|item| &item.line_items)
+            .map(|line_item| line_item.id)
+            .collect();
+        let instances = self.fulfill_line_items(&mut order, &line_item_ids).await?;
+
+        // Hand over items owned by other participants
+        self.create_deliveries(order.receiver_id, &instances)
+            .await?;
+
+        if !instances.is_empty() {
+            order.record_event(
+                req.user_id,
+                PurchaseOrderEventKind::Fulfilled {
+                    line_item_ids: instances
+                        .iter()
+                        .map(|instance| instance.source_order_line_item_id)
+                        .collect(),
+                },
+            );
+        }
+
+        // Save order
+        self.order.save(&order).await?;
+
+        self.record_obtained_instances(&instances).await?;
+
+        Ok(order)
+    }
+
+    async fn fulfill_order_items(
+        &self,
+        req: &FulfillOrderItemsRequest,
+    ) -> Result<PurchaseOrder, FulfillOrderItemsError> {
+        // Load order
+        let mut order = self
+            .order
+            .find_by_id(&req.order_id, &req.user_id)
+            .await?
+            .ok_or(FulfillOrderItemsError::OrderNotFound)?;
+
+        // Only the creator or the receiver can record arrivals
+        if order.creator_id != req.user_id && order.receiver_id != req.user_id {
+            return Err(FulfillOrderItemsError::PermissionDenied {
+                user_id: req.user_id,
+            });
+        }
+
+        // Validate order is ready for fulfillment
+        match order.status {
+            PurchaseOrderStatus::Incomplete | PurchaseOrderStatus::PartiallyFulfilled => {}
+            PurchaseOrderStatus::Fulfilled => {
+                return Ok(order);
+            }
+            PurchaseOrderStatus::Cancelled => {
+                return Err(FulfillOrderItemsError::OrderCancelled);
+            }
+        }
+
+        // Collect selected line items, validating their items are Pending
+        let mut line_item_ids = HashSet::new();
+        for item_id in &req.item_ids {
+            let item = order.items.iter().find(|item| item.id == *item_id).ok_or(
+                FulfillOrderItemsError::OrderItemNotFound {
+                    order_item_id: *item_id,
+                },
+            )?;
+            match item.status {
+                PurchaseOrderItemStatus::Pending => {
+                    line_item_ids.extend(item.line_items.iter().map(|line_item| line_item.id));
+                }
+                PurchaseOrderItemStatus::Fulfilled => {}
+                _ => return Err(FulfillOrderItemsError::ItemNotPending),
+            }
+        }
+        for line_item_id in &req.line_item_ids {
+            let item = order
+                .items
+                .iter()
+                .find(|item| {
+                    item.line_items
+                        .iter()
+                        .any(|line_item| line_item.id == *line_item_id)
+                })
+                .ok_or(FulfillOrderItemsError::LineItemNotFound {
+                    line_item_id: *line_item_id,
+                })?;
+            match item.status {
+                PurchaseOrderItemStatus::Pending => {
+                    line_item_ids.insert(*line_item_id);
+                }
+                PurchaseOrderItemStatus::Fulfilled => {}
+                _ => return Err(FulfillOrderItemsError::ItemNotPending),
+            }
+        }
+
+        let instances = self.fulfill_line_items(&mut order, &line_item_ids).await?;
+
+        // Hand over items owned by other participants
+        self.create_deliveries(order.receiver_id, &instances)
+            .await?;
+
+        if !instances.is_empty() {
+            order.record_event(
+                req.user_id,
+                PurchaseOrderEventKind::Fulfilled {
+                    line_item_ids: instances
+                        .iter()
+                        .map(|instance| instance.source_order_line_item_id)
+                        .collect(),
+                },
+            );
+        }
 
         // Save order
         self.order.save(&order).await?;
 
+        self.record_obtained_instances(&instances).await?;
+
         Ok(order)
     }
 
@@ -124,7 +370,7 @@ where
 
         // Validate order can be cancelled
         match order.status {
-            PurchaseOrderStatus::Incomplete => {}
+            PurchaseOrderStatus::Incomplete | PurchaseOrderStatus::PartiallyFulfilled => {}
             PurchaseOrderStatus::Fulfilled => {
                 return Err(CancelOrderError::OrderAlreadyCompleted);
             }
@@ -134,23 +380,243 @@ where
             }
         }
 
-        // Update order status
-        order.status = PurchaseOrderStatus::Cancelled;
-        order.cancelled_at = Some(Utc::now());
+        // Regular items are paid per line item, mystery boxes per box
+        let mut mystery_box_items = HashSet::new();
+        for item in &order.items {
+            let variant = self
+                .product_variant
+                .find_by_id(&item.purchased_variant_id)
+                .await?;
+            if variant.is_some_and(|variant| variant.mystery_box.is_some()) {
+                mystery_box_items.insert(item.id);
+            }
+        }
 
-        // Update all items to cancelled
+        // Cancel all remaining items
+        let has_fulfilled = order
+            .items
+            .iter()
+            .flat_map(|item| &item.line_items)
+            .any(|line_item| line_item.is_fulfilled());
+        let mut dropped = HashSet::new();
         for item in &mut order.items {
             match item.status {
-                PurchaseOrderItemStatus::Fulfilled => {
-                    // Should not happen if order is Incomplete
-                    // But handle defensively
+                PurchaseOrderItemStatus::Fulfilled => {}
+                _ if item
+                    .line_items
+                    .iter()
+                    .any(|line_item| line_item.is_fulfilled()) =>
+                {
+                    // Partially fulfilled item: drop the line items that never arrived
+                    dropped.extend(
+                        item.line_items
+                            .iter()
+                            .filter(|line_item| !line_item.is_fulfilled())
+                            .map(|line_item| line_item.id),
+                    );
+                    item.line_items.retain(|line_item| line_item.is_fulfilled());
+                    item.status = PurchaseOrderItemStatus::Fulfilled;
+
+                    // Only the line items that arrived are paid for
+                    if !mystery_box_items.contains(&item.id) {
+                        let kept = NonZeroU32::new(item.line_items.len() as u32)
+                            .expect("at least one line item is fulfilled");
+                        if let Some(unit_price) = item.unit_price {
+                            let cancelled = (item.quantity.get() - kept.get()) as u64;
+                            deduct(&mut order.total_price, unit_price.amount as u64 * cancelled);
+                        }
+                        item.quantity = kept;
+                    }
                 }
                 _ => {
+                    dropped.extend(item.line_items.iter().map(|line_item| line_item.id));
                     item.status = PurchaseOrderItemStatus::Cancelled;
+                    if has_fulfilled && let Some(unit_price) = item.unit_price {
+                        deduct(
+                            &mut order.total_price,
+                            unit_price.amount as u64 * item.quantity.get() as u64,
+                        );
+                    }
+                }
+            }
+        }
+
+        // Manual allocations to cancelled line items no longer apply
+        if has_fulfilled {
+            for adjustment in &mut order.adjustments {
+                let AdjustmentAllocation::Manual { allocations } = &mut adjustment.allocation
+                else {
+                    continue;
+                };
+                let removed: u32 = allocations
+                    .iter()
+                    .filter(|allocation| dropped.contains(&allocation.line_item_id))
+                    .map(|allocation| allocation.amount)
+                    .sum();
+                allocations.retain(|allocation| !dropped.contains(&allocation.line_item_id));
+                adjustment.amount.amount = adjustment.amount.amount.saturating_sub(removed);
+                if adjustment.kind.is_deduction() {
+                    order.total_price.amount = order.total_price.amount.saturating_add(removed);
+                } else {
+                    deduct(&mut order.total_price, removed as u64);
+                }
+            }
+            order
+                .adjustments
+                .retain(|adjustment| adjustment.amount.amount > 0);
+        }
+
+        // Update order status
+        if has_fulfilled {
+            // Some goods already arrived: complete the order with what has been fulfilled
+            order.status = PurchaseOrderStatus::Fulfilled;
+            order.completed_at = Some(Utc::now());
+        } else {
+            order.status = PurchaseOrderStatus::Cancelled;
+            order.cancelled_at = Some(Utc::now());
+        }
+        order.record_event(
+            req.user_id,
+            PurchaseOrderEventKind::Cancelled {
+                reason: req.reason.clone(),
+            },
+        );
+
+        // Save order
+        self.order.save(&order).await?;
+
+        Ok(order)
+    }
+
+    async fn return_order_items(
+        &self,
+        req: &ReturnOrderItemsRequest,
+    ) -> Result<PurchaseOrder, ReturnOrderItemsError> {
+        // Load order
+        let mut order = self
+            .order
+            .find_by_id(&req.order_id, &req.user_id)
+            .await?
+            .ok_or(ReturnOrderItemsError::OrderNotFound)?;
+
+        // Only creator can return items to the seller
+        if order.creator_id != req.user_id {
+            return Err(ReturnOrderItemsError::PermissionDenied {
+                user_id: req.user_id,
+            });
+        }
+
+        match order.status {
+            PurchaseOrderStatus::PartiallyFulfilled | PurchaseOrderStatus::Fulfilled => {}
+            PurchaseOrderStatus::Incomplete | PurchaseOrderStatus::Cancelled => {
+                return Err(ReturnOrderItemsError::OrderNotFulfilled);
+            }
+        }
+
+        if req.line_item_ids.is_empty() {
+            return Err(ReturnOrderItemsError::NoLineItems);
+        }
+
+        // The replacement takes over the cost of the returned line item,
+        // so a refund on top would credit the owner twice
+        if req.refund.is_some() && req.replace {
+            return Err(ReturnOrderItemsError::RefundWithReplacement);
+        }
+
+        if let Some(refund) = &req.refund
+            && refund.currency != order.total_price.currency
+        {
+            return Err(ReturnOrderItemsError::CurrencyMismatch {
+                expected: order.total_price.currency,
+                actual: refund.currency,
+            });
+        }
+
+        // Validate line items and their instances
+        let mut instances = Vec::new();
+        for line_item_id in &req.line_item_ids {
+            let line_item = order
+                .items
+                .iter()
+                .flat_map(|item| &item.line_items)
+                .find(|line_item| line_item.id == *line_item_id)
+                .ok_or(ReturnOrderItemsError::LineItemNotFound {
+                    line_item_id: *line_item_id,
+                })?;
+
+            if line_item.is_returned() {
+                return Err(ReturnOrderItemsError::LineItemAlreadyReturned {
+                    line_item_id: *line_item_id,
+                });
+            }
+
+            let instance_id = line_item
+                .instance_id()
+                .filter(|_| line_item.is_fulfilled())
+                .ok_or(ReturnOrderItemsError::LineItemNotFulfilled {
+                    line_item_id: *line_item_id,
+                })?;
+            let instance = self
+                .product_instance
+                .find_by_id(&instance_id)
+                .await?
+                .filter(|instance| {
+                    instance
+                        .status
+                        .can_transition_to(ProductInstanceStatus::Returned)
+                })
+                .ok_or(ReturnOrderItemsError::InstanceNotReturnable { instance_id })?;
+            instances.push(instance);
+        }
+
+        // Move instances to the terminal Returned status
+        let now = Utc::now();
+        for instance in &mut instances {
+            instance
+                .transition_to(ProductInstanceStatus::Returned, req.reason.clone())
+                .expect("Instances were checked to be returnable");
+        }
+        self.product_instance.save_batch(&instances).await?;
+
+        // Mark line items as returned, adding replacements if requested
+        for item in &mut order.items {
+            let mut replacements = vec![];
+            for line_item in &mut item.line_items {
+                if !req.line_item_ids.contains(&line_item.id) {
+                    continue;
+                }
+
+                line_item.returned_at = Some(now);
+                if req.replace {
+                    replacements.push(line_item.replacement());
                 }
             }
+
+            if !replacements.is_empty() {
+                item.line_items.extend(replacements);
+                item.status = PurchaseOrderItemStatus::Pending;
+            }
         }
 
+        if req.replace {
+            // Replacements still have to be fulfilled
+            order.status = PurchaseOrderStatus::PartiallyFulfilled;
+            order.completed_at = None;
+        }
+
+        let return_id = PurchaseOrderReturnId::new();
+        order.returns.push(PurchaseOrderReturn {
+            id: return_id,
+            line_item_ids: req.line_item_ids.clone(),
+            refund: req.refund,
+            reason: req.reason.clone(),
+            returned_at: now,
+        });
+        order.record_event(
+            req.user_id,
+            PurchaseOrderEventKind::ItemsReturned { return_id },
+        );
+
         // Save order
         self.order.save(&order).await?;
 
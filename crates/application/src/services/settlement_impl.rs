@@ -0,0 +1,219 @@
+use super::Service;
+use chrono::Utc;
+use sawa_core::{
+    models::{
+        misc::{Currency, Price},
+        purchase::{
+            OrderRoleFilter, PurchaseOrderEventKind, PurchaseOrderPayment, PurchaseOrderPaymentId,
+        },
+        settlement::{Balance, OrderSettlement, net_balances},
+        user::UserId,
+    },
+    repositories::*,
+    services::{
+        GetNettingError, GetNettingRequest, GetOrderSettlementError, GetOrderSettlementRequest,
+        ListBalancesError, ListBalancesRequest, RecordPaymentError, RecordPaymentRequest,
+        RemovePaymentError, RemovePaymentRequest, SettlementService,
+    },
+};
+
+impl<P, PV, PI, PO, UT, U, T, M, C, L, W, S> SettlementService
+    for Service<P, PV, PI, PO, UT, U, T, M, C, L, W, S>
+where
+    P: ProductRepository,
+    PV: ProductVariantRepository,
+    PI: ProductInstanceRepository,
+    PO: PurchaseOrderRepository,
+    UT: UserTransactionRepository,
+    U: UserRepository,
+    T: TagRepository,
+    M: MediaRepository,
+    C: CartRepository,
+    L: StorageLocationRepository,
+    W: WishlistRepository,
+    S: StatisticsRepository,
+{
+    async fn get_order_settlement(
+        &self,
+        req: GetOrderSettlementRequest,
+    ) -> Result<OrderSettlement, GetOrderSettlementError> {
+        let order = self
+            .order
+            .find_by_id(&req.order_id, &req.user_id)
+            .await?
+            .ok_or(GetOrderSettlementError::OrderNotFound {
+                order_id: req.order_id,
+            })?;
+
+        Ok(order.settlement_for(req.user_id))
+    }
+
+    async fn record_payment(
+        &self,
+        req: RecordPaymentRequest,
+    ) -> Result<PurchaseOrderPayment, RecordPaymentError> {
+        // Load and verify order exists
+        let mut order = self
+            .order
+            .find_by_id(&req.order_id, &req.user_id)
+            .await?
+            .ok_or(RecordPaymentError::OrderNotFound {
+                order_id: req.order_id,
+            })?;
+
+        // Either the creator confirms receiving the payment, or the payer reports it
+        if order.creator_id != req.user_id && req.payer_id != req.user_id {
+            return Err(RecordPaymentError::PermissionDenied {
+                user_id: req.user_id,
+            });
+        }
+
+        if req.payer_id == order.creator_id {
+            return Err(RecordPaymentError::PayerIsCreator {
+                payer_id: req.payer_id,
+            });
+        }
+
+        if !order.is_participant(req.payer_id) {
+            return Err(RecordPaymentError::PayerNotParticipant {
+                payer_id: req.payer_id,
+            });
+        }
+
+        if req.amount.amount == 0 {
+            return Err(RecordPaymentError::InvalidAmount);
+        }
+
+        if req.amount.currency != order.total_price.currency {
+            return Err(RecordPaymentError::CurrencyMismatch {
+                expected: order.total_price.currency,
+                actual: req.amount.currency,
+            });
+        }
+
+        let payment = PurchaseOrderPayment {
+            id: PurchaseOrderPaymentId::new(),
+            payer_id: req.payer_id,
+            amount: req.amount,
+            note: req.note,
+            paid_at: Utc::now(),
+        };
+        order.payments.push(payment.clone());
+        order.record_event(
+            req.user_id,
+            PurchaseOrderEventKind::PaymentRecorded {
+                payment_id: payment.id,
+            },
+        );
+
+        self.order.save(&order).await?;
+
+        Ok(payment)
+    }
+
+    async fn remove_payment(
+        &self,
+        req: RemovePaymentRequest,
+    ) -> Result<OrderSettlement, RemovePaymentError> {
+        // Load and verify order exists
+        let mut order = self
+            .order
+            .find_by_id(&req.order_id, &req.user_id)
+            .await?
+            .ok_or(RemovePaymentError::OrderNotFound {
+                order_id: req.order_id,
+            })?;
+
+        let index = order
+            .payments
+            .iter()
+            .position(|payment| payment.id == req.payment_id)
+            .ok_or(RemovePaymentError::PaymentNotFound {
+                payment_id: req.payment_id,
+            })?;
+
+        if order.creator_id != req.user_id && order.payments[index].payer_id != req.user_id {
+            return Err(RemovePaymentError::PermissionDenied {
+                user_id: req.user_id,
+            });
+        }
+
+        order.payments.remove(index);
+        order.record_event(
+            req.user_id,
+            PurchaseOrderEventKind::PaymentRemoved {
+                payment_id: req.payment_id,
+            },
+        );
+
+        self.order.save(&order).await?;
+
+        Ok(order.settlement_for(req.user_id))
+    }
+
+    async fn list_balances(
+        &self,
+        req: ListBalancesRequest,
+    ) -> Result<Vec<Balance>, ListBalancesError> {
+        let orders = self
+            .order
+            .find_by_user(&req.user_id, OrderRoleFilter::Participant, None)
+            .await?;
+
+        // Signed amount per counterparty and currency (positive: the counterparty owes the user)
+        let mut positions: Vec<(UserId, Currency, i64)> = vec![];
+        for balance in orders
+            .iter()
+            .flat_map(|order| order.settlement().balances())
+        {
+            let (counterparty, amount) = if balance.creditor_id == req.user_id {
+                (balance.debtor_id, balance.amount.amount as i64)
+            } else if balance.debtor_id == req.user_id {
+                (balance.creditor_id, -(balance.amount.amount as i64))
+            } else {
+                continue;
+            };
+
+            match positions.iter_mut().find(|(user_id, currency, _)| {
+                *user_id == counterparty && *currency == balance.amount.currency
+            }) {
+                Some((_, _, position)) => *position += amount,
+                None => positions.push((counterparty, balance.amount.currency, amount)),
+            }
+        }
+
+        Ok(positions
+            .into_iter()
+            .filter(|(_, _, position)| *position != 0)
+            .map(|(counterparty, currency, position)| {
+                let (debtor_id, creditor_id) = if position > 0 {
+                    (counterparty, req.user_id)
+                } else {
+                    (req.user_id, counterparty)
+                };
+                Balance {
+                    debtor_id,
+                    creditor_id,
+                    amount: Price {
+                        currency,
+                        amount: u32::try_from(position.unsigned_abs()).unwrap_or(u32::MAX),
+                    },
+                }
+            })
+            .collect())
+    }
+
+    async fn get_netting(&self, req: GetNettingRequest) -> Result<Vec<Balance>, GetNettingError> {
+        let orders = self
+            .order
+            .find_by_user(&req.user_id, OrderRoleFilter::Participant, None)
+            .await?;
+
+        let balances: Vec<Balance> = orders
+            .iter()
+            .flat_map(|order| order.settlement_for(req.user_id).balances())
+            .collect();
+
+        Ok(net_balances(&balances))
+    }
+}
@@ -0,0 +1,102 @@
+use super::Service;
+use sawa_core::{
+    models::statistics::{
+        DateRange, MonthlySpending, MysteryBoxStatistics, ProductStatistics, SeriesPurchaseCount,
+        StatusCount, TagStatistics,
+    },
+    repositories::*,
+    services::{
+        GetMostBoughtSeriesRequest, GetStatisticsError, GetStatisticsRequest, StatisticsService,
+    },
+};
+
+fn check_range(range: &DateRange) -> Result<(), GetStatisticsError> {
+    if range.is_valid() {
+        Ok(())
+    } else {
+        Err(GetStatisticsError::InvalidRange)
+    }
+}
+
+impl<P, PV, PI, PO, UT, U, T, M, C, L, W, S> StatisticsService
+    for Service<P, PV, PI, PO, UT, U, T, M, C, L, W, S>
+where
+    P: ProductRepository,
+    PV: ProductVariantRepository,
+    PI: ProductInstanceRepository,
+    PO: PurchaseOrderRepository,
+    UT: UserTransactionRepository,
+    U: UserRepository,
+    T: TagRepository,
+    M: MediaRepository,
+    C: CartRepository,
+    L: StorageLocationRepository,
+    W: WishlistRepository,
+    S: StatisticsRepository,
+{
+    async fn get_monthly_spending(
+        &self,
+        req: GetStatisticsRequest,
+    ) -> Result<Vec<MonthlySpending>, GetStatisticsError> {
+        check_range(&req.range)?;
+        Ok(self
+            .statistics
+            .monthly_spending(&req.user_id, &req.range)
+            .await?)
+    }
+
+    async fn get_status_counts(
+        &self,
+        req: GetStatisticsRequest,
+    ) -> Result<Vec<StatusCount>, GetStatisticsError> {
+        check_range(&req.range)?;
+        Ok(self
+            .statistics
+            .status_counts(&req.user_id, &req.range)
+            .await?)
+    }
+
+    async fn get_tag_statistics(
+        &self,
+        req: GetStatisticsRequest,
+    ) -> Result<Vec<TagStatistics>, GetStatisticsError> {
+        check_range(&req.range)?;
+        Ok(self
+            .statistics
+            .tag_statistics(&req.user_id, &req.range)
+            .await?)
+    }
+
+    async fn get_product_statistics(
+        &self,
+        req: GetStatisticsRequest,
+    ) -> Result<Vec<ProductStatistics>, GetStatisticsError> {
+        check_range(&req.range)?;
+        Ok(self
+            .statistics
+            .product_statistics(&req.user_id, &req.range)
+            .await?)
+    }
+
+    async fn get_most_bought_series(
+        &self,
+        req: GetMostBoughtSeriesRequest,
+    ) -> Result<Vec<SeriesPurchaseCount>, GetStatisticsError> {
+        check_range(&req.range)?;
+        Ok(self
+            .statistics
+            .most_bought_series(&req.user_id, &req.range, req.limit)
+            .await?)
+    }
+
+    async fn get_mystery_box_statistics(
+        &self,
+        req: GetStatisticsRequest,
+    ) -> Result<Vec<MysteryBoxStatistics>, GetStatisticsError> {
+        check_range(&req.range)?;
+        Ok(self
+            .statistics
+            .mystery_box_statistics(&req.user_id, &req.range)
+            .await?)
+    }
+}
@@ -0,0 +1,236 @@
+use super::Service;
+use sawa_core::{
+    errors::RepositoryError,
+    models::{
+        product::ProductInstance,
+        storage::{LocatedProductInstance, StorageLocation, StorageLocationId},
+        user::UserId,
+    },
+    repositories::*,
+    services::{
+        CreateStorageLocationError, CreateStorageLocationRequest, DeleteStorageLocationError,
+        DeleteStorageLocationRequest, ListStorageLocationsError, ListStorageLocationsRequest,
+        LocateProductInstancesError, LocateProductInstancesRequest, MoveProductInstancesError,
+        MoveProductInstancesRequest, StorageLocationService, UpdateStorageLocationError,
+        UpdateStorageLocationRequest,
+    },
+};
+use std::collections::{HashMap, HashSet};
+
+impl<P, PV, PI, PO, UT, U, T, M, C, L, W, S> Service<P, PV, PI, PO, UT, U, T, M, C, L, W, S>
+where
+    P: ProductRepository,
+    PV: ProductVariantRepository,
+    PI: ProductInstanceRepository,
+    PO: PurchaseOrderRepository,
+    UT: UserTransactionRepository,
+    U: UserRepository,
+    T: TagRepository,
+    M: MediaRepository,
+    C: CartRepository,
+    L: StorageLocationRepository,
+    W: WishlistRepository,
+    S: StatisticsRepository,
+{
+    /// Find a location of the user. Other users' locations are treated as missing.
+    async fn find_own_location(
+        &self,
+        id: &StorageLocationId,
+        user_id: UserId,
+    ) -> Result<Option<StorageLocation>, RepositoryError> {
+        Ok(self
+            .storage_location
+            .find_by_id(id)
+            .await?
+            .filter(|location| location.user_id == user_id))
+    }
+}
+
+impl<P, PV, PI, PO, UT, U, T, M, C, L, W, S> StorageLocationService
+    for Service<P, PV, PI, PO, UT, U, T, M, C, L, W, S>
+where
+    P: ProductRepository,
+    PV: ProductVariantRepository,
+    PI: ProductInstanceRepository,
+    PO: PurchaseOrderRepository,
+    UT: UserTransactionRepository,
+    U: UserRepository,
+    T: TagRepository,
+    M: MediaRepository,
+    C: CartRepository,
+    L: StorageLocationRepository,
+    W: WishlistRepository,
+    S: StatisticsRepository,
+{
+    async fn create_storage_location(
+        &self,
+        req: CreateStorageLocationRequest,
+    ) -> Result<StorageLocation, CreateStorageLocationError> {
+        if let Some(parent_id) = req.parent_id
+            && self
+                .find_own_location(&parent_id, req.user_id)
+                .await?
+                .is_none()
+        {
+            return Err(CreateStorageLocationError::ParentNotFound { parent_id });
+        }
+
+        let location = StorageLocation::new(req.user_id, req.name, req.parent_id);
+        self.storage_location.save(&location).await?;
+
+        Ok(location)
+    }
+
+    async fn list_storage_locations(
+        &self,
+        req: ListStorageLocationsRequest,
+    ) -> Result<Vec<StorageLocation>, ListStorageLocationsError> {
+        let mut locations = self.storage_location.find_by_user(&req.user_id).await?;
+        locations.sort_by_key(|location| location.created_at);
+        Ok(locations)
+    }
+
+    async fn update_storage_location(
+        &self,
+        req: UpdateStorageLocationRequest,
+    ) -> Result<StorageLocation, UpdateStorageLocationError> {
+        let mut location = self
+            .find_own_location(&req.id, req.user_id)
+            .await?
+            .ok_or(UpdateStorageLocationError::NotFound)?;
+
+        if let Some(parent_id) = req.parent_id {
+            let locations = self.storage_location.find_by_user(&req.user_id).await?;
+            if !locations.iter().any(|location| location.id == parent_id) {
+                return Err(UpdateStorageLocationError::ParentNotFound { parent_id });
+            }
+            if StorageLocation::descendants(&locations, location.id).contains(&parent_id) {
+                return Err(UpdateStorageLocationError::Cycle);
+            }
+        }
+
+        location.name = req.name;
+        location.parent_id = req.parent_id;
+        self.storage_location.save(&location).await?;
+
+        Ok(location)
+    }
+
+    async fn delete_storage_location(
+        &self,
+        req: DeleteStorageLocationRequest,
+    ) -> Result<(), DeleteStorageLocationError> {
+        let location = self
+            .find_own_location(&req.id, req.user_id)
+            .await?
+            .ok_or(DeleteStorageLocationError::NotFound)?;
+
+        let locations = self.storage_location.find_by_user(&req.user_id).await?;
+        if locations
+            .iter()
+            .any(|child| child.parent_id == Some(location.id))
+        {
+            return Err(DeleteStorageLocationError::NotEmpty);
+        }
+
+        let instances = self.product_instance.find_by_holder(&req.user_id).await?;
+        if instances
+            .iter()
+            .any(|instance| instance.location_id == Some(location.id))
+        {
+            return Err(DeleteStorageLocationError::NotEmpty);
+        }
+
+        self.storage_location.delete(&location.id).await?;
+
+        Ok(())
+    }
+
+    async fn move_product_instances(
+        &self,
+        req: MoveProductInstancesRequest,
+    ) -> Result<Vec<ProductInstance>, MoveProductInstancesError> {
+        if let Some(location_id) = req.location_id
+            && self
+                .find_own_location(&location_id, req.user_id)
+                .await?
+                .is_none()
+        {
+            return Err(MoveProductInstancesError::LocationNotFound);
+        }
+
+        // Validate all instances before moving any of them
+        let mut instances = Vec::new();
+        for instance_id in req.instance_ids {
+            let mut instance = self
+                .product_instance
+                .find_by_id(&instance_id)
+                .await?
+                .ok_or(MoveProductInstancesError::InstanceNotFound { instance_id })?;
+            if instance.holder_id != req.user_id {
+                return Err(MoveProductInstancesError::NotHeld { instance_id });
+            }
+
+            instance.location_id = req.location_id;
+            instances.push(instance);
+        }
+
+        self.product_instance.save_batch(&instances).await?;
+
+        Ok(instances)
+    }
+
+    async fn locate_product_instances(
+        &self,
+        req: LocateProductInstancesRequest,
+    ) -> Result<Vec<LocatedProductInstance>, LocateProductInstancesError> {
+        // Instances owned by the user, including lent out ones, and instances borrowed
+        let mut instances = self.product_instance.find_by_owner(&req.user_id).await?;
+        let owned: HashSet<_> = instances.iter().map(|instance| instance.id).collect();
+        instances.extend(
+            self.product_instance
+                .find_by_holder(&req.user_id)
+                .await?
+                .into_iter()
+                .filter(|instance| !owned.contains(&instance.id)),
+        );
+
+        let variant_ids: Vec<_> = instances
+            .iter()
+            .map(|instance| instance.variant_id)
+            .collect::<HashSet<_>>()
+            .into_iter()
+            .collect();
+        let query = req.query.to_lowercase();
+        let variants: HashMap<_, _> = self
+            .product_variant
+            .load_by_ids(&variant_ids)
+            .await?
+            .into_iter()
+            .flatten()
+            .filter(|variant| variant.name.as_str().to_lowercase().contains(&query))
+            .map(|variant| (variant.id, variant))
+            .collect();
+
+        let locations = self.storage_location.find_by_user(&req.user_id).await?;
+
+        Ok(instances
+            .into_iter()
+            .filter_map(|instance| {
+                let variant = variants.get(&instance.variant_id)?.clone();
+                // Lent out instances are kept in the holder's locations, which are not visible
+                let location_path = match instance.location_id {
+                    Some(location_id) if instance.holder_id == req.user_id => {
+                        StorageLocation::path(&locations, location_id)
+                    }
+                    _ => Vec::new(),
+                };
+                Some(LocatedProductInstance {
+                    instance,
+                    variant,
+                    location_path,
+                })
+            })
+            .collect())
+    }
+}
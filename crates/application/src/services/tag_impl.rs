@@ -10,7 +10,8 @@ use sawa_core::{
 
 use super::Service;
 
-impl<P, PV, PI, PO, UT, U, T, M> TagService for Service<P, PV, PI, PO, UT, U, T, M>
+impl<P, PV, PI, PO, UT, U, T, M, C, L, W, S> TagService
+    for Service<P, PV, PI, PO, UT, U, T, M, C, L, W, S>
 where
     P: ProductRepository,
     PV: ProductVariantRepository,
@@ -20,6 +21,10 @@ where
     U: UserRepository,
     T: TagRepository,
     M: MediaRepository,
+    C: CartRepository,
+    L: StorageLocationRepository,
+    W: WishlistRepository,
+    S: StatisticsRepository,
 {
     async fn get_tag(&self, req: GetTagRequest) -> Result<Tag, GetTagError> {
         self.tag
@@ -47,7 +52,7 @@ where
 /// Extension methods for TagService to support lazy tag creation.
 ///
 /// These methods provide convenience functions for common tag operations.
-impl<P, PV, PI, PO, UT, U, T, M> Service<P, PV, PI, PO, UT, U, T, M>
+impl<P, PV, PI, PO, UT, U, T, M, C, L, W, S> Service<P, PV, PI, PO, UT, U, T, M, C, L, W, S>
 where
     P: ProductRepository,
     PV: ProductVariantRepository,
@@ -57,6 +62,10 @@ where
     U: UserRepository,
     T: TagRepository,
     M: MediaRepository,
+    C: CartRepository,
+    L: StorageLocationRepository,
+    W: WishlistRepository,
+    S: StatisticsRepository,
 {
     /// Get or create a tag by name (lazy creation).
     ///
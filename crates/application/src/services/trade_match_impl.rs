@@ -0,0 +1,307 @@
+use super::Service;
+use chrono::Utc;
+use sawa_core::{
+    errors::RepositoryError,
+    models::{
+        product::{ProductInstanceId, ProductVariantId},
+        transfer::{
+            TradeLeg, TradeLegItem, TradeMatch, TransferReason, UserTransaction, UserTransactionId,
+            UserTransactionStatus,
+        },
+        user::UserId,
+        wishlist::Wishlist,
+    },
+    repositories::*,
+    services::{
+        CancelTransactionRequest, CreateTransactionRequest, FindTradeMatchesError,
+        FindTradeMatchesRequest, MAX_TRADE_CYCLE_LENGTH, ProposeTradeMatchError,
+        ProposeTradeMatchRequest, SetTradeOptInError, SetTradeOptInRequest, TradeMatchService,
+        TransactionLifecycleService,
+    },
+};
+use std::{
+    cmp::Reverse,
+    collections::{HashMap, HashSet},
+};
+
+/// The wants and spares of a user taking part in trade matching.
+struct Trader {
+    user_id: UserId,
+
+    /// How many more instances of each variant the user wants
+    wants: HashMap<ProductVariantId, u32>,
+
+    /// The spare instances of each variant, oldest first
+    spares: HashMap<ProductVariantId, Vec<ProductInstanceId>>,
+}
+
+impl Trader {
+    /// The spares of this trader the other one wants, if any.
+    fn leg_to(&self, other: &Trader) -> Option<TradeLeg> {
+        let mut items = Vec::new();
+        for (variant_id, instance_ids) in &self.spares {
+            let wanted = other.wants.get(variant_id).copied().unwrap_or(0) as usize;
+            items.extend(
+                instance_ids
+                    .iter()
+                    .take(wanted)
+                    .map(|instance_id| TradeLegItem {
+                        variant_id: *variant_id,
+                        instance_id: *instance_id,
+                    }),
+            );
+        }
+
+        if items.is_empty() {
+            return None;
+        }
+
+        Some(TradeLeg {
+            from_user_id: self.user_id,
+            to_user_id: other.user_id,
+            items,
+        })
+    }
+}
+
+/// Find the cycles of traders through `start` with at most `max_cycle_length` traders.
+fn find_cycles(traders: &[Trader], start: usize, max_cycle_length: usize) -> Vec<TradeMatch> {
+    let mut legs = HashMap::new();
+    for (i, from) in traders.iter().enumerate() {
+        for (j, to) in traders.iter().enumerate() {
+            if i != j
+                && let Some(leg) = from.leg_to(to)
+            {
+                legs.insert((i, j), leg);
+            }
+        }
+    }
+
+    let mut matches = Vec::new();
+    extend_cycles(
+        &legs,
+        traders.len(),
+        max_cycle_length,
+        &mut vec![start],
+        &mut matches,
+    );
+
+    matches
+        .sort_by_key(|trade_match| (Reverse(trade_match.satisfied_wants), trade_match.legs.len()));
+    matches
+}
+
+fn extend_cycles(
+    legs: &HashMap<(usize, usize), TradeLeg>,
+    trader_count: usize,
+    max_cycle_length: usize,
+    path: &mut Vec<usize>,
+    matches: &mut Vec<TradeMatch>,
+) {
+    let last = path[path.len() - 1];
+    for next in 0..trader_count {
+        if !legs.contains_key(&(last, next)) {
+            continue;
+        }
+
+        if next == path[0] {
+            // Close the cycle
+            let cycle_legs: Vec<_> = (0..path.len())
+                .map(|k| legs[&(path[k], path[(k + 1) % path.len()])].clone())
+                .collect();
+            matches.push(TradeMatch {
+                satisfied_wants: cycle_legs.iter().map(|leg| leg.items.len() as u32).sum(),
+                legs: cycle_legs,
+            });
+        } else if path.len() < max_cycle_length && !path.contains(&next) {
+            path.push(next);
+            extend_cycles(legs, trader_count, max_cycle_length, path, matches);
+            path.pop();
+        }
+    }
+}
+
+/// Whether two matches move the same instances between the same users.
+fn same_trade_match(a: &TradeMatch, b: &TradeMatch) -> bool {
+    let users = |trade_match: &TradeMatch| -> HashSet<_> {
+        trade_match
+            .legs
+            .iter()
+            .map(|leg| (leg.from_user_id, leg.to_user_id))
+            .collect()
+    };
+    let instances =
+        |trade_match: &TradeMatch| -> HashSet<_> { trade_match.instance_ids().collect() };
+
+    a.legs.len() == b.legs.len() && users(a) == users(b) && instances(a) == instances(b)
+}
+
+impl<P, PV, PI, PO, UT, U, T, M, C, L, W, S> Service<P, PV, PI, PO, UT, U, T, M, C, L, W, S>
+where
+    P: ProductRepository,
+    PV: ProductVariantRepository,
+    PI: ProductInstanceRepository,
+    PO: PurchaseOrderRepository,
+    UT: UserTransactionRepository,
+    U: UserRepository,
+    T: TagRepository,
+    M: MediaRepository,
+    C: CartRepository,
+    L: StorageLocationRepository,
+    W: WishlistRepository,
+    S: StatisticsRepository,
+{
+    /// The wants and spares of every user open to trades.
+    async fn load_traders(&self) -> Result<Vec<Trader>, RepositoryError> {
+        let mut traders = Vec::new();
+        for wishlist in self.wishlist.find_open_to_trades().await? {
+            let wants: HashMap<_, _> = wishlist.wants().filter(|(_, count)| *count > 0).collect();
+
+            let mut spares = HashMap::new();
+            for (variant_id, instances) in self
+                .find_active_instances_by_variant(&wishlist.user_id)
+                .await?
+            {
+                if wants.contains_key(&variant_id) {
+                    continue;
+                }
+                // Keep the oldest one, and only offer what the user has at hand
+                let instance_ids: Vec<_> = instances
+                    .into_iter()
+                    .skip(1)
+                    .filter(|instance| instance.holder_id == instance.owner_id)
+                    .map(|instance| instance.id)
+                    .collect();
+                if !instance_ids.is_empty() {
+                    spares.insert(variant_id, instance_ids);
+                }
+            }
+
+            traders.push(Trader {
+                user_id: wishlist.user_id,
+                wants,
+                spares,
+            });
+        }
+
+        Ok(traders)
+    }
+}
+
+impl<P, PV, PI, PO, UT, U, T, M, C, L, W, S> TradeMatchService
+    for Service<P, PV, PI, PO, UT, U, T, M, C, L, W, S>
+where
+    P: ProductRepository,
+    PV: ProductVariantRepository,
+    PI: ProductInstanceRepository,
+    PO: PurchaseOrderRepository,
+    UT: UserTransactionRepository,
+    U: UserRepository,
+    T: TagRepository,
+    M: MediaRepository,
+    C: CartRepository,
+    L: StorageLocationRepository,
+    W: WishlistRepository,
+    S: StatisticsRepository,
+{
+    async fn set_trade_opt_in(
+        &self,
+        req: SetTradeOptInRequest,
+    ) -> Result<Wishlist, SetTradeOptInError> {
+        let mut wishlist = self.load_wishlist(req.user_id).await?;
+        wishlist.open_to_trades = req.open_to_trades;
+
+        wishlist.updated_at = Utc::now();
+        self.wishlist.save(&wishlist).await?;
+
+        Ok(wishlist)
+    }
+
+    async fn find_trade_matches(
+        &self,
+        req: FindTradeMatchesRequest,
+    ) -> Result<Vec<TradeMatch>, FindTradeMatchesError> {
+        let traders = self.load_traders().await?;
+        let start = traders
+            .iter()
+            .position(|trader| trader.user_id == req.user_id)
+            .ok_or(FindTradeMatchesError::NotOptedIn)?;
+
+        Ok(find_cycles(
+            &traders,
+            start,
+            req.max_cycle_length.clamp(2, MAX_TRADE_CYCLE_LENGTH),
+        ))
+    }
+
+    async fn propose_trade_match(
+        &self,
+        req: ProposeTradeMatchRequest,
+    ) -> Result<Vec<UserTransaction>, ProposeTradeMatchError> {
+        // Spares and wants may have changed since the match was found
+        let trade_match = self
+            .find_trade_matches(FindTradeMatchesRequest {
+                user_id: req.user_id,
+                max_cycle_length: req.trade_match.legs.len(),
+            })
+            .await
+            .map_err(|e| match e {
+                FindTradeMatchesError::NotOptedIn => ProposeTradeMatchError::NotOptedIn,
+                FindTradeMatchesError::Repository(e) => e.into(),
+            })?
+            .into_iter()
+            .find(|trade_match| same_trade_match(trade_match, &req.trade_match))
+            .ok_or(ProposeTradeMatchError::MatchNotAvailable)?;
+
+        let mut transactions: Vec<UserTransaction> = Vec::new();
+        for leg in trade_match.legs {
+            let items = leg.items.iter().map(|item| item.instance_id).collect();
+
+            // Only the proposer's spares are locked, the other senders accept their legs first
+            let result = if leg.from_user_id == req.user_id {
+                self.create_transaction(CreateTransactionRequest {
+                    from_user_id: leg.from_user_id,
+                    to_user_id: leg.to_user_id,
+                    items,
+                })
+                .await
+            } else {
+                let transaction = UserTransaction {
+                    id: UserTransactionId::new(),
+                    from_user_id: leg.from_user_id,
+                    to_user_id: leg.to_user_id,
+                    items,
+                    reason: TransferReason::Trade,
+                    return_by: None,
+                    status: UserTransactionStatus::Proposed,
+                    created_at: Utc::now(),
+                    completed_at: None,
+                    cancelled_at: None,
+                };
+                self.transaction
+                    .save(&transaction)
+                    .await
+                    .map(|()| transaction)
+                    .map_err(Into::into)
+            };
+
+            match result {
+                Ok(transaction) => transactions.push(transaction),
+                Err(e) => {
+                    // Rollback: Cancel the transactions of the previous legs, unlocking any items
+                    for transaction in &transactions {
+                        let _ = self
+                            .cancel_transaction(CancelTransactionRequest {
+                                transaction_id: transaction.id,
+                                user_id: transaction.from_user_id,
+                            })
+                            .await;
+                    }
+                    return Err(e.into());
+                }
+            }
+        }
+
+        Ok(transactions)
+    }
+}
@@ -3,7 +3,8 @@ use sawa_core::models::transfer::UserTransaction;
 use sawa_core::repositories::*;
 use sawa_core::services::*;
 
-impl<P, PV, PI, PO, UT, U, T, M> TransactionService for Service<P, PV, PI, PO, UT, U, T, M>
+impl<P, PV, PI, PO, UT, U, T, M, C, L, W, S> TransactionService
+    for Service<P, PV, PI, PO, UT, U, T, M, C, L, W, S>
 where
     P: ProductRepository,
     PV: ProductVariantRepository,
@@ -13,6 +14,10 @@ where
     U: UserRepository,
     T: TagRepository,
     M: MediaRepository,
+    C: CartRepository,
+    L: StorageLocationRepository,
+    W: WishlistRepository,
+    S: StatisticsRepository,
 {
     async fn get_transaction(
         &self,
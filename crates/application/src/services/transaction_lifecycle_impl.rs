@@ -1,11 +1,14 @@
 use crate::services::Service;
 use chrono::Utc;
-use sawa_core::models::product::ProductInstanceStatus;
-use sawa_core::models::transfer::{UserTransaction, UserTransactionId, UserTransactionStatus};
+use sawa_core::models::product::{ProductInstance, ProductInstanceStatus};
+use sawa_core::models::transfer::{
+    ProductInstanceTransferHistory, ProductInstanceTransferHistoryId, TransferReason,
+    UserTransaction, UserTransactionId, UserTransactionStatus,
+};
 use sawa_core::repositories::{ProductInstanceRepository, UserTransactionRepository};
 use sawa_core::services::*;
 
-impl<P, PV, PI, PO, UT, U, T, M> TransactionLifecycleService for Service<P, PV, PI, PO, UT, U, T, M>
+impl<P, PV, PI, PO, UT, U, T, M, C, L, W, S> Service<P, PV, PI, PO, UT, U, T, M, C, L, W, S>
 where
     P: sawa_core::repositories::ProductRepository,
     PV: sawa_core::repositories::ProductVariantRepository,
@@ -15,24 +18,59 @@ where
     U: sawa_core::repositories::UserRepository,
     T: sawa_core::repositories::TagRepository,
     M: sawa_core::repositories::MediaRepository,
+    C: sawa_core::repositories::CartRepository,
+    L: sawa_core::repositories::StorageLocationRepository,
+    W: sawa_core::repositories::WishlistRepository,
+    S: sawa_core::repositories::StatisticsRepository,
 {
-    async fn create_transaction(
+    /// Lock the items of a new pending transaction and save it.
+    async fn open_transaction(
         &self,
-        req: CreateTransactionRequest,
+        transaction: UserTransaction,
     ) -> Result<UserTransaction, CreateTransactionError> {
+        let mut instances = self.lock_items(&transaction).await?;
+
+        if let Err(e) = self.transaction.save(&transaction).await {
+            // Rollback: Unlock items
+            // Try to revert changes. If this fails, we have an inconsistency.
+            // Ideally this should be in a DB transaction.
+            self.unlock_items(&mut instances).await;
+            return Err(e.into());
+        }
+
+        Ok(transaction)
+    }
+
+    /// Verify the items of a transaction and lock them.
+    ///
+    /// Every item must be held by the sender and Active, and owned by the receiver
+    /// for deliveries or by the sender otherwise.
+    async fn lock_items(
+        &self,
+        transaction: &UserTransaction,
+    ) -> Result<Vec<ProductInstance>, CreateTransactionError> {
+        let owner_id = match transaction.reason {
+            TransferReason::Delivery => transaction.to_user_id,
+            _ => transaction.from_user_id,
+        };
+
         // 1. Verify items
         let mut instances = Vec::new();
-        for item_id in &req.items {
+        for item_id in &transaction.items {
             let instance = self
                 .product_instance
                 .find_by_id(item_id)
                 .await?
                 .ok_or(CreateTransactionError::ItemNotFound)?;
 
-            if instance.owner_id != req.from_user_id {
+            if instance.owner_id != owner_id {
                 return Err(CreateTransactionError::ItemNotOwned);
             }
 
+            if instance.holder_id != transaction.from_user_id {
+                return Err(CreateTransactionError::ItemNotHeld);
+            }
+
             if instance.status != ProductInstanceStatus::Active {
                 return Err(CreateTransactionError::ItemNotActive);
             }
@@ -46,26 +84,116 @@ where
         }
         self.product_instance.save_batch(&instances).await?;
 
-        // 3. Create transaction
-        let transaction = UserTransaction {
+        Ok(instances)
+    }
+
+    /// Unlock the items of a transaction that could not be saved.
+    async fn unlock_items(&self, instances: &mut [ProductInstance]) {
+        for instance in instances.iter_mut() {
+            instance.status = ProductInstanceStatus::Active;
+        }
+        let _ = self.product_instance.save_batch(instances).await;
+    }
+}
+
+impl<P, PV, PI, PO, UT, U, T, M, C, L, W, S> TransactionLifecycleService
+    for Service<P, PV, PI, PO, UT, U, T, M, C, L, W, S>
+where
+    P: sawa_core::repositories::ProductRepository,
+    PV: sawa_core::repositories::ProductVariantRepository,
+    PI: ProductInstanceRepository,
+    PO: sawa_core::repositories::PurchaseOrderRepository,
+    UT: UserTransactionRepository,
+    U: sawa_core::repositories::UserRepository,
+    T: sawa_core::repositories::TagRepository,
+    M: sawa_core::repositories::MediaRepository,
+    C: sawa_core::repositories::CartRepository,
+    L: sawa_core::repositories::StorageLocationRepository,
+    W: sawa_core::repositories::WishlistRepository,
+    S: sawa_core::repositories::StatisticsRepository,
+{
+    async fn create_transaction(
+        &self,
+        req: CreateTransactionRequest,
+    ) -> Result<UserTransaction, CreateTransactionError> {
+        self.open_transaction(UserTransaction {
             id: UserTransactionId::new(),
             from_user_id: req.from_user_id,
             to_user_id: req.to_user_id,
             items: req.items,
+            reason: TransferReason::Trade,
+            return_by: None,
             status: UserTransactionStatus::Pending,
             created_at: Utc::now(),
             completed_at: None,
             cancelled_at: None,
-        };
+        })
+        .await
+    }
+
+    async fn create_delivery_transaction(
+        &self,
+        req: CreateDeliveryTransactionRequest,
+    ) -> Result<UserTransaction, CreateTransactionError> {
+        self.open_transaction(UserTransaction {
+            id: UserTransactionId::new(),
+            from_user_id: req.from_user_id,
+            to_user_id: req.to_user_id,
+            items: req.items,
+            reason: TransferReason::Delivery,
+            return_by: None,
+            status: UserTransactionStatus::Pending,
+            created_at: Utc::now(),
+            completed_at: None,
+            cancelled_at: None,
+        })
+        .await
+    }
+
+    async fn create_lending_transaction(
+        &self,
+        req: CreateLendingTransactionRequest,
+    ) -> Result<UserTransaction, CreateTransactionError> {
+        self.open_transaction(UserTransaction {
+            id: UserTransactionId::new(),
+            from_user_id: req.from_user_id,
+            to_user_id: req.to_user_id,
+            items: req.items,
+            reason: TransferReason::Lending,
+            return_by: req.return_by,
+            status: UserTransactionStatus::Pending,
+            created_at: Utc::now(),
+            completed_at: None,
+            cancelled_at: None,
+        })
+        .await
+    }
+
+    async fn accept_transaction(
+        &self,
+        req: AcceptTransactionRequest,
+    ) -> Result<UserTransaction, AcceptTransactionError> {
+        let mut transaction = self
+            .transaction
+            .find_by_id(&req.transaction_id)
+            .await?
+            .ok_or(AcceptTransactionError::NotFound)?;
+
+        if transaction.status != UserTransactionStatus::Proposed {
+            return Err(AcceptTransactionError::NotProposed);
+        }
+
+        if transaction.from_user_id != req.user_id {
+            return Err(AcceptTransactionError::PermissionDenied);
+        }
+
+        let mut instances = self.lock_items(&transaction).await?;
+
+        transaction.status = UserTransactionStatus::Pending;
 
         if let Err(e) = self.transaction.save(&transaction).await {
             // Rollback: Unlock items
-            for instance in &mut instances {
-                instance.status = ProductInstanceStatus::Active;
-            }
-            // Try to revert changes. If this fails, we have an inconsistency.
-            // Ideally this should be in a DB transaction.
-            let _ = self.product_instance.save_batch(&instances).await;
+            self.unlock_items(&mut instances).await;
             return Err(e.into());
         }
 
@@ -82,6 +210,9 @@ where
             .await?
             .ok_or(CompleteTransactionError::NotFound)?;
 
+        if transaction.status == UserTransactionStatus::Proposed {
+            return Err(CompleteTransactionError::NotAccepted);
+        }
         if transaction.status == UserTransactionStatus::Completed {
             return Err(CompleteTransactionError::AlreadyCompleted);
         }
@@ -89,18 +220,43 @@ where
             return Err(CompleteTransactionError::Cancelled);
         }
 
+        // Placeholders cannot log in, so their manager confirms receipt for them
         if transaction.to_user_id != req.user_id {
-            return Err(CompleteTransactionError::PermissionDenied);
+            let receiver = self.user.find_by_id(&transaction.to_user_id).await?;
+            if !receiver.is_some_and(|receiver| receiver.is_managed_by(req.user_id)) {
+                return Err(CompleteTransactionError::PermissionDenied);
+            }
         }
 
-        // Transfer ownership
+        // Transfer custody, and ownership unless the owner keeps it
+        let now = Utc::now();
         let mut instances = Vec::new();
+        let mut originals = Vec::new();
         for item_id in &transaction.items {
             // We assume items exist because they were checked at creation
             // But we should handle the case where they might have been deleted (though unlikely in Locked state)
             if let Some(mut instance) = self.product_instance.find_by_id(item_id).await? {
-                instance.owner_id = transaction.to_user_id;
+                originals.push(instance.clone());
+                let to_owner_id = match transaction.reason {
+                    TransferReason::Delivery | TransferReason::Lending => instance.owner_id,
+                    _ => transaction.to_user_id,
+                };
+                instance
+                    .transfer_history
+                    .push(ProductInstanceTransferHistory {
+                        id: ProductInstanceTransferHistoryId::new(),
+                        from_owner_id: Some(instance.owner_id),
+                        from_holder_id: Some(instance.holder_id),
+                        to_owner_id,
+                        to_holder_id: transaction.to_user_id,
+                        reason: transaction.reason,
+                        return_by: transaction.return_by,
+                        transferred_at: now,
+                    });
+                instance.owner_id = to_owner_id;
                 instance.holder_id = transaction.to_user_id;
+                // The location belongs to the previous holder
+                instance.location_id = None;
                 instance.status = ProductInstanceStatus::Active;
                 instances.push(instance);
             }
@@ -109,19 +265,23 @@ where
         self.product_instance.save_batch(&instances).await?;
 
         transaction.status = UserTransactionStatus::Completed;
-        transaction.completed_at = Some(Utc::now());
+        transaction.completed_at = Some(now);
 
         if let Err(e) = self.transaction.save(&transaction).await {
             // Rollback: Revert ownership transfer
-            for instance in &mut instances {
-                instance.owner_id = transaction.from_user_id;
-                instance.holder_id = transaction.from_user_id; // Assuming holder was owner
-                instance.status = ProductInstanceStatus::Locked;
-            }
-            let _ = self.product_instance.save_batch(&instances).await;
+            let _ = self.product_instance.save_batch(&originals).await;
             return Err(e.into());
         }
 
+        // Only instances that changed owner entered someone's inventory.
+        let acquired: Vec<_> = instances
+            .into_iter()
+            .zip(&originals)
+            .filter(|(instance, original)| instance.owner_id != original.owner_id)
+            .map(|(instance, _)| instance)
+            .collect();
+        self.record_obtained_instances(&acquired).await?;
+
         Ok(transaction)
     }
 
@@ -146,15 +306,17 @@ where
             return Err(CancelTransactionError::PermissionDenied);
         }
 
-        // Unlock items
+        // Unlock items, which a proposal has not locked yet
         let mut instances = Vec::new();
-        for item_id in &transaction.items {
-            if let Some(mut instance) = self.product_instance.find_by_id(item_id).await? {
-                instance.status = ProductInstanceStatus::Active;
-                instances.push(instance);
+        if transaction.status == UserTransactionStatus::Pending {
+            for item_id in &transaction.items {
+                if let Some(mut instance) = self.product_instance.find_by_id(item_id).await? {
+                    instance.status = ProductInstanceStatus::Active;
+                    instances.push(instance);
+                }
             }
+            self.product_instance.save_batch(&instances).await?;
         }
-        self.product_instance.save_batch(&instances).await?;
 
         transaction.status = UserTransactionStatus::Cancelled;
         transaction.cancelled_at = Some(Utc::now());
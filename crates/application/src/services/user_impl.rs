@@ -2,18 +2,24 @@ use chrono::Utc;
 use sawa_core::{
     models::{
         misc::NonEmptyString,
-        user::{User, UserId},
+        purchase::OrderRoleFilter,
+        user::{Email, Placeholder, User, UserId, UserUpdate, Username},
     },
     repositories::*,
     services::{
-        CreateUserError, CreateUserRequest, GetUserError, GetUserRequest, LoginError, LoginRequest,
-        UserService,
+        ClaimPlaceholderError, ClaimPlaceholderRequest, CreatePlaceholderError,
+        CreatePlaceholderRequest, CreateUserError, CreateUserRequest, GetUserError, GetUserRequest,
+        ListPlaceholdersError, ListPlaceholdersRequest, LoginError, LoginRequest,
+        ResetCalendarTokenError, ResetCalendarTokenRequest, RevokeCalendarTokenError,
+        RevokeCalendarTokenRequest, UserService,
     },
 };
+use std::collections::HashMap;
 
 use super::Service;
 
-impl<P, PV, PI, PO, UT, U, T, M> UserService for Service<P, PV, PI, PO, UT, U, T, M>
+impl<P, PV, PI, PO, UT, U, T, M, C, L, W, S> UserService
+    for Service<P, PV, PI, PO, UT, U, T, M, C, L, W, S>
 where
     P: ProductRepository,
     PV: ProductVariantRepository,
@@ -23,6 +29,10 @@ where
     U: UserRepository,
     T: TagRepository,
     M: MediaRepository,
+    C: CartRepository,
+    L: StorageLocationRepository,
+    W: WishlistRepository,
+    S: StatisticsRepository,
 {
     async fn get_user(&self, req: GetUserRequest) -> Result<User, GetUserError> {
         match req {
@@ -41,6 +51,11 @@ where
                 .find_by_email(&email)
                 .await?
                 .ok_or(GetUserError::NotFound),
+            GetUserRequest::ByCalendarToken(calendar_token) => self
+                .user
+                .find_by_calendar_token(&calendar_token)
+                .await?
+                .ok_or(GetUserError::NotFound),
         }
     }
 
@@ -52,6 +67,11 @@ where
             .await?
             .ok_or(sawa_core::services::LoginError::NotFound)?;
 
+        // Placeholders have no password and must be claimed instead
+        if user.is_placeholder() {
+            return Err(LoginError::NotFound);
+        }
+
         let password_hash = user.password_hash.clone();
         // Verify password
         let is_valid = tokio::task::spawn_blocking(move || {
@@ -79,6 +99,13 @@ where
             return Err(CreateUserError::AlreadyExists);
         }
 
+        // Check the invite code before creating anything
+        if let Some(invite_code) = &req.invite_code
+            && self.user.find_by_invite_code(invite_code).await?.is_none()
+        {
+            return Err(CreateUserError::InvalidInviteCode);
+        }
+
         // Hash password using bcrypt
         // Default cost factor is 12, which provides a good balance between security and performance
         let password_hash: NonEmptyString =
@@ -94,12 +121,236 @@ where
             email: req.email,
             password_hash,
             avatar: req.avatar,
+            placeholder: None,
+            calendar_token: None,
             created_at: Utc::now(),
         };
 
         // Save user
         let saved_user = self.user.create(user).await?;
 
-        Ok(saved_user)
+        let Some(invite_code) = req.invite_code else {
+            return Ok(saved_user);
+        };
+        match self
+            .claim_placeholder(ClaimPlaceholderRequest {
+                user_id: saved_user.id,
+                invite_code,
+            })
+            .await
+        {
+            Ok(user) => Ok(user),
+            Err(e) => {
+                // Rollback: Do not leave an account behind that missed its placeholder
+                let _ = self.user.delete(&saved_user.id).await;
+                Err(match e {
+                    ClaimPlaceholderError::Repository(e) => e.into(),
+                    _ => CreateUserError::InvalidInviteCode,
+                })
+            }
+        }
+    }
+
+    async fn create_placeholder(
+        &self,
+        req: CreatePlaceholderRequest,
+    ) -> Result<User, CreatePlaceholderError> {
+        let manager = self
+            .user
+            .find_by_id(&req.user_id)
+            .await?
+            .ok_or(CreatePlaceholderError::UserNotFound)?;
+        if manager.is_placeholder() {
+            return Err(CreatePlaceholderError::PermissionDenied);
+        }
+
+        // Placeholders never log in, so they get a unique username and email
+        // that cannot collide with real accounts, and a password hash no password matches
+        let id = UserId::new();
+        let unique = uuid::Uuid::from(id.0).simple().to_string();
+        let user = User {
+            id,
+            username: Username(format!("placeholder_{unique}")),
+            email: Email(format!("{unique}@placeholder.invalid")),
+            password_hash: NonEmptyString::new("!".to_string()).unwrap(),
+            avatar: None,
+            placeholder: Some(Placeholder {
+                display_name: req.display_name.into_string(),
+                manager_id: manager.id,
+                invite_code: uuid::Uuid::new_v4().simple().to_string(),
+            }),
+            calendar_token: None,
+            created_at: Utc::now(),
+        };
+
+        Ok(self.user.create(user).await?)
+    }
+
+    async fn list_placeholders(
+        &self,
+        req: ListPlaceholdersRequest,
+    ) -> Result<Vec<User>, ListPlaceholdersError> {
+        let mut placeholders = self.user.find_placeholders_by_manager(&req.user_id).await?;
+        placeholders.sort_by_key(|user| user.created_at);
+        Ok(placeholders)
+    }
+
+    async fn claim_placeholder(
+        &self,
+        req: ClaimPlaceholderRequest,
+    ) -> Result<User, ClaimPlaceholderError> {
+        let user = self
+            .user
+            .find_by_id(&req.user_id)
+            .await?
+            .ok_or(ClaimPlaceholderError::UserNotFound)?;
+        if user.is_placeholder() {
+            return Err(ClaimPlaceholderError::PermissionDenied);
+        }
+
+        let placeholder = self
+            .user
+            .find_by_invite_code(&req.invite_code)
+            .await?
+            .ok_or(ClaimPlaceholderError::InvalidInviteCode)?;
+
+        // Instances owned or held by the placeholder
+        let mut instances = HashMap::new();
+        for instance in self
+            .product_instance
+            .find_by_owner(&placeholder.id)
+            .await?
+            .into_iter()
+            .chain(
+                self.product_instance
+                    .find_by_holder(&placeholder.id)
+                    .await?,
+            )
+        {
+            instances.insert(instance.id, instance);
+        }
+        let original_instances: Vec<_> = instances.into_values().collect();
+        let instances: Vec<_> = original_instances
+            .iter()
+            .cloned()
+            .map(|mut instance| {
+                instance.replace_user(placeholder.id, user.id);
+                instance
+            })
+            .collect();
+
+        // Orders the placeholder created, receives or owns line items in
+        let original_orders = self
+            .order
+            .find_by_user(&placeholder.id, OrderRoleFilter::Participant, None)
+            .await?;
+        let orders: Vec<_> = original_orders
+            .iter()
+            .cloned()
+            .map(|mut order| {
+                order.replace_user(placeholder.id, user.id);
+                order
+            })
+            .collect();
+
+        // Transactions from or to the placeholder
+        let mut transactions = HashMap::new();
+        for transaction in self
+            .transaction
+            .find_by_from_user(&placeholder.id, None)
+            .await?
+            .into_iter()
+            .chain(
+                self.transaction
+                    .find_by_to_user(&placeholder.id, None)
+                    .await?,
+            )
+        {
+            transactions.insert(transaction.id, transaction);
+        }
+        let original_transactions: Vec<_> = transactions.into_values().collect();
+        let transactions: Vec<_> = original_transactions
+            .iter()
+            .cloned()
+            .map(|mut transaction| {
+                if transaction.from_user_id == placeholder.id {
+                    transaction.from_user_id = user.id;
+                }
+                if transaction.to_user_id == placeholder.id {
+                    transaction.to_user_id = user.id;
+                }
+                transaction
+            })
+            .collect();
+
+        // Everything is prepared, so only the writes below can fail
+        let merge = async {
+            self.product_instance.save_batch(&instances).await?;
+            for order in &orders {
+                self.order.save(order).await?;
+            }
+            self.transaction.save_batch(&transactions).await?;
+            self.user.delete(&placeholder.id).await
+        };
+        if let Err(e) = merge.await {
+            // Rollback: Hand everything back to the placeholder
+            let _ = self.product_instance.save_batch(&original_instances).await;
+            for order in &original_orders {
+                let _ = self.order.save(order).await;
+            }
+            let _ = self.transaction.save_batch(&original_transactions).await;
+            return Err(e.into());
+        }
+
+        Ok(user)
+    }
+
+    async fn reset_calendar_token(
+        &self,
+        req: ResetCalendarTokenRequest,
+    ) -> Result<String, ResetCalendarTokenError> {
+        let user = self
+            .user
+            .find_by_id(&req.user_id)
+            .await?
+            .ok_or(ResetCalendarTokenError::UserNotFound)?;
+
+        let calendar_token = uuid::Uuid::new_v4().simple().to_string();
+        self.user
+            .update(UserUpdate {
+                id: user.id,
+                username: None,
+                email: None,
+                password_hash: None,
+                avatar: user.avatar,
+                calendar_token: Some(Some(calendar_token.clone())),
+            })
+            .await?;
+
+        Ok(calendar_token)
+    }
+
+    async fn revoke_calendar_token(
+        &self,
+        req: RevokeCalendarTokenRequest,
+    ) -> Result<(), RevokeCalendarTokenError> {
+        let user = self
+            .user
+            .find_by_id(&req.user_id)
+            .await?
+            .ok_or(RevokeCalendarTokenError::UserNotFound)?;
+
+        self.user
+            .update(UserUpdate {
+                id: user.id,
+                username: None,
+                email: None,
+                password_hash: None,
+                avatar: user.avatar,
+                calendar_token: Some(None),
+            })
+            .await?;
+
+        Ok(())
     }
 }
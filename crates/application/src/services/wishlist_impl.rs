@@ -0,0 +1,209 @@
+use super::Service;
+use chrono::Utc;
+use sawa_core::{
+    errors::RepositoryError,
+    models::{
+        product::ProductInstance,
+        user::UserId,
+        wishlist::{Wishlist, WishlistItem, WishlistItemId},
+    },
+    repositories::*,
+    services::{
+        AddWishlistItemError, AddWishlistItemRequest, GetWishlistError, GetWishlistRequest,
+        RemoveWishlistItemError, RemoveWishlistItemRequest, SetWishlistVisibilityError,
+        SetWishlistVisibilityRequest, UpdateWishlistItemError, UpdateWishlistItemRequest,
+        WishlistService,
+    },
+};
+use std::collections::HashMap;
+
+impl<P, PV, PI, PO, UT, U, T, M, C, L, W, S> Service<P, PV, PI, PO, UT, U, T, M, C, L, W, S>
+where
+    P: ProductRepository,
+    PV: ProductVariantRepository,
+    PI: ProductInstanceRepository,
+    PO: PurchaseOrderRepository,
+    UT: UserTransactionRepository,
+    U: UserRepository,
+    T: TagRepository,
+    M: MediaRepository,
+    C: CartRepository,
+    L: StorageLocationRepository,
+    W: WishlistRepository,
+    S: StatisticsRepository,
+{
+    pub(super) async fn load_wishlist(&self, user_id: UserId) -> Result<Wishlist, RepositoryError> {
+        Ok(self
+            .wishlist
+            .find_by_user(&user_id)
+            .await?
+            .unwrap_or_else(|| Wishlist::empty(user_id)))
+    }
+
+    /// Satisfy the wishlist items of the owners of instances that just entered their inventory.
+    pub(super) async fn record_obtained_instances(
+        &self,
+        instances: &[ProductInstance],
+    ) -> Result<(), RepositoryError> {
+        let mut variants_by_owner: HashMap<_, Vec<_>> = HashMap::new();
+        for instance in instances {
+            variants_by_owner
+                .entry(instance.owner_id)
+                .or_default()
+                .push(instance.variant_id);
+        }
+
+        for (owner_id, variant_ids) in variants_by_owner {
+            let Some(mut wishlist) = self.wishlist.find_by_user(&owner_id).await? else {
+                continue;
+            };
+
+            let mut changed = false;
+            for variant_id in variant_ids {
+                changed |= wishlist.record_obtained(variant_id);
+            }
+            if changed {
+                self.wishlist.save(&wishlist).await?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl<P, PV, PI, PO, UT, U, T, M, C, L, W, S> WishlistService
+    for Service<P, PV, PI, PO, UT, U, T, M, C, L, W, S>
+where
+    P: ProductRepository,
+    PV: ProductVariantRepository,
+    PI: ProductInstanceRepository,
+    PO: PurchaseOrderRepository,
+    UT: UserTransactionRepository,
+    U: UserRepository,
+    T: TagRepository,
+    M: MediaRepository,
+    C: CartRepository,
+    L: StorageLocationRepository,
+    W: WishlistRepository,
+    S: StatisticsRepository,
+{
+    async fn get_wishlist(&self, req: GetWishlistRequest) -> Result<Wishlist, GetWishlistError> {
+        let wishlist = self.load_wishlist(req.user_id).await?;
+
+        if req.viewer_id != Some(req.user_id) && !wishlist.is_public {
+            return Err(GetWishlistError::NotFound);
+        }
+
+        Ok(wishlist)
+    }
+
+    async fn add_wishlist_item(
+        &self,
+        req: AddWishlistItemRequest,
+    ) -> Result<Wishlist, AddWishlistItemError> {
+        if self
+            .product_variant
+            .find_by_id(&req.variant_id)
+            .await?
+            .is_none()
+        {
+            return Err(AddWishlistItemError::VariantNotFound {
+                variant_id: req.variant_id,
+            });
+        }
+
+        let mut wishlist = self.load_wishlist(req.user_id).await?;
+        if wishlist
+            .items
+            .iter()
+            .any(|item| item.variant_id == req.variant_id)
+        {
+            return Err(AddWishlistItemError::AlreadyWishlisted {
+                variant_id: req.variant_id,
+            });
+        }
+
+        wishlist.items.push(WishlistItem {
+            id: WishlistItemId::new(),
+            variant_id: req.variant_id,
+            priority: req.priority,
+            desired_quantity: req.desired_quantity,
+            obtained_quantity: 0,
+            max_price: req.max_price,
+            note: req.note,
+            satisfied_at: None,
+            added_at: Utc::now(),
+        });
+
+        wishlist.updated_at = Utc::now();
+        self.wishlist.save(&wishlist).await?;
+
+        Ok(wishlist)
+    }
+
+    async fn update_wishlist_item(
+        &self,
+        req: UpdateWishlistItemRequest,
+    ) -> Result<Wishlist, UpdateWishlistItemError> {
+        let mut wishlist = self.load_wishlist(req.user_id).await?;
+        let item = wishlist
+            .items
+            .iter_mut()
+            .find(|item| item.id == req.item_id)
+            .ok_or(UpdateWishlistItemError::ItemNotFound {
+                item_id: req.item_id,
+            })?;
+
+        if let Some(priority) = req.priority {
+            item.priority = priority;
+        }
+        if let Some(desired_quantity) = req.desired_quantity {
+            item.desired_quantity = desired_quantity;
+            item.refresh_satisfied();
+        }
+        if let Some(max_price) = req.max_price {
+            item.max_price = Some(max_price);
+        }
+        if let Some(note) = req.note {
+            item.note = note;
+        }
+
+        wishlist.updated_at = Utc::now();
+        self.wishlist.save(&wishlist).await?;
+
+        Ok(wishlist)
+    }
+
+    async fn remove_wishlist_item(
+        &self,
+        req: RemoveWishlistItemRequest,
+    ) -> Result<Wishlist, RemoveWishlistItemError> {
+        let mut wishlist = self.load_wishlist(req.user_id).await?;
+        let index = wishlist
+            .items
+            .iter()
+            .position(|item| item.id == req.item_id)
+            .ok_or(RemoveWishlistItemError::ItemNotFound {
+                item_id: req.item_id,
+            })?;
+        wishlist.items.remove(index);
+
+        wishlist.updated_at = Utc::now();
+        self.wishlist.save(&wishlist).await?;
+
+        Ok(wishlist)
+    }
+
+    async fn set_wishlist_visibility(
+        &self,
+        req: SetWishlistVisibilityRequest,
+    ) -> Result<Wishlist, SetWishlistVisibilityError> {
+        let mut wishlist = self.load_wishlist(req.user_id).await?;
+        wishlist.is_public = req.is_public;
+
+        wishlist.updated_at = Utc::now();
+        self.wishlist.save(&wishlist).await?;
+
+        Ok(wishlist)
+    }
+}
@@ -0,0 +1,217 @@
+mod common;
+
+use common::{create_service, create_user};
+use sawa_core::models::misc::{Currency, NonEmptyString, Price};
+use sawa_core::models::purchase::PurchaseOrderStatus;
+use sawa_core::repositories::*;
+use sawa_core::services::*;
+use std::num::NonZeroU32;
+
+#[tokio::test]
+async fn test_cart_checkout() {
+    let service = create_service();
+
+    // Setup: Users, Product, Variants
+    let user = create_user("buyer");
+    let user = service.user.create(user).await.unwrap();
+    let friend = create_user("friend");
+    let friend = service.user.create(friend).await.unwrap();
+
+    let product = service
+        .create_product(CreateProductRequest {
+            name: NonEmptyString::new("P1".to_string()).unwrap(),
+            description: "".to_string(),
+            medias: vec![],
+        })
+        .await
+        .unwrap();
+
+    let mut variants = Vec::new();
+    for name in ["V1", "V2"] {
+        let variant = service
+            .create_product_variant(CreateProductVariantRequest {
+                product_id: product.id,
+                name: NonEmptyString::new(name.to_string()).unwrap(),
+                description: "".to_string(),
+                price: None,
+                sort_order: 0,
+                medias: vec![],
+                tags: vec![],
+                mystery_box: None,
+            })
+            .await
+            .unwrap();
+        variants.push(variant);
+    }
+
+    // 1. A new user has an empty cart
+    let cart = service
+        .get_cart(GetCartRequest { user_id: user.id })
+        .await
+        .unwrap();
+    assert!(cart.lines.is_empty());
+
+    let result = service
+        .checkout_cart(CheckoutCartRequest {
+            user_id: user.id,
+            receiver_id: None,
+            shipping_address: None,
+            total_price: None,
+        })
+        .await;
+    assert!(matches!(result, Err(CheckoutCartError::CartEmpty)));
+
+    // 2. Adding the same variant for the same owner twice merges the lines
+    for _ in 0..2 {
+        service
+            .add_cart_line(AddCartLineRequest {
+                user_id: user.id,
+                variant_id: variants[0].id,
+                quantity: NonZeroU32::new(1).unwrap(),
+                owner_id: None,
+                unit_price: Some(Price {
+                    currency: Currency::JPY,
+                    amount: 500,
+                }),
+            })
+            .await
+            .unwrap();
+    }
+    let cart = service
+        .add_cart_line(AddCartLineRequest {
+            user_id: user.id,
+            variant_id: variants[1].id,
+            quantity: NonZeroU32::new(1).unwrap(),
+            owner_id: Some(friend.id),
+            unit_price: None,
+        })
+        .await
+        .unwrap();
+    assert_eq!(cart.lines.len(), 2);
+    assert_eq!(cart.lines[0].quantity.get(), 2);
+    assert_eq!(cart.lines[1].owner_id, Some(friend.id));
+
+    let result = service
+        .add_cart_line(AddCartLineRequest {
+            user_id: user.id,
+            variant_id: sawa_core::models::product::ProductVariantId::new(),
+            quantity: NonZeroU32::new(1).unwrap(),
+            owner_id: None,
+            unit_price: None,
+        })
+        .await;
+    assert!(matches!(
+        result,
+        Err(AddCartLineError::VariantNotFound { .. })
+    ));
+
+    // 3. The cart is kept per user
+    let friend_cart = service
+        .get_cart(GetCartRequest { user_id: friend.id })
+        .await
+        .unwrap();
+    assert!(friend_cart.lines.is_empty());
+
+    // 4. Update and remove lines
+    let cart = service
+        .update_cart_line(UpdateCartLineRequest {
+            user_id: user.id,
+            line_id: cart.lines[0].id,
+            quantity: Some(NonZeroU32::new(3).unwrap()),
+            owner_id: None,
+            unit_price: None,
+        })
+        .await
+        .unwrap();
+    assert_eq!(cart.lines[0].quantity.get(), 3);
+    assert_eq!(cart.lines[0].unit_price.unwrap().amount, 500);
+
+    let line_id = cart.lines[1].id;
+    let cart = service
+        .remove_cart_line(RemoveCartLineRequest {
+            user_id: user.id,
+            line_id,
+        })
+        .await
+        .unwrap();
+    assert_eq!(cart.lines.len(), 1);
+
+    let result = service
+        .remove_cart_line(RemoveCartLineRequest {
+            user_id: user.id,
+            line_id,
+        })
+        .await;
+    assert!(matches!(
+        result,
+        Err(RemoveCartLineError::LineNotFound { .. })
+    ));
+
+    // 5. Checkout fails while the cart holds a deleted variant
+    service
+        .add_cart_line(AddCartLineRequest {
+            user_id: user.id,
+            variant_id: variants[1].id,
+            quantity: NonZeroU32::new(1).unwrap(),
+            owner_id: Some(friend.id),
+            unit_price: None,
+        })
+        .await
+        .unwrap();
+    service
+        .product_variant
+        .delete(&variants[1].id)
+        .await
+        .unwrap();
+
+    let result = service
+        .checkout_cart(CheckoutCartRequest {
+            user_id: user.id,
+            receiver_id: None,
+            shipping_address: None,
+            total_price: None,
+        })
+        .await;
+    match result {
+        Err(CheckoutCartError::VariantsNotFound { variant_ids }) => {
+            assert_eq!(variant_ids, vec![variants[1].id]);
+        }
+        other => panic!("expected VariantsNotFound, got {:?}", other.map(|o| o.id)),
+    }
+
+    // 6. Checkout turns the remaining lines into an order and clears the cart
+    let cart = service
+        .get_cart(GetCartRequest { user_id: user.id })
+        .await
+        .unwrap();
+    assert_eq!(cart.lines.len(), 2);
+    let cart = service
+        .remove_cart_line(RemoveCartLineRequest {
+            user_id: user.id,
+            line_id: cart.lines[1].id,
+        })
+        .await
+        .unwrap();
+    assert_eq!(cart.lines.len(), 1);
+
+    let order = service
+        .checkout_cart(CheckoutCartRequest {
+            user_id: user.id,
+            receiver_id: None,
+            shipping_address: None,
+            total_price: None,
+        })
+        .await
+        .unwrap();
+    assert_eq!(order.creator_id, user.id);
+    assert_eq!(order.status, PurchaseOrderStatus::Incomplete);
+    assert_eq!(order.items.len(), 1);
+    assert_eq!(order.items[0].purchased_variant_id, variants[0].id);
+    assert_eq!(order.items[0].quantity.get(), 3);
+
+    let cart = service
+        .get_cart(GetCartRequest { user_id: user.id })
+        .await
+        .unwrap();
+    assert!(cart.lines.is_empty());
+}
@@ -0,0 +1,291 @@
+mod common;
+
+use chrono::{Duration, Utc};
+use common::{TestService, create_service, create_test_product_instance, create_user};
+use sawa_core::models::collection::CollectionScope;
+use sawa_core::models::misc::{NonEmptyString, TagId};
+use sawa_core::models::product::{
+    MysteryBoxConfig, MysteryBoxValidation, ProductId, ProductInstanceStatus, ProductVariant,
+};
+use sawa_core::repositories::*;
+use sawa_core::services::*;
+use std::num::NonZeroU32;
+
+async fn create_variant(
+    service: &TestService,
+    product_id: ProductId,
+    name: &str,
+    tags: &[&str],
+    mystery_box: Option<MysteryBoxConfig>,
+) -> ProductVariant {
+    service
+        .create_product_variant(CreateProductVariantRequest {
+            product_id,
+            name: NonEmptyString::new(name.to_string()).unwrap(),
+            description: "".to_string(),
+            price: None,
+            sort_order: 0,
+            medias: vec![],
+            tags: tags
+                .iter()
+                .map(|tag| NonEmptyString::new(tag.to_string()).unwrap())
+                .collect(),
+            mystery_box,
+        })
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_collection_completeness() {
+    let service = create_service();
+
+    // Setup: User, tags VOCALOID > {Miku, Rin}
+    let alice = create_user("alice");
+    let alice = service.user.create(alice).await.unwrap();
+
+    let vocaloid = service
+        .create_tag(CreateTagRequest {
+            name: NonEmptyString::new("VOCALOID".to_string()).unwrap(),
+            description: "".to_string(),
+            parent_id: None,
+        })
+        .await
+        .unwrap();
+    for name in ["Miku", "Rin"] {
+        service
+            .create_tag(CreateTagRequest {
+                name: NonEmptyString::new(name.to_string()).unwrap(),
+                description: "".to_string(),
+                parent_id: Some(vocaloid.id),
+            })
+            .await
+            .unwrap();
+    }
+
+    // Setup: a blind box series with two Miku variants, and a Rin secret from another product
+    let create_product = |name: &str| CreateProductRequest {
+        name: NonEmptyString::new(name.to_string()).unwrap(),
+        description: "".to_string(),
+        medias: vec![],
+    };
+    let series = service
+        .create_product(create_product("Series"))
+        .await
+        .unwrap();
+    let secret_product = service
+        .create_product(create_product("Secret"))
+        .await
+        .unwrap();
+
+    let first = create_variant(&service, series.id, "Miku A", &["Miku"], None).await;
+    let second = create_variant(&service, series.id, "Miku B", &["Miku"], None).await;
+    let secret = create_variant(&service, secret_product.id, "Rin", &["Rin"], None).await;
+    create_variant(
+        &service,
+        series.id,
+        "Blind box",
+        &[],
+        Some(MysteryBoxConfig {
+            items_count: NonZeroU32::new(1).unwrap(),
+            possible_variants: vec![first.id, second.id, secret.id],
+            validation: MysteryBoxValidation::Lenient,
+        }),
+    )
+    .await;
+
+    // Alice owns two of the first variant; her copy of the second one was consumed
+    let instances = vec![
+        create_test_product_instance(first.id, alice.id, alice.id, ProductInstanceStatus::Active),
+        create_test_product_instance(first.id, alice.id, alice.id, ProductInstanceStatus::Active),
+        create_test_product_instance(
+            second.id,
+            alice.id,
+            alice.id,
+            ProductInstanceStatus::Consumed,
+        ),
+    ];
+    service
+        .product_instance
+        .save_batch(&instances)
+        .await
+        .unwrap();
+
+    // 1. By product: the blind box is replaced by its contents, including the secret
+    let completeness = service
+        .get_collection_completeness(GetCollectionCompletenessRequest {
+            user_id: alice.id,
+            scope: CollectionScope::Product {
+                product_id: series.id,
+            },
+        })
+        .await
+        .unwrap();
+    let counts: Vec<_> = completeness
+        .variants
+        .iter()
+        .map(|entry| (entry.variant.id, entry.owned_count))
+        .collect();
+    assert_eq!(counts.len(), 3);
+    assert!(counts.contains(&(first.id, 2)));
+    assert!(counts.contains(&(second.id, 0)));
+    assert!(counts.contains(&(secret.id, 0)));
+    assert_eq!(completeness.missing.len(), 2);
+    assert!((completeness.percentage - 100.0 / 3.0).abs() < 1e-9);
+
+    // 2. By tag: only variants tagged directly, unless descendants are included
+    let completeness = service
+        .get_collection_completeness(GetCollectionCompletenessRequest {
+            user_id: alice.id,
+            scope: CollectionScope::Tag {
+                tag_id: vocaloid.id,
+                include_descendants: false,
+            },
+        })
+        .await
+        .unwrap();
+    assert!(completeness.variants.is_empty());
+    assert_eq!(completeness.percentage, 0.0);
+
+    let completeness = service
+        .get_collection_completeness(GetCollectionCompletenessRequest {
+            user_id: alice.id,
+            scope: CollectionScope::Tag {
+                tag_id: vocaloid.id,
+                include_descendants: true,
+            },
+        })
+        .await
+        .unwrap();
+    assert_eq!(completeness.variants.len(), 3);
+    assert!(completeness.missing.contains(&secret.id));
+
+    // 3. Unknown tags are reported
+    let result = service
+        .get_collection_completeness(GetCollectionCompletenessRequest {
+            user_id: alice.id,
+            scope: CollectionScope::Tag {
+                tag_id: TagId::new(),
+                include_descendants: true,
+            },
+        })
+        .await;
+    assert!(matches!(
+        result,
+        Err(GetCollectionCompletenessError::Scope(
+            CollectionScopeError::TagNotFound { .. }
+        ))
+    ));
+}
+
+#[tokio::test]
+async fn test_duplicates_report() {
+    let service = create_service();
+
+    let alice = create_user("alice");
+    let alice = service.user.create(alice).await.unwrap();
+
+    let create_product = |name: &str| CreateProductRequest {
+        name: NonEmptyString::new(name.to_string()).unwrap(),
+        description: "".to_string(),
+        medias: vec![],
+    };
+    let series = service
+        .create_product(create_product("Series"))
+        .await
+        .unwrap();
+    let other = service
+        .create_product(create_product("Other"))
+        .await
+        .unwrap();
+
+    let first = create_variant(&service, series.id, "Miku A", &["Miku"], None).await;
+    let second = create_variant(&service, series.id, "Miku B", &["Miku"], None).await;
+    let third = create_variant(&service, other.id, "Rin", &["Rin"], None).await;
+
+    // Alice owns three of the first variant (acquired on different days), one of the second,
+    // and two of the third plus a consumed one
+    let mut instances = Vec::new();
+    for days in 0..3 {
+        let mut instance = create_test_product_instance(
+            first.id,
+            alice.id,
+            alice.id,
+            ProductInstanceStatus::Active,
+        );
+        instance.created_at = Utc::now() - Duration::days(days);
+        instances.push(instance);
+    }
+    instances.push(create_test_product_instance(
+        second.id,
+        alice.id,
+        alice.id,
+        ProductInstanceStatus::Active,
+    ));
+    for status in [
+        ProductInstanceStatus::Active,
+        ProductInstanceStatus::Active,
+        ProductInstanceStatus::Consumed,
+    ] {
+        instances.push(create_test_product_instance(
+            third.id, alice.id, alice.id, status,
+        ));
+    }
+    service
+        .product_instance
+        .save_batch(&instances)
+        .await
+        .unwrap();
+    let oldest = instances[2].id;
+
+    // 1. Whole collection, keeping one of each
+    let duplicates = service
+        .get_duplicates(GetDuplicatesRequest {
+            user_id: alice.id,
+            threshold: 1,
+            scope: None,
+        })
+        .await
+        .unwrap();
+    assert_eq!(duplicates.len(), 2);
+    assert_eq!(duplicates[0].variant.id, first.id);
+    assert_eq!(duplicates[0].owned_count, 3);
+    assert_eq!(duplicates[0].spare_instance_ids.len(), 2);
+    assert!(!duplicates[0].spare_instance_ids.contains(&oldest));
+    assert_eq!(duplicates[0].product.id, series.id);
+    assert_eq!(duplicates[0].tags.len(), 1);
+    assert_eq!(duplicates[1].variant.id, third.id);
+    assert_eq!(duplicates[1].owned_count, 2);
+    assert_eq!(duplicates[1].spare_instance_ids.len(), 1);
+
+    // 2. Limited to a product, with a higher threshold
+    let duplicates = service
+        .get_duplicates(GetDuplicatesRequest {
+            user_id: alice.id,
+            threshold: 2,
+            scope: Some(CollectionScope::Product {
+                product_id: series.id,
+            }),
+        })
+        .await
+        .unwrap();
+    assert_eq!(duplicates.len(), 1);
+    assert_eq!(duplicates[0].variant.id, first.id);
+    assert_eq!(duplicates[0].spare_instance_ids.len(), 1);
+
+    // 3. Limited to a tag
+    let rin = service.tag.find_by_name("Rin").await.unwrap().unwrap();
+    let duplicates = service
+        .get_duplicates(GetDuplicatesRequest {
+            user_id: alice.id,
+            threshold: 1,
+            scope: Some(CollectionScope::Tag {
+                tag_id: rin.id,
+                include_descendants: false,
+            }),
+        })
+        .await
+        .unwrap();
+    assert_eq!(duplicates.len(), 1);
+    assert_eq!(duplicates[0].variant.id, third.id);
+}
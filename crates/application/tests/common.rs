@@ -15,18 +15,39 @@ pub type TestService = Service<
     InMemoryUserRepository,
     InMemoryTagRepository,
     InMemoryMediaRepository,
+    InMemoryCartRepository,
+    InMemoryStorageLocationRepository,
+    InMemoryWishlistRepository,
+    InMemoryStatisticsRepository,
 >;
 
 pub fn create_service() -> TestService {
+    let product = InMemoryProductRepository::new();
+    let product_variant = InMemoryProductVariantRepository::new();
+    let product_instance = InMemoryProductInstanceRepository::new();
+    let order = InMemoryPurchaseOrderRepository::new();
+    let tag = InMemoryTagRepository::new();
+    let statistics = InMemoryStatisticsRepository::new(
+        order.clone(),
+        product_instance.clone(),
+        product_variant.clone(),
+        product.clone(),
+        tag.clone(),
+    );
+
     Service {
-        product: InMemoryProductRepository::new(),
-        product_variant: InMemoryProductVariantRepository::new(),
-        product_instance: InMemoryProductInstanceRepository::new(),
-        order: InMemoryPurchaseOrderRepository::new(),
+        product,
+        product_variant,
+        product_instance,
+        order,
         transaction: InMemoryUserTransactionRepository::new(),
         user: InMemoryUserRepository::new(),
-        tag: InMemoryTagRepository::new(),
+        tag,
         media: InMemoryMediaRepository::new(),
+        cart: InMemoryCartRepository::new(),
+        storage_location: InMemoryStorageLocationRepository::new(),
+        wishlist: InMemoryWishlistRepository::new(),
+        statistics,
     }
 }
 
@@ -38,6 +59,8 @@ pub fn create_user(username: &str) -> User {
         email: Email(format!("{}@example.com", username)),
         password_hash: NonEmptyString::new("hash".to_string()).unwrap(),
         avatar: None,
+        placeholder: None,
+        calendar_token: None,
         created_at: Utc::now(),
     }
 }
@@ -54,9 +77,14 @@ pub fn create_test_product_instance(
         variant_id,
         owner_id,
         holder_id,
+        location_id: None,
         status,
+        condition: None,
+        note: String::new(),
+        medias: vec![],
         source_order_line_item_id: PurchaseOrderLineItemId::new(),
         created_at: Utc::now(),
+        acquisition_cost: None,
         transfer_history: vec![],
         status_history: vec![],
     }
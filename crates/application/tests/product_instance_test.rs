@@ -2,7 +2,9 @@ mod common;
 
 use common::{create_service, create_test_product_instance, create_user};
 use sawa_core::models::misc::NonEmptyString;
-use sawa_core::models::product::ProductInstanceStatus;
+use sawa_core::models::product::{
+    ProductInstanceCondition, ProductInstanceId, ProductInstanceStatus,
+};
 use sawa_core::repositories::*;
 use sawa_core::services::*;
 
@@ -56,6 +58,8 @@ async fn test_list_product_instances_by_holder() {
             query_by: ListProductInstancesQueryBy::Owned,
             variant_id: None,
             status: None,
+            location_id: None,
+            condition: None,
         })
         .await
         .expect("Failed to list owner instances");
@@ -71,6 +75,8 @@ async fn test_list_product_instances_by_holder() {
             query_by: ListProductInstancesQueryBy::Held,
             variant_id: None,
             status: None,
+            location_id: None,
+            condition: None,
         })
         .await
         .expect("Failed to list holder instances");
@@ -97,6 +103,8 @@ async fn test_list_product_instances_by_holder() {
             query_by: ListProductInstancesQueryBy::Held,
             variant_id: None,
             status: Some(ProductInstanceStatus::Locked),
+            location_id: None,
+            condition: None,
         })
         .await
         .expect("Failed to list holder instances");
@@ -124,6 +132,8 @@ async fn test_list_product_instances_by_holder() {
             query_by: ListProductInstancesQueryBy::Owned,
             variant_id: None,
             status: None,
+            location_id: None,
+            condition: None,
         })
         .await
         .expect("Failed to list owner instances");
@@ -139,6 +149,8 @@ async fn test_list_product_instances_by_holder() {
             query_by: ListProductInstancesQueryBy::Held,
             variant_id: None,
             status: None,
+            location_id: None,
+            condition: None,
         })
         .await
         .expect("Failed to list holder instances");
@@ -154,9 +166,591 @@ async fn test_list_product_instances_by_holder() {
             query_by: ListProductInstancesQueryBy::Held,
             variant_id: None,
             status: None,
+            location_id: None,
+            condition: None,
         })
         .await
         .expect("Failed to list holder instances");
 
     assert_eq!(alice_holder_instances.len(), 0);
 }
+
+#[tokio::test]
+async fn test_product_instance_status_transitions() {
+    let service = create_service();
+
+    // Setup: User, Product and Variant
+    let alice = create_user("alice");
+    let alice = service.user.create(alice).await.unwrap();
+
+    let product = service
+        .create_product(CreateProductRequest {
+            name: NonEmptyString::new("P1".to_string()).unwrap(),
+            description: "".to_string(),
+            medias: vec![],
+        })
+        .await
+        .unwrap();
+
+    let variant = service
+        .create_product_variant(CreateProductVariantRequest {
+            product_id: product.id,
+            name: NonEmptyString::new("V1".to_string()).unwrap(),
+            description: "".to_string(),
+            price: None,
+            sort_order: 0,
+            medias: vec![],
+            tags: vec![],
+            mystery_box: None,
+        })
+        .await
+        .unwrap();
+
+    let instance = create_test_product_instance(
+        variant.id,
+        alice.id,
+        alice.id,
+        ProductInstanceStatus::Active,
+    );
+    service.product_instance.save(&instance).await.unwrap();
+
+    // 1. Consumed by mistake, then made active again
+    let consumed = service
+        .consume_product_instance(ConsumeProductInstanceRequest {
+            id: instance.id,
+            user_id: alice.id,
+        })
+        .await
+        .unwrap();
+    assert_eq!(consumed.status, ProductInstanceStatus::Consumed);
+
+    let result = service
+        .recover_product_instance(RecoverProductInstanceRequest {
+            id: instance.id,
+            user_id: alice.id,
+            reason: None,
+        })
+        .await;
+    assert!(matches!(
+        result,
+        Err(RecoverProductInstanceError::InvalidTransition(_))
+    ));
+
+    let active = service
+        .unconsume_product_instance(UnconsumeProductInstanceRequest {
+            id: instance.id,
+            user_id: alice.id,
+        })
+        .await
+        .unwrap();
+    assert_eq!(active.status, ProductInstanceStatus::Active);
+
+    // 2. Lost, then found again
+    service
+        .mark_product_instance_lost(MarkProductInstanceLostRequest {
+            id: instance.id,
+            user_id: alice.id,
+            reason: Some("Misplaced after moving".to_string()),
+        })
+        .await
+        .unwrap();
+
+    let result = service
+        .consume_product_instance(ConsumeProductInstanceRequest {
+            id: instance.id,
+            user_id: alice.id,
+        })
+        .await;
+    assert!(matches!(
+        result,
+        Err(ConsumeProductInstanceError::InvalidTransition(_))
+    ));
+
+    let recovered = service
+        .recover_product_instance(RecoverProductInstanceRequest {
+            id: instance.id,
+            user_id: alice.id,
+            reason: Some("Found in a drawer".to_string()),
+        })
+        .await
+        .unwrap();
+    assert_eq!(recovered.status, ProductInstanceStatus::Active);
+
+    // 3. Lost again and written off; destroyed is final
+    service
+        .mark_product_instance_lost(MarkProductInstanceLostRequest {
+            id: instance.id,
+            user_id: alice.id,
+            reason: None,
+        })
+        .await
+        .unwrap();
+    let destroyed = service
+        .mark_product_instance_destroyed(MarkProductInstanceDestroyedRequest {
+            id: instance.id,
+            user_id: alice.id,
+            reason: None,
+        })
+        .await
+        .unwrap();
+    assert_eq!(destroyed.status, ProductInstanceStatus::Destroyed);
+
+    let result = service
+        .recover_product_instance(RecoverProductInstanceRequest {
+            id: instance.id,
+            user_id: alice.id,
+            reason: None,
+        })
+        .await;
+    assert!(matches!(
+        result,
+        Err(RecoverProductInstanceError::InvalidTransition(_))
+    ));
+
+    // Every change is recorded in the status history
+    let statuses: Vec<_> = destroyed
+        .status_history
+        .iter()
+        .map(|history| history.status)
+        .collect();
+    assert_eq!(
+        statuses,
+        vec![
+            ProductInstanceStatus::Consumed,
+            ProductInstanceStatus::Active,
+            ProductInstanceStatus::NotFound,
+            ProductInstanceStatus::Active,
+            ProductInstanceStatus::NotFound,
+            ProductInstanceStatus::Destroyed,
+        ]
+    );
+    assert_eq!(
+        destroyed.status_history[3].reason.as_deref(),
+        Some("Found in a drawer")
+    );
+}
+
+#[tokio::test]
+async fn test_update_product_instance_details() {
+    let service = create_service();
+
+    // Setup: Users, Product, Variant and instances lent to Bob
+    let alice = create_user("alice");
+    let bob = create_user("bob");
+    let alice = service.user.create(alice).await.unwrap();
+    let bob = service.user.create(bob).await.unwrap();
+
+    let product = service
+        .create_product(CreateProductRequest {
+            name: NonEmptyString::new("P1".to_string()).unwrap(),
+            description: "".to_string(),
+            medias: vec![],
+        })
+        .await
+        .unwrap();
+
+    let variant = service
+        .create_product_variant(CreateProductVariantRequest {
+            product_id: product.id,
+            name: NonEmptyString::new("V1".to_string()).unwrap(),
+            description: "".to_string(),
+            price: None,
+            sort_order: 0,
+            medias: vec![],
+            tags: vec![],
+            mystery_box: None,
+        })
+        .await
+        .unwrap();
+
+    let signed =
+        create_test_product_instance(variant.id, alice.id, bob.id, ProductInstanceStatus::Active);
+    let plain =
+        create_test_product_instance(variant.id, alice.id, bob.id, ProductInstanceStatus::Active);
+    service
+        .product_instance
+        .save_batch(&[signed.clone(), plain.clone()])
+        .await
+        .unwrap();
+
+    let medias = service
+        .create_media_batch(CreateMediaBatchRequest {
+            urls: vec!["https://example.com/signed.png".parse().unwrap()],
+        })
+        .await
+        .unwrap();
+
+    // 1. Only the owner can update the details
+    let result = service
+        .update_product_instance_details(UpdateProductInstanceDetailsRequest {
+            id: signed.id,
+            user_id: bob.id,
+            condition: Some(ProductInstanceCondition::Poor),
+            note: "".to_string(),
+            medias: vec![],
+        })
+        .await;
+    assert!(matches!(
+        result,
+        Err(UpdateProductInstanceDetailsError::PermissionDenied)
+    ));
+
+    let updated = service
+        .update_product_instance_details(UpdateProductInstanceDetailsRequest {
+            id: signed.id,
+            user_id: alice.id,
+            condition: Some(ProductInstanceCondition::Mint),
+            note: "Signed at the live".to_string(),
+            medias: vec![medias[0].id],
+        })
+        .await
+        .unwrap();
+    assert_eq!(updated.condition, Some(ProductInstanceCondition::Mint));
+    assert_eq!(updated.medias, vec![medias[0].id]);
+
+    // 2. Filter by condition
+    let mint = service
+        .list_product_instances(ListProductInstancesRequest {
+            user_id: alice.id,
+            query_by: ListProductInstancesQueryBy::Owned,
+            variant_id: None,
+            status: None,
+            location_id: None,
+            condition: Some(ProductInstanceCondition::Mint),
+        })
+        .await
+        .unwrap();
+    assert_eq!(mint.len(), 1);
+    assert_eq!(mint[0].id, signed.id);
+    assert_eq!(mint[0].note, "Signed at the live");
+
+    // 3. The note is private to the owner
+    let borrowed = service
+        .list_product_instances(ListProductInstancesRequest {
+            user_id: bob.id,
+            query_by: ListProductInstancesQueryBy::Borrowed,
+            variant_id: None,
+            status: None,
+            location_id: None,
+            condition: Some(ProductInstanceCondition::Mint),
+        })
+        .await
+        .unwrap();
+    assert_eq!(borrowed.len(), 1);
+    assert!(borrowed[0].note.is_empty());
+
+    let instance = service
+        .get_product_instance(GetProductInstanceRequest {
+            id: signed.id,
+            user_id: bob.id,
+        })
+        .await
+        .unwrap();
+    assert!(instance.note.is_empty());
+    let instance = service
+        .get_product_instance(GetProductInstanceRequest {
+            id: signed.id,
+            user_id: alice.id,
+        })
+        .await
+        .unwrap();
+    assert_eq!(instance.note, "Signed at the live");
+}
+
+#[tokio::test]
+async fn test_bulk_change_instance_status() {
+    let service = create_service();
+
+    // Setup: Users, Product and Variant
+    let alice = create_user("alice");
+    let bob = create_user("bob");
+    let alice = service.user.create(alice).await.unwrap();
+    let bob = service.user.create(bob).await.unwrap();
+
+    let product = service
+        .create_product(CreateProductRequest {
+            name: NonEmptyString::new("P1".to_string()).unwrap(),
+            description: "".to_string(),
+            medias: vec![],
+        })
+        .await
+        .unwrap();
+
+    let variant = service
+        .create_product_variant(CreateProductVariantRequest {
+            product_id: product.id,
+            name: NonEmptyString::new("V1".to_string()).unwrap(),
+            description: "".to_string(),
+            price: None,
+            sort_order: 0,
+            medias: vec![],
+            tags: vec![],
+            mystery_box: None,
+        })
+        .await
+        .unwrap();
+
+    // Alice owns three active instances and a consumed one, Bob owns one
+    let mut owned = vec![];
+    for _ in 0..3 {
+        let instance = create_test_product_instance(
+            variant.id,
+            alice.id,
+            alice.id,
+            ProductInstanceStatus::Active,
+        );
+        service.product_instance.save(&instance).await.unwrap();
+        owned.push(instance.id);
+    }
+    let consumed = create_test_product_instance(
+        variant.id,
+        alice.id,
+        alice.id,
+        ProductInstanceStatus::Consumed,
+    );
+    service.product_instance.save(&consumed).await.unwrap();
+    let bobs =
+        create_test_product_instance(variant.id, bob.id, bob.id, ProductInstanceStatus::Active);
+    service.product_instance.save(&bobs).await.unwrap();
+
+    // 1. One bad instance rejects the whole operation, with a result per instance
+    let missing = ProductInstanceId::new();
+    let result = service
+        .bulk_change_instance_status(BulkChangeInstanceStatusRequest {
+            user_id: alice.id,
+            selection: BulkInstanceSelection::Ids(vec![owned[0], consumed.id, bobs.id, missing]),
+            status: ProductInstanceStatus::NotFound,
+            reason: None,
+        })
+        .await;
+    let Err(BulkChangeInstanceStatusError::Rejected { results }) = result else {
+        panic!("Expected Rejected error");
+    };
+    let errors: Vec<_> = results.iter().map(|result| result.error).collect();
+    assert_eq!(
+        errors,
+        vec![
+            None,
+            Some(BulkInstanceItemError::InvalidTransition {
+                from: ProductInstanceStatus::Consumed,
+                to: ProductInstanceStatus::NotFound,
+            }),
+            Some(BulkInstanceItemError::PermissionDenied),
+            Some(BulkInstanceItemError::NotFound),
+        ]
+    );
+    let unchanged = service
+        .product_instance
+        .find_by_id(&owned[0])
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(unchanged.status, ProductInstanceStatus::Active);
+
+    // 2. Mark all by ID as lost after a move (duplicates are ignored)
+    let mut ids = owned.clone();
+    ids.push(owned[0]);
+    let results = service
+        .bulk_change_instance_status(BulkChangeInstanceStatusRequest {
+            user_id: alice.id,
+            selection: BulkInstanceSelection::Ids(ids),
+            status: ProductInstanceStatus::NotFound,
+            reason: Some("Lost in the move".to_string()),
+        })
+        .await
+        .unwrap();
+    assert_eq!(results.len(), 3);
+    for id in &owned {
+        let instance = service
+            .product_instance
+            .find_by_id(id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(instance.status, ProductInstanceStatus::NotFound);
+        assert_eq!(
+            instance.status_history.last().unwrap().reason.as_deref(),
+            Some("Lost in the move")
+        );
+    }
+
+    // 3. Found again, selected by filter
+    let results = service
+        .bulk_change_instance_status(BulkChangeInstanceStatusRequest {
+            user_id: alice.id,
+            selection: BulkInstanceSelection::Filter {
+                variant_id: Some(variant.id),
+                status: Some(ProductInstanceStatus::NotFound),
+                location_id: None,
+                condition: None,
+            },
+            status: ProductInstanceStatus::Active,
+            reason: None,
+        })
+        .await
+        .unwrap();
+    assert_eq!(results.len(), 3);
+    assert!(results.iter().all(|result| result.error.is_none()));
+
+    // 4. Locking and unlocking are left to transactions, and something must be selected
+    let locked = create_test_product_instance(
+        variant.id,
+        alice.id,
+        alice.id,
+        ProductInstanceStatus::Locked,
+    );
+    service.product_instance.save(&locked).await.unwrap();
+    let result = service
+        .bulk_change_instance_status(BulkChangeInstanceStatusRequest {
+            user_id: alice.id,
+            selection: BulkInstanceSelection::Ids(vec![owned[0], locked.id]),
+            status: ProductInstanceStatus::Active,
+            reason: None,
+        })
+        .await;
+    let Err(BulkChangeInstanceStatusError::Rejected { results }) = result else {
+        panic!("Expected Rejected error");
+    };
+    assert_eq!(results[0].error, None);
+    assert_eq!(results[1].error, Some(BulkInstanceItemError::Locked));
+    let unchanged = service
+        .product_instance
+        .find_by_id(&locked.id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(unchanged.status, ProductInstanceStatus::Locked);
+
+    let result = service
+        .bulk_change_instance_status(BulkChangeInstanceStatusRequest {
+            user_id: alice.id,
+            selection: BulkInstanceSelection::Ids(owned.clone()),
+            status: ProductInstanceStatus::Locked,
+            reason: None,
+        })
+        .await;
+    assert!(matches!(
+        result,
+        Err(BulkChangeInstanceStatusError::UnsupportedStatus { .. })
+    ));
+
+    let result = service
+        .bulk_change_instance_status(BulkChangeInstanceStatusRequest {
+            user_id: alice.id,
+            selection: BulkInstanceSelection::Ids(vec![]),
+            status: ProductInstanceStatus::NotFound,
+            reason: None,
+        })
+        .await;
+    assert!(matches!(
+        result,
+        Err(BulkChangeInstanceStatusError::NoInstances)
+    ));
+}
+
+#[tokio::test]
+async fn test_bulk_transfer_instances() {
+    let service = create_service();
+
+    // Setup: Users, Product and Variant
+    let alice = create_user("alice");
+    let bob = create_user("bob");
+    let alice = service.user.create(alice).await.unwrap();
+    let bob = service.user.create(bob).await.unwrap();
+
+    let product = service
+        .create_product(CreateProductRequest {
+            name: NonEmptyString::new("P1".to_string()).unwrap(),
+            description: "".to_string(),
+            medias: vec![],
+        })
+        .await
+        .unwrap();
+
+    let variant = service
+        .create_product_variant(CreateProductVariantRequest {
+            product_id: product.id,
+            name: NonEmptyString::new("V1".to_string()).unwrap(),
+            description: "".to_string(),
+            price: None,
+            sort_order: 0,
+            medias: vec![],
+            tags: vec![],
+            mystery_box: None,
+        })
+        .await
+        .unwrap();
+
+    let mut owned = vec![];
+    for _ in 0..2 {
+        let instance = create_test_product_instance(
+            variant.id,
+            alice.id,
+            alice.id,
+            ProductInstanceStatus::Active,
+        );
+        service.product_instance.save(&instance).await.unwrap();
+        owned.push(instance.id);
+    }
+
+    // 1. Cannot transfer to oneself
+    let result = service
+        .bulk_transfer_instances(BulkTransferInstancesRequest {
+            user_id: alice.id,
+            selection: BulkInstanceSelection::Ids(owned.clone()),
+            to_user_id: alice.id,
+        })
+        .await;
+    assert!(matches!(result, Err(BulkTransferInstancesError::SameUser)));
+
+    // 2. Transfer everything of the variant to Bob in one pending transaction
+    let response = service
+        .bulk_transfer_instances(BulkTransferInstancesRequest {
+            user_id: alice.id,
+            selection: BulkInstanceSelection::Filter {
+                variant_id: Some(variant.id),
+                status: None,
+                location_id: None,
+                condition: None,
+            },
+            to_user_id: bob.id,
+        })
+        .await
+        .unwrap();
+    assert_eq!(response.results.len(), 2);
+
+    let transaction = service
+        .transaction
+        .find_by_id(&response.transaction_id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(transaction.to_user_id, bob.id);
+    assert_eq!(transaction.items.len(), 2);
+    for id in &owned {
+        let instance = service
+            .product_instance
+            .find_by_id(id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(instance.status, ProductInstanceStatus::Locked);
+    }
+
+    // 3. Locked instances cannot be transferred again
+    let result = service
+        .bulk_transfer_instances(BulkTransferInstancesRequest {
+            user_id: alice.id,
+            selection: BulkInstanceSelection::Ids(owned.clone()),
+            to_user_id: bob.id,
+        })
+        .await;
+    let Err(BulkTransferInstancesError::Rejected { results }) = result else {
+        panic!("Expected Rejected error");
+    };
+    assert!(results.iter().all(|result| result.error
+        == Some(BulkInstanceItemError::InvalidTransition {
+            from: ProductInstanceStatus::Locked,
+            to: ProductInstanceStatus::Locked,
+        })));
+}
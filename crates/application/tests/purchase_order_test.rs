@@ -1,8 +1,15 @@
 mod common;
 
+use chrono::NaiveDate;
 use common::{create_service, create_user};
-use sawa_core::models::misc::{Address, Currency, NonEmptyString, Price};
-use sawa_core::models::product::ProductInstanceStatus;
+use sawa_core::models::misc::{Address, Currency, MediaId, NonEmptyString, Price};
+use sawa_core::models::product::{MysteryBoxValidation, MysteryBoxWarning, ProductInstanceStatus};
+use sawa_core::models::purchase::{
+    AdjustmentAllocation, ManualAllocation, OrderRoleFilter, PurchaseOrderAdjustmentKind,
+    PurchaseOrderAttachmentKind, PurchaseOrderEventKind, PurchaseOrderItemStatus,
+    PurchaseOrderSchedule, PurchaseOrderStatus, PurchaseOrderView, ShipmentTracking,
+};
+use sawa_core::models::transfer::{TransferReason, UserTransactionStatus};
 use sawa_core::repositories::*;
 use sawa_core::services::*;
 use std::num::NonZeroU32;
@@ -97,6 +104,8 @@ async fn test_purchase_flow() {
             query_by: ListProductInstancesQueryBy::Owned,
             variant_id: None,
             status: None,
+            location_id: None,
+            condition: None,
         })
         .await
         .expect("Failed to list instances");
@@ -208,6 +217,8 @@ async fn test_order_fulfillment_flow() {
             query_by: ListProductInstancesQueryBy::Owned,
             variant_id: None,
             status: None,
+            location_id: None,
+            condition: None,
         })
         .await
         .expect("Failed to list instances");
@@ -310,13 +321,16 @@ async fn test_order_with_different_creator_and_receiver() {
         sawa_core::models::purchase::PurchaseOrderStatus::Fulfilled
     );
 
-    // 4. Verify ProductInstances are owned by creator (owner) and held by receiver
+    // 4. Verify ProductInstances are owned by creator (owner) and held by receiver,
+    // locked while being delivered to the creator
     let creator_instances = service
         .list_product_instances(ListProductInstancesRequest {
             user_id: creator.id,
             query_by: ListProductInstancesQueryBy::Owned,
             variant_id: None,
             status: None,
+            location_id: None,
+            condition: None,
         })
         .await
         .expect("Failed to list creator instances");
@@ -326,7 +340,7 @@ async fn test_order_with_different_creator_and_receiver() {
     assert!(
         creator_instances
             .iter()
-            .all(|i| i.status == ProductInstanceStatus::Active)
+            .all(|i| i.status == ProductInstanceStatus::Locked)
     );
 
     // 5. Verify receiver has no instances (by owner)
@@ -336,6 +350,8 @@ async fn test_order_with_different_creator_and_receiver() {
             query_by: ListProductInstancesQueryBy::Owned,
             variant_id: None,
             status: None,
+            location_id: None,
+            condition: None,
         })
         .await
         .expect("Failed to list receiver instances");
@@ -348,6 +364,8 @@ async fn test_order_with_different_creator_and_receiver() {
             query_by: ListProductInstancesQueryBy::Held,
             variant_id: None,
             status: None,
+            location_id: None,
+            condition: None,
         })
         .await
         .expect("Failed to list receiver holds");
@@ -362,6 +380,8 @@ async fn test_order_with_different_creator_and_receiver() {
             query_by: ListProductInstancesQueryBy::Held,
             variant_id: None,
             status: None,
+            location_id: None,
+            condition: None,
         })
         .await
         .expect("Failed to list creator holds");
@@ -463,6 +483,8 @@ async fn test_order_cancellation_flow() {
             query_by: ListProductInstancesQueryBy::Owned,
             variant_id: None,
             status: None,
+            location_id: None,
+            condition: None,
         })
         .await
         .expect("Failed to list instances");
@@ -673,6 +695,7 @@ async fn test_fulfill_order_without_pending_items_fails() {
             mystery_box: Some(sawa_core::models::product::MysteryBoxConfig {
                 items_count: NonZeroU32::new(1).unwrap(),
                 possible_variants: vec![],
+                validation: MysteryBoxValidation::Lenient,
             }),
         })
         .await
@@ -878,3 +901,2886 @@ async fn test_cancel_order_permission_denied() {
         _ => panic!("Expected PermissionDenied error"),
     }
 }
+
+#[tokio::test]
+async fn test_edit_incomplete_order() {
+    let service = create_service();
+
+    // Setup: Users, Product, Variants
+    let creator = create_user("creator");
+    let creator = service.user.create(creator).await.unwrap();
+    let receiver = create_user("receiver");
+    let receiver = service.user.create(receiver).await.unwrap();
+
+    let product = service
+        .create_product(CreateProductRequest {
+            name: NonEmptyString::new("P1".to_string()).unwrap(),
+            description: "".to_string(),
+            medias: vec![],
+        })
+        .await
+        .unwrap();
+
+    let variant1 = service
+        .create_product_variant(CreateProductVariantRequest {
+            product_id: product.id,
+            name: NonEmptyString::new("V1".to_string()).unwrap(),
+            description: "".to_string(),
+            price: None,
+            sort_order: 0,
+            medias: vec![],
+            tags: vec![],
+            mystery_box: None,
+        })
+        .await
+        .unwrap();
+
+    let variant2 = service
+        .create_product_variant(CreateProductVariantRequest {
+            product_id: product.id,
+            name: NonEmptyString::new("V2".to_string()).unwrap(),
+            description: "".to_string(),
+            price: None,
+            sort_order: 1,
+            medias: vec![],
+            tags: vec![],
+            mystery_box: None,
+        })
+        .await
+        .unwrap();
+
+    // Create order with two items: 2 x 1000 + 1 x 500
+    let order = service
+        .create_order(CreateOrderRequest {
+            user_id: creator.id,
+            receiver_id: None,
+            shipping_address: None,
+            total_price: None,
+            items: vec![
+                CreateOrderItemRequest {
+                    variant_id: variant1.id,
+                    owner_id: None,
+                    quantity: NonZeroU32::new(2).unwrap(),
+                    unit_price: Some(Price {
+                        currency: Currency::JPY,
+                        amount: 1000,
+                    }),
+                },
+                CreateOrderItemRequest {
+                    variant_id: variant2.id,
+                    owner_id: None,
+                    quantity: NonZeroU32::new(1).unwrap(),
+                    unit_price: Some(Price {
+                        currency: Currency::JPY,
+                        amount: 500,
+                    }),
+                },
+            ],
+        })
+        .await
+        .expect("Failed to create order");
+    assert_eq!(order.total_price.amount, 2500);
+
+    let item1_id = order.items[0].id;
+    let item2_id = order.items[1].id;
+
+    // Increase quantity and correct the unit price of the first item
+    let order = service
+        .update_order_item(UpdateOrderItemRequest {
+            user_id: creator.id,
+            order_id: order.id,
+            order_item_id: item1_id,
+            quantity: Some(NonZeroU32::new(3).unwrap()),
+            unit_price: Some(Price {
+                currency: Currency::JPY,
+                amount: 800,
+            }),
+        })
+        .await
+        .expect("Failed to update order item");
+
+    let item1 = order.items.iter().find(|i| i.id == item1_id).unwrap();
+    assert_eq!(item1.quantity.get(), 3);
+    assert_eq!(item1.line_items.len(), 3);
+    assert!(item1.line_items.iter().all(|li| li.owner_id == creator.id));
+    assert_eq!(order.total_price.amount, 2900);
+
+    // Decrease quantity
+    let order = service
+        .update_order_item(UpdateOrderItemRequest {
+            user_id: creator.id,
+            order_id: order.id,
+            order_item_id: item1_id,
+            quantity: Some(NonZeroU32::new(1).unwrap()),
+            unit_price: None,
+        })
+        .await
+        .expect("Failed to update order item");
+
+    let item1 = order.items.iter().find(|i| i.id == item1_id).unwrap();
+    assert_eq!(item1.line_items.len(), 1);
+    assert_eq!(order.total_price.amount, 1300);
+
+    // Currency mismatch is rejected
+    let result = service
+        .update_order_item(UpdateOrderItemRequest {
+            user_id: creator.id,
+            order_id: order.id,
+            order_item_id: item1_id,
+            quantity: None,
+            unit_price: Some(Price {
+                currency: Currency::USD,
+                amount: 10,
+            }),
+        })
+        .await;
+    assert!(matches!(
+        result,
+        Err(UpdateOrderItemError::CurrencyMismatch { .. })
+    ));
+
+    // Remove the second item
+    let order = service
+        .remove_order_item(RemoveOrderItemRequest {
+            user_id: creator.id,
+            order_id: order.id,
+            order_item_id: item2_id,
+        })
+        .await
+        .expect("Failed to remove order item");
+
+    assert_eq!(order.items.len(), 1);
+    assert_eq!(order.total_price.amount, 800);
+
+    // Update receiver and shipping address
+    let order = service
+        .update_order(UpdateOrderRequest {
+            user_id: creator.id,
+            order_id: order.id,
+            receiver_id: Some(receiver.id),
+            shipping_address: Some(Address {
+                line1: "123 Main St".to_string(),
+                line2: None,
+                city: "Tokyo".to_string(),
+                state_or_province: "Tokyo".to_string(),
+                postal_code: "100-0001".to_string(),
+                country: "JP".to_string(),
+            }),
+        })
+        .await
+        .expect("Failed to update order");
+
+    assert_eq!(order.receiver_id, receiver.id);
+    assert!(order.shipping_address.is_some());
+
+    // Changes are persisted
+    let PurchaseOrderView::Full(saved) = service
+        .get_order(GetOrderRequest {
+            user_id: creator.id,
+            order_id: order.id,
+        })
+        .await
+        .unwrap()
+    else {
+        panic!("Creator should see the full order");
+    };
+    assert_eq!(saved.items.len(), 1);
+    assert_eq!(saved.total_price.amount, 800);
+    assert_eq!(saved.receiver_id, receiver.id);
+}
+
+#[tokio::test]
+async fn test_edit_order_restrictions() {
+    let service = create_service();
+
+    // Setup: Users, Product, Variant
+    let creator = create_user("creator");
+    let creator = service.user.create(creator).await.unwrap();
+    let receiver = create_user("receiver");
+    let receiver = service.user.create(receiver).await.unwrap();
+
+    let product = service
+        .create_product(CreateProductRequest {
+            name: NonEmptyString::new("P1".to_string()).unwrap(),
+            description: "".to_string(),
+            medias: vec![],
+        })
+        .await
+        .unwrap();
+
+    let variant = service
+        .create_product_variant(CreateProductVariantRequest {
+            product_id: product.id,
+            name: NonEmptyString::new("V1".to_string()).unwrap(),
+            description: "".to_string(),
+            price: None,
+            sort_order: 0,
+            medias: vec![],
+            tags: vec![],
+            mystery_box: None,
+        })
+        .await
+        .unwrap();
+
+    let order = service
+        .create_order(CreateOrderRequest {
+            user_id: creator.id,
+            receiver_id: Some(receiver.id),
+            shipping_address: None,
+            total_price: None,
+            items: vec![CreateOrderItemRequest {
+                variant_id: variant.id,
+                owner_id: None,
+                quantity: NonZeroU32::new(1).unwrap(),
+                unit_price: None,
+            }],
+        })
+        .await
+        .expect("Failed to create order");
+    let item_id = order.items[0].id;
+
+    // Receiver is not allowed to edit
+    let result = service
+        .remove_order_item(RemoveOrderItemRequest {
+            user_id: receiver.id,
+            order_id: order.id,
+            order_item_id: item_id,
+        })
+        .await;
+    assert!(matches!(
+        result,
+        Err(RemoveOrderItemError::PermissionDenied { .. })
+    ));
+
+    // Unknown receiver is rejected
+    let result = service
+        .update_order(UpdateOrderRequest {
+            user_id: creator.id,
+            order_id: order.id,
+            receiver_id: Some(sawa_core::models::user::UserId::new()),
+            shipping_address: None,
+        })
+        .await;
+    assert!(matches!(result, Err(UpdateOrderError::UserNotFound { .. })));
+
+    // Fulfilled orders are no longer editable
+    service
+        .fulfill_order(&FulfillOrderRequest {
+            user_id: creator.id,
+            order_id: order.id,
+        })
+        .await
+        .expect("Failed to fulfill order");
+
+    let result = service
+        .update_order_item(UpdateOrderItemRequest {
+            user_id: creator.id,
+            order_id: order.id,
+            order_item_id: item_id,
+            quantity: Some(NonZeroU32::new(2).unwrap()),
+            unit_price: None,
+        })
+        .await;
+    assert!(matches!(
+        result,
+        Err(UpdateOrderItemError::OrderNotEditable)
+    ));
+}
+
+#[tokio::test]
+async fn test_order_adjustments_and_landed_cost() {
+    let service = create_service();
+
+    // Setup: User, Product, Variants
+    let user = create_user("test_user");
+    let user = service.user.create(user).await.unwrap();
+
+    let product = service
+        .create_product(CreateProductRequest {
+            name: NonEmptyString::new("P1".to_string()).unwrap(),
+            description: "".to_string(),
+            medias: vec![],
+        })
+        .await
+        .unwrap();
+
+    let variant_a = service
+        .create_product_variant(CreateProductVariantRequest {
+            product_id: product.id,
+            name: NonEmptyString::new("A".to_string()).unwrap(),
+            description: "".to_string(),
+            price: None,
+            sort_order: 0,
+            medias: vec![],
+            tags: vec![],
+            mystery_box: None,
+        })
+        .await
+        .unwrap();
+
+    let variant_b = service
+        .create_product_variant(CreateProductVariantRequest {
+            product_id: product.id,
+            name: NonEmptyString::new("B".to_string()).unwrap(),
+            description: "".to_string(),
+            price: None,
+            sort_order: 1,
+            medias: vec![],
+            tags: vec![],
+            mystery_box: None,
+        })
+        .await
+        .unwrap();
+
+    // Create order: 2 x A @ 1000 + 1 x B @ 2000
+    let order = service
+        .create_order(CreateOrderRequest {
+            user_id: user.id,
+            receiver_id: None,
+            shipping_address: None,
+            total_price: None,
+            items: vec![
+                CreateOrderItemRequest {
+                    variant_id: variant_a.id,
+                    owner_id: None,
+                    quantity: NonZeroU32::new(2).unwrap(),
+                    unit_price: Some(Price {
+                        currency: Currency::JPY,
+                        amount: 1000,
+                    }),
+                },
+                CreateOrderItemRequest {
+                    variant_id: variant_b.id,
+                    owner_id: None,
+                    quantity: NonZeroU32::new(1).unwrap(),
+                    unit_price: Some(Price {
+                        currency: Currency::JPY,
+                        amount: 2000,
+                    }),
+                },
+            ],
+        })
+        .await
+        .expect("Failed to create order");
+    assert_eq!(order.total_price.amount, 4000);
+    let line_item_b = order.items[1].line_items[0].id;
+
+    // Shipping split evenly, tax split by price, discount applied to B only
+    service
+        .add_order_adjustment(AddOrderAdjustmentRequest {
+            user_id: user.id,
+            order_id: order.id,
+            kind: PurchaseOrderAdjustmentKind::InternationalShipping,
+            amount: Price {
+                currency: Currency::JPY,
+                amount: 600,
+            },
+            allocation: AdjustmentAllocation::ByCount,
+            note: None,
+        })
+        .await
+        .expect("Failed to add shipping");
+
+    service
+        .add_order_adjustment(AddOrderAdjustmentRequest {
+            user_id: user.id,
+            order_id: order.id,
+            kind: PurchaseOrderAdjustmentKind::Tax,
+            amount: Price {
+                currency: Currency::JPY,
+                amount: 400,
+            },
+            allocation: AdjustmentAllocation::ByPrice,
+            note: None,
+        })
+        .await
+        .expect("Failed to add tax");
+
+    // Manual allocation must add up to the amount
+    let result = service
+        .add_order_adjustment(AddOrderAdjustmentRequest {
+            user_id: user.id,
+            order_id: order.id,
+            kind: PurchaseOrderAdjustmentKind::Discount,
+            amount: Price {
+                currency: Currency::JPY,
+                amount: 300,
+            },
+            allocation: AdjustmentAllocation::Manual {
+                allocations: vec![ManualAllocation {
+                    line_item_id: line_item_b,
+                    amount: 200,
+                }],
+            },
+            note: None,
+        })
+        .await;
+    assert!(matches!(
+        result,
+        Err(AddOrderAdjustmentError::AllocationMismatch { .. })
+    ));
+
+    let discount_id = service
+        .add_order_adjustment(AddOrderAdjustmentRequest {
+            user_id: user.id,
+            order_id: order.id,
+            kind: PurchaseOrderAdjustmentKind::Discount,
+            amount: Price {
+                currency: Currency::JPY,
+                amount: 300,
+            },
+            allocation: AdjustmentAllocation::Manual {
+                allocations: vec![ManualAllocation {
+                    line_item_id: line_item_b,
+                    amount: 300,
+                }],
+            },
+            note: Some("COUPON".to_string()),
+        })
+        .await
+        .expect("Failed to add discount");
+
+    let PurchaseOrderView::Full(order) = service
+        .get_order(GetOrderRequest {
+            user_id: user.id,
+            order_id: order.id,
+        })
+        .await
+        .unwrap()
+    else {
+        panic!("Creator should see the full order");
+    };
+    assert_eq!(order.adjustments.len(), 3);
+    assert_eq!(order.total_price.amount, 4700);
+
+    // Removing an adjustment reverts its effect on the total
+    let order = service
+        .remove_order_adjustment(RemoveOrderAdjustmentRequest {
+            user_id: user.id,
+            order_id: order.id,
+            adjustment_id: discount_id,
+        })
+        .await
+        .expect("Failed to remove discount");
+    assert_eq!(order.adjustments.len(), 2);
+    assert_eq!(order.total_price.amount, 5000);
+
+    // Adjustments that would take the total below zero or past the maximum are rejected
+    let result = service
+        .add_order_adjustment(AddOrderAdjustmentRequest {
+            user_id: user.id,
+            order_id: order.id,
+            kind: PurchaseOrderAdjustmentKind::Discount,
+            amount: Price {
+                currency: Currency::JPY,
+                amount: 5001,
+            },
+            allocation: AdjustmentAllocation::ByPrice,
+            note: None,
+        })
+        .await;
+    assert!(matches!(result, Err(AddOrderAdjustmentError::ExceedsTotal)));
+
+    let result = service
+        .add_order_adjustment(AddOrderAdjustmentRequest {
+            user_id: user.id,
+            order_id: order.id,
+            kind: PurchaseOrderAdjustmentKind::Tax,
+            amount: Price {
+                currency: Currency::JPY,
+                amount: u32::MAX,
+            },
+            allocation: AdjustmentAllocation::ByPrice,
+            note: None,
+        })
+        .await;
+    assert!(matches!(
+        result,
+        Err(AddOrderAdjustmentError::PriceOverflow)
+    ));
+
+    // Fulfill and check landed costs on instances
+    service
+        .fulfill_order(&FulfillOrderRequest {
+            user_id: user.id,
+            order_id: order.id,
+        })
+        .await
+        .expect("Failed to fulfill order");
+
+    let instances = service
+        .list_product_instances(ListProductInstancesRequest {
+            user_id: user.id,
+            query_by: ListProductInstancesQueryBy::Owned,
+            variant_id: None,
+            status: None,
+            location_id: None,
+            condition: None,
+        })
+        .await
+        .unwrap();
+    assert_eq!(instances.len(), 3);
+
+    for instance in &instances {
+        let cost = instance.acquisition_cost.expect("Missing acquisition cost");
+        if instance.variant_id == variant_a.id {
+            // 1000 + 200 shipping + 100 tax
+            assert_eq!(cost.amount, 1300);
+        } else {
+            // 2000 + 200 shipping + 200 tax
+            assert_eq!(cost.amount, 2400);
+        }
+    }
+    let total: u32 = instances
+        .iter()
+        .map(|i| i.acquisition_cost.unwrap().amount)
+        .sum();
+    assert_eq!(total, order.total_price.amount);
+}
+
+#[tokio::test]
+async fn test_partial_fulfillment_flow() {
+    let service = create_service();
+
+    // Setup: User, Product, Variants
+    let user = create_user("test_user");
+    let user = service.user.create(user).await.unwrap();
+
+    let product = service
+        .create_product(CreateProductRequest {
+            name: NonEmptyString::new("P1".to_string()).unwrap(),
+            description: "".to_string(),
+            medias: vec![],
+        })
+        .await
+        .unwrap();
+
+    let regular = service
+        .create_product_variant(CreateProductVariantRequest {
+            product_id: product.id,
+            name: NonEmptyString::new("Regular".to_string()).unwrap(),
+            description: "".to_string(),
+            price: None,
+            sort_order: 0,
+            medias: vec![],
+            tags: vec![],
+            mystery_box: None,
+        })
+        .await
+        .unwrap();
+
+    let mystery_box = service
+        .create_product_variant(CreateProductVariantRequest {
+            product_id: product.id,
+            name: NonEmptyString::new("Mystery Box".to_string()).unwrap(),
+            description: "".to_string(),
+            price: None,
+            sort_order: 1,
+            medias: vec![],
+            tags: vec![],
+            mystery_box: Some(sawa_core::models::product::MysteryBoxConfig {
+                items_count: NonZeroU32::new(1).unwrap(),
+                possible_variants: vec![regular.id],
+                validation: MysteryBoxValidation::Lenient,
+            }),
+        })
+        .await
+        .unwrap();
+
+    // Create order: 2 x regular + 1 x mystery box (results unknown yet)
+    let order = service
+        .create_order(CreateOrderRequest {
+            user_id: user.id,
+            receiver_id: None,
+            shipping_address: None,
+            total_price: None,
+            items: vec![
+                CreateOrderItemRequest {
+                    variant_id: regular.id,
+                    owner_id: None,
+                    quantity: NonZeroU32::new(2).unwrap(),
+                    unit_price: None,
+                },
+                CreateOrderItemRequest {
+                    variant_id: mystery_box.id,
+                    owner_id: None,
+                    quantity: NonZeroU32::new(1).unwrap(),
+                    unit_price: None,
+                },
+            ],
+        })
+        .await
+        .expect("Failed to create order");
+    let regular_item = order.items[0].clone();
+    let box_item_id = order.items[1].id;
+
+    // Fulfilling the whole order fails while the mystery box awaits input
+    let result = service
+        .fulfill_order(&FulfillOrderRequest {
+            user_id: user.id,
+            order_id: order.id,
+        })
+        .await;
+    assert!(matches!(result, Err(FulfillOrderError::ItemNotPending)));
+
+    // First shipment: one of the regular line items
+    let order = service
+        .fulfill_order_items(&FulfillOrderItemsRequest {
+            user_id: user.id,
+            order_id: order.id,
+            item_ids: vec![],
+            line_item_ids: vec![regular_item.line_items[0].id],
+        })
+        .await
+        .expect("Failed to fulfill line item");
+
+    assert_eq!(order.status, PurchaseOrderStatus::PartiallyFulfilled);
+    assert_eq!(order.items[0].status, PurchaseOrderItemStatus::Pending);
+    assert!(order.items[0].line_items[0].is_fulfilled());
+    assert!(!order.items[0].line_items[1].is_fulfilled());
+
+    // Second shipment: the rest of the regular item
+    let order = service
+        .fulfill_order_items(&FulfillOrderItemsRequest {
+            user_id: user.id,
+            order_id: order.id,
+            item_ids: vec![regular_item.id],
+            line_item_ids: vec![],
+        })
+        .await
+        .expect("Failed to fulfill item");
+
+    assert_eq!(order.status, PurchaseOrderStatus::PartiallyFulfilled);
+    assert_eq!(order.items[0].status, PurchaseOrderItemStatus::Fulfilled);
+
+    // The mystery box can't be fulfilled before its results are known
+    let result = service
+        .fulfill_order_items(&FulfillOrderItemsRequest {
+            user_id: user.id,
+            order_id: order.id,
+            item_ids: vec![box_item_id],
+            line_item_ids: vec![],
+        })
+        .await;
+    assert!(matches!(
+        result,
+        Err(FulfillOrderItemsError::ItemNotPending)
+    ));
+
+    // Submit mystery box results, then fulfill the rest
+    service
+        .submit_mystery_box_results(SubmitMysteryBoxResultsRequest {
+            user_id: user.id,
+            order_id: order.id,
+            order_item_id: box_item_id,
+            owner_id: user.id,
+            received_variants: vec![regular.id],
+        })
+        .await
+        .expect("Failed to submit mystery box results");
+
+    let order = service
+        .fulfill_order(&FulfillOrderRequest {
+            user_id: user.id,
+            order_id: order.id,
+        })
+        .await
+        .expect("Failed to fulfill order");
+
+    assert_eq!(order.status, PurchaseOrderStatus::Fulfilled);
+    assert!(order.completed_at.is_some());
+    assert!(
+        order
+            .items
+            .iter()
+            .all(|item| item.status == PurchaseOrderItemStatus::Fulfilled)
+    );
+
+    // Each line item was fulfilled exactly once
+    let instances = service
+        .list_product_instances(ListProductInstancesRequest {
+            user_id: user.id,
+            query_by: ListProductInstancesQueryBy::Owned,
+            variant_id: None,
+            status: None,
+            location_id: None,
+            condition: None,
+        })
+        .await
+        .unwrap();
+    assert_eq!(instances.len(), 3);
+
+    // A received mystery box cannot be resubmitted
+    let result = service
+        .submit_mystery_box_results(SubmitMysteryBoxResultsRequest {
+            user_id: user.id,
+            order_id: order.id,
+            order_item_id: box_item_id,
+            owner_id: user.id,
+            received_variants: vec![regular.id],
+        })
+        .await;
+    assert!(matches!(
+        result,
+        Err(SubmitMysteryBoxResultsError::OrderNotEditable)
+    ));
+    let order = service
+        .order
+        .find_by_id(&order.id, &user.id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(
+        order
+            .items
+            .iter()
+            .flat_map(|item| &item.line_items)
+            .all(|line_item| line_item.is_fulfilled())
+    );
+}
+
+#[tokio::test]
+async fn test_cancel_partially_fulfilled_order() {
+    let service = create_service();
+
+    // Setup: User, Product, Variants
+    let user = create_user("test_user");
+    let user = service.user.create(user).await.unwrap();
+
+    let product = service
+        .create_product(CreateProductRequest {
+            name: NonEmptyString::new("P1".to_string()).unwrap(),
+            description: "".to_string(),
+            medias: vec![],
+        })
+        .await
+        .unwrap();
+
+    let variant1 = service
+        .create_product_variant(CreateProductVariantRequest {
+            product_id: product.id,
+            name: NonEmptyString::new("V1".to_string()).unwrap(),
+            description: "".to_string(),
+            price: None,
+            sort_order: 0,
+            medias: vec![],
+            tags: vec![],
+            mystery_box: None,
+        })
+        .await
+        .unwrap();
+
+    let variant2 = service
+        .create_product_variant(CreateProductVariantRequest {
+            product_id: product.id,
+            name: NonEmptyString::new("V2".to_string()).unwrap(),
+            description: "".to_string(),
+            price: None,
+            sort_order: 1,
+            medias: vec![],
+            tags: vec![],
+            mystery_box: None,
+        })
+        .await
+        .unwrap();
+
+    let order = service
+        .create_order(CreateOrderRequest {
+            user_id: user.id,
+            receiver_id: None,
+            shipping_address: None,
+            total_price: None,
+            items: vec![
+                CreateOrderItemRequest {
+                    variant_id: variant1.id,
+                    owner_id: None,
+                    quantity: NonZeroU32::new(2).unwrap(),
+                    unit_price: None,
+                },
+                CreateOrderItemRequest {
+                    variant_id: variant2.id,
+                    owner_id: None,
+                    quantity: NonZeroU32::new(1).unwrap(),
+                    unit_price: None,
+                },
+            ],
+        })
+        .await
+        .expect("Failed to create order");
+    let fulfilled_line_item_id = order.items[0].line_items[0].id;
+
+    // Only one line item arrives
+    service
+        .fulfill_order_items(&FulfillOrderItemsRequest {
+            user_id: user.id,
+            order_id: order.id,
+            item_ids: vec![],
+            line_item_ids: vec![fulfilled_line_item_id],
+        })
+        .await
+        .expect("Failed to fulfill line item");
+
+    // Cancel the rest
+    let order = service
+        .cancel_order(&CancelOrderRequest {
+            user_id: user.id,
+            order_id: order.id,
+            reason: Some("Out of stock".to_string()),
+        })
+        .await
+        .expect("Failed to cancel remaining items");
+
+    // The order completes with what has been fulfilled
+    assert_eq!(order.status, PurchaseOrderStatus::Fulfilled);
+    assert_eq!(order.items[0].status, PurchaseOrderItemStatus::Fulfilled);
+    assert_eq!(order.items[0].line_items.len(), 1);
+    assert_eq!(order.items[0].line_items[0].id, fulfilled_line_item_id);
+    assert_eq!(order.items[1].status, PurchaseOrderItemStatus::Cancelled);
+
+    let instances = service
+        .list_product_instances(ListProductInstancesRequest {
+            user_id: user.id,
+            query_by: ListProductInstancesQueryBy::Owned,
+            variant_id: None,
+            status: None,
+            location_id: None,
+            condition: None,
+        })
+        .await
+        .unwrap();
+    assert_eq!(instances.len(), 1);
+}
+
+#[tokio::test]
+async fn test_group_buy_delivery_on_fulfillment() {
+    let service = create_service();
+
+    // Setup: Alice buys on behalf of Bob
+    let alice = create_user("alice");
+    let bob = create_user("bob");
+    let alice = service.user.create(alice).await.unwrap();
+    let bob = service.user.create(bob).await.unwrap();
+
+    // Setup: Product and Variant
+    let product = service
+        .create_product(CreateProductRequest {
+            name: NonEmptyString::new("P1".to_string()).unwrap(),
+            description: "".to_string(),
+            medias: vec![],
+        })
+        .await
+        .unwrap();
+
+    let variant = service
+        .create_product_variant(CreateProductVariantRequest {
+            product_id: product.id,
+            name: NonEmptyString::new("V1".to_string()).unwrap(),
+            description: "".to_string(),
+            price: None,
+            sort_order: 0,
+            medias: vec![],
+            tags: vec![],
+            mystery_box: None,
+        })
+        .await
+        .unwrap();
+
+    let order = service
+        .create_order(CreateOrderRequest {
+            user_id: alice.id,
+            receiver_id: None,
+            shipping_address: None,
+            total_price: None,
+            items: vec![],
+        })
+        .await
+        .unwrap();
+
+    // One item for Alice herself, two for Bob
+    service
+        .add_order_item(AddOrderItemRequest {
+            user_id: alice.id,
+            order_id: order.id,
+            variant_id: variant.id,
+            owner_id: alice.id,
+            quantity: NonZeroU32::new(1).unwrap(),
+            unit_price: None,
+        })
+        .await
+        .unwrap();
+    service
+        .add_order_item(AddOrderItemRequest {
+            user_id: alice.id,
+            order_id: order.id,
+            variant_id: variant.id,
+            owner_id: bob.id,
+            quantity: NonZeroU32::new(2).unwrap(),
+            unit_price: None,
+        })
+        .await
+        .unwrap();
+
+    // 1. Fulfill the order
+    service
+        .fulfill_order(&FulfillOrderRequest {
+            user_id: alice.id,
+            order_id: order.id,
+        })
+        .await
+        .expect("Failed to fulfill order");
+
+    // 2. A single pending delivery transaction from Alice to Bob is created
+    let deliveries = service
+        .transaction
+        .find_by_to_user(&bob.id, None)
+        .await
+        .unwrap();
+    assert_eq!(deliveries.len(), 1);
+    let delivery = &deliveries[0];
+    assert_eq!(delivery.from_user_id, alice.id);
+    assert_eq!(delivery.reason, TransferReason::Delivery);
+    assert_eq!(delivery.status, UserTransactionStatus::Pending);
+    assert_eq!(delivery.items.len(), 2);
+
+    // Bob's items are held by Alice and locked until delivered
+    for item_id in &delivery.items {
+        let instance = service
+            .get_product_instance(GetProductInstanceRequest {
+                id: *item_id,
+                user_id: bob.id,
+            })
+            .await
+            .unwrap();
+        assert_eq!(instance.owner_id, bob.id);
+        assert_eq!(instance.holder_id, alice.id);
+        assert_eq!(instance.status, ProductInstanceStatus::Locked);
+    }
+
+    // Alice's own item needs no delivery
+    let alice_instances = service
+        .list_product_instances(ListProductInstancesRequest {
+            user_id: alice.id,
+            query_by: ListProductInstancesQueryBy::Owned,
+            variant_id: None,
+            status: None,
+            location_id: None,
+            condition: None,
+        })
+        .await
+        .unwrap();
+    assert_eq!(alice_instances.len(), 1);
+    assert_eq!(alice_instances[0].status, ProductInstanceStatus::Active);
+    assert!(
+        service
+            .transaction
+            .find_by_to_user(&alice.id, None)
+            .await
+            .unwrap()
+            .is_empty()
+    );
+
+    // 3. Bob confirms the hand-off
+    service
+        .complete_transaction(CompleteTransactionRequest {
+            transaction_id: delivery.id,
+            user_id: bob.id,
+        })
+        .await
+        .expect("Failed to complete delivery");
+
+    for item_id in &delivery.items {
+        let instance = service
+            .get_product_instance(GetProductInstanceRequest {
+                id: *item_id,
+                user_id: bob.id,
+            })
+            .await
+            .unwrap();
+        assert_eq!(instance.owner_id, bob.id);
+        assert_eq!(instance.holder_id, bob.id);
+        assert_eq!(instance.status, ProductInstanceStatus::Active);
+
+        let last_transfer = instance.transfer_history.last().unwrap();
+        assert_eq!(last_transfer.reason, TransferReason::Delivery);
+        assert_eq!(last_transfer.from_holder_id, Some(alice.id));
+        assert_eq!(last_transfer.to_holder_id, bob.id);
+    }
+}
+
+#[tokio::test]
+async fn test_return_and_replace_fulfilled_items() {
+    let service = create_service();
+
+    // Setup: User, Product, Variant
+    let user = create_user("test_user");
+    let user = service.user.create(user).await.unwrap();
+
+    let product = service
+        .create_product(CreateProductRequest {
+            name: NonEmptyString::new("P1".to_string()).unwrap(),
+            description: "".to_string(),
+            medias: vec![],
+        })
+        .await
+        .unwrap();
+
+    let variant = service
+        .create_product_variant(CreateProductVariantRequest {
+            product_id: product.id,
+            name: NonEmptyString::new("V1".to_string()).unwrap(),
+            description: "".to_string(),
+            price: None,
+            sort_order: 0,
+            medias: vec![],
+            tags: vec![],
+            mystery_box: None,
+        })
+        .await
+        .unwrap();
+
+    // Create and fulfill order: 2 x V1 @ 1000
+    let order = service
+        .create_order(CreateOrderRequest {
+            user_id: user.id,
+            receiver_id: None,
+            shipping_address: None,
+            total_price: None,
+            items: vec![CreateOrderItemRequest {
+                variant_id: variant.id,
+                owner_id: None,
+                quantity: NonZeroU32::new(2).unwrap(),
+                unit_price: Some(Price {
+                    currency: Currency::JPY,
+                    amount: 1000,
+                }),
+            }],
+        })
+        .await
+        .unwrap();
+
+    let order = service
+        .fulfill_order(&FulfillOrderRequest {
+            user_id: user.id,
+            order_id: order.id,
+        })
+        .await
+        .unwrap();
+    let defective = order.items[0].line_items[0].clone();
+    let kept = order.items[0].line_items[1].clone();
+
+    // Fulfilled orders can't be cancelled
+    let result = service
+        .cancel_order(&CancelOrderRequest {
+            user_id: user.id,
+            order_id: order.id,
+            reason: None,
+        })
+        .await;
+    assert!(matches!(
+        result,
+        Err(CancelOrderError::OrderAlreadyCompleted)
+    ));
+
+    // 1. Return the defective item and request a replacement
+    let order = service
+        .return_order_items(&ReturnOrderItemsRequest {
+            user_id: user.id,
+            order_id: order.id,
+            line_item_ids: vec![defective.id],
+            refund: None,
+            reason: Some("Defective".to_string()),
+            replace: true,
+        })
+        .await
+        .expect("Failed to return items");
+
+    assert_eq!(order.status, PurchaseOrderStatus::PartiallyFulfilled);
+    assert!(order.completed_at.is_none());
+    assert_eq!(order.returns.len(), 1);
+    assert_eq!(order.items[0].status, PurchaseOrderItemStatus::Pending);
+    assert_eq!(order.items[0].line_items.len(), 3);
+    assert!(order.items[0].line_items[0].is_returned());
+
+    let replacement = &order.items[0].line_items[2];
+    assert_eq!(replacement.replaces, Some(defective.id));
+    assert!(!replacement.is_fulfilled());
+
+    // The returned instance reaches its terminal status
+    let returned_instance = service
+        .get_product_instance(GetProductInstanceRequest {
+            id: defective.instance_id.unwrap(),
+            user_id: user.id,
+        })
+        .await
+        .unwrap();
+    assert_eq!(returned_instance.status, ProductInstanceStatus::Returned);
+    let last_status = returned_instance.status_history.last().unwrap();
+    assert_eq!(last_status.status, ProductInstanceStatus::Returned);
+    assert_eq!(last_status.reason.as_deref(), Some("Defective"));
+
+    // The replacement takes over the cost of the returned line item
+    let landed_costs = order.landed_costs();
+    assert_eq!(landed_costs.len(), 2);
+    assert_eq!(landed_costs[&replacement.id].amount, 1000);
+    assert!(!landed_costs.contains_key(&defective.id));
+
+    // 2. Returning the same line item again fails
+    let result = service
+        .return_order_items(&ReturnOrderItemsRequest {
+            user_id: user.id,
+            order_id: order.id,
+            line_item_ids: vec![defective.id],
+            refund: None,
+            reason: None,
+            replace: false,
+        })
+        .await;
+    assert!(matches!(
+        result,
+        Err(ReturnOrderItemsError::LineItemAlreadyReturned { .. })
+    ));
+
+    // 3. Fulfill the replacement
+    let order = service
+        .fulfill_order(&FulfillOrderRequest {
+            user_id: user.id,
+            order_id: order.id,
+        })
+        .await
+        .expect("Failed to fulfill replacement");
+    assert_eq!(order.status, PurchaseOrderStatus::Fulfilled);
+    assert_eq!(order.items[0].status, PurchaseOrderItemStatus::Fulfilled);
+
+    let replacement_instance = service
+        .get_product_instance(GetProductInstanceRequest {
+            id: order.items[0].line_items[2].instance_id.unwrap(),
+            user_id: user.id,
+        })
+        .await
+        .unwrap();
+    assert_eq!(replacement_instance.status, ProductInstanceStatus::Active);
+    assert_eq!(
+        replacement_instance.acquisition_cost.map(|p| p.amount),
+        Some(1000)
+    );
+
+    // 4. Return the other item for a refund only
+    let order = service
+        .return_order_items(&ReturnOrderItemsRequest {
+            user_id: user.id,
+            order_id: order.id,
+            line_item_ids: vec![kept.id],
+            refund: Some(Price {
+                currency: Currency::JPY,
+                amount: 1000,
+            }),
+            reason: None,
+            replace: false,
+        })
+        .await
+        .expect("Failed to return items");
+    assert_eq!(order.status, PurchaseOrderStatus::Fulfilled);
+    assert_eq!(order.returns.len(), 2);
+    assert_eq!(order.returns[1].refund.map(|p| p.amount), Some(1000));
+    assert_eq!(order.items[0].line_items.len(), 3);
+}
+
+#[tokio::test]
+async fn test_order_event_log() {
+    let service = create_service();
+
+    // Setup: User, Product, Variant
+    let user = create_user("test_user");
+    let user = service.user.create(user).await.unwrap();
+
+    let product = service
+        .create_product(CreateProductRequest {
+            name: NonEmptyString::new("P1".to_string()).unwrap(),
+            description: "".to_string(),
+            medias: vec![],
+        })
+        .await
+        .unwrap();
+
+    let variant = service
+        .create_product_variant(CreateProductVariantRequest {
+            product_id: product.id,
+            name: NonEmptyString::new("V1".to_string()).unwrap(),
+            description: "".to_string(),
+            price: None,
+            sort_order: 0,
+            medias: vec![],
+            tags: vec![],
+            mystery_box: None,
+        })
+        .await
+        .unwrap();
+
+    // 1. Create order with one item
+    let order = service
+        .create_order(CreateOrderRequest {
+            user_id: user.id,
+            receiver_id: None,
+            shipping_address: None,
+            total_price: None,
+            items: vec![CreateOrderItemRequest {
+                variant_id: variant.id,
+                owner_id: None,
+                quantity: NonZeroU32::new(1).unwrap(),
+                unit_price: None,
+            }],
+        })
+        .await
+        .unwrap();
+    let item_id = order.items[0].id;
+
+    // 2. Edit the item
+    service
+        .update_order_item(UpdateOrderItemRequest {
+            user_id: user.id,
+            order_id: order.id,
+            order_item_id: item_id,
+            quantity: Some(NonZeroU32::new(2).unwrap()),
+            unit_price: None,
+        })
+        .await
+        .unwrap();
+
+    // 3. Cancel with a reason
+    service
+        .cancel_order(&CancelOrderRequest {
+            user_id: user.id,
+            order_id: order.id,
+            reason: Some("Sold out".to_string()),
+        })
+        .await
+        .unwrap();
+
+    // 4. Check the event log
+    let events = service
+        .get_order_events(GetOrderEventsRequest {
+            user_id: user.id,
+            order_id: order.id,
+        })
+        .await
+        .expect("Failed to get events");
+
+    let kinds: Vec<_> = events.iter().map(|event| event.kind.clone()).collect();
+    assert_eq!(
+        kinds,
+        vec![
+            PurchaseOrderEventKind::Created,
+            PurchaseOrderEventKind::ItemAdded {
+                order_item_id: item_id,
+                variant_id: variant.id,
+                quantity: NonZeroU32::new(1).unwrap(),
+            },
+            PurchaseOrderEventKind::ItemUpdated {
+                order_item_id: item_id,
+            },
+            PurchaseOrderEventKind::Cancelled {
+                reason: Some("Sold out".to_string()),
+            },
+        ]
+    );
+    assert!(events.iter().all(|event| event.actor_id == user.id));
+    assert!(
+        events
+            .windows(2)
+            .all(|pair| pair[0].created_at <= pair[1].created_at)
+    );
+
+    // Other users can't see the events
+    let other = create_user("other_user");
+    let other = service.user.create(other).await.unwrap();
+    let result = service
+        .get_order_events(GetOrderEventsRequest {
+            user_id: other.id,
+            order_id: order.id,
+        })
+        .await;
+    assert!(matches!(result, Err(GetOrderEventsError::NotFound)));
+}
+
+#[tokio::test]
+async fn test_incremental_mystery_box_results() {
+    let service = create_service();
+
+    // Setup: Users, Product, Variants
+    let user = create_user("test_user");
+    let user = service.user.create(user).await.unwrap();
+    let friend = create_user("friend");
+    let friend = service.user.create(friend).await.unwrap();
+
+    let product = service
+        .create_product(CreateProductRequest {
+            name: NonEmptyString::new("P1".to_string()).unwrap(),
+            description: "".to_string(),
+            medias: vec![],
+        })
+        .await
+        .unwrap();
+
+    let character_a = service
+        .create_product_variant(CreateProductVariantRequest {
+            product_id: product.id,
+            name: NonEmptyString::new("Character A".to_string()).unwrap(),
+            description: "".to_string(),
+            price: None,
+            sort_order: 0,
+            medias: vec![],
+            tags: vec![],
+            mystery_box: None,
+        })
+        .await
+        .unwrap();
+
+    let character_b = service
+        .create_product_variant(CreateProductVariantRequest {
+            product_id: product.id,
+            name: NonEmptyString::new("Character B".to_string()).unwrap(),
+            description: "".to_string(),
+            price: None,
+            sort_order: 1,
+            medias: vec![],
+            tags: vec![],
+            mystery_box: None,
+        })
+        .await
+        .unwrap();
+
+    let blind_box = service
+        .create_product_variant(CreateProductVariantRequest {
+            product_id: product.id,
+            name: NonEmptyString::new("Blind Box".to_string()).unwrap(),
+            description: "".to_string(),
+            price: None,
+            sort_order: 2,
+            medias: vec![],
+            tags: vec![],
+            mystery_box: Some(sawa_core::models::product::MysteryBoxConfig {
+                items_count: NonZeroU32::new(1).unwrap(),
+                possible_variants: vec![character_a.id, character_b.id],
+                validation: MysteryBoxValidation::Lenient,
+            }),
+        })
+        .await
+        .unwrap();
+
+    // Create order with 3 blind boxes
+    let order = service
+        .create_order(CreateOrderRequest {
+            user_id: user.id,
+            receiver_id: None,
+            shipping_address: None,
+            total_price: None,
+            items: vec![CreateOrderItemRequest {
+                variant_id: blind_box.id,
+                owner_id: None,
+                quantity: NonZeroU32::new(3).unwrap(),
+                unit_price: None,
+            }],
+        })
+        .await
+        .unwrap();
+    let item_id = order.items[0].id;
+
+    // 1. Open the first two boxes, one of them for a friend
+    let order = service
+        .add_mystery_box_results(AddMysteryBoxResultsRequest {
+            user_id: user.id,
+            order_id: order.id,
+            order_item_id: item_id,
+            results: vec![
+                MysteryBoxResult {
+                    variant_id: character_a.id,
+                    owner_id: None,
+                },
+                MysteryBoxResult {
+                    variant_id: character_a.id,
+                    owner_id: Some(friend.id),
+                },
+            ],
+        })
+        .await
+        .expect("Failed to add results")
+        .order;
+
+    assert_eq!(order.items[0].line_items.len(), 2);
+    assert_eq!(
+        order.items[0].status,
+        PurchaseOrderItemStatus::AwaitingInput
+    );
+    assert_eq!(order.items[0].line_items[0].owner_id, user.id);
+    assert_eq!(order.items[0].line_items[1].owner_id, friend.id);
+
+    // 2. Fix a mistaken entry: the second box was actually Character B
+    let mistaken = order.items[0].line_items[1].id;
+    let order = service
+        .update_mystery_box_result(UpdateMysteryBoxResultRequest {
+            user_id: user.id,
+            order_id: order.id,
+            order_item_id: item_id,
+            line_item_id: mistaken,
+            variant_id: Some(character_b.id),
+            owner_id: None,
+        })
+        .await
+        .expect("Failed to update result")
+        .order;
+    assert_eq!(order.items[0].line_items[1].variant_id, character_b.id);
+    assert_eq!(order.items[0].line_items[1].owner_id, friend.id);
+
+    // Unknown owners are rejected
+    let unknown = sawa_core::models::user::UserId::new();
+    let result = service
+        .add_mystery_box_results(AddMysteryBoxResultsRequest {
+            user_id: user.id,
+            order_id: order.id,
+            order_item_id: item_id,
+            results: vec![MysteryBoxResult {
+                variant_id: character_a.id,
+                owner_id: Some(unknown),
+            }],
+        })
+        .await;
+    assert!(matches!(
+        result,
+        Err(AddMysteryBoxResultsError::UserNotFound { user_id }) if user_id == unknown
+    ));
+
+    let result = service
+        .update_mystery_box_result(UpdateMysteryBoxResultRequest {
+            user_id: user.id,
+            order_id: order.id,
+            order_item_id: item_id,
+            line_item_id: mistaken,
+            variant_id: None,
+            owner_id: Some(unknown),
+        })
+        .await;
+    assert!(matches!(
+        result,
+        Err(UpdateMysteryBoxResultError::UserNotFound { user_id }) if user_id == unknown
+    ));
+
+    // 3. Too many results are rejected
+    let result = service
+        .add_mystery_box_results(AddMysteryBoxResultsRequest {
+            user_id: user.id,
+            order_id: order.id,
+            order_item_id: item_id,
+            results: vec![
+                MysteryBoxResult {
+                    variant_id: character_a.id,
+                    owner_id: None,
+                },
+                MysteryBoxResult {
+                    variant_id: character_b.id,
+                    owner_id: None,
+                },
+            ],
+        })
+        .await;
+    assert!(matches!(
+        result,
+        Err(AddMysteryBoxResultsError::TooManyResults {
+            expected: 3,
+            actual: 4
+        })
+    ));
+
+    // 4. The last box completes the item
+    let order = service
+        .add_mystery_box_results(AddMysteryBoxResultsRequest {
+            user_id: user.id,
+            order_id: order.id,
+            order_item_id: item_id,
+            results: vec![MysteryBoxResult {
+                variant_id: character_b.id,
+                owner_id: None,
+            }],
+        })
+        .await
+        .expect("Failed to add results")
+        .order;
+    assert_eq!(order.items[0].line_items.len(), 3);
+    assert_eq!(order.items[0].status, PurchaseOrderItemStatus::Pending);
+
+    // 5. Removing a result makes the item wait for input again
+    let removed = order.items[0].line_items[2].id;
+    let order = service
+        .remove_mystery_box_result(RemoveMysteryBoxResultRequest {
+            user_id: user.id,
+            order_id: order.id,
+            order_item_id: item_id,
+            line_item_id: removed,
+        })
+        .await
+        .expect("Failed to remove result");
+    assert_eq!(order.items[0].line_items.len(), 2);
+    assert_eq!(
+        order.items[0].status,
+        PurchaseOrderItemStatus::AwaitingInput
+    );
+
+    // 6. Complete and fulfill: each box goes to its owner
+    service
+        .add_mystery_box_results(AddMysteryBoxResultsRequest {
+            user_id: user.id,
+            order_id: order.id,
+            order_item_id: item_id,
+            results: vec![MysteryBoxResult {
+                variant_id: character_a.id,
+                owner_id: None,
+            }],
+        })
+        .await
+        .unwrap();
+    let order = service
+        .fulfill_order(&FulfillOrderRequest {
+            user_id: user.id,
+            order_id: order.id,
+        })
+        .await
+        .expect("Failed to fulfill order");
+    assert_eq!(order.status, PurchaseOrderStatus::Fulfilled);
+
+    let friend_instances = service
+        .list_product_instances(ListProductInstancesRequest {
+            user_id: friend.id,
+            query_by: ListProductInstancesQueryBy::Owned,
+            variant_id: None,
+            status: None,
+            location_id: None,
+            condition: None,
+        })
+        .await
+        .unwrap();
+    assert_eq!(friend_instances.len(), 1);
+    assert_eq!(friend_instances[0].variant_id, character_b.id);
+
+    // Fulfilled results can no longer be changed
+    let result = service
+        .remove_mystery_box_result(RemoveMysteryBoxResultRequest {
+            user_id: user.id,
+            order_id: order.id,
+            order_item_id: item_id,
+            line_item_id: mistaken,
+        })
+        .await;
+    assert!(matches!(
+        result,
+        Err(RemoveMysteryBoxResultError::OrderNotEditable)
+    ));
+}
+
+#[tokio::test]
+async fn test_mystery_box_validation_policy() {
+    let service = create_service();
+
+    // Setup: User, Product, Variants
+    let user = create_user("test_user");
+    let user = service.user.create(user).await.unwrap();
+
+    let product = service
+        .create_product(CreateProductRequest {
+            name: NonEmptyString::new("P1".to_string()).unwrap(),
+            description: "".to_string(),
+            medias: vec![],
+        })
+        .await
+        .unwrap();
+
+    let expected = service
+        .create_product_variant(CreateProductVariantRequest {
+            product_id: product.id,
+            name: NonEmptyString::new("Expected".to_string()).unwrap(),
+            description: "".to_string(),
+            price: None,
+            sort_order: 0,
+            medias: vec![],
+            tags: vec![],
+            mystery_box: None,
+        })
+        .await
+        .unwrap();
+
+    let unrelated = service
+        .create_product_variant(CreateProductVariantRequest {
+            product_id: product.id,
+            name: NonEmptyString::new("Unrelated".to_string()).unwrap(),
+            description: "".to_string(),
+            price: None,
+            sort_order: 1,
+            medias: vec![],
+            tags: vec![],
+            mystery_box: None,
+        })
+        .await
+        .unwrap();
+
+    let mut boxes = vec![];
+    for validation in [MysteryBoxValidation::Warn, MysteryBoxValidation::Strict] {
+        let variant = service
+            .create_product_variant(CreateProductVariantRequest {
+                product_id: product.id,
+                name: NonEmptyString::new("Blind Box".to_string()).unwrap(),
+                description: "".to_string(),
+                price: None,
+                sort_order: 2,
+                medias: vec![],
+                tags: vec![],
+                mystery_box: Some(sawa_core::models::product::MysteryBoxConfig {
+                    items_count: NonZeroU32::new(1).unwrap(),
+                    possible_variants: vec![expected.id],
+                    validation,
+                }),
+            })
+            .await
+            .unwrap();
+        assert_eq!(variant.mystery_box.as_ref().unwrap().validation, validation);
+        boxes.push(variant);
+    }
+
+    // Create order with one box of each policy
+    let order = service
+        .create_order(CreateOrderRequest {
+            user_id: user.id,
+            receiver_id: None,
+            shipping_address: None,
+            total_price: None,
+            items: boxes
+                .iter()
+                .map(|variant| CreateOrderItemRequest {
+                    variant_id: variant.id,
+                    owner_id: None,
+                    quantity: NonZeroU32::new(1).unwrap(),
+                    unit_price: None,
+                })
+                .collect(),
+        })
+        .await
+        .unwrap();
+    let warn_item_id = order.items[0].id;
+    let strict_item_id = order.items[1].id;
+
+    // 1. Warn: the unexpected variant is accepted and reported
+    let response = service
+        .submit_mystery_box_results(SubmitMysteryBoxResultsRequest {
+            user_id: user.id,
+            order_id: order.id,
+            order_item_id: warn_item_id,
+            owner_id: user.id,
+            received_variants: vec![unrelated.id],
+        })
+        .await
+        .expect("Failed to submit mystery box results");
+    assert_eq!(
+        response.warnings,
+        vec![MysteryBoxWarning::UnexpectedVariant {
+            variant_id: unrelated.id
+        }]
+    );
+    assert_eq!(
+        response.order.items[0].status,
+        PurchaseOrderItemStatus::Pending
+    );
+
+    // 2. Strict: the unexpected variant is rejected
+    let result = service
+        .submit_mystery_box_results(SubmitMysteryBoxResultsRequest {
+            user_id: user.id,
+            order_id: order.id,
+            order_item_id: strict_item_id,
+            owner_id: user.id,
+            received_variants: vec![unrelated.id],
+        })
+        .await;
+    assert!(matches!(
+        result,
+        Err(SubmitMysteryBoxResultsError::UnexpectedVariant { variant_id }) if variant_id == unrelated.id
+    ));
+
+    let result = service
+        .add_mystery_box_results(AddMysteryBoxResultsRequest {
+            user_id: user.id,
+            order_id: order.id,
+            order_item_id: strict_item_id,
+            results: vec![MysteryBoxResult {
+                variant_id: unrelated.id,
+                owner_id: None,
+            }],
+        })
+        .await;
+    assert!(matches!(
+        result,
+        Err(AddMysteryBoxResultsError::UnexpectedVariant { .. })
+    ));
+
+    // 3. Strict: expected variants pass without warnings
+    let response = service
+        .add_mystery_box_results(AddMysteryBoxResultsRequest {
+            user_id: user.id,
+            order_id: order.id,
+            order_item_id: strict_item_id,
+            results: vec![MysteryBoxResult {
+                variant_id: expected.id,
+                owner_id: None,
+            }],
+        })
+        .await
+        .expect("Failed to add results");
+    assert!(response.warnings.is_empty());
+    assert_eq!(
+        response.order.items[1].status,
+        PurchaseOrderItemStatus::Pending
+    );
+
+    // Corrections are validated too
+    let result = service
+        .update_mystery_box_result(UpdateMysteryBoxResultRequest {
+            user_id: user.id,
+            order_id: order.id,
+            order_item_id: strict_item_id,
+            line_item_id: response.order.items[1].line_items[0].id,
+            variant_id: Some(unrelated.id),
+            owner_id: None,
+        })
+        .await;
+    assert!(matches!(
+        result,
+        Err(UpdateMysteryBoxResultError::UnexpectedVariant { .. })
+    ));
+}
+
+#[tokio::test]
+async fn test_order_schedule_and_arrivals() {
+    let service = create_service();
+
+    // Setup: Users, Product, Variant
+    let user = create_user("test_user");
+    let user = service.user.create(user).await.unwrap();
+    let other = create_user("other_user");
+    let other = service.user.create(other).await.unwrap();
+
+    let product = service
+        .create_product(CreateProductRequest {
+            name: NonEmptyString::new("P1".to_string()).unwrap(),
+            description: "".to_string(),
+            medias: vec![],
+        })
+        .await
+        .unwrap();
+
+    let variant = service
+        .create_product_variant(CreateProductVariantRequest {
+            product_id: product.id,
+            name: NonEmptyString::new("V1".to_string()).unwrap(),
+            description: "".to_string(),
+            price: None,
+            sort_order: 0,
+            medias: vec![],
+            tags: vec![],
+            mystery_box: None,
+        })
+        .await
+        .unwrap();
+
+    let today = NaiveDate::from_ymd_opt(2025, 6, 15).unwrap();
+    let mut order_ids = vec![];
+    for _ in 0..4 {
+        let order = service
+            .create_order(CreateOrderRequest {
+                user_id: user.id,
+                receiver_id: None,
+                shipping_address: None,
+                total_price: None,
+                items: vec![CreateOrderItemRequest {
+                    variant_id: variant.id,
+                    owner_id: None,
+                    quantity: NonZeroU32::new(1).unwrap(),
+                    unit_price: None,
+                }],
+            })
+            .await
+            .unwrap();
+        order_ids.push(order.id);
+    }
+
+    // 1. Schedule: one overdue, one arriving in 3 days, one in 30 days, one without a date
+    for (order_id, arrival) in [
+        (order_ids[0], NaiveDate::from_ymd_opt(2025, 6, 10)),
+        (order_ids[1], NaiveDate::from_ymd_opt(2025, 6, 18)),
+        (order_ids[2], NaiveDate::from_ymd_opt(2025, 7, 15)),
+    ] {
+        service
+            .update_order_schedule(UpdateOrderScheduleRequest {
+                user_id: user.id,
+                order_id,
+                schedule: PurchaseOrderSchedule {
+                    expected_release_date: None,
+                    expected_ship_date: None,
+                    expected_arrival_date: arrival,
+                    tracking: None,
+                },
+            })
+            .await
+            .unwrap();
+    }
+
+    // Tracking can be attached later, replacing the schedule as a whole
+    let order = service
+        .update_order_schedule(UpdateOrderScheduleRequest {
+            user_id: user.id,
+            order_id: order_ids[1],
+            schedule: PurchaseOrderSchedule {
+                expected_release_date: None,
+                expected_ship_date: NaiveDate::from_ymd_opt(2025, 6, 12),
+                expected_arrival_date: NaiveDate::from_ymd_opt(2025, 6, 17),
+                tracking: Some(ShipmentTracking {
+                    carrier: "Yamato".to_string(),
+                    tracking_number: "1234-5678-9012".to_string(),
+                }),
+            },
+        })
+        .await
+        .unwrap();
+    assert_eq!(
+        order.schedule.expected_arrival_date,
+        NaiveDate::from_ymd_opt(2025, 6, 17)
+    );
+    assert_eq!(
+        order.events.last().unwrap().kind,
+        PurchaseOrderEventKind::ScheduleUpdated
+    );
+
+    // Users outside the order cannot see it
+    let result = service
+        .update_order_schedule(UpdateOrderScheduleRequest {
+            user_id: other.id,
+            order_id: order_ids[0],
+            schedule: PurchaseOrderSchedule::default(),
+        })
+        .await;
+    assert!(matches!(
+        result,
+        Err(UpdateOrderScheduleError::OrderNotFound { .. })
+    ));
+
+    // 2. Arrivals within a week
+    let arrivals = service
+        .list_order_arrivals(ListOrderArrivalsRequest {
+            user_id: user.id,
+            today,
+            within_days: Some(7),
+        })
+        .await
+        .unwrap();
+    assert_eq!(
+        arrivals
+            .overdue
+            .iter()
+            .map(|order| order.id())
+            .collect::<Vec<_>>(),
+        vec![order_ids[0]]
+    );
+    assert_eq!(
+        arrivals
+            .arriving_soon
+            .iter()
+            .map(|order| order.id())
+            .collect::<Vec<_>>(),
+        vec![order_ids[1]]
+    );
+
+    // 3. Without a window, all upcoming arrivals are included, soonest first
+    let arrivals = service
+        .list_order_arrivals(ListOrderArrivalsRequest {
+            user_id: user.id,
+            today,
+            within_days: None,
+        })
+        .await
+        .unwrap();
+    assert_eq!(
+        arrivals
+            .arriving_soon
+            .iter()
+            .map(|order| order.id())
+            .collect::<Vec<_>>(),
+        vec![order_ids[1], order_ids[2]]
+    );
+
+    // 4. Cancelled orders are no longer tracked
+    service
+        .cancel_order(&CancelOrderRequest {
+            user_id: user.id,
+            order_id: order_ids[0],
+            reason: None,
+        })
+        .await
+        .unwrap();
+    let arrivals = service
+        .list_order_arrivals(ListOrderArrivalsRequest {
+            user_id: user.id,
+            today,
+            within_days: Some(7),
+        })
+        .await
+        .unwrap();
+    assert!(arrivals.overdue.is_empty());
+
+    let result = service
+        .update_order_schedule(UpdateOrderScheduleRequest {
+            user_id: user.id,
+            order_id: order_ids[0],
+            schedule: PurchaseOrderSchedule::default(),
+        })
+        .await;
+    assert!(matches!(
+        result,
+        Err(UpdateOrderScheduleError::OrderNotEditable)
+    ));
+}
+
+#[tokio::test]
+async fn test_participant_order_view() {
+    let service = create_service();
+
+    // Setup: Users, Product, Variant
+    let creator = create_user("creator");
+    let creator = service.user.create(creator).await.unwrap();
+    let alice = create_user("alice");
+    let alice = service.user.create(alice).await.unwrap();
+    let bob = create_user("bob");
+    let bob = service.user.create(bob).await.unwrap();
+
+    let product = service
+        .create_product(CreateProductRequest {
+            name: NonEmptyString::new("P1".to_string()).unwrap(),
+            description: "".to_string(),
+            medias: vec![],
+        })
+        .await
+        .unwrap();
+
+    let variant = service
+        .create_product_variant(CreateProductVariantRequest {
+            product_id: product.id,
+            name: NonEmptyString::new("V1".to_string()).unwrap(),
+            description: "".to_string(),
+            price: None,
+            sort_order: 0,
+            medias: vec![],
+            tags: vec![],
+            mystery_box: None,
+        })
+        .await
+        .unwrap();
+
+    // 1. Group buy: one item for Alice, one for Bob, shipped to the creator
+    let order = service
+        .create_order(CreateOrderRequest {
+            user_id: creator.id,
+            receiver_id: None,
+            shipping_address: Some(Address {
+                line1: "123 Main St".to_string(),
+                line2: None,
+                city: "Tokyo".to_string(),
+                state_or_province: "Tokyo".to_string(),
+                postal_code: "100-0001".to_string(),
+                country: "JP".to_string(),
+            }),
+            total_price: None,
+            items: vec![
+                CreateOrderItemRequest {
+                    variant_id: variant.id,
+                    owner_id: Some(alice.id),
+                    quantity: NonZeroU32::new(2).unwrap(),
+                    unit_price: Some(Price {
+                        currency: Currency::JPY,
+                        amount: 1000,
+                    }),
+                },
+                CreateOrderItemRequest {
+                    variant_id: variant.id,
+                    owner_id: Some(bob.id),
+                    quantity: NonZeroU32::new(1).unwrap(),
+                    unit_price: Some(Price {
+                        currency: Currency::JPY,
+                        amount: 3000,
+                    }),
+                },
+            ],
+        })
+        .await
+        .unwrap();
+
+    // The creator keeps one of the pieces ordered for Alice
+    let order = service
+        .reassign_line_item_owners(ReassignLineItemOwnersRequest {
+            user_id: creator.id,
+            order_id: order.id,
+            assignments: vec![LineItemOwnerAssignment {
+                line_item_id: order.items[0].line_items[1].id,
+                owner_id: creator.id,
+            }],
+        })
+        .await
+        .unwrap();
+
+    // 2. The creator sees the whole order
+    let view = service
+        .get_order(GetOrderRequest {
+            user_id: creator.id,
+            order_id: order.id,
+        })
+        .await
+        .unwrap();
+    let PurchaseOrderView::Full(full) = view else {
+        panic!("Creator should see the full order");
+    };
+    assert_eq!(full.items.len(), 2);
+    assert!(full.shipping_address.is_some());
+
+    // 3. Alice only sees her own item and share
+    let view = service
+        .get_order(GetOrderRequest {
+            user_id: alice.id,
+            order_id: order.id,
+        })
+        .await
+        .unwrap();
+    let PurchaseOrderView::Participant(alice_view) = view else {
+        panic!("Participants should get a redacted view");
+    };
+    assert_eq!(alice_view.participant_id, alice.id);
+    assert_eq!(alice_view.items.len(), 1);
+    assert_eq!(alice_view.items[0].id, order.items[0].id);
+    assert!(
+        alice_view.items[0]
+            .line_items
+            .iter()
+            .all(|line_item| line_item.owner_id == alice.id)
+    );
+    // The item's quantity and price would reveal the creator's piece
+    assert_eq!(alice_view.items[0].quantity.get(), 1);
+    assert!(alice_view.items[0].unit_price.is_none());
+    assert_eq!(alice_view.share.owed.amount, 1000);
+    assert_eq!(alice_view.share.paid.amount, 0);
+
+    // 4. Listing applies the same redaction
+    let views = service
+        .list_orders(ListOrdersRequest {
+            user_id: bob.id,
+            role: OrderRoleFilter::Participant,
+            status: None,
+        })
+        .await
+        .unwrap();
+    assert_eq!(views.len(), 1);
+    let PurchaseOrderView::Participant(bob_view) = &views[0] else {
+        panic!("Participants should get a redacted view");
+    };
+    assert_eq!(bob_view.items.len(), 1);
+    assert_eq!(bob_view.items[0].id, order.items[1].id);
+    assert_eq!(bob_view.share.owed.amount, 3000);
+
+    // 5. Events about other participants' items are hidden
+    let events = service
+        .get_order_events(GetOrderEventsRequest {
+            user_id: creator.id,
+            order_id: order.id,
+        })
+        .await
+        .unwrap();
+    assert_eq!(events.len(), 4);
+
+    let events = service
+        .get_order_events(GetOrderEventsRequest {
+            user_id: alice.id,
+            order_id: order.id,
+        })
+        .await
+        .unwrap();
+    let kinds: Vec<_> = events.into_iter().map(|event| event.kind).collect();
+    assert_eq!(
+        kinds,
+        vec![
+            PurchaseOrderEventKind::Created,
+            PurchaseOrderEventKind::ItemAdded {
+                order_item_id: order.items[0].id,
+                variant_id: variant.id,
+                quantity: NonZeroU32::new(1).unwrap(),
+            },
+        ]
+    );
+
+    // 6. The settlement only contains Alice's own share
+    let settlement = service
+        .get_order_settlement(GetOrderSettlementRequest {
+            user_id: alice.id,
+            order_id: order.id,
+        })
+        .await
+        .unwrap();
+    assert_eq!(settlement.shares.len(), 1);
+    assert_eq!(settlement.shares[0].user_id, alice.id);
+
+    // 7. Fulfilling would return the whole order, and is left to the creator and receiver
+    let result = service
+        .fulfill_order(&FulfillOrderRequest {
+            user_id: alice.id,
+            order_id: order.id,
+        })
+        .await;
+    assert!(matches!(
+        result,
+        Err(FulfillOrderError::PermissionDenied { .. })
+    ));
+}
+
+#[tokio::test]
+async fn test_order_attachments() {
+    let service = create_service();
+
+    // Setup: Users, Product, Variant, Media
+    let creator = create_user("creator");
+    let creator = service.user.create(creator).await.unwrap();
+    let participant = create_user("participant");
+    let participant = service.user.create(participant).await.unwrap();
+
+    let product = service
+        .create_product(CreateProductRequest {
+            name: NonEmptyString::new("P1".to_string()).unwrap(),
+            description: "".to_string(),
+            medias: vec![],
+        })
+        .await
+        .unwrap();
+
+    let variant = service
+        .create_product_variant(CreateProductVariantRequest {
+            product_id: product.id,
+            name: NonEmptyString::new("V1".to_string()).unwrap(),
+            description: "".to_string(),
+            price: None,
+            sort_order: 0,
+            medias: vec![],
+            tags: vec![],
+            mystery_box: None,
+        })
+        .await
+        .unwrap();
+
+    let medias = service
+        .create_media_batch(CreateMediaBatchRequest {
+            urls: vec!["https://example.com/receipt.png".parse().unwrap()],
+        })
+        .await
+        .unwrap();
+
+    let order = service
+        .create_order(CreateOrderRequest {
+            user_id: creator.id,
+            receiver_id: None,
+            shipping_address: None,
+            total_price: None,
+            items: vec![CreateOrderItemRequest {
+                variant_id: variant.id,
+                owner_id: Some(participant.id),
+                quantity: NonZeroU32::new(1).unwrap(),
+                unit_price: None,
+            }],
+        })
+        .await
+        .unwrap();
+
+    // 1. The creator attaches the receipt
+    let attachment = service
+        .add_order_attachment(AddOrderAttachmentRequest {
+            user_id: creator.id,
+            order_id: order.id,
+            media_id: medias[0].id,
+            kind: PurchaseOrderAttachmentKind::Receipt,
+            note: Some("Order confirmation".to_string()),
+        })
+        .await
+        .expect("Failed to add attachment");
+    assert_eq!(attachment.uploader_id, creator.id);
+
+    let PurchaseOrderView::Full(full) = service
+        .get_order(GetOrderRequest {
+            user_id: creator.id,
+            order_id: order.id,
+        })
+        .await
+        .unwrap()
+    else {
+        panic!("Creator should see the full order");
+    };
+    assert_eq!(full.attachments.len(), 1);
+    assert_eq!(full.attachments[0].media_id, medias[0].id);
+
+    // 2. Unknown media cannot be attached
+    let result = service
+        .add_order_attachment(AddOrderAttachmentRequest {
+            user_id: creator.id,
+            order_id: order.id,
+            media_id: MediaId::new(),
+            kind: PurchaseOrderAttachmentKind::Photo,
+            note: None,
+        })
+        .await;
+    assert!(matches!(
+        result,
+        Err(AddOrderAttachmentError::MediaNotFound { .. })
+    ));
+
+    // 3. Other participants can neither see nor manage attachments
+    let result = service
+        .add_order_attachment(AddOrderAttachmentRequest {
+            user_id: participant.id,
+            order_id: order.id,
+            media_id: medias[0].id,
+            kind: PurchaseOrderAttachmentKind::Photo,
+            note: None,
+        })
+        .await;
+    assert!(matches!(
+        result,
+        Err(AddOrderAttachmentError::PermissionDenied { .. })
+    ));
+
+    let result = service
+        .remove_order_attachment(RemoveOrderAttachmentRequest {
+            user_id: participant.id,
+            order_id: order.id,
+            attachment_id: attachment.id,
+        })
+        .await;
+    assert!(matches!(
+        result,
+        Err(RemoveOrderAttachmentError::PermissionDenied { .. })
+    ));
+
+    let view = service
+        .get_order(GetOrderRequest {
+            user_id: participant.id,
+            order_id: order.id,
+        })
+        .await
+        .unwrap();
+    assert!(matches!(view, PurchaseOrderView::Participant(_)));
+
+    // 4. The creator removes the attachment
+    let order = service
+        .remove_order_attachment(RemoveOrderAttachmentRequest {
+            user_id: creator.id,
+            order_id: order.id,
+            attachment_id: attachment.id,
+        })
+        .await
+        .expect("Failed to remove attachment");
+    assert!(order.attachments.is_empty());
+    assert_eq!(
+        order.events.last().unwrap().kind,
+        PurchaseOrderEventKind::AttachmentRemoved {
+            attachment_id: attachment.id
+        }
+    );
+
+    let result = service
+        .remove_order_attachment(RemoveOrderAttachmentRequest {
+            user_id: creator.id,
+            order_id: order.id,
+            attachment_id: attachment.id,
+        })
+        .await;
+    assert!(matches!(
+        result,
+        Err(RemoveOrderAttachmentError::AttachmentNotFound { .. })
+    ));
+}
+
+#[tokio::test]
+async fn test_reassign_line_item_owners() {
+    let service = create_service();
+
+    // Setup: Users, Product, Variant
+    let creator = create_user("creator");
+    let creator = service.user.create(creator).await.unwrap();
+    let alice = create_user("alice");
+    let alice = service.user.create(alice).await.unwrap();
+    let bob = create_user("bob");
+    let bob = service.user.create(bob).await.unwrap();
+
+    let product = service
+        .create_product(CreateProductRequest {
+            name: NonEmptyString::new("P1".to_string()).unwrap(),
+            description: "".to_string(),
+            medias: vec![],
+        })
+        .await
+        .unwrap();
+
+    let variant = service
+        .create_product_variant(CreateProductVariantRequest {
+            product_id: product.id,
+            name: NonEmptyString::new("V1".to_string()).unwrap(),
+            description: "".to_string(),
+            price: None,
+            sort_order: 0,
+            medias: vec![],
+            tags: vec![],
+            mystery_box: None,
+        })
+        .await
+        .unwrap();
+
+    // 1. Order three copies before deciding who gets which
+    let order = service
+        .create_order(CreateOrderRequest {
+            user_id: creator.id,
+            receiver_id: None,
+            shipping_address: None,
+            total_price: None,
+            items: vec![CreateOrderItemRequest {
+                variant_id: variant.id,
+                owner_id: None,
+                quantity: NonZeroU32::new(3).unwrap(),
+                unit_price: None,
+            }],
+        })
+        .await
+        .unwrap();
+    let item_id = order.items[0].id;
+
+    // 2. Shares must cover every line item
+    let result = service
+        .split_order_item_owners(SplitOrderItemOwnersRequest {
+            user_id: creator.id,
+            order_id: order.id,
+            order_item_id: item_id,
+            shares: vec![OwnerShare {
+                owner_id: alice.id,
+                quantity: NonZeroU32::new(2).unwrap(),
+            }],
+        })
+        .await;
+    assert!(matches!(
+        result,
+        Err(SplitOrderItemOwnersError::QuantityMismatch {
+            expected: 3,
+            actual: 2
+        })
+    ));
+
+    // 3. Split: two for Alice, one for Bob
+    let order = service
+        .split_order_item_owners(SplitOrderItemOwnersRequest {
+            user_id: creator.id,
+            order_id: order.id,
+            order_item_id: item_id,
+            shares: vec![
+                OwnerShare {
+                    owner_id: alice.id,
+                    quantity: NonZeroU32::new(2).unwrap(),
+                },
+                OwnerShare {
+                    owner_id: bob.id,
+                    quantity: NonZeroU32::new(1).unwrap(),
+                },
+            ],
+        })
+        .await
+        .expect("Failed to split item");
+    let owners: Vec<_> = order.items[0]
+        .line_items
+        .iter()
+        .map(|line_item| line_item.owner_id)
+        .collect();
+    assert_eq!(owners, vec![alice.id, alice.id, bob.id]);
+
+    // 4. Reassign a single copy back to the creator
+    let line_item_id = order.items[0].line_items[1].id;
+    let order = service
+        .reassign_line_item_owners(ReassignLineItemOwnersRequest {
+            user_id: creator.id,
+            order_id: order.id,
+            assignments: vec![LineItemOwnerAssignment {
+                line_item_id,
+                owner_id: creator.id,
+            }],
+        })
+        .await
+        .expect("Failed to reassign line item");
+    assert_eq!(order.items[0].line_items[1].owner_id, creator.id);
+    assert_eq!(
+        order.events.last().unwrap().kind,
+        PurchaseOrderEventKind::OwnersReassigned {
+            line_item_ids: vec![line_item_id]
+        }
+    );
+
+    // 5. New owners must exist
+    let result = service
+        .reassign_line_item_owners(ReassignLineItemOwnersRequest {
+            user_id: creator.id,
+            order_id: order.id,
+            assignments: vec![LineItemOwnerAssignment {
+                line_item_id,
+                owner_id: sawa_core::models::user::UserId::new(),
+            }],
+        })
+        .await;
+    assert!(matches!(
+        result,
+        Err(ReassignLineItemOwnersError::UserNotFound { .. })
+    ));
+
+    // 6. Only the creator can reassign
+    let result = service
+        .reassign_line_item_owners(ReassignLineItemOwnersRequest {
+            user_id: alice.id,
+            order_id: order.id,
+            assignments: vec![LineItemOwnerAssignment {
+                line_item_id,
+                owner_id: alice.id,
+            }],
+        })
+        .await;
+    assert!(matches!(
+        result,
+        Err(ReassignLineItemOwnersError::PermissionDenied { .. })
+    ));
+}
+
+#[tokio::test]
+async fn test_manually_allocated_line_items_are_kept() {
+    let service = create_service();
+
+    // Setup: User, Product, Variant
+    let user = create_user("test_user");
+    let user = service.user.create(user).await.unwrap();
+
+    let product = service
+        .create_product(CreateProductRequest {
+            name: NonEmptyString::new("P1".to_string()).unwrap(),
+            description: "".to_string(),
+            medias: vec![],
+        })
+        .await
+        .unwrap();
+
+    let variant = service
+        .create_product_variant(CreateProductVariantRequest {
+            product_id: product.id,
+            name: NonEmptyString::new("A".to_string()).unwrap(),
+            description: "".to_string(),
+            price: None,
+            sort_order: 0,
+            medias: vec![],
+            tags: vec![],
+            mystery_box: None,
+        })
+        .await
+        .unwrap();
+
+    // Create order: 2 x A @ 1000
+    let order = service
+        .create_order(CreateOrderRequest {
+            user_id: user.id,
+            receiver_id: None,
+            shipping_address: None,
+            total_price: None,
+            items: vec![CreateOrderItemRequest {
+                variant_id: variant.id,
+                owner_id: None,
+                quantity: NonZeroU32::new(2).unwrap(),
+                unit_price: Some(Price {
+                    currency: Currency::JPY,
+                    amount: 1000,
+                }),
+            }],
+        })
+        .await
+        .expect("Failed to create order");
+    let item_id = order.items[0].id;
+    let allocated = order.items[0].line_items[1].id;
+
+    // Discount applied to the second line item only
+    let discount_id = service
+        .add_order_adjustment(AddOrderAdjustmentRequest {
+            user_id: user.id,
+            order_id: order.id,
+            kind: PurchaseOrderAdjustmentKind::Discount,
+            amount: Price {
+                currency: Currency::JPY,
+                amount: 300,
+            },
+            allocation: AdjustmentAllocation::Manual {
+                allocations: vec![ManualAllocation {
+                    line_item_id: allocated,
+                    amount: 300,
+                }],
+            },
+            note: None,
+        })
+        .await
+        .expect("Failed to add discount");
+
+    // 1. Reducing the quantity would drop the allocated line item
+    let result = service
+        .update_order_item(UpdateOrderItemRequest {
+            user_id: user.id,
+            order_id: order.id,
+            order_item_id: item_id,
+            quantity: Some(NonZeroU32::new(1).unwrap()),
+            unit_price: None,
+        })
+        .await;
+    assert!(matches!(
+        result,
+        Err(UpdateOrderItemError::LineItemAllocated { line_item_id }) if line_item_id == allocated
+    ));
+
+    // 2. Increasing it keeps the allocated line item
+    let order = service
+        .update_order_item(UpdateOrderItemRequest {
+            user_id: user.id,
+            order_id: order.id,
+            order_item_id: item_id,
+            quantity: Some(NonZeroU32::new(3).unwrap()),
+            unit_price: None,
+        })
+        .await
+        .expect("Failed to update item");
+    assert_eq!(order.items[0].line_items.len(), 3);
+    assert_eq!(order.items[0].line_items[1].id, allocated);
+
+    // 3. The item can't be removed while the allocation exists
+    let result = service
+        .remove_order_item(RemoveOrderItemRequest {
+            user_id: user.id,
+            order_id: order.id,
+            order_item_id: item_id,
+        })
+        .await;
+    assert!(matches!(
+        result,
+        Err(RemoveOrderItemError::LineItemAllocated { line_item_id }) if line_item_id == allocated
+    ));
+
+    service
+        .remove_order_adjustment(RemoveOrderAdjustmentRequest {
+            user_id: user.id,
+            order_id: order.id,
+            adjustment_id: discount_id,
+        })
+        .await
+        .expect("Failed to remove discount");
+
+    let order = service
+        .remove_order_item(RemoveOrderItemRequest {
+            user_id: user.id,
+            order_id: order.id,
+            order_item_id: item_id,
+        })
+        .await
+        .expect("Failed to remove item");
+    assert!(order.items.is_empty());
+    assert_eq!(order.total_price.amount, 0);
+
+    // 4. Totals that don't fit in a price are rejected
+    let result = service
+        .add_order_item(AddOrderItemRequest {
+            user_id: user.id,
+            order_id: order.id,
+            variant_id: variant.id,
+            owner_id: user.id,
+            quantity: NonZeroU32::new(2).unwrap(),
+            unit_price: Some(Price {
+                currency: Currency::JPY,
+                amount: u32::MAX,
+            }),
+        })
+        .await;
+    assert!(matches!(result, Err(AddOrderItemError::PriceOverflow)));
+}
+
+#[tokio::test]
+async fn test_cancel_partially_fulfilled_order_recomputes_total() {
+    let service = create_service();
+
+    // Setup: Users, Product, Variants
+    let alice = create_user("alice");
+    let bob = create_user("bob");
+    let alice = service.user.create(alice).await.unwrap();
+    let bob = service.user.create(bob).await.unwrap();
+
+    let product = service
+        .create_product(CreateProductRequest {
+            name: NonEmptyString::new("P1".to_string()).unwrap(),
+            description: "".to_string(),
+            medias: vec![],
+        })
+        .await
+        .unwrap();
+
+    let variant_a = service
+        .create_product_variant(CreateProductVariantRequest {
+            product_id: product.id,
+            name: NonEmptyString::new("A".to_string()).unwrap(),
+            description: "".to_string(),
+            price: None,
+            sort_order: 0,
+            medias: vec![],
+            tags: vec![],
+            mystery_box: None,
+        })
+        .await
+        .unwrap();
+
+    let variant_b = service
+        .create_product_variant(CreateProductVariantRequest {
+            product_id: product.id,
+            name: NonEmptyString::new("B".to_string()).unwrap(),
+            description: "".to_string(),
+            price: None,
+            sort_order: 1,
+            medias: vec![],
+            tags: vec![],
+            mystery_box: None,
+        })
+        .await
+        .unwrap();
+
+    // 3 x A @ 1000 for Alice, 1 x B @ 500 for Bob
+    let order = service
+        .create_order(CreateOrderRequest {
+            user_id: alice.id,
+            receiver_id: None,
+            shipping_address: None,
+            total_price: None,
+            items: vec![
+                CreateOrderItemRequest {
+                    variant_id: variant_a.id,
+                    owner_id: None,
+                    quantity: NonZeroU32::new(3).unwrap(),
+                    unit_price: Some(Price {
+                        currency: Currency::JPY,
+                        amount: 1000,
+                    }),
+                },
+                CreateOrderItemRequest {
+                    variant_id: variant_b.id,
+                    owner_id: Some(bob.id),
+                    quantity: NonZeroU32::new(1).unwrap(),
+                    unit_price: Some(Price {
+                        currency: Currency::JPY,
+                        amount: 500,
+                    }),
+                },
+            ],
+        })
+        .await
+        .expect("Failed to create order");
+    let line_items_a: Vec<_> = order.items[0]
+        .line_items
+        .iter()
+        .map(|line_item| line_item.id)
+        .collect();
+
+    // Shipping split evenly, discount applied to the third A only
+    service
+        .add_order_adjustment(AddOrderAdjustmentRequest {
+            user_id: alice.id,
+            order_id: order.id,
+            kind: PurchaseOrderAdjustmentKind::InternationalShipping,
+            amount: Price {
+                currency: Currency::JPY,
+                amount: 300,
+            },
+            allocation: AdjustmentAllocation::ByCount,
+            note: None,
+        })
+        .await
+        .expect("Failed to add shipping");
+    service
+        .add_order_adjustment(AddOrderAdjustmentRequest {
+            user_id: alice.id,
+            order_id: order.id,
+            kind: PurchaseOrderAdjustmentKind::Discount,
+            amount: Price {
+                currency: Currency::JPY,
+                amount: 200,
+            },
+            allocation: AdjustmentAllocation::Manual {
+                allocations: vec![ManualAllocation {
+                    line_item_id: line_items_a[2],
+                    amount: 200,
+                }],
+            },
+            note: None,
+        })
+        .await
+        .expect("Failed to add discount");
+
+    // 1. Bob owns a line item, but only the creator or receiver records arrivals
+    let result = service
+        .fulfill_order_items(&FulfillOrderItemsRequest {
+            user_id: bob.id,
+            order_id: order.id,
+            item_ids: vec![],
+            line_item_ids: vec![line_items_a[0]],
+        })
+        .await;
+    assert!(matches!(
+        result,
+        Err(FulfillOrderItemsError::PermissionDenied { .. })
+    ));
+
+    // 2. Two of the three A arrive, the rest is cancelled
+    service
+        .fulfill_order_items(&FulfillOrderItemsRequest {
+            user_id: alice.id,
+            order_id: order.id,
+            item_ids: vec![],
+            line_item_ids: vec![line_items_a[0], line_items_a[1]],
+        })
+        .await
+        .expect("Failed to fulfill line items");
+
+    let order = service
+        .cancel_order(&CancelOrderRequest {
+            user_id: alice.id,
+            order_id: order.id,
+            reason: None,
+        })
+        .await
+        .expect("Failed to cancel remaining items");
+    assert_eq!(order.status, PurchaseOrderStatus::Fulfilled);
+
+    // Only the two A and the shipping are paid for
+    assert_eq!(order.items[0].quantity.get(), 2);
+    assert_eq!(order.adjustments.len(), 1);
+    assert_eq!(order.total_price.amount, 2300);
+    let landed: u32 = order
+        .landed_costs()
+        .values()
+        .map(|price| price.amount)
+        .sum();
+    assert_eq!(landed, order.total_price.amount);
+    assert!(order.settlement().shares.is_empty());
+}
+
+#[tokio::test]
+async fn test_receiver_change_moves_default_owners() {
+    let service = create_service();
+
+    // Setup: Users, Product, Variant
+    let creator = service.user.create(create_user("creator")).await.unwrap();
+    let receiver = service.user.create(create_user("receiver")).await.unwrap();
+    let participant = service
+        .user
+        .create(create_user("participant"))
+        .await
+        .unwrap();
+
+    let product = service
+        .create_product(CreateProductRequest {
+            name: NonEmptyString::new("P1".to_string()).unwrap(),
+            description: "".to_string(),
+            medias: vec![],
+        })
+        .await
+        .unwrap();
+
+    let variant = service
+        .create_product_variant(CreateProductVariantRequest {
+            product_id: product.id,
+            name: NonEmptyString::new("V1".to_string()).unwrap(),
+            description: "".to_string(),
+            price: None,
+            sort_order: 0,
+            medias: vec![],
+            tags: vec![],
+            mystery_box: None,
+        })
+        .await
+        .unwrap();
+
+    // 3 line items default to the creator as receiver, 1 is designated to the participant
+    let order = service
+        .create_order(CreateOrderRequest {
+            user_id: creator.id,
+            receiver_id: None,
+            shipping_address: None,
+            total_price: None,
+            items: vec![
+                CreateOrderItemRequest {
+                    variant_id: variant.id,
+                    owner_id: None,
+                    quantity: NonZeroU32::new(3).unwrap(),
+                    unit_price: None,
+                },
+                CreateOrderItemRequest {
+                    variant_id: variant.id,
+                    owner_id: Some(participant.id),
+                    quantity: NonZeroU32::new(1).unwrap(),
+                    unit_price: None,
+                },
+            ],
+        })
+        .await
+        .expect("Failed to create order");
+    let kept_id = order.items[0].line_items[2].id;
+
+    // The creator explicitly keeps one of the defaulted line items
+    service
+        .reassign_line_item_owners(ReassignLineItemOwnersRequest {
+            user_id: creator.id,
+            order_id: order.id,
+            assignments: vec![LineItemOwnerAssignment {
+                line_item_id: kept_id,
+                owner_id: creator.id,
+            }],
+        })
+        .await
+        .expect("Failed to reassign owners");
+
+    let order = service
+        .update_order(UpdateOrderRequest {
+            user_id: creator.id,
+            order_id: order.id,
+            receiver_id: Some(receiver.id),
+            shipping_address: None,
+        })
+        .await
+        .expect("Failed to update order");
+
+    // Defaulted line items follow the receiver, assigned ones keep their owner
+    let owners: Vec<_> = order.items[0]
+        .line_items
+        .iter()
+        .map(|line_item| line_item.owner_id)
+        .collect();
+    assert_eq!(owners, vec![receiver.id, receiver.id, creator.id]);
+    assert_eq!(order.items[1].line_items[0].owner_id, participant.id);
+
+    // Changes are persisted
+    let PurchaseOrderView::Full(saved) = service
+        .get_order(GetOrderRequest {
+            user_id: creator.id,
+            order_id: order.id,
+        })
+        .await
+        .unwrap()
+    else {
+        panic!("Creator should see the full order");
+    };
+    assert_eq!(saved.items[0].line_items[0].owner_id, receiver.id);
+    assert_eq!(saved.items[0].line_items[2].owner_id, creator.id);
+}
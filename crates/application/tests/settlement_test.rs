@@ -0,0 +1,605 @@
+mod common;
+
+use common::{create_service, create_user};
+use sawa_core::models::misc::{Currency, NonEmptyString, Price};
+use sawa_core::models::product::{MysteryBoxConfig, MysteryBoxValidation};
+use sawa_core::models::purchase::{AdjustmentAllocation, PurchaseOrderAdjustmentKind};
+use sawa_core::repositories::*;
+use sawa_core::services::*;
+use std::num::NonZeroU32;
+
+#[tokio::test]
+async fn test_order_settlement_and_payments() {
+    let service = create_service();
+
+    // Setup: Alice runs a group buy for Bob and Carol
+    let alice = create_user("alice");
+    let bob = create_user("bob");
+    let carol = create_user("carol");
+    let alice = service.user.create(alice).await.unwrap();
+    let bob = service.user.create(bob).await.unwrap();
+    let carol = service.user.create(carol).await.unwrap();
+
+    // Setup: Product and Variant
+    let product = service
+        .create_product(CreateProductRequest {
+            name: NonEmptyString::new("P1".to_string()).unwrap(),
+            description: "".to_string(),
+            medias: vec![],
+        })
+        .await
+        .unwrap();
+
+    let variant = service
+        .create_product_variant(CreateProductVariantRequest {
+            product_id: product.id,
+            name: NonEmptyString::new("V1".to_string()).unwrap(),
+            description: "".to_string(),
+            price: None,
+            sort_order: 0,
+            medias: vec![],
+            tags: vec![],
+            mystery_box: None,
+        })
+        .await
+        .unwrap();
+
+    // 1 for Alice @ 300, 1 for Bob @ 1000, 2 for Carol @ 500
+    let order = service
+        .create_order(CreateOrderRequest {
+            user_id: alice.id,
+            receiver_id: None,
+            shipping_address: None,
+            total_price: None,
+            items: vec![
+                CreateOrderItemRequest {
+                    variant_id: variant.id,
+                    owner_id: None,
+                    quantity: NonZeroU32::new(1).unwrap(),
+                    unit_price: Some(Price {
+                        currency: Currency::JPY,
+                        amount: 300,
+                    }),
+                },
+                CreateOrderItemRequest {
+                    variant_id: variant.id,
+                    owner_id: Some(bob.id),
+                    quantity: NonZeroU32::new(1).unwrap(),
+                    unit_price: Some(Price {
+                        currency: Currency::JPY,
+                        amount: 1000,
+                    }),
+                },
+                CreateOrderItemRequest {
+                    variant_id: variant.id,
+                    owner_id: Some(carol.id),
+                    quantity: NonZeroU32::new(2).unwrap(),
+                    unit_price: Some(Price {
+                        currency: Currency::JPY,
+                        amount: 500,
+                    }),
+                },
+            ],
+        })
+        .await
+        .expect("Failed to create order");
+
+    // Shipping is shared evenly over the 4 line items
+    service
+        .add_order_adjustment(AddOrderAdjustmentRequest {
+            user_id: alice.id,
+            order_id: order.id,
+            kind: PurchaseOrderAdjustmentKind::InternationalShipping,
+            amount: Price {
+                currency: Currency::JPY,
+                amount: 400,
+            },
+            allocation: AdjustmentAllocation::ByCount,
+            note: None,
+        })
+        .await
+        .unwrap();
+
+    // 1. Verify what each participant owes
+    let settlement = service
+        .get_order_settlement(GetOrderSettlementRequest {
+            user_id: alice.id,
+            order_id: order.id,
+        })
+        .await
+        .expect("Failed to get settlement");
+    assert_eq!(settlement.creditor_id, alice.id);
+    assert_eq!(settlement.shares.len(), 2); // Alice's own share is omitted
+
+    let bob_share = settlement
+        .shares
+        .iter()
+        .find(|share| share.user_id == bob.id)
+        .unwrap();
+    assert_eq!(bob_share.owed.amount, 1100);
+    assert_eq!(bob_share.paid.amount, 0);
+
+    let carol_share = settlement
+        .shares
+        .iter()
+        .find(|share| share.user_id == carol.id)
+        .unwrap();
+    assert_eq!(carol_share.owed.amount, 1200);
+
+    // Participants only see their own share
+    let settlement = service
+        .get_order_settlement(GetOrderSettlementRequest {
+            user_id: bob.id,
+            order_id: order.id,
+        })
+        .await
+        .expect("Failed to get settlement");
+    assert_eq!(settlement.shares.len(), 1);
+    assert_eq!(settlement.shares[0].user_id, bob.id);
+    assert_eq!(settlement.shares[0].owed.amount, 1100);
+
+    // 2. Bob reports a partial payment
+    let payment = service
+        .record_payment(RecordPaymentRequest {
+            user_id: bob.id,
+            order_id: order.id,
+            payer_id: bob.id,
+            amount: Price {
+                currency: Currency::JPY,
+                amount: 1000,
+            },
+            note: Some("Bank transfer".to_string()),
+        })
+        .await
+        .expect("Failed to record payment");
+
+    let settlement = service
+        .get_order_settlement(GetOrderSettlementRequest {
+            user_id: alice.id,
+            order_id: order.id,
+        })
+        .await
+        .unwrap();
+    let balances = settlement.balances();
+    assert_eq!(balances.len(), 2);
+    let bob_balance = balances.iter().find(|b| b.debtor_id == bob.id).unwrap();
+    assert_eq!(bob_balance.creditor_id, alice.id);
+    assert_eq!(bob_balance.amount.amount, 100);
+
+    // 3. Invalid payments are rejected
+    let result = service
+        .record_payment(RecordPaymentRequest {
+            user_id: carol.id,
+            order_id: order.id,
+            payer_id: bob.id,
+            amount: Price {
+                currency: Currency::JPY,
+                amount: 100,
+            },
+            note: None,
+        })
+        .await;
+    assert!(matches!(
+        result,
+        Err(RecordPaymentError::PermissionDenied { .. })
+    ));
+
+    let result = service
+        .record_payment(RecordPaymentRequest {
+            user_id: alice.id,
+            order_id: order.id,
+            payer_id: alice.id,
+            amount: Price {
+                currency: Currency::JPY,
+                amount: 100,
+            },
+            note: None,
+        })
+        .await;
+    assert!(matches!(
+        result,
+        Err(RecordPaymentError::PayerIsCreator { .. })
+    ));
+
+    let result = service
+        .record_payment(RecordPaymentRequest {
+            user_id: alice.id,
+            order_id: order.id,
+            payer_id: carol.id,
+            amount: Price {
+                currency: Currency::USD,
+                amount: 100,
+            },
+            note: None,
+        })
+        .await;
+    assert!(matches!(
+        result,
+        Err(RecordPaymentError::CurrencyMismatch { .. })
+    ));
+
+    // Only participants can be credited
+    let stranger = service.user.create(create_user("dave")).await.unwrap();
+    let result = service
+        .record_payment(RecordPaymentRequest {
+            user_id: alice.id,
+            order_id: order.id,
+            payer_id: stranger.id,
+            amount: Price {
+                currency: Currency::JPY,
+                amount: 100,
+            },
+            note: None,
+        })
+        .await;
+    assert!(matches!(
+        result,
+        Err(RecordPaymentError::PayerNotParticipant { payer_id }) if payer_id == stranger.id
+    ));
+
+    // 4. Removing the payment restores the full balance
+    let settlement = service
+        .remove_payment(RemovePaymentRequest {
+            user_id: alice.id,
+            order_id: order.id,
+            payment_id: payment.id,
+        })
+        .await
+        .expect("Failed to remove payment");
+    let bob_share = settlement
+        .shares
+        .iter()
+        .find(|share| share.user_id == bob.id)
+        .unwrap();
+    assert_eq!(bob_share.paid.amount, 0);
+}
+
+#[tokio::test]
+async fn test_balances_and_netting() {
+    let service = create_service();
+
+    // Setup: Users
+    let alice = create_user("alice");
+    let bob = create_user("bob");
+    let carol = create_user("carol");
+    let alice = service.user.create(alice).await.unwrap();
+    let bob = service.user.create(bob).await.unwrap();
+    let carol = service.user.create(carol).await.unwrap();
+
+    // Setup: Product and Variant
+    let product = service
+        .create_product(CreateProductRequest {
+            name: NonEmptyString::new("P1".to_string()).unwrap(),
+            description: "".to_string(),
+            medias: vec![],
+        })
+        .await
+        .unwrap();
+
+    let variant = service
+        .create_product_variant(CreateProductVariantRequest {
+            product_id: product.id,
+            name: NonEmptyString::new("V1".to_string()).unwrap(),
+            description: "".to_string(),
+            price: None,
+            sort_order: 0,
+            medias: vec![],
+            tags: vec![],
+            mystery_box: None,
+        })
+        .await
+        .unwrap();
+
+    // Alice's order: Bob owes Alice 300, Carol owes Alice 1000
+    service
+        .create_order(CreateOrderRequest {
+            user_id: alice.id,
+            receiver_id: None,
+            shipping_address: None,
+            total_price: None,
+            items: vec![
+                CreateOrderItemRequest {
+                    variant_id: variant.id,
+                    owner_id: Some(bob.id),
+                    quantity: NonZeroU32::new(1).unwrap(),
+                    unit_price: Some(Price {
+                        currency: Currency::JPY,
+                        amount: 300,
+                    }),
+                },
+                CreateOrderItemRequest {
+                    variant_id: variant.id,
+                    owner_id: Some(carol.id),
+                    quantity: NonZeroU32::new(1).unwrap(),
+                    unit_price: Some(Price {
+                        currency: Currency::JPY,
+                        amount: 1000,
+                    }),
+                },
+            ],
+        })
+        .await
+        .unwrap();
+
+    // Bob's order: Alice owes Bob 500
+    service
+        .create_order(CreateOrderRequest {
+            user_id: bob.id,
+            receiver_id: None,
+            shipping_address: None,
+            total_price: None,
+            items: vec![CreateOrderItemRequest {
+                variant_id: variant.id,
+                owner_id: Some(alice.id),
+                quantity: NonZeroU32::new(1).unwrap(),
+                unit_price: Some(Price {
+                    currency: Currency::JPY,
+                    amount: 500,
+                }),
+            }],
+        })
+        .await
+        .unwrap();
+
+    // 1. Alice's balances are netted per counterparty
+    let balances = service
+        .list_balances(ListBalancesRequest { user_id: alice.id })
+        .await
+        .expect("Failed to list balances");
+    assert_eq!(balances.len(), 2);
+
+    let with_bob = balances
+        .iter()
+        .find(|b| b.debtor_id == bob.id || b.creditor_id == bob.id)
+        .unwrap();
+    assert_eq!(with_bob.debtor_id, alice.id);
+    assert_eq!(with_bob.creditor_id, bob.id);
+    assert_eq!(with_bob.amount.amount, 200);
+
+    let with_carol = balances
+        .iter()
+        .find(|b| b.debtor_id == carol.id || b.creditor_id == carol.id)
+        .unwrap();
+    assert_eq!(with_carol.debtor_id, carol.id);
+    assert_eq!(with_carol.creditor_id, alice.id);
+    assert_eq!(with_carol.amount.amount, 1000);
+
+    // 2. Netting over both orders settles everything with Carol's payments
+    // Alice sees all of her order and her own part of Bob's
+    // Net positions: Alice +800, Bob +200, Carol -1000
+    let netting = service
+        .get_netting(GetNettingRequest { user_id: alice.id })
+        .await
+        .expect("Failed to get netting");
+    assert_eq!(netting.len(), 2);
+    assert!(netting.iter().all(|b| b.debtor_id == carol.id));
+
+    let to_alice = netting.iter().find(|b| b.creditor_id == alice.id).unwrap();
+    assert_eq!(to_alice.amount.amount, 800);
+    let to_bob = netting.iter().find(|b| b.creditor_id == bob.id).unwrap();
+    assert_eq!(to_bob.amount.amount, 200);
+
+    // 3. Bob only sees his own part of Alice's order, not Carol's debt
+    let netting = service
+        .get_netting(GetNettingRequest { user_id: bob.id })
+        .await
+        .expect("Failed to get netting");
+    assert_eq!(netting.len(), 1);
+    assert_eq!(netting[0].debtor_id, alice.id);
+    assert_eq!(netting[0].creditor_id, bob.id);
+    assert_eq!(netting[0].amount.amount, 200);
+}
+
+#[tokio::test]
+async fn test_settlement_waits_for_mystery_box_results() {
+    let service = create_service();
+
+    let alice = create_user("alice");
+    let bob = create_user("bob");
+    let alice = service.user.create(alice).await.unwrap();
+    let bob = service.user.create(bob).await.unwrap();
+
+    let product = service
+        .create_product(CreateProductRequest {
+            name: NonEmptyString::new("P1".to_string()).unwrap(),
+            description: "".to_string(),
+            medias: vec![],
+        })
+        .await
+        .unwrap();
+    let character = service
+        .create_product_variant(CreateProductVariantRequest {
+            product_id: product.id,
+            name: NonEmptyString::new("Character".to_string()).unwrap(),
+            description: "".to_string(),
+            price: None,
+            sort_order: 0,
+            medias: vec![],
+            tags: vec![],
+            mystery_box: None,
+        })
+        .await
+        .unwrap();
+    let blind_box = service
+        .create_product_variant(CreateProductVariantRequest {
+            product_id: product.id,
+            name: NonEmptyString::new("Blind Box".to_string()).unwrap(),
+            description: "".to_string(),
+            price: None,
+            sort_order: 1,
+            medias: vec![],
+            tags: vec![],
+            mystery_box: Some(MysteryBoxConfig {
+                items_count: NonZeroU32::new(1).unwrap(),
+                possible_variants: vec![character.id],
+                validation: MysteryBoxValidation::Strict,
+            }),
+        })
+        .await
+        .unwrap();
+
+    // Alice buys 2 blind boxes @ 600
+    let order = service
+        .create_order(CreateOrderRequest {
+            user_id: alice.id,
+            receiver_id: None,
+            shipping_address: None,
+            total_price: None,
+            items: vec![CreateOrderItemRequest {
+                variant_id: blind_box.id,
+                owner_id: None,
+                quantity: NonZeroU32::new(2).unwrap(),
+                unit_price: Some(Price {
+                    currency: Currency::JPY,
+                    amount: 600,
+                }),
+            }],
+        })
+        .await
+        .unwrap();
+    let item_id = order.items[0].id;
+    let add_box_for_bob = || AddMysteryBoxResultsRequest {
+        user_id: alice.id,
+        order_id: order.id,
+        order_item_id: item_id,
+        results: vec![MysteryBoxResult {
+            variant_id: character.id,
+            owner_id: Some(bob.id),
+        }],
+    };
+    let settlement = || GetOrderSettlementRequest {
+        user_id: alice.id,
+        order_id: order.id,
+    };
+
+    // 1. Bob's first box is not charged the whole item until every box is opened
+    service
+        .add_mystery_box_results(add_box_for_bob())
+        .await
+        .unwrap();
+    let result = service.get_order_settlement(settlement()).await.unwrap();
+    assert!(result.shares.is_empty());
+
+    // 2. Once both boxes are in, each costs 600
+    service
+        .add_mystery_box_results(add_box_for_bob())
+        .await
+        .unwrap();
+    let result = service.get_order_settlement(settlement()).await.unwrap();
+    assert_eq!(result.shares.len(), 1);
+    assert_eq!(result.shares[0].user_id, bob.id);
+    assert_eq!(result.shares[0].owed.amount, 1200);
+}
+
+#[tokio::test]
+async fn test_settlement_after_returns() {
+    let service = create_service();
+
+    // Setup: Alice buys 2 x V1 @ 1000 for Bob
+    let alice = service.user.create(create_user("alice")).await.unwrap();
+    let bob = service.user.create(create_user("bob")).await.unwrap();
+
+    let product = service
+        .create_product(CreateProductRequest {
+            name: NonEmptyString::new("P1".to_string()).unwrap(),
+            description: "".to_string(),
+            medias: vec![],
+        })
+        .await
+        .unwrap();
+    let variant = service
+        .create_product_variant(CreateProductVariantRequest {
+            product_id: product.id,
+            name: NonEmptyString::new("V1".to_string()).unwrap(),
+            description: "".to_string(),
+            price: None,
+            sort_order: 0,
+            medias: vec![],
+            tags: vec![],
+            mystery_box: None,
+        })
+        .await
+        .unwrap();
+
+    let order = service
+        .create_order(CreateOrderRequest {
+            user_id: alice.id,
+            receiver_id: None,
+            shipping_address: None,
+            total_price: None,
+            items: vec![CreateOrderItemRequest {
+                variant_id: variant.id,
+                owner_id: Some(bob.id),
+                quantity: NonZeroU32::new(2).unwrap(),
+                unit_price: Some(Price {
+                    currency: Currency::JPY,
+                    amount: 1000,
+                }),
+            }],
+        })
+        .await
+        .unwrap();
+    let order = service
+        .fulfill_order(&FulfillOrderRequest {
+            user_id: alice.id,
+            order_id: order.id,
+        })
+        .await
+        .unwrap();
+    let defective = order.items[0].line_items[0].id;
+    let unwanted = order.items[0].line_items[1].id;
+
+    // 1. A refund can not be combined with a replacement, which already takes over the cost
+    let result = service
+        .return_order_items(&ReturnOrderItemsRequest {
+            user_id: alice.id,
+            order_id: order.id,
+            line_item_ids: vec![defective],
+            refund: Some(Price {
+                currency: Currency::JPY,
+                amount: 1000,
+            }),
+            reason: Some("Defective".to_string()),
+            replace: true,
+        })
+        .await;
+    assert!(matches!(
+        result,
+        Err(ReturnOrderItemsError::RefundWithReplacement)
+    ));
+
+    // 2. Bob still owes for the replacement of the defective item
+    let order = service
+        .return_order_items(&ReturnOrderItemsRequest {
+            user_id: alice.id,
+            order_id: order.id,
+            line_item_ids: vec![defective],
+            refund: None,
+            reason: Some("Defective".to_string()),
+            replace: true,
+        })
+        .await
+        .unwrap();
+    let settlement = order.settlement();
+    assert_eq!(settlement.shares.len(), 1);
+    assert_eq!(settlement.shares[0].owed.amount, 2000);
+
+    // 3. A refund without replacement is passed on to Bob
+    let order = service
+        .return_order_items(&ReturnOrderItemsRequest {
+            user_id: alice.id,
+            order_id: order.id,
+            line_item_ids: vec![unwanted],
+            refund: Some(Price {
+                currency: Currency::JPY,
+                amount: 1000,
+            }),
+            reason: None,
+            replace: false,
+        })
+        .await
+        .unwrap();
+    let settlement = order.settlement();
+    assert_eq!(settlement.shares[0].user_id, bob.id);
+    assert_eq!(settlement.shares[0].owed.amount, 1000);
+}
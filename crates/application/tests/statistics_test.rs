@@ -0,0 +1,337 @@
+mod common;
+
+use chrono::{Datelike, Duration, Utc};
+use common::{TestService, create_service, create_user};
+use sawa_core::models::misc::{Currency, NonEmptyString, Price};
+use sawa_core::models::product::{
+    MysteryBoxConfig, MysteryBoxValidation, ProductId, ProductInstanceStatus, ProductVariant,
+};
+use sawa_core::models::purchase::PurchaseOrderStatus;
+use sawa_core::models::statistics::{CurrencyAmount, DateRange};
+use sawa_core::repositories::*;
+use sawa_core::services::*;
+use std::num::NonZeroU32;
+
+async fn create_variant(
+    service: &TestService,
+    product_id: ProductId,
+    name: &str,
+    tags: &[&str],
+    mystery_box: Option<MysteryBoxConfig>,
+) -> ProductVariant {
+    service
+        .create_product_variant(CreateProductVariantRequest {
+            product_id,
+            name: NonEmptyString::new(name.to_string()).unwrap(),
+            description: "".to_string(),
+            price: None,
+            sort_order: 0,
+            medias: vec![],
+            tags: tags
+                .iter()
+                .map(|tag| NonEmptyString::new(tag.to_string()).unwrap())
+                .collect(),
+            mystery_box,
+        })
+        .await
+        .unwrap()
+}
+
+fn jpy(amount: u32) -> Option<Price> {
+    Some(Price {
+        currency: Currency::JPY,
+        amount,
+    })
+}
+
+#[tokio::test]
+async fn test_statistics() {
+    let service = create_service();
+
+    // Setup: User, a series with a Miku and a Rin variant, and a blind box of three
+    let alice = create_user("alice");
+    let alice = service.user.create(alice).await.unwrap();
+
+    let series = service
+        .create_product(CreateProductRequest {
+            name: NonEmptyString::new("Series".to_string()).unwrap(),
+            description: "".to_string(),
+            medias: vec![],
+        })
+        .await
+        .unwrap();
+    let miku = create_variant(&service, series.id, "Miku", &["Miku"], None).await;
+    let rin = create_variant(&service, series.id, "Rin", &["Rin"], None).await;
+    let blind_box = create_variant(
+        &service,
+        series.id,
+        "Blind Box",
+        &[],
+        Some(MysteryBoxConfig {
+            items_count: NonZeroU32::new(3).unwrap(),
+            possible_variants: vec![miku.id, rin.id],
+            validation: MysteryBoxValidation::Strict,
+        }),
+    )
+    .await;
+
+    // Order 1: 2 x Miku @ 1000, fulfilled
+    let order = service
+        .create_order(CreateOrderRequest {
+            user_id: alice.id,
+            receiver_id: None,
+            shipping_address: None,
+            total_price: None,
+            items: vec![CreateOrderItemRequest {
+                variant_id: miku.id,
+                owner_id: None,
+                quantity: NonZeroU32::new(2).unwrap(),
+                unit_price: jpy(1000),
+            }],
+        })
+        .await
+        .unwrap();
+    service
+        .fulfill_order(&FulfillOrderRequest {
+            user_id: alice.id,
+            order_id: order.id,
+        })
+        .await
+        .unwrap();
+
+    // Order 2: a blind box @ 1200 yielding Miku, Miku and Rin, fulfilled
+    let order = service
+        .create_order(CreateOrderRequest {
+            user_id: alice.id,
+            receiver_id: None,
+            shipping_address: None,
+            total_price: None,
+            items: vec![CreateOrderItemRequest {
+                variant_id: blind_box.id,
+                owner_id: None,
+                quantity: NonZeroU32::new(1).unwrap(),
+                unit_price: jpy(1200),
+            }],
+        })
+        .await
+        .unwrap();
+    service
+        .submit_mystery_box_results(SubmitMysteryBoxResultsRequest {
+            user_id: alice.id,
+            order_id: order.id,
+            order_item_id: order.items[0].id,
+            owner_id: alice.id,
+            received_variants: vec![miku.id, miku.id, rin.id],
+        })
+        .await
+        .unwrap();
+    service
+        .fulfill_order(&FulfillOrderRequest {
+            user_id: alice.id,
+            order_id: order.id,
+        })
+        .await
+        .unwrap();
+
+    // Order 3: Rin @ 500, not fulfilled yet
+    service
+        .create_order(CreateOrderRequest {
+            user_id: alice.id,
+            receiver_id: None,
+            shipping_address: None,
+            total_price: None,
+            items: vec![CreateOrderItemRequest {
+                variant_id: rin.id,
+                owner_id: None,
+                quantity: NonZeroU32::new(1).unwrap(),
+                unit_price: jpy(500),
+            }],
+        })
+        .await
+        .unwrap();
+
+    let all_time = || GetStatisticsRequest {
+        user_id: alice.id,
+        range: DateRange::default(),
+    };
+
+    // Spending: everything was received this month, the pending order is left out
+    let spending = service.get_monthly_spending(all_time()).await.unwrap();
+    assert_eq!(spending.len(), 1);
+    let now = Utc::now();
+    assert_eq!(spending[0].year, now.year());
+    assert_eq!(spending[0].month, now.month());
+    assert_eq!(
+        spending[0].spent,
+        CurrencyAmount {
+            currency: Currency::JPY,
+            amount: 3200,
+        }
+    );
+
+    // Status counts
+    let counts = service.get_status_counts(all_time()).await.unwrap();
+    assert_eq!(counts.len(), 1);
+    assert_eq!(counts[0].status, ProductInstanceStatus::Active);
+    assert_eq!(counts[0].count, 5);
+
+    // Per tag: 4 Miku (2000 + 2 x 400), 1 Rin (400)
+    let tags = service.get_tag_statistics(all_time()).await.unwrap();
+    assert_eq!(tags.len(), 2);
+    assert_eq!(tags[0].name, "Miku");
+    assert_eq!(tags[0].instance_count, 4);
+    assert_eq!(tags[0].spent[0].amount, 2800);
+    assert_eq!(tags[1].name, "Rin");
+    assert_eq!(tags[1].instance_count, 1);
+    assert_eq!(tags[1].spent[0].amount, 400);
+
+    // Per product
+    let products = service.get_product_statistics(all_time()).await.unwrap();
+    assert_eq!(products.len(), 1);
+    assert_eq!(products[0].product_id, series.id);
+    assert_eq!(products[0].instance_count, 5);
+    assert_eq!(products[0].spent[0].amount, 3200);
+
+    // Most-bought series
+    let most_bought = service
+        .get_most_bought_series(GetMostBoughtSeriesRequest {
+            user_id: alice.id,
+            range: DateRange::default(),
+            limit: 10,
+        })
+        .await
+        .unwrap();
+    assert_eq!(most_bought.len(), 1);
+    assert_eq!(most_bought[0].name, "Series");
+    assert_eq!(most_bought[0].purchased_count, 5);
+
+    // Mystery boxes: 3 items of 2 variants for 1200, so 600 per variant
+    let boxes = service
+        .get_mystery_box_statistics(all_time())
+        .await
+        .unwrap();
+    assert_eq!(boxes.len(), 1);
+    assert_eq!(boxes[0].variant_id, blind_box.id);
+    assert_eq!(boxes[0].items_obtained, 3);
+    assert_eq!(boxes[0].distinct_variants_obtained, 2);
+    assert_eq!(boxes[0].spent[0].amount, 1200);
+    assert_eq!(boxes[0].average_cost_per_variant[0].amount, 600);
+
+    // Date ranges
+    let today = now.date_naive();
+    let spending = service
+        .get_monthly_spending(GetStatisticsRequest {
+            user_id: alice.id,
+            range: DateRange {
+                from: Some(today),
+                to: Some(today),
+            },
+        })
+        .await
+        .unwrap();
+    assert_eq!(spending.len(), 1);
+
+    let tomorrow = today + Duration::days(1);
+    let spending = service
+        .get_monthly_spending(GetStatisticsRequest {
+            user_id: alice.id,
+            range: DateRange {
+                from: Some(tomorrow),
+                to: None,
+            },
+        })
+        .await
+        .unwrap();
+    assert!(spending.is_empty());
+
+    let result = service
+        .get_tag_statistics(GetStatisticsRequest {
+            user_id: alice.id,
+            range: DateRange {
+                from: Some(tomorrow),
+                to: Some(today),
+            },
+        })
+        .await;
+    assert!(matches!(result, Err(GetStatisticsError::InvalidRange)));
+}
+
+#[tokio::test]
+async fn test_statistics_include_partially_fulfilled_orders() {
+    let service = create_service();
+
+    let alice = create_user("alice");
+    let alice = service.user.create(alice).await.unwrap();
+
+    let series = service
+        .create_product(CreateProductRequest {
+            name: NonEmptyString::new("Series".to_string()).unwrap(),
+            description: "".to_string(),
+            medias: vec![],
+        })
+        .await
+        .unwrap();
+    let miku = create_variant(&service, series.id, "Miku", &["Miku"], None).await;
+    let rin = create_variant(&service, series.id, "Rin", &["Rin"], None).await;
+
+    // Miku @ 1000 arrives, Rin @ 500 is still on its way
+    let order = service
+        .create_order(CreateOrderRequest {
+            user_id: alice.id,
+            receiver_id: None,
+            shipping_address: None,
+            total_price: None,
+            items: vec![
+                CreateOrderItemRequest {
+                    variant_id: miku.id,
+                    owner_id: None,
+                    quantity: NonZeroU32::new(1).unwrap(),
+                    unit_price: jpy(1000),
+                },
+                CreateOrderItemRequest {
+                    variant_id: rin.id,
+                    owner_id: None,
+                    quantity: NonZeroU32::new(1).unwrap(),
+                    unit_price: jpy(500),
+                },
+            ],
+        })
+        .await
+        .unwrap();
+    let order = service
+        .fulfill_order_items(&FulfillOrderItemsRequest {
+            user_id: alice.id,
+            order_id: order.id,
+            item_ids: vec![order.items[0].id],
+            line_item_ids: vec![],
+        })
+        .await
+        .unwrap();
+    assert_eq!(order.status, PurchaseOrderStatus::PartiallyFulfilled);
+
+    let all_time = || GetStatisticsRequest {
+        user_id: alice.id,
+        range: DateRange::default(),
+    };
+
+    // The received Miku counts, the pending Rin does not
+    let spending = service.get_monthly_spending(all_time()).await.unwrap();
+    assert_eq!(spending.len(), 1);
+    assert_eq!(spending[0].spent.amount, 1000);
+
+    let tags = service.get_tag_statistics(all_time()).await.unwrap();
+    assert_eq!(tags.len(), 1);
+    assert_eq!(tags[0].name, "Miku");
+    assert_eq!(tags[0].instance_count, 1);
+
+    let most_bought = service
+        .get_most_bought_series(GetMostBoughtSeriesRequest {
+            user_id: alice.id,
+            range: DateRange::default(),
+            limit: 10,
+        })
+        .await
+        .unwrap();
+    assert_eq!(most_bought.len(), 1);
+    assert_eq!(most_bought[0].purchased_count, 1);
+}
@@ -0,0 +1,195 @@
+mod common;
+
+use common::{create_service, create_test_product_instance, create_user};
+use sawa_core::models::misc::NonEmptyString;
+use sawa_core::models::product::ProductInstanceStatus;
+use sawa_core::repositories::*;
+use sawa_core::services::*;
+
+#[tokio::test]
+async fn test_storage_locations() {
+    let service = create_service();
+
+    // Setup: Users, Product, Variant and instances
+    let alice = create_user("alice");
+    let bob = create_user("bob");
+    let alice = service.user.create(alice).await.unwrap();
+    let bob = service.user.create(bob).await.unwrap();
+
+    let product = service
+        .create_product(CreateProductRequest {
+            name: NonEmptyString::new("P1".to_string()).unwrap(),
+            description: "".to_string(),
+            medias: vec![],
+        })
+        .await
+        .unwrap();
+
+    let variant = service
+        .create_product_variant(CreateProductVariantRequest {
+            product_id: product.id,
+            name: NonEmptyString::new("Miku Acrylic Stand".to_string()).unwrap(),
+            description: "".to_string(),
+            price: None,
+            sort_order: 0,
+            medias: vec![],
+            tags: vec![],
+            mystery_box: None,
+        })
+        .await
+        .unwrap();
+
+    let first = create_test_product_instance(
+        variant.id,
+        alice.id,
+        alice.id,
+        ProductInstanceStatus::Active,
+    );
+    let second = create_test_product_instance(
+        variant.id,
+        alice.id,
+        alice.id,
+        ProductInstanceStatus::Active,
+    );
+    let bobs =
+        create_test_product_instance(variant.id, bob.id, bob.id, ProductInstanceStatus::Active);
+    service
+        .product_instance
+        .save_batch(&[first.clone(), second.clone(), bobs.clone()])
+        .await
+        .unwrap();
+
+    // 1. Room > Shelf > Box
+    let create = |name: &str, parent_id| CreateStorageLocationRequest {
+        user_id: alice.id,
+        name: NonEmptyString::new(name.to_string()).unwrap(),
+        parent_id,
+    };
+    let room = service
+        .create_storage_location(create("Room", None))
+        .await
+        .unwrap();
+    let shelf = service
+        .create_storage_location(create("Shelf", Some(room.id)))
+        .await
+        .unwrap();
+    let box_location = service
+        .create_storage_location(create("Box", Some(shelf.id)))
+        .await
+        .unwrap();
+
+    // A location cannot be moved inside its own descendants
+    let result = service
+        .update_storage_location(UpdateStorageLocationRequest {
+            id: room.id,
+            user_id: alice.id,
+            name: room.name.clone(),
+            parent_id: Some(box_location.id),
+        })
+        .await;
+    assert!(matches!(result, Err(UpdateStorageLocationError::Cycle)));
+
+    // 2. Moving is all-or-nothing: Bob's instance is not held by Alice
+    let result = service
+        .move_product_instances(MoveProductInstancesRequest {
+            user_id: alice.id,
+            instance_ids: vec![first.id, bobs.id],
+            location_id: Some(box_location.id),
+        })
+        .await;
+    assert!(matches!(
+        result,
+        Err(MoveProductInstancesError::NotHeld { .. })
+    ));
+    let unchanged = service
+        .product_instance
+        .find_by_id(&first.id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(unchanged.location_id, None);
+
+    let moved = service
+        .move_product_instances(MoveProductInstancesRequest {
+            user_id: alice.id,
+            instance_ids: vec![first.id],
+            location_id: Some(box_location.id),
+        })
+        .await
+        .unwrap();
+    assert_eq!(moved[0].location_id, Some(box_location.id));
+
+    // 3. Filtering by the room includes goods in nested locations
+    let in_room = service
+        .list_product_instances(ListProductInstancesRequest {
+            user_id: alice.id,
+            query_by: ListProductInstancesQueryBy::Held,
+            variant_id: None,
+            status: None,
+            location_id: Some(room.id),
+            condition: None,
+        })
+        .await
+        .unwrap();
+    assert_eq!(in_room.len(), 1);
+    assert_eq!(in_room[0].id, first.id);
+
+    // 4. Where is my Miku?
+    let located = service
+        .locate_product_instances(LocateProductInstancesRequest {
+            user_id: alice.id,
+            query: "miku".to_string(),
+        })
+        .await
+        .unwrap();
+    assert_eq!(located.len(), 2);
+    let found = located
+        .iter()
+        .find(|located| located.instance.id == first.id)
+        .unwrap();
+    let path: Vec<_> = found
+        .location_path
+        .iter()
+        .map(|location| location.id)
+        .collect();
+    assert_eq!(path, vec![room.id, shelf.id, box_location.id]);
+    assert!(
+        located
+            .iter()
+            .find(|located| located.instance.id == second.id)
+            .unwrap()
+            .location_path
+            .is_empty()
+    );
+
+    // 5. Locations with goods or nested locations cannot be deleted
+    let result = service
+        .delete_storage_location(DeleteStorageLocationRequest {
+            id: box_location.id,
+            user_id: alice.id,
+        })
+        .await;
+    assert!(matches!(result, Err(DeleteStorageLocationError::NotEmpty)));
+
+    service
+        .move_product_instances(MoveProductInstancesRequest {
+            user_id: alice.id,
+            instance_ids: vec![first.id],
+            location_id: None,
+        })
+        .await
+        .unwrap();
+    service
+        .delete_storage_location(DeleteStorageLocationRequest {
+            id: box_location.id,
+            user_id: alice.id,
+        })
+        .await
+        .unwrap();
+
+    let locations = service
+        .list_storage_locations(ListStorageLocationsRequest { user_id: alice.id })
+        .await
+        .unwrap();
+    assert_eq!(locations.len(), 2);
+}
@@ -0,0 +1,251 @@
+mod common;
+
+use common::{TestService, create_service, create_test_product_instance, create_user};
+use sawa_core::models::misc::NonEmptyString;
+use sawa_core::models::product::{ProductInstanceStatus, ProductVariantId};
+use sawa_core::models::transfer::UserTransactionStatus;
+use sawa_core::models::user::{User, UserId};
+use sawa_core::models::wishlist::WishlistPriority;
+use sawa_core::repositories::*;
+use sawa_core::services::*;
+use std::num::NonZeroU32;
+
+/// Create a user owning two instances of `owned`, wanting `wanted`.
+async fn create_trader(
+    service: &TestService,
+    username: &str,
+    owned: ProductVariantId,
+    wanted: ProductVariantId,
+) -> User {
+    let user = service.user.create(create_user(username)).await.unwrap();
+
+    let instances = vec![
+        create_test_product_instance(owned, user.id, user.id, ProductInstanceStatus::Active),
+        create_test_product_instance(owned, user.id, user.id, ProductInstanceStatus::Active),
+    ];
+    service
+        .product_instance
+        .save_batch(&instances)
+        .await
+        .unwrap();
+
+    service
+        .add_wishlist_item(AddWishlistItemRequest {
+            user_id: user.id,
+            variant_id: wanted,
+            priority: WishlistPriority::Normal,
+            desired_quantity: NonZeroU32::new(1).unwrap(),
+            max_price: None,
+            note: String::new(),
+        })
+        .await
+        .unwrap();
+
+    user
+}
+
+async fn opt_in(service: &TestService, user_id: UserId) {
+    service
+        .set_trade_opt_in(SetTradeOptInRequest {
+            user_id,
+            open_to_trades: true,
+        })
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn test_trade_matching() {
+    let service = create_service();
+
+    // Setup: three variants
+    let product = service
+        .create_product(CreateProductRequest {
+            name: NonEmptyString::new("Can Badge".to_string()).unwrap(),
+            description: "".to_string(),
+            medias: vec![],
+        })
+        .await
+        .unwrap();
+    let mut variant_ids = Vec::new();
+    for name in ["A", "B", "C"] {
+        let variant = service
+            .create_product_variant(CreateProductVariantRequest {
+                product_id: product.id,
+                name: NonEmptyString::new(name.to_string()).unwrap(),
+                description: "".to_string(),
+                price: None,
+                sort_order: 0,
+                medias: vec![],
+                tags: vec![],
+                mystery_box: None,
+            })
+            .await
+            .unwrap();
+        variant_ids.push(variant.id);
+    }
+    let (a, b, c) = (variant_ids[0], variant_ids[1], variant_ids[2]);
+
+    // Alice has a spare A and wants B, Bob has B and wants C, Carol has C and wants A.
+    // Dave has B and wants A too, but has not opted in yet.
+    let alice = create_trader(&service, "alice", a, b).await;
+    let bob = create_trader(&service, "bob", b, c).await;
+    let carol = create_trader(&service, "carol", c, a).await;
+    let dave = create_trader(&service, "dave", b, a).await;
+
+    // 1. Users must opt in
+    let result = service
+        .find_trade_matches(FindTradeMatchesRequest {
+            user_id: alice.id,
+            max_cycle_length: 3,
+        })
+        .await;
+    assert!(matches!(result, Err(FindTradeMatchesError::NotOptedIn)));
+
+    for user_id in [alice.id, bob.id, carol.id] {
+        opt_in(&service, user_id).await;
+    }
+
+    // 2. No pairs, but a cycle of three
+    let matches = service
+        .find_trade_matches(FindTradeMatchesRequest {
+            user_id: alice.id,
+            max_cycle_length: 2,
+        })
+        .await
+        .unwrap();
+    assert!(matches.is_empty());
+
+    let matches = service
+        .find_trade_matches(FindTradeMatchesRequest {
+            user_id: alice.id,
+            max_cycle_length: 3,
+        })
+        .await
+        .unwrap();
+    assert_eq!(matches.len(), 1);
+    let cycle = matches[0].clone();
+    assert_eq!(cycle.legs.len(), 3);
+    assert_eq!(cycle.satisfied_wants, 3);
+    assert_eq!(cycle.legs[0].from_user_id, alice.id);
+    assert_eq!(cycle.legs[0].to_user_id, carol.id);
+    assert_eq!(cycle.legs[0].items[0].variant_id, a);
+
+    // 3. Once Dave opts in, the pair with him is found as well, ranked after the cycle
+    opt_in(&service, dave.id).await;
+    let matches = service
+        .find_trade_matches(FindTradeMatchesRequest {
+            user_id: alice.id,
+            max_cycle_length: 3,
+        })
+        .await
+        .unwrap();
+    assert_eq!(matches.len(), 2);
+    assert_eq!(matches[0].legs.len(), 3);
+    assert_eq!(matches[1].legs.len(), 2);
+    assert_eq!(matches[1].legs[0].to_user_id, dave.id);
+
+    // 4. Proposing the cycle locks Alice's spare, the other legs await their senders
+    let transactions = service
+        .propose_trade_match(ProposeTradeMatchRequest {
+            user_id: alice.id,
+            trade_match: cycle.clone(),
+        })
+        .await
+        .unwrap();
+    assert_eq!(transactions.len(), 3);
+    for (transaction, leg) in transactions.iter().zip(&cycle.legs) {
+        let (status, instance_status) = if leg.from_user_id == alice.id {
+            (
+                UserTransactionStatus::Pending,
+                ProductInstanceStatus::Locked,
+            )
+        } else {
+            (
+                UserTransactionStatus::Proposed,
+                ProductInstanceStatus::Active,
+            )
+        };
+        assert_eq!(transaction.status, status);
+        for item in &leg.items {
+            let instance = service
+                .product_instance
+                .find_by_id(&item.instance_id)
+                .await
+                .unwrap()
+                .unwrap();
+            assert_eq!(instance.status, instance_status);
+        }
+    }
+
+    // The receiver cannot take a leg its sender has not accepted
+    let proposed = &transactions[1];
+    let result = service
+        .complete_transaction(CompleteTransactionRequest {
+            transaction_id: proposed.id,
+            user_id: proposed.to_user_id,
+        })
+        .await;
+    assert!(matches!(result, Err(CompleteTransactionError::NotAccepted)));
+
+    // Only the sender may accept it, which locks their spare
+    let result = service
+        .accept_transaction(AcceptTransactionRequest {
+            transaction_id: proposed.id,
+            user_id: alice.id,
+        })
+        .await;
+    assert!(matches!(
+        result,
+        Err(AcceptTransactionError::PermissionDenied)
+    ));
+
+    let accepted = service
+        .accept_transaction(AcceptTransactionRequest {
+            transaction_id: proposed.id,
+            user_id: proposed.from_user_id,
+        })
+        .await
+        .expect("Failed to accept transaction");
+    assert_eq!(accepted.status, UserTransactionStatus::Pending);
+    for instance_id in &proposed.items {
+        let instance = service
+            .product_instance
+            .find_by_id(instance_id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(instance.status, ProductInstanceStatus::Locked);
+    }
+
+    // Declining a proposal leaves the spares untouched
+    let declined = &transactions[2];
+    service
+        .cancel_transaction(CancelTransactionRequest {
+            transaction_id: declined.id,
+            user_id: declined.from_user_id,
+        })
+        .await
+        .expect("Failed to decline transaction");
+    for instance_id in &declined.items {
+        let instance = service
+            .product_instance
+            .find_by_id(instance_id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(instance.status, ProductInstanceStatus::Active);
+    }
+
+    // 5. The locked spares are no longer offered
+    let result = service
+        .propose_trade_match(ProposeTradeMatchRequest {
+            user_id: alice.id,
+            trade_match: cycle,
+        })
+        .await;
+    assert!(matches!(
+        result,
+        Err(ProposeTradeMatchError::MatchNotAvailable)
+    ));
+}
@@ -67,7 +67,10 @@ async fn test_transaction_complete_flow() {
 
     // Verify item is locked
     let locked_instance = service
-        .get_product_instance(GetProductInstanceRequest { id: instance.id })
+        .get_product_instance(GetProductInstanceRequest {
+            id: instance.id,
+            user_id: alice.id,
+        })
         .await
         .unwrap();
     assert_eq!(locked_instance.status, ProductInstanceStatus::Locked);
@@ -91,7 +94,10 @@ async fn test_transaction_complete_flow() {
 
     // Verify ownership transfer
     let transferred_instance = service
-        .get_product_instance(GetProductInstanceRequest { id: instance.id })
+        .get_product_instance(GetProductInstanceRequest {
+            id: instance.id,
+            user_id: alice.id,
+        })
         .await
         .unwrap();
     assert_eq!(transferred_instance.owner_id, bob.id);
@@ -154,7 +160,10 @@ async fn test_transaction_cancel_flow() {
 
     // Verify item is locked
     let locked_instance = service
-        .get_product_instance(GetProductInstanceRequest { id: instance.id })
+        .get_product_instance(GetProductInstanceRequest {
+            id: instance.id,
+            user_id: alice.id,
+        })
         .await
         .unwrap();
     assert_eq!(locked_instance.status, ProductInstanceStatus::Locked);
@@ -177,7 +186,10 @@ async fn test_transaction_cancel_flow() {
 
     // Verify item is unlocked and still owned by Alice
     let unlocked_instance = service
-        .get_product_instance(GetProductInstanceRequest { id: instance.id })
+        .get_product_instance(GetProductInstanceRequest {
+            id: instance.id,
+            user_id: alice.id,
+        })
         .await
         .unwrap();
     assert_eq!(unlocked_instance.status, ProductInstanceStatus::Active);
@@ -247,11 +259,17 @@ async fn test_transaction_multiple_items() {
 
     // Verify all items are locked
     let locked1 = service
-        .get_product_instance(GetProductInstanceRequest { id: instance1.id })
+        .get_product_instance(GetProductInstanceRequest {
+            id: instance1.id,
+            user_id: alice.id,
+        })
         .await
         .unwrap();
     let locked2 = service
-        .get_product_instance(GetProductInstanceRequest { id: instance2.id })
+        .get_product_instance(GetProductInstanceRequest {
+            id: instance2.id,
+            user_id: alice.id,
+        })
         .await
         .unwrap();
     assert_eq!(locked1.status, ProductInstanceStatus::Locked);
@@ -268,11 +286,17 @@ async fn test_transaction_multiple_items() {
 
     // Verify all items ownership transferred
     let transferred1 = service
-        .get_product_instance(GetProductInstanceRequest { id: instance1.id })
+        .get_product_instance(GetProductInstanceRequest {
+            id: instance1.id,
+            user_id: alice.id,
+        })
         .await
         .unwrap();
     let transferred2 = service
-        .get_product_instance(GetProductInstanceRequest { id: instance2.id })
+        .get_product_instance(GetProductInstanceRequest {
+            id: instance2.id,
+            user_id: alice.id,
+        })
         .await
         .unwrap();
     assert_eq!(transferred1.owner_id, bob.id);
@@ -846,3 +870,168 @@ async fn test_create_transaction_with_non_owned_item_fails() {
         _ => panic!("Expected ItemNotOwned error"),
     }
 }
+
+#[tokio::test]
+async fn test_lending_transaction_flow() {
+    let service = create_service();
+
+    // Setup: Users
+    let alice = create_user("alice");
+    let bob = create_user("bob");
+    let alice = service.user.create(alice).await.unwrap();
+    let bob = service.user.create(bob).await.unwrap();
+
+    // Setup: Alice owns an item
+    let product = service
+        .create_product(CreateProductRequest {
+            name: NonEmptyString::new("P1".to_string()).unwrap(),
+            description: "".to_string(),
+            medias: vec![],
+        })
+        .await
+        .unwrap();
+
+    let variant = service
+        .create_product_variant(CreateProductVariantRequest {
+            product_id: product.id,
+            name: NonEmptyString::new("V1".to_string()).unwrap(),
+            description: "".to_string(),
+            price: None,
+            sort_order: 0,
+            medias: vec![],
+            tags: vec![],
+            mystery_box: None,
+        })
+        .await
+        .unwrap();
+
+    let instance = create_test_product_instance(
+        variant.id,
+        alice.id,
+        alice.id,
+        ProductInstanceStatus::Active,
+    );
+    service.product_instance.save(&instance).await.unwrap();
+
+    // 1. Alice leaves the item with Bob until the next meetup
+    let return_by = chrono::NaiveDate::from_ymd_opt(2030, 1, 1).unwrap();
+    let transaction = service
+        .create_lending_transaction(CreateLendingTransactionRequest {
+            from_user_id: alice.id,
+            to_user_id: bob.id,
+            items: vec![instance.id],
+            return_by: Some(return_by),
+        })
+        .await
+        .expect("Failed to create lending transaction");
+
+    // Only Alice may lend her items out
+    let result = service
+        .create_lending_transaction(CreateLendingTransactionRequest {
+            from_user_id: bob.id,
+            to_user_id: alice.id,
+            items: vec![instance.id],
+            return_by: None,
+        })
+        .await;
+    assert!(matches!(result, Err(CreateTransactionError::ItemNotOwned)));
+
+    // 2. Bob confirms receipt
+    service
+        .complete_transaction(CompleteTransactionRequest {
+            transaction_id: transaction.id,
+            user_id: bob.id,
+        })
+        .await
+        .expect("Failed to complete transaction");
+
+    let lent = service
+        .product_instance
+        .find_by_id(&instance.id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(lent.owner_id, alice.id);
+    assert_eq!(lent.holder_id, bob.id);
+    assert_eq!(lent.status, ProductInstanceStatus::Active);
+    assert_eq!(lent.return_by(), Some(return_by));
+    let history = lent.transfer_history.last().unwrap();
+    assert_eq!(history.to_owner_id, alice.id);
+    assert_eq!(history.to_holder_id, bob.id);
+
+    // 3. The item shows up as lent out for Alice and borrowed for Bob
+    let lent_out = service
+        .list_product_instances(ListProductInstancesRequest {
+            user_id: alice.id,
+            query_by: ListProductInstancesQueryBy::LentOut,
+            variant_id: None,
+            status: None,
+            location_id: None,
+            condition: None,
+        })
+        .await
+        .unwrap();
+    assert_eq!(lent_out.len(), 1);
+
+    let borrowed = service
+        .list_product_instances(ListProductInstancesRequest {
+            user_id: bob.id,
+            query_by: ListProductInstancesQueryBy::Borrowed,
+            variant_id: None,
+            status: None,
+            location_id: None,
+            condition: None,
+        })
+        .await
+        .unwrap();
+    assert_eq!(borrowed.len(), 1);
+
+    // Alice cannot trade the item away while Bob has it
+    let result = service
+        .create_transaction(CreateTransactionRequest {
+            from_user_id: alice.id,
+            to_user_id: bob.id,
+            items: vec![instance.id],
+        })
+        .await;
+    assert!(matches!(result, Err(CreateTransactionError::ItemNotHeld)));
+
+    // 4. Bob gives the item back
+    let transaction = service
+        .create_delivery_transaction(CreateDeliveryTransactionRequest {
+            from_user_id: bob.id,
+            to_user_id: alice.id,
+            items: vec![instance.id],
+        })
+        .await
+        .expect("Failed to create delivery transaction");
+    service
+        .complete_transaction(CompleteTransactionRequest {
+            transaction_id: transaction.id,
+            user_id: alice.id,
+        })
+        .await
+        .expect("Failed to complete transaction");
+
+    let returned = service
+        .product_instance
+        .find_by_id(&instance.id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(returned.holder_id, alice.id);
+    assert_eq!(returned.return_by(), None);
+
+    let lent_out = service
+        .list_product_instances(ListProductInstancesRequest {
+            user_id: alice.id,
+            query_by: ListProductInstancesQueryBy::LentOut,
+            variant_id: None,
+            status: None,
+            location_id: None,
+            condition: None,
+        })
+        .await
+        .unwrap();
+    assert!(lent_out.is_empty());
+}
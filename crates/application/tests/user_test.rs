@@ -0,0 +1,391 @@
+mod common;
+
+use common::{create_service, create_user};
+use sawa_core::models::misc::NonEmptyString;
+use sawa_core::models::purchase::{OrderRoleFilter, PurchaseOrderStatus};
+use sawa_core::models::user::{Email, Username};
+use sawa_core::repositories::*;
+use sawa_core::services::*;
+use std::num::NonZeroU32;
+
+#[tokio::test]
+async fn test_claim_placeholder() {
+    let service = create_service();
+
+    // Setup: Users, Product, Variant
+    let creator = create_user("creator");
+    let creator = service.user.create(creator).await.unwrap();
+
+    let product = service
+        .create_product(CreateProductRequest {
+            name: NonEmptyString::new("P1".to_string()).unwrap(),
+            description: "".to_string(),
+            medias: vec![],
+        })
+        .await
+        .unwrap();
+
+    let variant = service
+        .create_product_variant(CreateProductVariantRequest {
+            product_id: product.id,
+            name: NonEmptyString::new("V1".to_string()).unwrap(),
+            description: "".to_string(),
+            price: None,
+            sort_order: 0,
+            medias: vec![],
+            tags: vec![],
+            mystery_box: None,
+        })
+        .await
+        .unwrap();
+
+    // 1. Create a placeholder for a participant without an account
+    let placeholder = service
+        .create_placeholder(CreatePlaceholderRequest {
+            user_id: creator.id,
+            display_name: NonEmptyString::new("Alice".to_string()).unwrap(),
+        })
+        .await
+        .unwrap();
+    let details = placeholder.placeholder.clone().unwrap();
+    assert_eq!(details.display_name, "Alice");
+    assert_eq!(details.manager_id, creator.id);
+
+    let placeholders = service
+        .list_placeholders(ListPlaceholdersRequest {
+            user_id: creator.id,
+        })
+        .await
+        .unwrap();
+    assert_eq!(placeholders.len(), 1);
+    assert_eq!(placeholders[0].id, placeholder.id);
+
+    // Placeholders cannot log in
+    let result = service
+        .login_user(LoginRequest {
+            username: placeholder.username.clone(),
+            password: NonEmptyString::new("!".to_string()).unwrap(),
+        })
+        .await;
+    assert!(matches!(result, Err(LoginError::NotFound)));
+
+    // 2. The placeholder owns line items, and the instances after fulfillment
+    let order = service
+        .create_order(CreateOrderRequest {
+            user_id: creator.id,
+            receiver_id: None,
+            shipping_address: None,
+            total_price: None,
+            items: vec![CreateOrderItemRequest {
+                variant_id: variant.id,
+                owner_id: Some(placeholder.id),
+                quantity: NonZeroU32::new(2).unwrap(),
+                unit_price: None,
+            }],
+        })
+        .await
+        .unwrap();
+
+    let order = service
+        .fulfill_order(&FulfillOrderRequest {
+            user_id: creator.id,
+            order_id: order.id,
+        })
+        .await
+        .unwrap();
+    assert_eq!(order.status, PurchaseOrderStatus::Fulfilled);
+
+    let instances = service
+        .product_instance
+        .find_by_owner(&placeholder.id)
+        .await
+        .unwrap();
+    assert_eq!(instances.len(), 2);
+
+    // 3. The participant registers and claims the placeholder
+    let alice = create_user("alice");
+    let alice = service.user.create(alice).await.unwrap();
+
+    let result = service
+        .claim_placeholder(ClaimPlaceholderRequest {
+            user_id: alice.id,
+            invite_code: "wrong".to_string(),
+        })
+        .await;
+    assert!(matches!(
+        result,
+        Err(ClaimPlaceholderError::InvalidInviteCode)
+    ));
+
+    let claimed = service
+        .claim_placeholder(ClaimPlaceholderRequest {
+            user_id: alice.id,
+            invite_code: details.invite_code.clone(),
+        })
+        .await
+        .unwrap();
+    assert_eq!(claimed.id, alice.id);
+
+    // Instances, including their history, now belong to Alice
+    let instances = service
+        .product_instance
+        .find_by_owner(&alice.id)
+        .await
+        .unwrap();
+    assert_eq!(instances.len(), 2);
+    assert!(instances.iter().all(|i| {
+        i.transfer_history
+            .iter()
+            .all(|h| h.to_owner_id != placeholder.id)
+    }));
+    assert!(
+        service
+            .product_instance
+            .find_by_owner(&placeholder.id)
+            .await
+            .unwrap()
+            .is_empty()
+    );
+
+    // So does her order participation
+    let orders = service
+        .order
+        .find_by_user(&alice.id, OrderRoleFilter::Participant, None)
+        .await
+        .unwrap();
+    assert_eq!(orders.len(), 1);
+    assert!(
+        orders[0].items[0]
+            .line_items
+            .iter()
+            .all(|line_item| line_item.owner_id == alice.id)
+    );
+
+    // And the delivery from the creator
+    let transactions = service
+        .transaction
+        .find_by_to_user(&alice.id, None)
+        .await
+        .unwrap();
+    assert_eq!(transactions.len(), 1);
+    assert_eq!(transactions[0].from_user_id, creator.id);
+
+    // The placeholder is gone, and its code cannot be used again
+    assert!(
+        service
+            .user
+            .find_by_id(&placeholder.id)
+            .await
+            .unwrap()
+            .is_none()
+    );
+    assert!(
+        service
+            .list_placeholders(ListPlaceholdersRequest {
+                user_id: creator.id,
+            })
+            .await
+            .unwrap()
+            .is_empty()
+    );
+    let result = service
+        .claim_placeholder(ClaimPlaceholderRequest {
+            user_id: alice.id,
+            invite_code: details.invite_code,
+        })
+        .await;
+    assert!(matches!(
+        result,
+        Err(ClaimPlaceholderError::InvalidInviteCode)
+    ));
+}
+
+#[tokio::test]
+async fn test_register_with_invite_code() {
+    let service = create_service();
+
+    let creator = create_user("creator");
+    let creator = service.user.create(creator).await.unwrap();
+    let placeholder = service
+        .create_placeholder(CreatePlaceholderRequest {
+            user_id: creator.id,
+            display_name: NonEmptyString::new("Alice".to_string()).unwrap(),
+        })
+        .await
+        .unwrap();
+    let invite_code = placeholder.placeholder.clone().unwrap().invite_code;
+
+    let request = |invite_code: &str| CreateUserRequest {
+        email: Email("alice@example.com".to_string()),
+        username: Username("alice".to_string()),
+        password: NonEmptyString::new("password".to_string()).unwrap(),
+        avatar: None,
+        invite_code: Some(invite_code.to_string()),
+    };
+
+    // 1. A wrong code is rejected before the account is created
+    let result = service.create_user(request("wrong")).await;
+    assert!(matches!(result, Err(CreateUserError::InvalidInviteCode)));
+    assert!(
+        service
+            .user
+            .find_by_username(&Username("alice".to_string()))
+            .await
+            .unwrap()
+            .is_none()
+    );
+
+    // 2. A valid code registers and claims in one go
+    let alice = service
+        .create_user(request(&invite_code))
+        .await
+        .expect("Failed to register with invite code");
+    assert!(!alice.is_placeholder());
+    assert!(
+        service
+            .user
+            .find_by_id(&placeholder.id)
+            .await
+            .unwrap()
+            .is_none()
+    );
+}
+
+#[tokio::test]
+async fn test_manager_completes_placeholder_delivery() {
+    let service = create_service();
+
+    let creator = create_user("creator");
+    let creator = service.user.create(creator).await.unwrap();
+    let bob = create_user("bob");
+    let bob = service.user.create(bob).await.unwrap();
+
+    let product = service
+        .create_product(CreateProductRequest {
+            name: NonEmptyString::new("P1".to_string()).unwrap(),
+            description: "".to_string(),
+            medias: vec![],
+        })
+        .await
+        .unwrap();
+    let variant = service
+        .create_product_variant(CreateProductVariantRequest {
+            product_id: product.id,
+            name: NonEmptyString::new("V1".to_string()).unwrap(),
+            description: "".to_string(),
+            price: None,
+            sort_order: 0,
+            medias: vec![],
+            tags: vec![],
+            mystery_box: None,
+        })
+        .await
+        .unwrap();
+
+    let placeholder = service
+        .create_placeholder(CreatePlaceholderRequest {
+            user_id: creator.id,
+            display_name: NonEmptyString::new("Alice".to_string()).unwrap(),
+        })
+        .await
+        .unwrap();
+
+    // 1. Fulfilling leaves a delivery from the creator to the placeholder
+    let order = service
+        .create_order(CreateOrderRequest {
+            user_id: creator.id,
+            receiver_id: None,
+            shipping_address: None,
+            total_price: None,
+            items: vec![CreateOrderItemRequest {
+                variant_id: variant.id,
+                owner_id: Some(placeholder.id),
+                quantity: NonZeroU32::new(1).unwrap(),
+                unit_price: None,
+            }],
+        })
+        .await
+        .unwrap();
+    service
+        .fulfill_order(&FulfillOrderRequest {
+            user_id: creator.id,
+            order_id: order.id,
+        })
+        .await
+        .unwrap();
+
+    let transactions = service
+        .transaction
+        .find_by_to_user(&placeholder.id, None)
+        .await
+        .unwrap();
+    assert_eq!(transactions.len(), 1);
+
+    // 2. Only the placeholder's manager may confirm it
+    let result = service
+        .complete_transaction(CompleteTransactionRequest {
+            transaction_id: transactions[0].id,
+            user_id: bob.id,
+        })
+        .await;
+    assert!(matches!(
+        result,
+        Err(CompleteTransactionError::PermissionDenied)
+    ));
+
+    service
+        .complete_transaction(CompleteTransactionRequest {
+            transaction_id: transactions[0].id,
+            user_id: creator.id,
+        })
+        .await
+        .expect("Manager failed to complete delivery");
+
+    let instances = service
+        .product_instance
+        .find_by_owner(&placeholder.id)
+        .await
+        .unwrap();
+    assert_eq!(instances.len(), 1);
+    assert_eq!(instances[0].holder_id, placeholder.id);
+}
+
+#[tokio::test]
+async fn test_calendar_token() {
+    let service = create_service();
+
+    let alice = create_user("alice");
+    let alice = service.user.create(alice).await.unwrap();
+
+    // 1. Issue a token and look the user up by it
+    let token = service
+        .reset_calendar_token(ResetCalendarTokenRequest { user_id: alice.id })
+        .await
+        .expect("Failed to reset calendar token");
+    let user = service
+        .get_user(GetUserRequest::ByCalendarToken(token.clone()))
+        .await
+        .expect("Failed to find user by calendar token");
+    assert_eq!(user.id, alice.id);
+
+    // 2. Resetting revokes the previous token
+    let new_token = service
+        .reset_calendar_token(ResetCalendarTokenRequest { user_id: alice.id })
+        .await
+        .unwrap();
+    assert_ne!(new_token, token);
+    let result = service
+        .get_user(GetUserRequest::ByCalendarToken(token))
+        .await;
+    assert!(matches!(result, Err(GetUserError::NotFound)));
+
+    // 3. Revoking disables the feed
+    service
+        .revoke_calendar_token(RevokeCalendarTokenRequest { user_id: alice.id })
+        .await
+        .expect("Failed to revoke calendar token");
+    let result = service
+        .get_user(GetUserRequest::ByCalendarToken(new_token))
+        .await;
+    assert!(matches!(result, Err(GetUserError::NotFound)));
+}
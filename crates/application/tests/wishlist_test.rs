@@ -0,0 +1,195 @@
+mod common;
+
+use common::{create_service, create_test_product_instance, create_user};
+use sawa_core::models::misc::{Currency, NonEmptyString, Price};
+use sawa_core::models::product::{ProductInstanceStatus, ProductVariantId};
+use sawa_core::models::wishlist::WishlistPriority;
+use sawa_core::repositories::*;
+use sawa_core::services::*;
+use std::num::NonZeroU32;
+
+#[tokio::test]
+async fn test_wishlist_flow() {
+    let service = create_service();
+
+    // Setup: Users and a product with two variants
+    let alice = create_user("alice");
+    let bob = create_user("bob");
+    let alice = service.user.create(alice).await.unwrap();
+    let bob = service.user.create(bob).await.unwrap();
+
+    let product = service
+        .create_product(CreateProductRequest {
+            name: NonEmptyString::new("Acrylic Stand".to_string()).unwrap(),
+            description: "".to_string(),
+            medias: vec![],
+        })
+        .await
+        .unwrap();
+    let mut variants = Vec::new();
+    for name in ["Miku", "Rin"] {
+        let variant = service
+            .create_product_variant(CreateProductVariantRequest {
+                product_id: product.id,
+                name: NonEmptyString::new(name.to_string()).unwrap(),
+                description: "".to_string(),
+                price: None,
+                sort_order: 0,
+                medias: vec![],
+                tags: vec![],
+                mystery_box: None,
+            })
+            .await
+            .unwrap();
+        variants.push(variant);
+    }
+    let (miku, rin) = (&variants[0], &variants[1]);
+
+    // 1. Add items
+    let add_item = |variant_id, desired_quantity| AddWishlistItemRequest {
+        user_id: alice.id,
+        variant_id,
+        priority: WishlistPriority::High,
+        desired_quantity: NonZeroU32::new(desired_quantity).unwrap(),
+        max_price: Some(Price {
+            currency: Currency::JPY,
+            amount: 1500,
+        }),
+        note: "Any condition".to_string(),
+    };
+    service
+        .add_wishlist_item(add_item(miku.id, 2))
+        .await
+        .unwrap();
+    let wishlist = service
+        .add_wishlist_item(add_item(rin.id, 1))
+        .await
+        .unwrap();
+    assert_eq!(wishlist.items.len(), 2);
+    assert!(!wishlist.is_public);
+
+    let result = service.add_wishlist_item(add_item(miku.id, 1)).await;
+    assert!(matches!(
+        result,
+        Err(AddWishlistItemError::AlreadyWishlisted { .. })
+    ));
+    let result = service
+        .add_wishlist_item(add_item(ProductVariantId::new(), 1))
+        .await;
+    assert!(matches!(
+        result,
+        Err(AddWishlistItemError::VariantNotFound { .. })
+    ));
+
+    // 2. Only shared wishlists are visible to others
+    let view_as_bob = || GetWishlistRequest {
+        user_id: alice.id,
+        viewer_id: Some(bob.id),
+    };
+    let result = service.get_wishlist(view_as_bob()).await;
+    assert!(matches!(result, Err(GetWishlistError::NotFound)));
+
+    service
+        .set_wishlist_visibility(SetWishlistVisibilityRequest {
+            user_id: alice.id,
+            is_public: true,
+        })
+        .await
+        .unwrap();
+    let wishlist = service.get_wishlist(view_as_bob()).await.unwrap();
+    assert_eq!(wishlist.items.len(), 2);
+
+    // 3. Fulfilling an order counts towards the wanted quantity
+    let order = service
+        .create_order(CreateOrderRequest {
+            user_id: alice.id,
+            receiver_id: None,
+            shipping_address: None,
+            total_price: None,
+            items: vec![CreateOrderItemRequest {
+                variant_id: miku.id,
+                owner_id: None,
+                quantity: NonZeroU32::new(1).unwrap(),
+                unit_price: None,
+            }],
+        })
+        .await
+        .unwrap();
+    service
+        .fulfill_order(&FulfillOrderRequest {
+            user_id: alice.id,
+            order_id: order.id,
+        })
+        .await
+        .unwrap();
+
+    let wishlist = service.get_wishlist(view_as_bob()).await.unwrap();
+    let item = &wishlist.items[0];
+    assert_eq!(item.obtained_quantity, 1);
+    assert!(!item.is_satisfied());
+
+    // 4. Receiving one in a trade satisfies the item
+    let instance =
+        create_test_product_instance(miku.id, bob.id, bob.id, ProductInstanceStatus::Active);
+    service.product_instance.save(&instance).await.unwrap();
+    let transaction = service
+        .create_transaction(CreateTransactionRequest {
+            from_user_id: bob.id,
+            to_user_id: alice.id,
+            items: vec![instance.id],
+        })
+        .await
+        .unwrap();
+    service
+        .complete_transaction(CompleteTransactionRequest {
+            transaction_id: transaction.id,
+            user_id: alice.id,
+        })
+        .await
+        .unwrap();
+
+    let wishlist = service.get_wishlist(view_as_bob()).await.unwrap();
+    let item = &wishlist.items[0];
+    assert_eq!(item.obtained_quantity, 2);
+    assert!(item.is_satisfied());
+    assert!(!wishlist.items[1].is_satisfied());
+
+    // 5. Wanting more makes the item unsatisfied again
+    let item_id = item.id;
+    let wishlist = service
+        .update_wishlist_item(UpdateWishlistItemRequest {
+            user_id: alice.id,
+            item_id,
+            priority: Some(WishlistPriority::Low),
+            desired_quantity: Some(NonZeroU32::new(3).unwrap()),
+            max_price: None,
+            note: None,
+        })
+        .await
+        .unwrap();
+    assert_eq!(wishlist.items[0].priority, WishlistPriority::Low);
+    assert!(!wishlist.items[0].is_satisfied());
+    assert_eq!(wishlist.items[0].note, "Any condition");
+
+    // 6. Remove an item
+    let wishlist = service
+        .remove_wishlist_item(RemoveWishlistItemRequest {
+            user_id: alice.id,
+            item_id,
+        })
+        .await
+        .unwrap();
+    assert_eq!(wishlist.items.len(), 1);
+    assert_eq!(wishlist.items[0].variant_id, rin.id);
+
+    let result = service
+        .remove_wishlist_item(RemoveWishlistItemRequest {
+            user_id: alice.id,
+            item_id,
+        })
+        .await;
+    assert!(matches!(
+        result,
+        Err(RemoveWishlistItemError::ItemNotFound { .. })
+    ));
+}
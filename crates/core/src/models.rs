@@ -1,5 +1,11 @@
+pub mod cart;
+pub mod collection;
 pub mod misc;
 pub mod product;
 pub mod purchase;
+pub mod settlement;
+pub mod statistics;
+pub mod storage;
 pub mod transfer;
 pub mod user;
+pub mod wishlist;
@@ -0,0 +1,55 @@
+use crate::models::{misc::Price, product::ProductVariantId, user::UserId};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::num::NonZeroU32;
+
+crate::create_entity_id!(CartLineId);
+
+/// A user's shopping cart, kept on the server so it follows the user across devices.
+///
+/// Each user has at most one cart. Checking out turns its lines into a new purchase order.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct Cart {
+    /// The user the cart belongs to
+    pub user_id: UserId,
+
+    /// The variants to order, in the order they were added
+    pub lines: Vec<CartLine>,
+
+    /// The timestamp when the cart was last changed.
+    pub updated_at: DateTime<Utc>,
+}
+
+impl Cart {
+    /// An empty cart for a user who has not added anything yet.
+    pub fn empty(user_id: UserId) -> Self {
+        Self {
+            user_id,
+            lines: Vec::new(),
+            updated_at: Utc::now(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct CartLine {
+    pub id: CartLineId,
+
+    /// The variant to purchase
+    pub variant_id: ProductVariantId,
+
+    /// Quantity to purchase
+    pub quantity: NonZeroU32,
+
+    /// The user who will own the purchased instances.
+    /// If None, defaults to the order receiver.
+    pub owner_id: Option<UserId>,
+
+    /// Expected price per unit
+    pub unit_price: Option<Price>,
+
+    /// The timestamp when the line was added.
+    pub added_at: DateTime<Utc>,
+}
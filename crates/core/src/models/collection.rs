@@ -0,0 +1,8 @@
+mod collection_scope;
+pub use collection_scope::*;
+
+mod completeness;
+pub use completeness::*;
+
+mod duplicate;
+pub use duplicate::*;
@@ -0,0 +1,19 @@
+use crate::models::{misc::TagId, product::ProductId};
+use serde::{Deserialize, Serialize};
+
+/// Which part of the catalog a collection query looks at.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum CollectionScope {
+    /// All variants of a product
+    Product { product_id: ProductId },
+
+    /// All variants with a tag
+    Tag {
+        tag_id: TagId,
+        /// Also include variants with any tag nested under this one
+        #[serde(default)]
+        include_descendants: bool,
+    },
+}
@@ -0,0 +1,49 @@
+use crate::models::product::{ProductVariant, ProductVariantId};
+use serde::{Deserialize, Serialize};
+
+/// How much of a product or tag a user has collected.
+///
+/// Mystery-box variants are not collectible themselves, so they are replaced by
+/// the variants they may contain.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct CollectionCompleteness {
+    /// Every variant in the scope, with how many of it the user owns
+    pub variants: Vec<VariantCompleteness>,
+
+    /// The variants the user does not own any of
+    pub missing: Vec<ProductVariantId>,
+
+    /// The share of variants the user owns at least one of, from 0 to 100
+    pub percentage: f64,
+}
+
+impl CollectionCompleteness {
+    pub fn new(variants: Vec<VariantCompleteness>) -> Self {
+        let missing: Vec<_> = variants
+            .iter()
+            .filter(|entry| entry.owned_count == 0)
+            .map(|entry| entry.variant.id)
+            .collect();
+        let percentage = if variants.is_empty() {
+            0.0
+        } else {
+            (variants.len() - missing.len()) as f64 * 100.0 / variants.len() as f64
+        };
+
+        Self {
+            variants,
+            missing,
+            percentage,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct VariantCompleteness {
+    pub variant: ProductVariant,
+
+    /// The number of `Active` instances of the variant the user owns
+    pub owned_count: u32,
+}
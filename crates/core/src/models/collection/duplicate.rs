@@ -0,0 +1,24 @@
+use crate::models::{
+    misc::Tag,
+    product::{Product, ProductInstanceId, ProductVariant},
+};
+use serde::{Deserialize, Serialize};
+
+/// A variant a user owns more copies of than they want to keep.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct DuplicateVariant {
+    pub variant: ProductVariant,
+
+    /// The product the variant belongs to
+    pub product: Product,
+
+    /// The tags of the variant
+    pub tags: Vec<Tag>,
+
+    /// The number of `Active` instances of the variant the user owns
+    pub owned_count: u32,
+
+    /// The instances beyond the oldest ones the user keeps
+    pub spare_instance_ids: Vec<ProductInstanceId>,
+}
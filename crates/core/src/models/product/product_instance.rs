@@ -1,8 +1,12 @@
 use crate::models::{
-    product::ProductVariantId, purchase::PurchaseOrderLineItemId,
-    transfer::ProductInstanceTransferHistory, user::UserId,
+    misc::{MediaId, Price},
+    product::ProductVariantId,
+    purchase::PurchaseOrderLineItemId,
+    storage::StorageLocationId,
+    transfer::ProductInstanceTransferHistory,
+    user::UserId,
 };
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, NaiveDate, Utc};
 
 crate::create_entity_id!(ProductInstanceId);
 
@@ -26,9 +30,21 @@ pub struct ProductInstance {
     /// The holder of this product instance.
     pub holder_id: UserId,
 
+    /// Where the holder keeps this instance, one of the holder's storage locations
+    pub location_id: Option<StorageLocationId>,
+
     /// Status of this instance
     pub status: ProductInstanceStatus,
 
+    /// Physical condition of this copy, if graded
+    pub condition: Option<ProductInstanceCondition>,
+
+    /// Private note about this copy (e.g. "signed at the live"), only visible to the owner
+    pub note: String,
+
+    /// Photos of this copy
+    pub medias: Vec<MediaId>,
+
     /// The line item that created this instance
     ///
     /// @unique constraint to prevent duplicate instances from the same order line item
@@ -37,6 +53,10 @@ pub struct ProductInstance {
     /// When this instance was created (when order was fulfilled)
     pub created_at: DateTime<Utc>,
 
+    /// Landed acquisition cost (snapshot at fulfillment):
+    /// the unit price plus its share of the order's adjustments
+    pub acquisition_cost: Option<Price>,
+
     /// Transfer history (optional, for auditing)
     pub transfer_history: Vec<ProductInstanceTransferHistory>,
 
@@ -44,6 +64,65 @@ pub struct ProductInstance {
     pub status_history: Vec<ProductInstanceStatusHistory>,
 }
 
+impl ProductInstance {
+    /// Replace every reference to the user `from` with the user `to`, including the history.
+    ///
+    /// Used when a placeholder is claimed by a real account.
+    pub fn replace_user(&mut self, from: UserId, to: UserId) {
+        let replace = |id: &mut UserId| {
+            if *id == from {
+                *id = to;
+            }
+        };
+
+        replace(&mut self.owner_id);
+        replace(&mut self.holder_id);
+        for history in &mut self.transfer_history {
+            history.from_owner_id.iter_mut().for_each(replace);
+            history.from_holder_id.iter_mut().for_each(replace);
+            replace(&mut history.to_owner_id);
+            replace(&mut history.to_holder_id);
+        }
+    }
+
+    /// When the current holder should give this instance back to its owner, if it was lent out.
+    pub fn return_by(&self) -> Option<NaiveDate> {
+        if self.holder_id == self.owner_id {
+            return None;
+        }
+        self.transfer_history
+            .last()
+            .filter(|history| history.to_holder_id == self.holder_id)
+            .and_then(|history| history.return_by)
+    }
+
+    /// Change the status of this instance, recording the change in the status history.
+    ///
+    /// Fails without changing anything if the state machine does not allow the transition,
+    /// see `ProductInstanceStatus::can_transition_to`.
+    pub fn transition_to(
+        &mut self,
+        status: ProductInstanceStatus,
+        reason: Option<String>,
+    ) -> Result<(), InvalidStatusTransition> {
+        if !self.status.can_transition_to(status) {
+            return Err(InvalidStatusTransition {
+                from: self.status,
+                to: status,
+            });
+        }
+
+        self.status = status;
+        self.status_history.push(ProductInstanceStatusHistory {
+            id: ProductInstanceStatusHistoryId::new(),
+            status,
+            changed_at: Utc::now(),
+            reason,
+        });
+        Ok(())
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
 #[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "snake_case")]
@@ -62,6 +141,62 @@ pub enum ProductInstanceStatus {
 
     /// Destroyed/deleted (terminal state)
     Destroyed,
+
+    /// Returned to the seller, e.g. defective or wrong item (terminal state)
+    Returned,
+}
+
+impl ProductInstanceStatus {
+    /// Whether an instance may change from this status to `to`.
+    ///
+    /// ```text
+    /// Active ──▶ Consumed ──▶ Active      (consumed by mistake)
+    /// Active ──▶ NotFound ──▶ Active      (found again)
+    ///            NotFound ──▶ Destroyed   (written off)
+    /// Active ──▶ Destroyed, Returned      (terminal)
+    /// ```
+    ///
+    /// `Locked` is not part of this state machine: only transactions lock `Active` instances
+    /// and unlock them again, without recording it in the status history.
+    pub fn can_transition_to(self, to: ProductInstanceStatus) -> bool {
+        use ProductInstanceStatus::*;
+
+        matches!(
+            (self, to),
+            (Active, Consumed | NotFound | Destroyed | Returned)
+                | (Consumed, Active)
+                | (NotFound, Active | Destroyed)
+        )
+    }
+}
+
+/// A status change not allowed by the instance state machine.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+#[error("Cannot change product instance status from {from:?} to {to:?}")]
+pub struct InvalidStatusTransition {
+    pub from: ProductInstanceStatus,
+    pub to: ProductInstanceStatus,
+}
+
+/// How well preserved a copy is, from best to worst.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum ProductInstanceCondition {
+    /// Never opened, still in the original packaging
+    Sealed,
+
+    /// Opened, but indistinguishable from new
+    Mint,
+
+    /// Minor signs of handling
+    Good,
+
+    /// Visible wear, such as scratches or bent corners
+    Fair,
+
+    /// Damaged
+    Poor,
 }
 
 crate::create_entity_id!(ProductInstanceStatusHistoryId);
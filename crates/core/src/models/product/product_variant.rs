@@ -71,6 +71,52 @@ pub struct MysteryBoxConfig {
 
     /// Possible variants that can be received/select from
     pub possible_variants: Vec<ProductVariantId>,
+
+    /// How strictly submitted results are checked against `possible_variants`
+    #[serde(default)]
+    pub validation: MysteryBoxValidation,
+}
+
+impl MysteryBoxConfig {
+    /// Find the received variants that are not possible results of this box.
+    ///
+    /// An empty `possible_variants` means the contents are unknown, so nothing is unexpected.
+    pub fn unexpected_variants(&self, received: &[ProductVariantId]) -> Vec<ProductVariantId> {
+        if self.possible_variants.is_empty() {
+            return vec![];
+        }
+
+        received
+            .iter()
+            .filter(|variant_id| !self.possible_variants.contains(variant_id))
+            .copied()
+            .collect()
+    }
+}
+
+/// Policy for mystery box results outside `possible_variants`.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum MysteryBoxValidation {
+    /// Accept any variant, e.g. for bonuses and special promotions
+    #[default]
+    Lenient,
+
+    /// Accept any variant, but report the unexpected ones
+    Warn,
+
+    /// Reject results containing unexpected variants
+    Strict,
+}
+
+/// A problem in submitted mystery box results that did not block the submission.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum MysteryBoxWarning {
+    /// The variant is not one of the box's `possible_variants` (possibly a typo)
+    UnexpectedVariant { variant_id: ProductVariantId },
 }
 
 impl ProductVariant {
@@ -117,6 +163,7 @@ impl ProductVariant {
             mystery_box: Some(MysteryBoxConfig {
                 items_count,
                 possible_variants,
+                validation: MysteryBoxValidation::default(),
             }),
             sort_order: 0,
         }
@@ -142,6 +189,14 @@ impl ProductVariant {
         self.sort_order = sort_order;
     }
 
+    /// Set how strictly mystery box results are validated.
+    /// Has no effect on regular variants.
+    pub fn set_mystery_box_validation(&mut self, validation: MysteryBoxValidation) {
+        if let Some(mystery_box) = &mut self.mystery_box {
+            mystery_box.validation = validation;
+        }
+    }
+
     /// Add a tag to this variant.
     pub fn add_tag(&mut self, tag_id: TagId) {
         if !self.tags.contains(&tag_id) {
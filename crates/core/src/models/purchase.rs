@@ -1,11 +1,32 @@
 mod purchase_order;
 pub use purchase_order::*;
 
+mod purchase_order_adjustment;
+pub use purchase_order_adjustment::*;
+
+mod purchase_order_attachment;
+pub use purchase_order_attachment::*;
+
+mod purchase_order_event;
+pub use purchase_order_event::*;
+
 mod purchase_order_item;
 pub use purchase_order_item::*;
 
 mod purchase_order_line_item;
 pub use purchase_order_line_item::*;
 
+mod purchase_order_payment;
+pub use purchase_order_payment::*;
+
+mod purchase_order_return;
+pub use purchase_order_return::*;
+
+mod purchase_order_schedule;
+pub use purchase_order_schedule::*;
+
+mod purchase_order_view;
+pub use purchase_order_view::*;
+
 mod role_filter;
 pub use role_filter::*;
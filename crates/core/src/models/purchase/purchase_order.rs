@@ -1,10 +1,20 @@
 use crate::models::{
     misc::{Address, Price},
-    purchase::PurchaseOrderItem,
+    purchase::{
+        ParticipantOrderView, PurchaseOrderAdjustment, PurchaseOrderAttachment, PurchaseOrderEvent,
+        PurchaseOrderEventKind, PurchaseOrderItem, PurchaseOrderItemId, PurchaseOrderItemStatus,
+        PurchaseOrderLineItemId, PurchaseOrderPayment, PurchaseOrderReturn, PurchaseOrderSchedule,
+        PurchaseOrderView, purchase_order_adjustment::split_by_weight,
+    },
+    settlement::{OrderSettlement, ParticipantShare},
     user::UserId,
 };
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, NaiveDate, Utc};
 use serde::{Deserialize, Serialize};
+use std::{
+    collections::{HashMap, HashSet},
+    num::NonZeroU32,
+};
 
 crate::create_entity_id!(PurchaseOrderId);
 
@@ -18,16 +28,36 @@ pub struct PurchaseOrder {
 
     /// The user who will receive the items.
     ///
-    /// Items will belong to this user first after fulfillment. Then an transaction will be created
-    /// automatically if items are designated for other users, or the receiver differs from the creator.
+    /// Items will be held by this user first after fulfillment. Then a delivery transaction
+    /// (receiver → owner) will be created automatically for items owned by other users,
+    /// e.g. items designated for other participants, or for the creator when the receiver
+    /// differs from the creator.
     pub receiver_id: UserId,
 
     /// The items being purchased
     pub items: Vec<PurchaseOrderItem>,
 
+    /// Order-level costs not belonging to any item (shipping, fees, tax, discounts)
+    pub adjustments: Vec<PurchaseOrderAdjustment>,
+
+    /// Payments from participants to the creator
+    pub payments: Vec<PurchaseOrderPayment>,
+
+    /// Line items returned to the seller after fulfillment
+    pub returns: Vec<PurchaseOrderReturn>,
+
+    /// Receipts, invoices and photos kept with this order
+    pub attachments: Vec<PurchaseOrderAttachment>,
+
+    /// Append-only log of changes to this order, oldest first
+    pub events: Vec<PurchaseOrderEvent>,
+
     /// Shipping/delivery address (if physical goods)
     pub shipping_address: Option<Address>,
 
+    /// Expected release/ship/arrival dates and shipment tracking
+    pub schedule: PurchaseOrderSchedule,
+
     /// Total amount paid
     pub total_price: Price,
 
@@ -42,6 +72,405 @@ pub struct PurchaseOrder {
     pub cancelled_at: Option<DateTime<Utc>>,
 }
 
+impl PurchaseOrder {
+    /// Append an event to the order's event log.
+    pub fn record_event(&mut self, actor_id: UserId, kind: PurchaseOrderEventKind) {
+        self.events.push(PurchaseOrderEvent::new(actor_id, kind));
+    }
+
+    /// The expected arrival date of an order that is still awaiting delivery.
+    ///
+    /// Returns `None` once the order is completed or cancelled.
+    pub fn pending_arrival_date(&self) -> Option<NaiveDate> {
+        match self.status {
+            PurchaseOrderStatus::Incomplete | PurchaseOrderStatus::PartiallyFulfilled => {
+                self.schedule.expected_arrival_date
+            }
+            PurchaseOrderStatus::Fulfilled | PurchaseOrderStatus::Cancelled => None,
+        }
+    }
+
+    /// Replace every reference to the user `from` with the user `to`.
+    ///
+    /// Used when a placeholder is claimed by a real account.
+    pub fn replace_user(&mut self, from: UserId, to: UserId) {
+        let replace = |id: &mut UserId| {
+            if *id == from {
+                *id = to;
+            }
+        };
+
+        replace(&mut self.creator_id);
+        replace(&mut self.receiver_id);
+        self.items
+            .iter_mut()
+            .flat_map(|item| item.line_items.iter_mut())
+            .for_each(|line_item| replace(&mut line_item.owner_id));
+        self.payments
+            .iter_mut()
+            .for_each(|payment| replace(&mut payment.payer_id));
+        self.attachments
+            .iter_mut()
+            .for_each(|attachment| replace(&mut attachment.uploader_id));
+        self.events
+            .iter_mut()
+            .for_each(|event| replace(&mut event.actor_id));
+    }
+
+    /// Whether `user_id` is the creator or the receiver, who see the whole order.
+    pub fn has_full_access(&self, user_id: UserId) -> bool {
+        user_id == self.creator_id || user_id == self.receiver_id
+    }
+
+    /// Whether `user_id` takes part in this order, as creator, receiver or line item owner.
+    pub fn is_participant(&self, user_id: UserId) -> bool {
+        self.has_full_access(user_id)
+            || self
+                .items
+                .iter()
+                .flat_map(|item| &item.line_items)
+                .any(|line_item| line_item.owner_id == user_id)
+    }
+
+    /// The settlement of this order visible to `user_id`.
+    ///
+    /// Like `into_view`, only the creator and the receiver see every participant's share.
+    /// Any other participant only sees their own.
+    pub fn settlement_for(&self, user_id: UserId) -> OrderSettlement {
+        let mut settlement = self.settlement();
+        if !self.has_full_access(user_id) {
+            settlement.shares.retain(|share| share.user_id == user_id);
+        }
+        settlement
+    }
+
+    /// The events of this order visible to `user_id`, oldest first.
+    ///
+    /// Like `into_view`, the creator and the receiver see every event. Any other participant
+    /// only sees events about the order as a whole, their own line items and their own
+    /// payments, with item quantities counting only their own line items.
+    pub fn events_visible_to(&self, user_id: UserId) -> Vec<PurchaseOrderEvent> {
+        if self.has_full_access(user_id) {
+            return self.events.clone();
+        }
+
+        let own_line_items: HashSet<PurchaseOrderLineItemId> = self
+            .items
+            .iter()
+            .flat_map(|item| &item.line_items)
+            .filter(|line_item| line_item.owner_id == user_id)
+            .map(|line_item| line_item.id)
+            .collect();
+        let own_items: HashSet<PurchaseOrderItemId> = self
+            .items
+            .iter()
+            .filter(|item| {
+                item.line_items
+                    .iter()
+                    .any(|line_item| own_line_items.contains(&line_item.id))
+            })
+            .map(|item| item.id)
+            .collect();
+        let own_line_item_ids = |line_item_ids: &[PurchaseOrderLineItemId]| {
+            let own: Vec<_> = line_item_ids
+                .iter()
+                .filter(|id| own_line_items.contains(*id))
+                .copied()
+                .collect();
+            (!own.is_empty()).then_some(own)
+        };
+        let own_quantity = |order_item_id: &PurchaseOrderItemId| {
+            let item = self.items.iter().find(|item| item.id == *order_item_id)?;
+            let count = item
+                .line_items
+                .iter()
+                .filter(|line_item| own_line_items.contains(&line_item.id))
+                .count();
+            NonZeroU32::new(count as u32)
+        };
+
+        self.events
+            .iter()
+            .filter_map(|event| {
+                let kind =
+                    match &event.kind {
+                        PurchaseOrderEventKind::Created
+                        | PurchaseOrderEventKind::ScheduleUpdated
+                        | PurchaseOrderEventKind::Cancelled { .. } => event.kind.clone(),
+                        PurchaseOrderEventKind::ItemAdded {
+                            order_item_id,
+                            variant_id,
+                            ..
+                        } => PurchaseOrderEventKind::ItemAdded {
+                            order_item_id: *order_item_id,
+                            variant_id: *variant_id,
+                            quantity: own_quantity(order_item_id)?,
+                        },
+                        PurchaseOrderEventKind::ItemUpdated { order_item_id }
+                        | PurchaseOrderEventKind::MysteryBoxResultsSubmitted { order_item_id }
+                            if own_items.contains(order_item_id) =>
+                        {
+                            event.kind.clone()
+                        }
+                        PurchaseOrderEventKind::MysteryBoxResultUpdated {
+                            line_item_id, ..
+                        } if own_line_items.contains(line_item_id) => event.kind.clone(),
+                        PurchaseOrderEventKind::OwnersReassigned { line_item_ids } => {
+                            PurchaseOrderEventKind::OwnersReassigned {
+                                line_item_ids: own_line_item_ids(line_item_ids)?,
+                            }
+                        }
+                        PurchaseOrderEventKind::Fulfilled { line_item_ids } => {
+                            PurchaseOrderEventKind::Fulfilled {
+                                line_item_ids: own_line_item_ids(line_item_ids)?,
+                            }
+                        }
+                        PurchaseOrderEventKind::ItemsReturned { return_id }
+                            if self.returns.iter().any(|order_return| {
+                                order_return.id == *return_id
+                                    && own_line_item_ids(&order_return.line_item_ids).is_some()
+                            }) =>
+                        {
+                            event.kind.clone()
+                        }
+                        PurchaseOrderEventKind::PaymentRecorded { payment_id }
+                        | PurchaseOrderEventKind::PaymentRemoved { payment_id }
+                            if event.actor_id == user_id
+                                || self.payments.iter().any(|payment| {
+                                    payment.id == *payment_id && payment.payer_id == user_id
+                                }) =>
+                        {
+                            event.kind.clone()
+                        }
+                        _ => return None,
+                    };
+                Some(PurchaseOrderEvent {
+                    kind,
+                    ..event.clone()
+                })
+            })
+            .collect()
+    }
+
+    /// The view of this order visible to `user_id`.
+    ///
+    /// The creator and the receiver see the whole order. Any other participant only sees
+    /// the line items they own and their share of the cost, so that the prices and items of
+    /// other participants and the shipping address are not disclosed to them.
+    pub fn into_view(self, user_id: UserId) -> PurchaseOrderView {
+        if self.has_full_access(user_id) {
+            return PurchaseOrderView::Full(self);
+        }
+
+        let share = self
+            .settlement()
+            .shares
+            .into_iter()
+            .find(|share| share.user_id == user_id)
+            .unwrap_or_else(|| {
+                let zero = Price {
+                    currency: self.total_price.currency,
+                    amount: 0,
+                };
+                ParticipantShare {
+                    user_id,
+                    owed: zero,
+                    paid: zero,
+                }
+            });
+
+        let items = self
+            .items
+            .into_iter()
+            .filter_map(|mut item| {
+                item.line_items
+                    .retain(|line_item| line_item.owner_id == user_id);
+
+                // The quantity and price of the whole item reveal what others bought
+                item.quantity = NonZeroU32::new(item.line_items.len() as u32)?;
+                item.unit_price = None;
+                Some(item)
+            })
+            .collect();
+
+        PurchaseOrderView::Participant(ParticipantOrderView {
+            id: self.id,
+            creator_id: self.creator_id,
+            receiver_id: self.receiver_id,
+            participant_id: user_id,
+            items,
+            share,
+            schedule: self.schedule,
+            status: self.status,
+            created_at: self.created_at,
+            completed_at: self.completed_at,
+            cancelled_at: self.cancelled_at,
+        })
+    }
+
+    /// Calculate the landed acquisition cost of each line item.
+    ///
+    /// The base cost of a line item is its item's `unit_price * quantity` split evenly over
+    /// the item's line items. A replacement line item takes over the cost of the line item
+    /// it replaces. Every adjustment is then allocated on top according to its
+    /// allocation policy. Cancelled items are excluded, and so are mystery boxes still
+    /// awaiting results, since their cost cannot be split before every result is in.
+    ///
+    /// Line items without any price information (no unit price and no adjustments) are omitted.
+    pub fn landed_costs(&self) -> HashMap<PurchaseOrderLineItemId, Price> {
+        let mut bases: Vec<(PurchaseOrderLineItemId, u64)> = vec![];
+        let mut priced: Vec<bool> = vec![];
+        for item in &self.items {
+            if matches!(
+                item.status,
+                PurchaseOrderItemStatus::Cancelled | PurchaseOrderItemStatus::AwaitingInput
+            ) || item.line_items.is_empty()
+            {
+                continue;
+            }
+
+            let item_total = item
+                .unit_price
+                .map(|price| price.amount as u64 * item.quantity.get() as u64)
+                .unwrap_or(0);
+            let originals: Vec<PurchaseOrderLineItemId> = item
+                .line_items
+                .iter()
+                .filter(|line_item| line_item.replaces.is_none())
+                .map(|line_item| line_item.id)
+                .collect();
+            let shares = split_by_weight(item_total, &vec![1; originals.len()]);
+            let mut item_bases: Vec<(PurchaseOrderLineItemId, u64)> =
+                originals.into_iter().zip(shares).collect();
+
+            // A replacement takes over the share of the line item it replaces
+            for line_item in &item.line_items {
+                if let Some(base) = item_bases
+                    .iter_mut()
+                    .find(|(id, _)| line_item.replaces == Some(*id))
+                {
+                    base.0 = line_item.id;
+                }
+            }
+
+            for base in item_bases {
+                bases.push(base);
+                priced.push(item.unit_price.is_some());
+            }
+        }
+
+        let mut costs: Vec<i64> = bases.iter().map(|(_, base)| *base as i64).collect();
+        for adjustment in &self.adjustments {
+            let shares = adjustment.allocate(&bases);
+            for (cost, share) in costs.iter_mut().zip(shares) {
+                if adjustment.kind.is_deduction() {
+                    *cost -= share as i64;
+                } else {
+                    *cost += share as i64;
+                }
+            }
+        }
+
+        bases
+            .into_iter()
+            .zip(costs)
+            .zip(priced)
+            .filter(|(_, priced)| *priced || !self.adjustments.is_empty())
+            .map(|(((id, _), cost), _)| {
+                let price = Price {
+                    currency: self.total_price.currency,
+                    amount: cost.clamp(0, u32::MAX as i64) as u32,
+                };
+                (id, price)
+            })
+            .collect()
+    }
+
+    /// Calculate what each participant owes the creator.
+    ///
+    /// A participant owes the landed cost of every line item they own (see `landed_costs`),
+    /// so shared costs are split by the allocation policy of each adjustment.
+    /// Refunds of returned line items are passed on to their owners, and recorded payments
+    /// are credited to their payers. The creator's own share is omitted.
+    pub fn settlement(&self) -> OrderSettlement {
+        let landed_costs = self.landed_costs();
+
+        // (user, owed, paid)
+        let mut totals: Vec<(UserId, u64, u64)> = vec![];
+        for line_item in self.items.iter().flat_map(|item| &item.line_items) {
+            if line_item.owner_id == self.creator_id {
+                continue;
+            }
+            let Some(cost) = landed_costs.get(&line_item.id) else {
+                continue;
+            };
+
+            match totals
+                .iter_mut()
+                .find(|(user_id, _, _)| *user_id == line_item.owner_id)
+            {
+                Some((_, owed, _)) => *owed += cost.amount as u64,
+                None => totals.push((line_item.owner_id, cost.amount as u64, 0)),
+            }
+        }
+        for order_return in &self.returns {
+            let Some(refund) = order_return.refund else {
+                continue;
+            };
+
+            let shares = split_by_weight(
+                refund.amount as u64,
+                &vec![1; order_return.line_item_ids.len()],
+            );
+            for (line_item_id, share) in order_return.line_item_ids.iter().zip(shares) {
+                let Some(line_item) = self
+                    .items
+                    .iter()
+                    .flat_map(|item| &item.line_items)
+                    .find(|line_item| line_item.id == *line_item_id)
+                else {
+                    continue;
+                };
+
+                if let Some((_, owed, _)) = totals
+                    .iter_mut()
+                    .find(|(user_id, _, _)| *user_id == line_item.owner_id)
+                {
+                    *owed = owed.saturating_sub(share);
+                }
+            }
+        }
+        for payment in &self.payments {
+            match totals
+                .iter_mut()
+                .find(|(user_id, _, _)| *user_id == payment.payer_id)
+            {
+                Some((_, _, paid)) => *paid += payment.amount.amount as u64,
+                None => totals.push((payment.payer_id, 0, payment.amount.amount as u64)),
+            }
+        }
+
+        let currency = self.total_price.currency;
+        let to_price = |amount: u64| Price {
+            currency,
+            amount: u32::try_from(amount).unwrap_or(u32::MAX),
+        };
+        OrderSettlement {
+            order_id: self.id,
+            creditor_id: self.creator_id,
+            currency,
+            shares: totals
+                .into_iter()
+                .map(|(user_id, owed, paid)| ParticipantShare {
+                    user_id,
+                    owed: to_price(owed),
+                    paid: to_price(paid),
+                })
+                .collect(),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "snake_case")]
@@ -50,13 +479,19 @@ pub enum PurchaseOrderStatus {
     /// (e.g., filling in mystery box results)
     Incomplete,
 
-    /// All details filled, instances created in user's inventory
+    /// Some items or line items have been fulfilled, others are still awaiting
+    /// input or delivery (e.g. split shipments)
+    PartiallyFulfilled,
+
+    /// All items are done (fulfilled, or the remainder cancelled),
+    /// instances created in user's inventory
     Fulfilled,
 
     /// Order was cancelled before fulfillment.
     ///
     /// This status can only be reached from Incomplete state.
-    /// Completed orders cannot be cancelled.
+    /// Completed orders cannot be cancelled. Cancelling a partially fulfilled order
+    /// cancels the remaining items and completes the order instead.
     ///
     /// Common reasons:
     /// - User input error (wants to delete the record)
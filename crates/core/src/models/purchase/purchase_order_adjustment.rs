@@ -0,0 +1,150 @@
+use crate::models::{misc::Price, purchase::PurchaseOrderLineItemId};
+use serde::{Deserialize, Serialize};
+
+crate::create_entity_id!(PurchaseOrderAdjustmentId);
+
+/// An order-level cost that doesn't belong to any `PurchaseOrderItem`,
+/// such as shipping, service fees, tax or coupon discounts.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct PurchaseOrderAdjustment {
+    pub id: PurchaseOrderAdjustmentId,
+
+    /// What this adjustment is for
+    pub kind: PurchaseOrderAdjustmentKind,
+
+    /// The amount of this adjustment (always non-negative).
+    /// Whether it is added to or subtracted from the total depends on `kind`.
+    pub amount: Price,
+
+    /// How this adjustment is spread over line items
+    pub allocation: AdjustmentAllocation,
+
+    /// Optional free-form note (e.g. coupon code, carrier)
+    pub note: Option<String>,
+}
+
+impl PurchaseOrderAdjustment {
+    /// Whether this adjustment is manually allocated to the given line item.
+    pub fn allocates_to(&self, line_item_id: PurchaseOrderLineItemId) -> bool {
+        match &self.allocation {
+            AdjustmentAllocation::Manual { allocations } => allocations
+                .iter()
+                .any(|allocation| allocation.line_item_id == line_item_id),
+            AdjustmentAllocation::ByPrice | AdjustmentAllocation::ByCount => false,
+        }
+    }
+
+    /// Split this adjustment over the given line items.
+    ///
+    /// `line_items` pairs each line item with its base cost. The returned amounts are in
+    /// the same order, and are always non-negative (see `kind` for the sign).
+    pub fn allocate(&self, line_items: &[(PurchaseOrderLineItemId, u64)]) -> Vec<u64> {
+        let amount = self.amount.amount as u64;
+        match &self.allocation {
+            AdjustmentAllocation::ByPrice => {
+                let weights: Vec<u64> = line_items.iter().map(|(_, base)| *base).collect();
+                split_by_weight(amount, &weights)
+            }
+            AdjustmentAllocation::ByCount => split_by_weight(amount, &vec![1; line_items.len()]),
+            AdjustmentAllocation::Manual { allocations } => line_items
+                .iter()
+                .map(|(id, _)| {
+                    allocations
+                        .iter()
+                        .filter(|allocation| allocation.line_item_id == *id)
+                        .map(|allocation| allocation.amount as u64)
+                        .sum()
+                })
+                .collect(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum PurchaseOrderAdjustmentKind {
+    /// Shipping from the seller to the proxy/forwarder
+    DomesticShipping,
+
+    /// Shipping from the proxy/forwarder to the receiver
+    InternationalShipping,
+
+    /// Proxy or payment service fee
+    ServiceFee,
+
+    /// Tax and import duties
+    Tax,
+
+    /// Coupon or other discount (subtracted from the total)
+    Discount,
+}
+
+impl PurchaseOrderAdjustmentKind {
+    /// Whether this kind of adjustment reduces the total.
+    pub fn is_deduction(&self) -> bool {
+        matches!(self, PurchaseOrderAdjustmentKind::Discount)
+    }
+}
+
+/// Policy for spreading an adjustment over line items.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum AdjustmentAllocation {
+    /// Proportional to each line item's price.
+    /// Falls back to `ByCount` if no line item has a price.
+    ByPrice,
+
+    /// Evenly across line items
+    ByCount,
+
+    /// Explicit amounts per line item
+    Manual { allocations: Vec<ManualAllocation> },
+}
+
+/// A manually assigned share of an adjustment.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct ManualAllocation {
+    pub line_item_id: PurchaseOrderLineItemId,
+
+    /// The amount in the smallest currency unit
+    pub amount: u32,
+}
+
+/// Split `amount` proportionally to `weights`.
+///
+/// Rounding remainders go to the first weighted entries, so the shares always sum to `amount`.
+/// If all weights are zero, the amount is split evenly.
+pub(super) fn split_by_weight(amount: u64, weights: &[u64]) -> Vec<u64> {
+    if weights.is_empty() {
+        return vec![];
+    }
+
+    let total: u64 = weights.iter().sum();
+    if total == 0 {
+        return split_by_weight(amount, &vec![1; weights.len()]);
+    }
+
+    let mut shares: Vec<u64> = weights
+        .iter()
+        .map(|weight| (amount as u128 * *weight as u128 / total as u128) as u64)
+        .collect();
+
+    let mut remainder = amount - shares.iter().sum::<u64>();
+    for (share, _) in shares
+        .iter_mut()
+        .zip(weights)
+        .filter(|(_, weight)| **weight > 0)
+    {
+        if remainder == 0 {
+            break;
+        }
+        *share += 1;
+        remainder -= 1;
+    }
+
+    shares
+}
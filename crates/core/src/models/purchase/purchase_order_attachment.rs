@@ -0,0 +1,43 @@
+use crate::models::{misc::MediaId, user::UserId};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+crate::create_entity_id!(PurchaseOrderAttachmentId);
+
+/// A file kept with an order, such as a receipt screenshot, an invoice or a shipping label.
+///
+/// Attachments are only visible to the creator and the receiver of the order.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct PurchaseOrderAttachment {
+    pub id: PurchaseOrderAttachmentId,
+
+    /// The uploaded file
+    pub media_id: MediaId,
+
+    /// What the file is
+    pub kind: PurchaseOrderAttachmentKind,
+
+    /// Optional free-form note
+    pub note: Option<String>,
+
+    /// The user who attached the file
+    pub uploader_id: UserId,
+
+    /// The timestamp when the file was attached.
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum PurchaseOrderAttachmentKind {
+    /// Receipt or order confirmation (e.g. a screenshot from the shop)
+    Receipt,
+
+    /// Invoice issued by the seller or the proxy service
+    Invoice,
+
+    /// Any other photo, such as the parcel or its shipping label
+    Photo,
+}
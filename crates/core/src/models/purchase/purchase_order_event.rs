@@ -0,0 +1,127 @@
+use crate::models::{
+    product::ProductVariantId,
+    purchase::{
+        PurchaseOrderAdjustmentId, PurchaseOrderAttachmentId, PurchaseOrderItemId,
+        PurchaseOrderLineItemId, PurchaseOrderPaymentId, PurchaseOrderReturnId,
+    },
+    user::UserId,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::num::NonZeroU32;
+
+crate::create_entity_id!(PurchaseOrderEventId);
+
+/// An entry in the append-only event log of an order.
+///
+/// Events are recorded by the services whenever an order changes,
+/// and are never modified or removed afterwards.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct PurchaseOrderEvent {
+    pub id: PurchaseOrderEventId,
+
+    /// The user who performed the change
+    pub actor_id: UserId,
+
+    /// What happened
+    pub kind: PurchaseOrderEventKind,
+
+    /// The timestamp when the event happened.
+    pub created_at: DateTime<Utc>,
+}
+
+impl PurchaseOrderEvent {
+    pub fn new(actor_id: UserId, kind: PurchaseOrderEventKind) -> Self {
+        Self {
+            id: PurchaseOrderEventId::new(),
+            actor_id,
+            kind,
+            created_at: Utc::now(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum PurchaseOrderEventKind {
+    /// The order was created
+    Created,
+
+    /// Order details (receiver, shipping address) were edited
+    Updated,
+
+    /// The expected dates or shipment tracking were edited
+    ScheduleUpdated,
+
+    /// An item was added to the order
+    ItemAdded {
+        order_item_id: PurchaseOrderItemId,
+        variant_id: ProductVariantId,
+        quantity: NonZeroU32,
+    },
+
+    /// The quantity or unit price of an item was edited
+    ItemUpdated { order_item_id: PurchaseOrderItemId },
+
+    /// An item was removed from the order
+    ItemRemoved { order_item_id: PurchaseOrderItemId },
+
+    /// Line items were assigned to different owners
+    OwnersReassigned {
+        line_item_ids: Vec<PurchaseOrderLineItemId>,
+    },
+
+    /// An order-level adjustment was added
+    AdjustmentAdded {
+        adjustment_id: PurchaseOrderAdjustmentId,
+    },
+
+    /// An order-level adjustment was removed
+    AdjustmentRemoved {
+        adjustment_id: PurchaseOrderAdjustmentId,
+    },
+
+    /// A receipt, invoice or photo was attached
+    AttachmentAdded {
+        attachment_id: PurchaseOrderAttachmentId,
+    },
+
+    /// An attachment was removed
+    AttachmentRemoved {
+        attachment_id: PurchaseOrderAttachmentId,
+    },
+
+    /// The opened contents of a mystery box item were submitted
+    MysteryBoxResultsSubmitted { order_item_id: PurchaseOrderItemId },
+
+    /// A mystery box result was corrected or reassigned
+    MysteryBoxResultUpdated {
+        order_item_id: PurchaseOrderItemId,
+        line_item_id: PurchaseOrderLineItemId,
+    },
+
+    /// A mystery box result was removed
+    MysteryBoxResultRemoved {
+        order_item_id: PurchaseOrderItemId,
+        line_item_id: PurchaseOrderLineItemId,
+    },
+
+    /// Line items were fulfilled (instances created)
+    Fulfilled {
+        line_item_ids: Vec<PurchaseOrderLineItemId>,
+    },
+
+    /// The order was cancelled, or its remaining items were
+    Cancelled { reason: Option<String> },
+
+    /// Fulfilled line items were returned to the seller
+    ItemsReturned { return_id: PurchaseOrderReturnId },
+
+    /// A participant payment was recorded
+    PaymentRecorded { payment_id: PurchaseOrderPaymentId },
+
+    /// A participant payment was removed
+    PaymentRemoved { payment_id: PurchaseOrderPaymentId },
+}
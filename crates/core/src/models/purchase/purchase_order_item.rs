@@ -18,9 +18,10 @@ pub struct PurchaseOrderItem {
     /// Invariants:
     /// - For regular items: SHOULD be equal to `product_variant_id` * `quantity`
     /// - For mystery boxes: length SHOULD equal `quantity * mystery_box.count`
-    ///   - Each variant SHOULD be in `mystery_box.possible_variants` (soft requirement)
+    ///   - Each variant SHOULD be in `mystery_box.possible_variants`, enforced according to
+    ///     `mystery_box.validation` (lenient by default)
     ///
-    /// Note: Validation is lenient to allow for:
+    /// Note: Validation is lenient by default to allow for:
     /// - Special promotions/bonuses
     /// - User errors that can be corrected later
     pub line_items: Vec<PurchaseOrderLineItem>,
@@ -53,6 +54,7 @@ pub enum PurchaseOrderItemStatus {
 
     /// All information complete, ready to create instances.
     /// This is the "gate" status - order can only be fulfilled when ALL items are Pending.
+    /// Some line items may already be fulfilled individually (e.g. split shipments).
     Pending,
 
     /// Instances created successfully (terminal state).
@@ -1,4 +1,5 @@
 use crate::models::{
+    misc::Price,
     product::{
         ProductInstance, ProductInstanceId, ProductInstanceStatus, ProductInstanceStatusHistory,
         ProductInstanceStatusHistoryId, ProductVariantId,
@@ -31,6 +32,12 @@ pub struct PurchaseOrderLineItem {
     pub instance_id: Option<ProductInstanceId>,
     /// The timestamp when the instance was created
     pub fulfilled_at: Option<DateTime<Utc>>,
+
+    /// The timestamp when the instance was returned to the seller
+    pub returned_at: Option<DateTime<Utc>>,
+
+    /// The returned line item this one replaces (e.g. a defective item sent again)
+    pub replaces: Option<PurchaseOrderLineItemId>,
 }
 
 impl PurchaseOrderLineItem {
@@ -46,6 +53,16 @@ impl PurchaseOrderLineItem {
             owner_id,
             instance_id: None,
             fulfilled_at: None,
+            returned_at: None,
+            replaces: None,
+        }
+    }
+
+    /// Create an unfulfilled line item replacing this one.
+    pub fn replacement(&self) -> Self {
+        Self {
+            replaces: Some(self.id),
+            ..Self::new(self.variant_id, self.purchase_order_item_id, self.owner_id)
         }
     }
 
@@ -53,16 +70,29 @@ impl PurchaseOrderLineItem {
         self.instance_id.is_some() && self.fulfilled_at.is_some()
     }
 
-    pub fn to_product_instance(&self, holder_id: UserId) -> ProductInstance {
+    pub fn is_returned(&self) -> bool {
+        self.returned_at.is_some()
+    }
+
+    pub fn to_product_instance(
+        &self,
+        holder_id: UserId,
+        acquisition_cost: Option<Price>,
+    ) -> ProductInstance {
         let now = Utc::now();
         ProductInstance {
             id: ProductInstanceId::new(),
             variant_id: self.variant_id,
             owner_id: self.owner_id,
             holder_id,
+            location_id: None,
             status: ProductInstanceStatus::Active,
+            condition: None,
+            note: String::new(),
+            medias: Vec::new(),
             source_order_line_item_id: self.id,
             created_at: now,
+            acquisition_cost,
             transfer_history: vec![ProductInstanceTransferHistory {
                 id: ProductInstanceTransferHistoryId::new(),
                 from_owner_id: None,
@@ -70,6 +100,7 @@ impl PurchaseOrderLineItem {
                 to_owner_id: self.owner_id,
                 to_holder_id: holder_id,
                 reason: TransferReason::Purchase,
+                return_by: None,
                 transferred_at: now,
             }],
             status_history: vec![ProductInstanceStatusHistory {
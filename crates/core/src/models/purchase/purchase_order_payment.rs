@@ -0,0 +1,25 @@
+use crate::models::{misc::Price, user::UserId};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+crate::create_entity_id!(PurchaseOrderPaymentId);
+
+/// A payment from a group-buy participant to the order creator,
+/// recorded against the order it settles.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct PurchaseOrderPayment {
+    pub id: PurchaseOrderPaymentId,
+
+    /// The participant who paid
+    pub payer_id: UserId,
+
+    /// The amount paid, in the order currency
+    pub amount: Price,
+
+    /// Optional free-form note (e.g. payment method, reference)
+    pub note: Option<String>,
+
+    /// The timestamp when the payment was made.
+    pub paid_at: DateTime<Utc>,
+}
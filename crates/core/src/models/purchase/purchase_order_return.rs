@@ -0,0 +1,27 @@
+use crate::models::{misc::Price, purchase::PurchaseOrderLineItemId};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+crate::create_entity_id!(PurchaseOrderReturnId);
+
+/// A return of fulfilled line items to the seller.
+///
+/// The instances of returned line items end up `Returned`. Replacements sent by the seller
+/// are added as new line items referencing the returned ones (see `PurchaseOrderLineItem::replaces`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct PurchaseOrderReturn {
+    pub id: PurchaseOrderReturnId,
+
+    /// The returned line items
+    pub line_item_ids: Vec<PurchaseOrderLineItemId>,
+
+    /// The amount refunded by the seller, if any
+    pub refund: Option<Price>,
+
+    /// Optional reason (e.g. defective, wrong item)
+    pub reason: Option<String>,
+
+    /// The timestamp when the items were returned.
+    pub returned_at: DateTime<Utc>,
+}
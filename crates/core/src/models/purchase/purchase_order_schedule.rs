@@ -0,0 +1,31 @@
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+/// Expected dates and shipment tracking of an order.
+///
+/// Most useful for pre-orders, which stay `Incomplete` for months before they arrive.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct PurchaseOrderSchedule {
+    /// When the products are expected to be released
+    pub expected_release_date: Option<NaiveDate>,
+
+    /// When the seller is expected to ship the order
+    pub expected_ship_date: Option<NaiveDate>,
+
+    /// When the order is expected to arrive at the receiver
+    pub expected_arrival_date: Option<NaiveDate>,
+
+    /// Tracking of the shipment, once shipped
+    pub tracking: Option<ShipmentTracking>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct ShipmentTracking {
+    /// The carrier handling the shipment (e.g. Japan Post, Yamato)
+    pub carrier: String,
+
+    /// The carrier's tracking number
+    pub tracking_number: String,
+}
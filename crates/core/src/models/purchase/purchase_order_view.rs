@@ -0,0 +1,96 @@
+use crate::models::{
+    purchase::{
+        PurchaseOrder, PurchaseOrderId, PurchaseOrderItem, PurchaseOrderSchedule,
+        PurchaseOrderStatus,
+    },
+    settlement::ParticipantShare,
+    user::UserId,
+};
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+
+/// An order as visible to one of its participants.
+///
+/// See `PurchaseOrder::into_view` for who gets which view.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(tag = "view", rename_all = "snake_case")]
+pub enum PurchaseOrderView {
+    /// The whole order, visible to its creator and receiver
+    Full(PurchaseOrder),
+
+    /// Only the part of the order belonging to a line item owner
+    Participant(ParticipantOrderView),
+}
+
+/// The part of a group-buy order visible to a participant who only owns some line items.
+///
+/// Other participants' line items, the order-level costs, the attachments and the shipping
+/// address are hidden.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct ParticipantOrderView {
+    pub id: PurchaseOrderId,
+
+    /// The user who created (and paid for) the order
+    pub creator_id: UserId,
+
+    /// The user who will receive the shipment and hand the items over
+    pub receiver_id: UserId,
+
+    /// The participant this view belongs to
+    pub participant_id: UserId,
+
+    /// The items containing line items owned by the participant, with only those line items.
+    ///
+    /// The quantity counts only the participant's line items, and the unit price is hidden.
+    pub items: Vec<PurchaseOrderItem>,
+
+    /// What the participant owes the creator for their line items, and has paid so far
+    pub share: ParticipantShare,
+
+    /// Expected release/ship/arrival dates and shipment tracking
+    pub schedule: PurchaseOrderSchedule,
+
+    /// Current status of the order
+    pub status: PurchaseOrderStatus,
+
+    /// The timestamp when the order was created.
+    pub created_at: DateTime<Utc>,
+    /// The timestamp when the order was completed.
+    pub completed_at: Option<DateTime<Utc>>,
+    /// The timestamp when the order was cancelled.
+    pub cancelled_at: Option<DateTime<Utc>>,
+}
+
+impl PurchaseOrderView {
+    pub fn id(&self) -> PurchaseOrderId {
+        match self {
+            PurchaseOrderView::Full(order) => order.id,
+            PurchaseOrderView::Participant(view) => view.id,
+        }
+    }
+
+    pub fn schedule(&self) -> &PurchaseOrderSchedule {
+        match self {
+            PurchaseOrderView::Full(order) => &order.schedule,
+            PurchaseOrderView::Participant(view) => &view.schedule,
+        }
+    }
+
+    /// The expected arrival date of an order that is still awaiting delivery.
+    ///
+    /// See `PurchaseOrder::pending_arrival_date`.
+    pub fn pending_arrival_date(&self) -> Option<NaiveDate> {
+        let status = match self {
+            PurchaseOrderView::Full(order) => order.status,
+            PurchaseOrderView::Participant(view) => view.status,
+        };
+        match status {
+            PurchaseOrderStatus::Incomplete | PurchaseOrderStatus::PartiallyFulfilled => {
+                self.schedule().expected_arrival_date
+            }
+            PurchaseOrderStatus::Fulfilled | PurchaseOrderStatus::Cancelled => None,
+        }
+    }
+}
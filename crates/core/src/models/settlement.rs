@@ -0,0 +1,5 @@
+mod balance;
+pub use balance::*;
+
+mod order_settlement;
+pub use order_settlement::*;
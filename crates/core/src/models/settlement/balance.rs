@@ -0,0 +1,88 @@
+use crate::models::{
+    misc::{Currency, Price},
+    user::UserId,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// An amount owed by one user to another.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct Balance {
+    /// The user who owes the amount
+    pub debtor_id: UserId,
+
+    /// The user who is owed the amount
+    pub creditor_id: UserId,
+
+    /// The amount owed
+    pub amount: Price,
+}
+
+/// Net a set of balances into the fewest transfers that settle all of them.
+///
+/// Balances are reduced to the net position of every user in each currency, then the
+/// largest debtors are matched with the largest creditors. Currencies are never mixed.
+pub fn net_balances(balances: &[Balance]) -> Vec<Balance> {
+    // Net position of every user in each currency (positive: owed money)
+    let mut positions: Vec<(Currency, HashMap<UserId, i64>)> = vec![];
+    for balance in balances {
+        let index = match positions
+            .iter()
+            .position(|(currency, _)| *currency == balance.amount.currency)
+        {
+            Some(index) => index,
+            None => {
+                positions.push((balance.amount.currency, HashMap::new()));
+                positions.len() - 1
+            }
+        };
+
+        let users = &mut positions[index].1;
+        let amount = balance.amount.amount as i64;
+        *users.entry(balance.creditor_id).or_default() += amount;
+        *users.entry(balance.debtor_id).or_default() -= amount;
+    }
+
+    let mut netted = vec![];
+    for (currency, users) in positions {
+        let mut creditors: Vec<(UserId, i64)> = users
+            .iter()
+            .filter(|(_, position)| **position > 0)
+            .map(|(user_id, position)| (*user_id, *position))
+            .collect();
+        let mut debtors: Vec<(UserId, i64)> = users
+            .iter()
+            .filter(|(_, position)| **position < 0)
+            .map(|(user_id, position)| (*user_id, -*position))
+            .collect();
+
+        // Largest amounts first, ties broken by id for a stable result
+        creditors.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.0.cmp(&b.0.0)));
+        debtors.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.0.cmp(&b.0.0)));
+
+        let (mut debtor, mut creditor) = (0, 0);
+        while debtor < debtors.len() && creditor < creditors.len() {
+            let amount = debtors[debtor].1.min(creditors[creditor].1);
+            netted.push(Balance {
+                debtor_id: debtors[debtor].0,
+                creditor_id: creditors[creditor].0,
+                amount: Price {
+                    currency,
+                    amount: u32::try_from(amount).unwrap_or(u32::MAX),
+                },
+            });
+
+            debtors[debtor].1 -= amount;
+            creditors[creditor].1 -= amount;
+            if debtors[debtor].1 == 0 {
+                debtor += 1;
+            }
+            if creditors[creditor].1 == 0 {
+                creditor += 1;
+            }
+        }
+    }
+
+    netted
+}
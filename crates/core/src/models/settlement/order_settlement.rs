@@ -0,0 +1,73 @@
+use crate::models::{
+    misc::{Currency, Price},
+    purchase::PurchaseOrderId,
+    settlement::Balance,
+    user::UserId,
+};
+use serde::{Deserialize, Serialize};
+
+/// What each participant of a group-buy order owes the order creator.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct OrderSettlement {
+    pub order_id: PurchaseOrderId,
+
+    /// The user who paid for the order, owed by all other participants
+    pub creditor_id: UserId,
+
+    /// The currency of the order, all amounts are in this currency
+    pub currency: Currency,
+
+    /// The share of every participant other than the creditor
+    pub shares: Vec<ParticipantShare>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct ParticipantShare {
+    pub user_id: UserId,
+
+    /// The landed cost of the line items owned by this participant, less refunds
+    pub owed: Price,
+
+    /// The total of the payments recorded from this participant
+    pub paid: Price,
+}
+
+impl OrderSettlement {
+    /// The outstanding balances between the participants and the creditor.
+    ///
+    /// Underpaid shares are owed to the creditor, overpaid shares (e.g. after an item
+    /// was cancelled) are owed back to the participant. Settled shares are omitted.
+    pub fn balances(&self) -> Vec<Balance> {
+        self.shares
+            .iter()
+            .filter_map(|share| {
+                let (debtor_id, creditor_id, amount) = if share.owed.amount > share.paid.amount {
+                    (
+                        share.user_id,
+                        self.creditor_id,
+                        share.owed.amount - share.paid.amount,
+                    )
+                } else if share.paid.amount > share.owed.amount {
+                    (
+                        self.creditor_id,
+                        share.user_id,
+                        share.paid.amount - share.owed.amount,
+                    )
+                } else {
+                    return None;
+                };
+
+                Some(Balance {
+                    debtor_id,
+                    creditor_id,
+                    amount: Price {
+                        currency: self.currency,
+                        amount,
+                    },
+                })
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,146 @@
+use crate::models::{
+    misc::{Currency, TagId},
+    product::{ProductId, ProductInstanceStatus, ProductVariantId},
+};
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+
+/// The days a statistic covers, both ends inclusive. A missing end is unbounded.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct DateRange {
+    /// The first day to include
+    pub from: Option<NaiveDate>,
+
+    /// The last day to include
+    pub to: Option<NaiveDate>,
+}
+
+impl DateRange {
+    /// Whether the start is not after the end.
+    pub fn is_valid(&self) -> bool {
+        match (self.from, self.to) {
+            (Some(from), Some(to)) => from <= to,
+            _ => true,
+        }
+    }
+
+    pub fn contains(&self, at: DateTime<Utc>) -> bool {
+        let date = at.date_naive();
+        self.from.is_none_or(|from| from <= date) && self.to.is_none_or(|to| date <= to)
+    }
+}
+
+/// A sum of money in one currency.
+///
+/// Unlike `Price`, the amount is wide enough for totals over many orders.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct CurrencyAmount {
+    pub currency: Currency,
+
+    /// The amount in the smallest currency unit
+    pub amount: u64,
+}
+
+impl CurrencyAmount {
+    /// Add `amount` to the total of its currency in `totals`.
+    pub fn add_to(totals: &mut Vec<Self>, currency: Currency, amount: u64) {
+        match totals.iter_mut().find(|total| total.currency == currency) {
+            Some(total) => total.amount += amount,
+            None => totals.push(Self { currency, amount }),
+        }
+    }
+}
+
+/// What a user spent in one month and currency.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct MonthlySpending {
+    pub year: i32,
+
+    /// The month, from 1 to 12
+    pub month: u32,
+
+    pub spent: CurrencyAmount,
+}
+
+/// How many of a user's instances are in a status.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct StatusCount {
+    pub status: ProductInstanceStatus,
+    pub count: u64,
+}
+
+/// How many instances with a tag a user owns, and what they cost.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct TagStatistics {
+    pub tag_id: TagId,
+    pub name: String,
+    pub instance_count: u64,
+
+    /// The landed acquisition costs of the instances, per currency
+    pub spent: Vec<CurrencyAmount>,
+}
+
+/// How many instances of a product a user owns, and what they cost.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct ProductStatistics {
+    pub product_id: ProductId,
+    pub name: String,
+    pub instance_count: u64,
+
+    /// The landed acquisition costs of the instances, per currency
+    pub spent: Vec<CurrencyAmount>,
+}
+
+/// How many items of a series (product) a user bought.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct SeriesPurchaseCount {
+    pub product_id: ProductId,
+    pub name: String,
+
+    /// The number of fulfilled line items, counting each item drawn from a mystery box
+    pub purchased_count: u64,
+}
+
+/// What a user paid for the contents of a mystery-box variant.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct MysteryBoxStatistics {
+    /// The mystery-box variant
+    pub variant_id: ProductVariantId,
+    pub name: String,
+
+    /// The number of items drawn from the boxes
+    pub items_obtained: u64,
+
+    /// The number of different variants drawn from the boxes
+    pub distinct_variants_obtained: u64,
+
+    /// The landed acquisition costs of the drawn items, per currency
+    pub spent: Vec<CurrencyAmount>,
+
+    /// `spent` divided by `distinct_variants_obtained`, per currency
+    pub average_cost_per_variant: Vec<CurrencyAmount>,
+}
+
+impl MysteryBoxStatistics {
+    /// Fill in `average_cost_per_variant` from `spent` and `distinct_variants_obtained`.
+    pub fn with_averages(mut self) -> Self {
+        let distinct = self.distinct_variants_obtained.max(1);
+        self.average_cost_per_variant = self
+            .spent
+            .iter()
+            .map(|total| CurrencyAmount {
+                currency: total.currency,
+                amount: total.amount / distinct,
+            })
+            .collect();
+        self
+    }
+}
@@ -0,0 +1,5 @@
+mod storage_location;
+pub use storage_location::*;
+
+mod located_product_instance;
+pub use located_product_instance::*;
@@ -0,0 +1,19 @@
+use crate::models::{
+    product::{ProductInstance, ProductVariant},
+    storage::StorageLocation,
+};
+use serde::{Deserialize, Serialize};
+
+/// A product instance with where it is kept, answering "where is my X".
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct LocatedProductInstance {
+    pub instance: ProductInstance,
+
+    /// The variant of the instance
+    pub variant: ProductVariant,
+
+    /// The path from the top-level location down to the instance's location,
+    /// empty if the instance has no location
+    pub location_path: Vec<StorageLocation>,
+}
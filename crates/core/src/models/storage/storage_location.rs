@@ -0,0 +1,87 @@
+use crate::models::{misc::NonEmptyString, user::UserId};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+crate::create_entity_id!(StorageLocationId);
+
+/// A place where a user physically keeps their goods, such as a room, a shelf or a box.
+///
+/// Locations form a per-user tree, e.g. "Bedroom" > "Shelf 2" > "Blue box".
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct StorageLocation {
+    pub id: StorageLocationId,
+
+    /// The user the location belongs to
+    pub user_id: UserId,
+
+    /// The name of the location
+    pub name: NonEmptyString,
+
+    /// The location containing this one, or `None` for a top-level location.
+    ///
+    /// Cycles are prevented at the application layer.
+    pub parent_id: Option<StorageLocationId>,
+
+    /// The timestamp when the location was created.
+    pub created_at: DateTime<Utc>,
+}
+
+impl StorageLocation {
+    pub fn new(
+        user_id: UserId,
+        name: NonEmptyString,
+        parent_id: Option<StorageLocationId>,
+    ) -> Self {
+        Self {
+            id: StorageLocationId::new(),
+            user_id,
+            name,
+            parent_id,
+            created_at: Utc::now(),
+        }
+    }
+
+    /// The IDs of `root` and every location nested inside it.
+    pub fn descendants(
+        locations: &[StorageLocation],
+        root: StorageLocationId,
+    ) -> HashSet<StorageLocationId> {
+        let mut ids = HashSet::from([root]);
+        // Repeat until no more children are found, as the locations are not sorted
+        loop {
+            let before = ids.len();
+            for location in locations {
+                if let Some(parent_id) = location.parent_id
+                    && ids.contains(&parent_id)
+                {
+                    ids.insert(location.id);
+                }
+            }
+            if ids.len() == before {
+                return ids;
+            }
+        }
+    }
+
+    /// The path from the top-level location down to `id`, e.g. room, shelf, box.
+    ///
+    /// Returns an empty path if `id` is not in `locations`.
+    pub fn path(locations: &[StorageLocation], id: StorageLocationId) -> Vec<StorageLocation> {
+        let mut path = Vec::new();
+        let mut current = Some(id);
+        while let Some(id) = current
+            && let Some(location) = locations.iter().find(|location| location.id == id)
+        {
+            // Stop at cycles rather than looping forever
+            if path.iter().any(|l: &StorageLocation| l.id == id) {
+                break;
+            }
+            path.push(location.clone());
+            current = location.parent_id;
+        }
+        path.reverse();
+        path
+    }
+}
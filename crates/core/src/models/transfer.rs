@@ -3,3 +3,6 @@ pub use transfer_history::*;
 
 mod user_transaction;
 pub use user_transaction::*;
+
+mod trade_match;
+pub use trade_match::*;
@@ -0,0 +1,49 @@
+use crate::models::{
+    product::{ProductInstanceId, ProductVariantId},
+    user::UserId,
+};
+use serde::{Deserialize, Serialize};
+
+/// A possible exchange between users who opted in to trade matching.
+///
+/// The legs form a cycle: every participant gives spares to the next one and receives
+/// wanted variants from the previous one. A pair is a cycle of two.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct TradeMatch {
+    /// The transfers, starting with the one from the user the match was found for
+    pub legs: Vec<TradeLeg>,
+
+    /// The number of wanted instances the match delivers, across all participants
+    pub satisfied_wants: u32,
+}
+
+impl TradeMatch {
+    /// Every instance moved by the match.
+    pub fn instance_ids(&self) -> impl Iterator<Item = ProductInstanceId> + '_ {
+        self.legs
+            .iter()
+            .flat_map(|leg| leg.items.iter().map(|item| item.instance_id))
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct TradeLeg {
+    /// The user giving spares
+    pub from_user_id: UserId,
+
+    /// The user wanting them
+    pub to_user_id: UserId,
+
+    pub items: Vec<TradeLegItem>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct TradeLegItem {
+    pub variant_id: ProductVariantId,
+
+    /// The spare instance to give
+    pub instance_id: ProductInstanceId,
+}
@@ -1,5 +1,5 @@
 use crate::models::user::UserId;
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, NaiveDate, Utc};
 
 crate::create_entity_id!(ProductInstanceTransferHistoryId);
 
@@ -22,6 +22,9 @@ pub struct ProductInstanceTransferHistory {
     /// Transfer reason
     pub reason: TransferReason,
 
+    /// When the new holder agreed to give the item back, for lending
+    pub return_by: Option<NaiveDate>,
+
     /// When the transfer happened
     pub transferred_at: DateTime<Utc>,
 }
@@ -35,6 +38,9 @@ pub enum TransferReason {
     /// The holder is changing (e.g., item moved to a different inventory)
     Delivery,
 
+    /// The owner leaves the item with another user (lending, consignment), keeping ownership
+    Lending,
+
     /// User-to-user trade
     Trade,
 
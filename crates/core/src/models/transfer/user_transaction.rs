@@ -1,9 +1,10 @@
-use crate::models::{product::ProductInstanceId, user::UserId};
-use chrono::{DateTime, Utc};
+use crate::models::{product::ProductInstanceId, transfer::TransferReason, user::UserId};
+use chrono::{DateTime, NaiveDate, Utc};
 
 crate::create_entity_id!(UserTransactionId);
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct UserTransaction {
     pub id: UserTransactionId,
 
@@ -16,6 +17,17 @@ pub struct UserTransaction {
     /// Items being transferred
     pub items: Vec<ProductInstanceId>,
 
+    /// Why the items are transferred
+    ///
+    /// - `Trade`: ownership and custody move to the receiving user
+    /// - `Delivery`: the receiving user already owns the items and takes custody of them
+    ///   (e.g. a group-buy receiver handing items over to their owners)
+    /// - `Lending`: the receiving user takes custody of the items, the sender keeps ownership
+    pub reason: TransferReason,
+
+    /// When the receiver should give the items back, for lending
+    pub return_by: Option<NaiveDate>,
+
     /// Transaction status
     pub status: UserTransactionStatus,
 
@@ -27,8 +39,13 @@ pub struct UserTransaction {
     pub cancelled_at: Option<DateTime<Utc>>,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub enum UserTransactionStatus {
+    /// The transaction is proposed to the sender, who must accept it before the items
+    /// are locked (e.g. a leg of a trade match proposed by another user).
+    Proposed,
+
     /// The transaction is pending.
     Pending,
 
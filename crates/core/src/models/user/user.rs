@@ -22,10 +22,49 @@ pub struct User {
     /// The avatar media id of the user.
     pub avatar: Option<MediaId>,
 
+    /// Set if this is a placeholder for someone without an account.
+    ///
+    /// Placeholders can own line items and instances, but cannot log in.
+    pub placeholder: Option<Placeholder>,
+
+    /// The secret token authenticating the user's calendar feeds.
+    ///
+    /// Calendar apps cannot log in, so feeds are accessed with this token instead.
+    /// Resetting it revokes the previous one.
+    pub calendar_token: Option<String>,
+
     /// The timestamp when the user was created.
     pub created_at: DateTime<Utc>,
 }
 
+impl User {
+    pub fn is_placeholder(&self) -> bool {
+        self.placeholder.is_some()
+    }
+
+    /// Whether this is a placeholder managed by the given user.
+    pub fn is_managed_by(&self, user_id: UserId) -> bool {
+        self.placeholder
+            .as_ref()
+            .is_some_and(|placeholder| placeholder.manager_id == user_id)
+    }
+}
+
+/// A lightweight user standing in for a group-buy participant without an account.
+///
+/// The placeholder is merged into a real account when someone claims it with its invite code.
+#[derive(Debug, Clone)]
+pub struct Placeholder {
+    /// The name shown for the placeholder
+    pub display_name: String,
+
+    /// The user who created and manages the placeholder
+    pub manager_id: UserId,
+
+    /// The code to hand to the participant so they can claim the placeholder
+    pub invite_code: String,
+}
+
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 #[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(transparent)]
@@ -42,4 +81,6 @@ pub struct UserUpdate {
     pub email: Option<Email>,
     pub password_hash: Option<NonEmptyString>,
     pub avatar: Option<MediaId>,
+    /// `Some(None)` revokes the calendar token.
+    pub calendar_token: Option<Option<String>>,
 }
@@ -0,0 +1,2 @@
+mod wishlist;
+pub use wishlist::*;
@@ -0,0 +1,135 @@
+use crate::models::{misc::Price, product::ProductVariantId, user::UserId};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::num::NonZeroU32;
+
+crate::create_entity_id!(WishlistItemId);
+
+/// The variants a user wants to get.
+///
+/// Each user has at most one wishlist. Items are satisfied automatically as
+/// matching instances enter the user's inventory.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct Wishlist {
+    /// The user the wishlist belongs to
+    pub user_id: UserId,
+
+    /// Whether other users can view the wishlist
+    pub is_public: bool,
+
+    /// Whether the user takes part in trade matching.
+    ///
+    /// Unsatisfied items are then the variants the user wants in exchange for their spares.
+    pub open_to_trades: bool,
+
+    /// The wanted variants, in the order they were added
+    pub items: Vec<WishlistItem>,
+
+    /// The timestamp when the wishlist was last changed.
+    pub updated_at: DateTime<Utc>,
+}
+
+impl Wishlist {
+    /// An empty, private wishlist for a user who has not added anything yet.
+    pub fn empty(user_id: UserId) -> Self {
+        Self {
+            user_id,
+            is_public: false,
+            open_to_trades: false,
+            items: Vec::new(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    /// The variants the user still wants, with how many more instances they want.
+    pub fn wants(&self) -> impl Iterator<Item = (ProductVariantId, u32)> + '_ {
+        self.items
+            .iter()
+            .filter(|item| !item.is_satisfied())
+            .map(|item| {
+                (
+                    item.variant_id,
+                    item.desired_quantity
+                        .get()
+                        .saturating_sub(item.obtained_quantity),
+                )
+            })
+    }
+
+    /// Record that the user obtained one instance of a variant.
+    ///
+    /// Returns whether an unsatisfied item wanted the variant.
+    pub fn record_obtained(&mut self, variant_id: ProductVariantId) -> bool {
+        let Some(item) = self
+            .items
+            .iter_mut()
+            .find(|item| item.variant_id == variant_id && !item.is_satisfied())
+        else {
+            return false;
+        };
+
+        item.obtained_quantity += 1;
+        item.refresh_satisfied();
+        self.updated_at = Utc::now();
+        true
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct WishlistItem {
+    pub id: WishlistItemId,
+
+    /// The wanted variant
+    pub variant_id: ProductVariantId,
+
+    /// How much the user wants the variant
+    pub priority: WishlistPriority,
+
+    /// How many instances the user wants
+    pub desired_quantity: NonZeroU32,
+
+    /// How many instances the user obtained since adding the item
+    pub obtained_quantity: u32,
+
+    /// The most the user is willing to pay per instance
+    pub max_price: Option<Price>,
+
+    /// Free-form notes (e.g. "any condition is fine")
+    pub note: String,
+
+    /// When the user obtained the desired quantity
+    pub satisfied_at: Option<DateTime<Utc>>,
+
+    /// The timestamp when the item was added.
+    pub added_at: DateTime<Utc>,
+}
+
+impl WishlistItem {
+    pub fn is_satisfied(&self) -> bool {
+        self.satisfied_at.is_some()
+    }
+
+    /// Update `satisfied_at` after the desired or obtained quantity changed.
+    pub fn refresh_satisfied(&mut self) {
+        if self.obtained_quantity < self.desired_quantity.get() {
+            self.satisfied_at = None;
+        } else if self.satisfied_at.is_none() {
+            self.satisfied_at = Some(Utc::now());
+        }
+    }
+}
+
+/// How much a user wants a wishlisted variant, from least to most.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum WishlistPriority {
+    Low,
+
+    #[default]
+    Normal,
+
+    High,
+}
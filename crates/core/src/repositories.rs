@@ -18,3 +18,15 @@ pub use tag::*;
 
 mod media;
 pub use media::*;
+
+mod cart;
+pub use cart::*;
+
+mod storage_location;
+pub use storage_location::*;
+
+mod wishlist;
+pub use wishlist::*;
+
+mod statistics;
+pub use statistics::*;
@@ -0,0 +1,21 @@
+use crate::{
+    errors::RepositoryError,
+    models::{cart::Cart, user::UserId},
+};
+
+/// Repository for the Cart aggregate.
+///
+/// Each user has at most one cart, identified by the user's ID.
+pub trait CartRepository: Send + Sync + 'static {
+    /// Find the cart of a user.
+    fn find_by_user(
+        &self,
+        user_id: &UserId,
+    ) -> impl Future<Output = Result<Option<Cart>, RepositoryError>> + Send;
+
+    /// Save a cart (create or update), replacing all its lines.
+    fn save(&self, cart: &Cart) -> impl Future<Output = Result<(), RepositoryError>> + Send;
+
+    /// Delete the cart of a user.
+    fn delete(&self, user_id: &UserId) -> impl Future<Output = Result<(), RepositoryError>> + Send;
+}
@@ -0,0 +1,73 @@
+use crate::{
+    errors::RepositoryError,
+    models::{
+        statistics::{
+            DateRange, MonthlySpending, MysteryBoxStatistics, ProductStatistics,
+            SeriesPurchaseCount, StatusCount, TagStatistics,
+        },
+        user::UserId,
+    },
+};
+
+/// Read-only aggregates over a user's purchase orders and product instances.
+///
+/// Purchases are the line items a user owns that were received within the
+/// range and not returned, even if the rest of their order is still pending.
+/// What they cost is the landed acquisition cost of the instances created from
+/// them; instances without a cost are counted but add nothing to the totals.
+pub trait StatisticsRepository: Send + Sync + 'static {
+    /// Sum the cost of a user's purchases per month and currency.
+    ///
+    /// The month is the one the line item was received in (UTC). Results are
+    /// ordered by month, then currency code.
+    fn monthly_spending(
+        &self,
+        user_id: &UserId,
+        range: &DateRange,
+    ) -> impl Future<Output = Result<Vec<MonthlySpending>, RepositoryError>> + Send;
+
+    /// Count the instances a user owns per status, for instances created within the range.
+    fn status_counts(
+        &self,
+        user_id: &UserId,
+        range: &DateRange,
+    ) -> impl Future<Output = Result<Vec<StatusCount>, RepositoryError>> + Send;
+
+    /// Count the instances a user owns per tag of their variant, and sum their costs.
+    ///
+    /// Only the tags set directly on a variant are counted. Instances created
+    /// outside the range and returned instances are left out. Results are
+    /// ordered by instance count, highest first.
+    fn tag_statistics(
+        &self,
+        user_id: &UserId,
+        range: &DateRange,
+    ) -> impl Future<Output = Result<Vec<TagStatistics>, RepositoryError>> + Send;
+
+    /// Count the instances a user owns per product, and sum their costs.
+    ///
+    /// Instances created outside the range and returned instances are left out.
+    /// Results are ordered by instance count, highest first.
+    fn product_statistics(
+        &self,
+        user_id: &UserId,
+        range: &DateRange,
+    ) -> impl Future<Output = Result<Vec<ProductStatistics>, RepositoryError>> + Send;
+
+    /// Find the products a user bought the most items of, at most `limit` of them.
+    fn most_bought_series(
+        &self,
+        user_id: &UserId,
+        range: &DateRange,
+        limit: usize,
+    ) -> impl Future<Output = Result<Vec<SeriesPurchaseCount>, RepositoryError>> + Send;
+
+    /// Sum what a user's purchases of each mystery-box variant yielded and cost.
+    ///
+    /// Results are ordered by items obtained, highest first.
+    fn mystery_box_statistics(
+        &self,
+        user_id: &UserId,
+        range: &DateRange,
+    ) -> impl Future<Output = Result<Vec<MysteryBoxStatistics>, RepositoryError>> + Send;
+}
@@ -0,0 +1,34 @@
+use crate::{
+    errors::RepositoryError,
+    models::{
+        storage::{StorageLocation, StorageLocationId},
+        user::UserId,
+    },
+};
+
+/// Repository for the StorageLocation aggregate.
+pub trait StorageLocationRepository: Send + Sync + 'static {
+    /// Find a location by its ID.
+    fn find_by_id(
+        &self,
+        id: &StorageLocationId,
+    ) -> impl Future<Output = Result<Option<StorageLocation>, RepositoryError>> + Send;
+
+    /// Find all locations of a user.
+    fn find_by_user(
+        &self,
+        user_id: &UserId,
+    ) -> impl Future<Output = Result<Vec<StorageLocation>, RepositoryError>> + Send;
+
+    /// Save a location (create or update).
+    fn save(
+        &self,
+        location: &StorageLocation,
+    ) -> impl Future<Output = Result<(), RepositoryError>> + Send;
+
+    /// Delete a location by its ID.
+    fn delete(
+        &self,
+        id: &StorageLocationId,
+    ) -> impl Future<Output = Result<(), RepositoryError>> + Send;
+}
@@ -26,6 +26,24 @@ pub trait UserRepository: Send + Sync + 'static {
         username: &Username,
     ) -> impl Future<Output = Result<Option<User>, RepositoryError>> + Send;
 
+    /// Find the placeholder with the given invite code.
+    fn find_by_invite_code(
+        &self,
+        invite_code: &str,
+    ) -> impl Future<Output = Result<Option<User>, RepositoryError>> + Send;
+
+    /// Find the user with the given calendar feed token.
+    fn find_by_calendar_token(
+        &self,
+        calendar_token: &str,
+    ) -> impl Future<Output = Result<Option<User>, RepositoryError>> + Send;
+
+    /// Find all placeholders managed by a user.
+    fn find_placeholders_by_manager(
+        &self,
+        manager_id: &UserId,
+    ) -> impl Future<Output = Result<Vec<User>, RepositoryError>> + Send;
+
     /// Create a new user.
     fn create(&self, user: User) -> impl Future<Output = Result<User, RepositoryError>> + Send;
 
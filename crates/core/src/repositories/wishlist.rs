@@ -0,0 +1,27 @@
+use crate::{
+    errors::RepositoryError,
+    models::{user::UserId, wishlist::Wishlist},
+};
+
+/// Repository for the Wishlist aggregate.
+///
+/// Each user has at most one wishlist, identified by the user's ID.
+pub trait WishlistRepository: Send + Sync + 'static {
+    /// Find the wishlist of a user.
+    fn find_by_user(
+        &self,
+        user_id: &UserId,
+    ) -> impl Future<Output = Result<Option<Wishlist>, RepositoryError>> + Send;
+
+    /// Find the wishlists of all users open to trades.
+    fn find_open_to_trades(
+        &self,
+    ) -> impl Future<Output = Result<Vec<Wishlist>, RepositoryError>> + Send;
+
+    /// Save a wishlist (create or update), replacing all its items.
+    fn save(&self, wishlist: &Wishlist)
+    -> impl Future<Output = Result<(), RepositoryError>> + Send;
+
+    /// Delete the wishlist of a user.
+    fn delete(&self, user_id: &UserId) -> impl Future<Output = Result<(), RepositoryError>> + Send;
+}
@@ -40,3 +40,24 @@ pub use transaction::*;
 
 mod transaction_lifecycle;
 pub use transaction_lifecycle::*;
+
+mod settlement;
+pub use settlement::*;
+
+mod cart;
+pub use cart::*;
+
+mod storage_location;
+pub use storage_location::*;
+
+mod collection;
+pub use collection::*;
+
+mod wishlist;
+pub use wishlist::*;
+
+mod trade_match;
+pub use trade_match::*;
+
+mod statistics;
+pub use statistics::*;
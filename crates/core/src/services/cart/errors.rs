@@ -0,0 +1,57 @@
+use crate::models::{cart::CartLineId, product::ProductVariantId, user::UserId};
+
+#[derive(Debug, thiserror::Error)]
+pub enum GetCartError {
+    #[error("Repository error: {0}")]
+    Repository(#[from] crate::errors::RepositoryError),
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum AddCartLineError {
+    #[error("Variant not found: {variant_id:?}")]
+    VariantNotFound { variant_id: ProductVariantId },
+
+    #[error("User not found: {user_id:?}")]
+    UserNotFound { user_id: UserId },
+
+    #[error("Repository error: {0}")]
+    Repository(#[from] crate::errors::RepositoryError),
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum UpdateCartLineError {
+    #[error("Cart line not found: {line_id:?}")]
+    LineNotFound { line_id: CartLineId },
+
+    #[error("User not found: {user_id:?}")]
+    UserNotFound { user_id: UserId },
+
+    #[error("Repository error: {0}")]
+    Repository(#[from] crate::errors::RepositoryError),
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum RemoveCartLineError {
+    #[error("Cart line not found: {line_id:?}")]
+    LineNotFound { line_id: CartLineId },
+
+    #[error("Repository error: {0}")]
+    Repository(#[from] crate::errors::RepositoryError),
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum CheckoutCartError {
+    #[error("Cart is empty")]
+    CartEmpty,
+
+    /// Variants that were deleted after being added to the cart.
+    /// The lines have to be removed before checking out.
+    #[error("Variants not found: {variant_ids:?}")]
+    VariantsNotFound { variant_ids: Vec<ProductVariantId> },
+
+    #[error(transparent)]
+    CreateOrder(#[from] crate::services::CreateOrderError),
+
+    #[error("Repository error: {0}")]
+    Repository(#[from] crate::errors::RepositoryError),
+}
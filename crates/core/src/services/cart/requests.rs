@@ -0,0 +1,78 @@
+use std::num::NonZeroU32;
+
+use crate::models::{
+    cart::CartLineId,
+    misc::{Address, Price},
+    product::ProductVariantId,
+    user::UserId,
+};
+
+/// Request to get the cart of a user.
+pub struct GetCartRequest {
+    pub user_id: UserId,
+}
+
+/// Request to add a variant to the cart.
+///
+/// Adding a variant already in the cart for the same owner increases its quantity.
+pub struct AddCartLineRequest {
+    /// The user whose cart to change.
+    pub user_id: UserId,
+
+    /// The variant to purchase.
+    pub variant_id: ProductVariantId,
+
+    /// Quantity to purchase.
+    pub quantity: NonZeroU32,
+
+    /// The user who will own the purchased instances.
+    /// If None, defaults to the order receiver.
+    pub owner_id: Option<UserId>,
+
+    /// Expected price per unit.
+    pub unit_price: Option<Price>,
+}
+
+/// Request to update a line of the cart.
+pub struct UpdateCartLineRequest {
+    /// The user whose cart to change.
+    pub user_id: UserId,
+
+    /// The line to update.
+    pub line_id: CartLineId,
+
+    /// New quantity to purchase.
+    pub quantity: Option<NonZeroU32>,
+
+    /// New owner of the purchased instances.
+    pub owner_id: Option<UserId>,
+
+    /// New expected price per unit.
+    pub unit_price: Option<Price>,
+}
+
+/// Request to remove a line from the cart.
+pub struct RemoveCartLineRequest {
+    /// The user whose cart to change.
+    pub user_id: UserId,
+
+    /// The line to remove.
+    pub line_id: CartLineId,
+}
+
+/// Request to turn the cart into a purchase order.
+///
+/// The cart lines become the order items, and the cart is cleared.
+pub struct CheckoutCartRequest {
+    /// The user checking out (will be set as creator_id).
+    pub user_id: UserId,
+
+    /// The user who will receive the shipment (default to creator).
+    pub receiver_id: Option<UserId>,
+
+    /// Shipping/delivery address (optional, for physical goods).
+    pub shipping_address: Option<Address>,
+
+    /// The total price of the order.
+    pub total_price: Option<Price>,
+}
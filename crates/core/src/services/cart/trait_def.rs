@@ -0,0 +1,46 @@
+use crate::models::{cart::Cart, purchase::PurchaseOrder};
+
+use super::{
+    AddCartLineError, AddCartLineRequest, CheckoutCartError, CheckoutCartRequest, GetCartError,
+    GetCartRequest, RemoveCartLineError, RemoveCartLineRequest, UpdateCartLineError,
+    UpdateCartLineRequest,
+};
+
+/// Service for managing shopping carts (Port).
+///
+/// This service handles the per-user cart kept on the server:
+/// - Adding, updating and removing lines
+/// - Checking out the cart into a new purchase order
+pub trait CartService: Send + Sync + 'static {
+    /// Get the cart of a user. Users who never added anything get an empty cart.
+    fn get_cart(
+        &self,
+        req: GetCartRequest,
+    ) -> impl Future<Output = Result<Cart, GetCartError>> + Send;
+
+    /// Add a variant to the cart.
+    fn add_cart_line(
+        &self,
+        req: AddCartLineRequest,
+    ) -> impl Future<Output = Result<Cart, AddCartLineError>> + Send;
+
+    /// Update the quantity, owner or price of a cart line.
+    fn update_cart_line(
+        &self,
+        req: UpdateCartLineRequest,
+    ) -> impl Future<Output = Result<Cart, UpdateCartLineError>> + Send;
+
+    /// Remove a line from the cart.
+    fn remove_cart_line(
+        &self,
+        req: RemoveCartLineRequest,
+    ) -> impl Future<Output = Result<Cart, RemoveCartLineError>> + Send;
+
+    /// Create a purchase order from the cart and clear the cart.
+    ///
+    /// Fails without changing anything if a variant in the cart no longer exists.
+    fn checkout_cart(
+        &self,
+        req: CheckoutCartRequest,
+    ) -> impl Future<Output = Result<PurchaseOrder, CheckoutCartError>> + Send;
+}
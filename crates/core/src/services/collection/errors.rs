@@ -0,0 +1,32 @@
+use crate::models::{misc::TagId, product::ProductId};
+
+/// The product or tag a collection query looks at does not exist.
+#[derive(Debug, thiserror::Error)]
+pub enum CollectionScopeError {
+    #[error("Product not found: {product_id:?}")]
+    ProductNotFound { product_id: ProductId },
+
+    #[error("Tag not found: {tag_id:?}")]
+    TagNotFound { tag_id: TagId },
+
+    #[error("Repository error: {0}")]
+    Repository(#[from] crate::errors::RepositoryError),
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum GetCollectionCompletenessError {
+    #[error(transparent)]
+    Scope(#[from] CollectionScopeError),
+
+    #[error("Repository error: {0}")]
+    Repository(#[from] crate::errors::RepositoryError),
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum GetDuplicatesError {
+    #[error(transparent)]
+    Scope(#[from] CollectionScopeError),
+
+    #[error("Repository error: {0}")]
+    Repository(#[from] crate::errors::RepositoryError),
+}
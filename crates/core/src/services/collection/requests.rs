@@ -0,0 +1,18 @@
+use crate::models::{collection::CollectionScope, user::UserId};
+
+/// Request to find which variants of a product or tag a user owns and misses.
+pub struct GetCollectionCompletenessRequest {
+    pub user_id: UserId,
+    pub scope: CollectionScope,
+}
+
+/// Request to find the variants a user owns more copies of than they want to keep.
+pub struct GetDuplicatesRequest {
+    pub user_id: UserId,
+
+    /// Variants with more `Active` instances than this are reported, and this many are kept
+    pub threshold: u32,
+
+    /// Only look at a product or tag, or the whole collection when `None`
+    pub scope: Option<CollectionScope>,
+}
@@ -0,0 +1,30 @@
+use crate::models::collection::{CollectionCompleteness, DuplicateVariant};
+
+use super::{
+    GetCollectionCompletenessError, GetCollectionCompletenessRequest, GetDuplicatesError,
+    GetDuplicatesRequest,
+};
+
+/// Service for questions about a user's collection as a whole (Port).
+///
+/// This service handles:
+/// - Completeness of a product or tag ("which ones am I missing?")
+/// - Duplicates that can be offered in trades ("which ones do I have too many of?")
+pub trait CollectionService: Send + Sync + 'static {
+    /// Get how many of each variant in the scope the user owns, and which ones are missing.
+    ///
+    /// Only `Active` instances count as owned. Mystery-box variants are replaced by
+    /// their possible variants.
+    fn get_collection_completeness(
+        &self,
+        req: GetCollectionCompletenessRequest,
+    ) -> impl Future<Output = Result<CollectionCompleteness, GetCollectionCompletenessError>> + Send;
+
+    /// Get the variants the user owns more than `threshold` `Active` instances of.
+    ///
+    /// The oldest `threshold` instances are kept, the rest are reported as spare.
+    fn get_duplicates(
+        &self,
+        req: GetDuplicatesRequest,
+    ) -> impl Future<Output = Result<Vec<DuplicateVariant>, GetDuplicatesError>> + Send;
+}
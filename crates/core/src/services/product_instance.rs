@@ -4,5 +4,8 @@ pub use errors::*;
 mod requests;
 pub use requests::*;
 
+mod responses;
+pub use responses::*;
+
 mod trait_def;
 pub use trait_def::*;
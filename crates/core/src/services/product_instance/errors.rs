@@ -1,4 +1,11 @@
-use crate::errors::RepositoryError;
+use super::BulkInstanceResult;
+use crate::{
+    errors::RepositoryError,
+    models::{
+        misc::MediaId,
+        product::{InvalidStatusTransition, ProductInstanceStatus},
+    },
+};
 use thiserror::Error;
 
 #[derive(Debug, Error)]
@@ -21,8 +28,8 @@ pub enum ConsumeProductInstanceError {
     NotFound,
     #[error("Permission denied")]
     PermissionDenied,
-    #[error("Product instance is not active")]
-    NotActive,
+    #[error(transparent)]
+    InvalidTransition(#[from] InvalidStatusTransition),
     #[error("Product instance is not held by owner")]
     NotHeldByOwner,
     #[error(transparent)]
@@ -35,8 +42,8 @@ pub enum MarkProductInstanceLostError {
     NotFound,
     #[error("Permission denied")]
     PermissionDenied,
-    #[error("Product instance is not active")]
-    NotActive,
+    #[error(transparent)]
+    InvalidTransition(#[from] InvalidStatusTransition),
     #[error("Product instance is not held by owner")]
     NotHeldByOwner,
     #[error(transparent)]
@@ -49,10 +56,80 @@ pub enum MarkProductInstanceDestroyedError {
     NotFound,
     #[error("Permission denied")]
     PermissionDenied,
-    #[error("Product instance is not active")]
-    NotActive,
+    #[error(transparent)]
+    InvalidTransition(#[from] InvalidStatusTransition),
+    #[error("Product instance is not held by owner")]
+    NotHeldByOwner,
+    #[error(transparent)]
+    Repository(#[from] RepositoryError),
+}
+
+#[derive(Debug, Error)]
+pub enum RecoverProductInstanceError {
+    #[error("Product instance not found")]
+    NotFound,
+    #[error("Permission denied")]
+    PermissionDenied,
+    #[error(transparent)]
+    InvalidTransition(#[from] InvalidStatusTransition),
     #[error("Product instance is not held by owner")]
     NotHeldByOwner,
     #[error(transparent)]
     Repository(#[from] RepositoryError),
 }
+
+#[derive(Debug, Error)]
+pub enum UnconsumeProductInstanceError {
+    #[error("Product instance not found")]
+    NotFound,
+    #[error("Permission denied")]
+    PermissionDenied,
+    #[error(transparent)]
+    InvalidTransition(#[from] InvalidStatusTransition),
+    #[error("Product instance is not held by owner")]
+    NotHeldByOwner,
+    #[error(transparent)]
+    Repository(#[from] RepositoryError),
+}
+
+#[derive(Debug, Error)]
+pub enum UpdateProductInstanceDetailsError {
+    #[error("Product instance not found")]
+    NotFound,
+    #[error("Permission denied")]
+    PermissionDenied,
+    #[error("Media not found: {media_id:?}")]
+    MediaNotFound { media_id: MediaId },
+    #[error(transparent)]
+    Repository(#[from] RepositoryError),
+}
+
+#[derive(Debug, Error)]
+pub enum BulkChangeInstanceStatusError {
+    #[error("No product instances selected")]
+    NoInstances,
+    /// Only the statuses users set themselves can be set in bulk; `Locked` and
+    /// `Returned` are managed by transactions and order returns.
+    #[error("Cannot change product instances to {status:?} in bulk")]
+    UnsupportedStatus { status: ProductInstanceStatus },
+    #[error("Some product instances cannot be changed")]
+    Rejected { results: Vec<BulkInstanceResult> },
+    #[error(transparent)]
+    Repository(#[from] RepositoryError),
+}
+
+#[derive(Debug, Error)]
+pub enum BulkTransferInstancesError {
+    #[error("No product instances selected")]
+    NoInstances,
+    #[error("Recipient not found")]
+    RecipientNotFound,
+    #[error("Cannot transfer product instances to their owner")]
+    SameUser,
+    #[error("Some product instances cannot be transferred")]
+    Rejected { results: Vec<BulkInstanceResult> },
+    #[error(transparent)]
+    CreateTransaction(#[from] crate::services::CreateTransactionError),
+    #[error(transparent)]
+    Repository(#[from] RepositoryError),
+}
@@ -1,11 +1,17 @@
 use crate::models::{
-    product::{ProductInstanceId, ProductInstanceStatus, ProductVariantId},
+    misc::MediaId,
+    product::{
+        ProductInstanceCondition, ProductInstanceId, ProductInstanceStatus, ProductVariantId,
+    },
+    storage::StorageLocationId,
     user::UserId,
 };
 
 /// Request to get a product instance by ID.
 pub struct GetProductInstanceRequest {
     pub id: ProductInstanceId,
+    /// The user viewing the instance, only the owner sees its note
+    pub user_id: UserId,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
@@ -14,6 +20,10 @@ pub struct GetProductInstanceRequest {
 pub enum ListProductInstancesQueryBy {
     Owned,
     Held,
+    /// Owned by the user but held by someone else
+    LentOut,
+    /// Held by the user but owned by someone else
+    Borrowed,
 }
 
 /// Request to list product instances.
@@ -24,6 +34,9 @@ pub struct ListProductInstancesRequest {
     pub query_by: ListProductInstancesQueryBy,
     pub variant_id: Option<ProductVariantId>,
     pub status: Option<ProductInstanceStatus>,
+    /// Only instances kept in this location of the user, or any location nested inside it.
+    pub location_id: Option<StorageLocationId>,
+    pub condition: Option<ProductInstanceCondition>,
 }
 
 /// Request to consume a product instance.
@@ -32,6 +45,12 @@ pub struct ConsumeProductInstanceRequest {
     pub user_id: UserId,
 }
 
+/// Request to undo consuming a product instance, e.g. when consumed by mistake.
+pub struct UnconsumeProductInstanceRequest {
+    pub id: ProductInstanceId,
+    pub user_id: UserId,
+}
+
 /// Request to mark a product instance as lost.
 pub struct MarkProductInstanceLostRequest {
     pub id: ProductInstanceId,
@@ -39,9 +58,62 @@ pub struct MarkProductInstanceLostRequest {
     pub reason: Option<String>,
 }
 
+/// Request to mark a lost product instance as found again.
+pub struct RecoverProductInstanceRequest {
+    pub id: ProductInstanceId,
+    pub user_id: UserId,
+    pub reason: Option<String>,
+}
+
 /// Request to mark a product instance as destroyed.
 pub struct MarkProductInstanceDestroyedRequest {
     pub id: ProductInstanceId,
     pub user_id: UserId,
     pub reason: Option<String>,
 }
+
+/// Request to update the condition, note and photos of a product instance.
+///
+/// The details are replaced as a whole.
+pub struct UpdateProductInstanceDetailsRequest {
+    pub id: ProductInstanceId,
+    /// The user attempting the update (must be the owner)
+    pub user_id: UserId,
+    pub condition: Option<ProductInstanceCondition>,
+    pub note: String,
+    pub medias: Vec<MediaId>,
+}
+
+/// Which instances a bulk operation applies to.
+pub enum BulkInstanceSelection {
+    /// Exactly these instances, in this order
+    Ids(Vec<ProductInstanceId>),
+
+    /// The instances owned by the user matching all the given criteria
+    Filter {
+        variant_id: Option<ProductVariantId>,
+        status: Option<ProductInstanceStatus>,
+        /// Only instances kept in this location of the user, or any location nested inside it.
+        location_id: Option<StorageLocationId>,
+        condition: Option<ProductInstanceCondition>,
+    },
+}
+
+/// Request to change the status of many instances at once, e.g. marking
+/// everything that did not turn up after a move as lost.
+pub struct BulkChangeInstanceStatusRequest {
+    /// The user changing the status (must own and hold all instances)
+    pub user_id: UserId,
+    pub selection: BulkInstanceSelection,
+    pub status: ProductInstanceStatus,
+    /// Why the status changed, recorded in the status history of every instance
+    pub reason: Option<String>,
+}
+
+/// Request to start transferring many instances to another user at once.
+pub struct BulkTransferInstancesRequest {
+    /// The user giving the instances away (must own and hold all instances)
+    pub user_id: UserId,
+    pub selection: BulkInstanceSelection,
+    pub to_user_id: UserId,
+}
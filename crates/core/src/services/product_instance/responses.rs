@@ -0,0 +1,48 @@
+use crate::models::{
+    product::{ProductInstanceId, ProductInstanceStatus},
+    transfer::UserTransactionId,
+};
+
+/// The outcome of a bulk operation for one selected instance.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct BulkInstanceResult {
+    pub instance_id: ProductInstanceId,
+
+    /// Why the operation cannot be applied to this instance.
+    ///
+    /// If any instance has an error, the whole operation is rejected and no
+    /// instance is changed.
+    pub error: Option<BulkInstanceItemError>,
+}
+
+/// Why a bulk operation cannot be applied to an instance.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum BulkInstanceItemError {
+    NotFound,
+
+    /// The instance is owned by someone else
+    PermissionDenied,
+
+    /// The instance is owned by the user, but someone else holds it
+    NotHeldByOwner,
+
+    /// The instance is locked by a pending transaction
+    Locked,
+
+    /// The instance state machine does not allow the change
+    InvalidTransition {
+        from: ProductInstanceStatus,
+        to: ProductInstanceStatus,
+    },
+}
+
+/// The pending transaction started by a bulk transfer, with the result per instance.
+#[derive(Debug, Clone, serde::Serialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct BulkTransferResponse {
+    pub transaction_id: UserTransactionId,
+    pub results: Vec<BulkInstanceResult>,
+}
@@ -6,7 +6,9 @@ use crate::models::product::ProductInstance;
 /// This service handles operations related to individual items owned by users:
 /// - Retrieving product instances
 /// - Listing user's instances
-/// - Updating instance status
+/// - Updating the condition, note and photos of instances
+/// - Updating instance status, following the state machine on `ProductInstanceStatus`
+/// - Changing the status of, or transferring, many instances at once
 pub trait ProductInstanceService: Send + Sync + 'static {
     /// Get a specific product instance (item owned by a user).
     fn get_product_instance(
@@ -22,21 +24,63 @@ pub trait ProductInstanceService: Send + Sync + 'static {
         req: ListProductInstancesRequest,
     ) -> impl Future<Output = Result<Vec<ProductInstance>, ListProductInstancesError>> + Send;
 
+    /// Update the condition, private note and photos of a product instance.
+    ///
+    /// Only the owner can update the details.
+    fn update_product_instance_details(
+        &self,
+        req: UpdateProductInstanceDetailsRequest,
+    ) -> impl Future<Output = Result<ProductInstance, UpdateProductInstanceDetailsError>> + Send;
+
     /// Consume a product instance (e.g. use a ticket, eat food).
     fn consume_product_instance(
         &self,
         req: ConsumeProductInstanceRequest,
     ) -> impl Future<Output = Result<ProductInstance, ConsumeProductInstanceError>> + Send;
 
+    /// Undo consuming a product instance, making it active again.
+    fn unconsume_product_instance(
+        &self,
+        req: UnconsumeProductInstanceRequest,
+    ) -> impl Future<Output = Result<ProductInstance, UnconsumeProductInstanceError>> + Send;
+
     /// Mark a product instance as lost (cannot find it).
     fn mark_product_instance_lost(
         &self,
         req: MarkProductInstanceLostRequest,
     ) -> impl Future<Output = Result<ProductInstance, MarkProductInstanceLostError>> + Send;
 
+    /// Mark a lost product instance as found again, making it active again.
+    fn recover_product_instance(
+        &self,
+        req: RecoverProductInstanceRequest,
+    ) -> impl Future<Output = Result<ProductInstance, RecoverProductInstanceError>> + Send;
+
     /// Mark a product instance as destroyed (broken, thrown away).
+    ///
+    /// Lost instances can also be written off as destroyed.
     fn mark_product_instance_destroyed(
         &self,
         req: MarkProductInstanceDestroyedRequest,
     ) -> impl Future<Output = Result<ProductInstance, MarkProductInstanceDestroyedError>> + Send;
+
+    /// Change the status of many instances at once.
+    ///
+    /// All selected instances are checked first: either all of them are changed,
+    /// or none and the error lists the result of every instance. Instances already
+    /// in the status are left as they are.
+    fn bulk_change_instance_status(
+        &self,
+        req: BulkChangeInstanceStatusRequest,
+    ) -> impl Future<Output = Result<Vec<BulkInstanceResult>, BulkChangeInstanceStatusError>> + Send;
+
+    /// Start transferring many instances to another user at once.
+    ///
+    /// Creates a single pending transaction locking all selected instances, which
+    /// the recipient completes as usual. Either all instances are included, or none
+    /// and the error lists the result of every instance.
+    fn bulk_transfer_instances(
+        &self,
+        req: BulkTransferInstancesRequest,
+    ) -> impl Future<Output = Result<BulkTransferResponse, BulkTransferInstancesError>> + Send;
 }
@@ -1,7 +1,10 @@
 use crate::models::{
-    misc::Currency,
+    misc::{Currency, MediaId},
     product::ProductVariantId,
-    purchase::{PurchaseOrderId, PurchaseOrderItemId},
+    purchase::{
+        PurchaseOrderAdjustmentId, PurchaseOrderAttachmentId, PurchaseOrderId, PurchaseOrderItemId,
+        PurchaseOrderLineItemId,
+    },
     user::UserId,
 };
 
@@ -19,6 +22,9 @@ pub enum CreateOrderError {
         actual: Currency,
     },
 
+    #[error("Total price overflows")]
+    PriceOverflow,
+
     #[error("Repository error: {0}")]
     Repository(#[from] crate::errors::RepositoryError),
 }
@@ -43,6 +49,243 @@ pub enum AddOrderItemError {
         actual: Currency,
     },
 
+    #[error("Total price overflows")]
+    PriceOverflow,
+
+    #[error("Repository error: {0}")]
+    Repository(#[from] crate::errors::RepositoryError),
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum RemoveOrderItemError {
+    #[error("Order not found: {order_id:?}")]
+    OrderNotFound { order_id: PurchaseOrderId },
+
+    #[error("Permission denied: user {user_id:?} cannot modify this order")]
+    PermissionDenied { user_id: UserId },
+
+    #[error("Order item not found: {order_item_id:?}")]
+    OrderItemNotFound { order_item_id: PurchaseOrderItemId },
+
+    #[error("Order is not editable")]
+    OrderNotEditable,
+
+    #[error("Line item has a manual adjustment allocation: {line_item_id:?}")]
+    LineItemAllocated {
+        line_item_id: PurchaseOrderLineItemId,
+    },
+
+    #[error("Repository error: {0}")]
+    Repository(#[from] crate::errors::RepositoryError),
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum UpdateOrderItemError {
+    #[error("Order not found: {order_id:?}")]
+    OrderNotFound { order_id: PurchaseOrderId },
+
+    #[error("Permission denied: user {user_id:?} cannot modify this order")]
+    PermissionDenied { user_id: UserId },
+
+    #[error("Order item not found: {order_item_id:?}")]
+    OrderItemNotFound { order_item_id: PurchaseOrderItemId },
+
+    #[error("Variant not found: {variant_id:?}")]
+    VariantNotFound { variant_id: ProductVariantId },
+
+    #[error("Order is not editable")]
+    OrderNotEditable,
+
+    #[error("Currency mismatch: expected {expected:?}, got {actual:?}")]
+    CurrencyMismatch {
+        expected: Currency,
+        actual: Currency,
+    },
+
+    #[error("Total price overflows")]
+    PriceOverflow,
+
+    #[error("Line item has a manual adjustment allocation: {line_item_id:?}")]
+    LineItemAllocated {
+        line_item_id: PurchaseOrderLineItemId,
+    },
+
+    #[error("Repository error: {0}")]
+    Repository(#[from] crate::errors::RepositoryError),
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum UpdateOrderError {
+    #[error("Order not found: {order_id:?}")]
+    OrderNotFound { order_id: PurchaseOrderId },
+
+    #[error("Permission denied: user {user_id:?} cannot modify this order")]
+    PermissionDenied { user_id: UserId },
+
+    #[error("User not found: {user_id:?}")]
+    UserNotFound { user_id: UserId },
+
+    #[error("Order is not editable")]
+    OrderNotEditable,
+
+    #[error("Repository error: {0}")]
+    Repository(#[from] crate::errors::RepositoryError),
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum UpdateOrderScheduleError {
+    #[error("Order not found: {order_id:?}")]
+    OrderNotFound { order_id: PurchaseOrderId },
+
+    #[error("Permission denied: user {user_id:?} cannot modify this order")]
+    PermissionDenied { user_id: UserId },
+
+    #[error("Order is not editable")]
+    OrderNotEditable,
+
+    #[error("Repository error: {0}")]
+    Repository(#[from] crate::errors::RepositoryError),
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum ReassignLineItemOwnersError {
+    #[error("Order not found: {order_id:?}")]
+    OrderNotFound { order_id: PurchaseOrderId },
+
+    #[error("Permission denied: user {user_id:?} cannot modify this order")]
+    PermissionDenied { user_id: UserId },
+
+    #[error("Order is not editable")]
+    OrderNotEditable,
+
+    #[error("Line item not found: {line_item_id:?}")]
+    LineItemNotFound {
+        line_item_id: PurchaseOrderLineItemId,
+    },
+
+    #[error("User not found: {user_id:?}")]
+    UserNotFound { user_id: UserId },
+
+    #[error("Repository error: {0}")]
+    Repository(#[from] crate::errors::RepositoryError),
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum SplitOrderItemOwnersError {
+    #[error("Order not found: {order_id:?}")]
+    OrderNotFound { order_id: PurchaseOrderId },
+
+    #[error("Permission denied: user {user_id:?} cannot modify this order")]
+    PermissionDenied { user_id: UserId },
+
+    #[error("Order is not editable")]
+    OrderNotEditable,
+
+    #[error("Order item not found: {order_item_id:?}")]
+    OrderItemNotFound { order_item_id: PurchaseOrderItemId },
+
+    #[error("Order item is not editable: {order_item_id:?}")]
+    OrderItemNotEditable { order_item_id: PurchaseOrderItemId },
+
+    #[error("User not found: {user_id:?}")]
+    UserNotFound { user_id: UserId },
+
+    #[error("Quantity mismatch: expected {expected}, got {actual}")]
+    QuantityMismatch { expected: u32, actual: u32 },
+
+    #[error("Repository error: {0}")]
+    Repository(#[from] crate::errors::RepositoryError),
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum AddOrderAdjustmentError {
+    #[error("Order not found: {order_id:?}")]
+    OrderNotFound { order_id: PurchaseOrderId },
+
+    #[error("Permission denied: user {user_id:?} cannot modify this order")]
+    PermissionDenied { user_id: UserId },
+
+    #[error("Order is not editable")]
+    OrderNotEditable,
+
+    #[error("Currency mismatch: expected {expected:?}, got {actual:?}")]
+    CurrencyMismatch {
+        expected: Currency,
+        actual: Currency,
+    },
+
+    #[error("Line item not found: {line_item_id:?}")]
+    LineItemNotFound {
+        line_item_id: PurchaseOrderLineItemId,
+    },
+
+    #[error("Allocated amount mismatch: expected {expected}, got {actual}")]
+    AllocationMismatch { expected: u64, actual: u64 },
+
+    #[error("Adjustment exceeds the order total")]
+    ExceedsTotal,
+
+    #[error("Total price overflows")]
+    PriceOverflow,
+
+    #[error("Repository error: {0}")]
+    Repository(#[from] crate::errors::RepositoryError),
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum RemoveOrderAdjustmentError {
+    #[error("Order not found: {order_id:?}")]
+    OrderNotFound { order_id: PurchaseOrderId },
+
+    #[error("Permission denied: user {user_id:?} cannot modify this order")]
+    PermissionDenied { user_id: UserId },
+
+    #[error("Adjustment not found: {adjustment_id:?}")]
+    AdjustmentNotFound {
+        adjustment_id: PurchaseOrderAdjustmentId,
+    },
+
+    #[error("Order is not editable")]
+    OrderNotEditable,
+
+    #[error("Adjustment exceeds the order total")]
+    ExceedsTotal,
+
+    #[error("Total price overflows")]
+    PriceOverflow,
+
+    #[error("Repository error: {0}")]
+    Repository(#[from] crate::errors::RepositoryError),
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum AddOrderAttachmentError {
+    #[error("Order not found: {order_id:?}")]
+    OrderNotFound { order_id: PurchaseOrderId },
+
+    #[error("Permission denied: user {user_id:?} cannot modify this order")]
+    PermissionDenied { user_id: UserId },
+
+    #[error("Media not found: {media_id:?}")]
+    MediaNotFound { media_id: MediaId },
+
+    #[error("Repository error: {0}")]
+    Repository(#[from] crate::errors::RepositoryError),
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum RemoveOrderAttachmentError {
+    #[error("Order not found: {order_id:?}")]
+    OrderNotFound { order_id: PurchaseOrderId },
+
+    #[error("Permission denied: user {user_id:?} cannot modify this order")]
+    PermissionDenied { user_id: UserId },
+
+    #[error("Attachment not found: {attachment_id:?}")]
+    AttachmentNotFound {
+        attachment_id: PurchaseOrderAttachmentId,
+    },
+
     #[error("Repository error: {0}")]
     Repository(#[from] crate::errors::RepositoryError),
 }
@@ -55,9 +298,15 @@ pub enum SubmitMysteryBoxResultsError {
     #[error("Permission denied: user {user_id:?} cannot modify this order")]
     PermissionDenied { user_id: UserId },
 
+    #[error("Order is not editable")]
+    OrderNotEditable,
+
     #[error("Order item not found: {order_item_id:?}")]
     OrderItemNotFound { order_item_id: PurchaseOrderItemId },
 
+    #[error("Order item is not editable: {order_item_id:?}")]
+    OrderItemNotEditable { order_item_id: PurchaseOrderItemId },
+
     #[error("Variant not found: {variant_id:?}")]
     VariantNotFound { variant_id: ProductVariantId },
 
@@ -67,6 +316,135 @@ pub enum SubmitMysteryBoxResultsError {
     #[error("Invalid number of variants: expected {expected}, got {actual}")]
     InvalidVariantCount { expected: u32, actual: u32 },
 
+    #[error("Unexpected variant for this mystery box: {variant_id:?}")]
+    UnexpectedVariant { variant_id: ProductVariantId },
+
+    #[error("Line item has a manual adjustment allocation: {line_item_id:?}")]
+    LineItemAllocated {
+        line_item_id: PurchaseOrderLineItemId,
+    },
+
+    #[error("Repository error: {0}")]
+    Repository(#[from] crate::errors::RepositoryError),
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum AddMysteryBoxResultsError {
+    #[error("Order not found: {order_id:?}")]
+    OrderNotFound { order_id: PurchaseOrderId },
+
+    #[error("Permission denied: user {user_id:?} cannot modify this order")]
+    PermissionDenied { user_id: UserId },
+
+    #[error("Order is not editable")]
+    OrderNotEditable,
+
+    #[error("Order item not found: {order_item_id:?}")]
+    OrderItemNotFound { order_item_id: PurchaseOrderItemId },
+
+    #[error("Order item is not editable: {order_item_id:?}")]
+    OrderItemNotEditable { order_item_id: PurchaseOrderItemId },
+
+    #[error("Variant not found: {variant_id:?}")]
+    VariantNotFound { variant_id: ProductVariantId },
+
+    #[error("Not a mystery box item")]
+    NotMysteryBox,
+
+    #[error("Too many results: expected {expected}, got {actual}")]
+    TooManyResults { expected: u32, actual: u32 },
+
+    #[error("Unexpected variant for this mystery box: {variant_id:?}")]
+    UnexpectedVariant { variant_id: ProductVariantId },
+
+    #[error("User not found: {user_id:?}")]
+    UserNotFound { user_id: UserId },
+
+    #[error("Repository error: {0}")]
+    Repository(#[from] crate::errors::RepositoryError),
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum UpdateMysteryBoxResultError {
+    #[error("Order not found: {order_id:?}")]
+    OrderNotFound { order_id: PurchaseOrderId },
+
+    #[error("Permission denied: user {user_id:?} cannot modify this order")]
+    PermissionDenied { user_id: UserId },
+
+    #[error("Order is not editable")]
+    OrderNotEditable,
+
+    #[error("Order item not found: {order_item_id:?}")]
+    OrderItemNotFound { order_item_id: PurchaseOrderItemId },
+
+    #[error("Order item is not editable: {order_item_id:?}")]
+    OrderItemNotEditable { order_item_id: PurchaseOrderItemId },
+
+    #[error("Variant not found: {variant_id:?}")]
+    VariantNotFound { variant_id: ProductVariantId },
+
+    #[error("Not a mystery box item")]
+    NotMysteryBox,
+
+    #[error("Line item not found: {line_item_id:?}")]
+    LineItemNotFound {
+        line_item_id: PurchaseOrderLineItemId,
+    },
+
+    #[error("Line item already fulfilled: {line_item_id:?}")]
+    LineItemAlreadyFulfilled {
+        line_item_id: PurchaseOrderLineItemId,
+    },
+
+    #[error("Unexpected variant for this mystery box: {variant_id:?}")]
+    UnexpectedVariant { variant_id: ProductVariantId },
+
+    #[error("User not found: {user_id:?}")]
+    UserNotFound { user_id: UserId },
+
+    #[error("Repository error: {0}")]
+    Repository(#[from] crate::errors::RepositoryError),
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum RemoveMysteryBoxResultError {
+    #[error("Order not found: {order_id:?}")]
+    OrderNotFound { order_id: PurchaseOrderId },
+
+    #[error("Permission denied: user {user_id:?} cannot modify this order")]
+    PermissionDenied { user_id: UserId },
+
+    #[error("Order is not editable")]
+    OrderNotEditable,
+
+    #[error("Order item not found: {order_item_id:?}")]
+    OrderItemNotFound { order_item_id: PurchaseOrderItemId },
+
+    #[error("Order item is not editable: {order_item_id:?}")]
+    OrderItemNotEditable { order_item_id: PurchaseOrderItemId },
+
+    #[error("Variant not found: {variant_id:?}")]
+    VariantNotFound { variant_id: ProductVariantId },
+
+    #[error("Not a mystery box item")]
+    NotMysteryBox,
+
+    #[error("Line item not found: {line_item_id:?}")]
+    LineItemNotFound {
+        line_item_id: PurchaseOrderLineItemId,
+    },
+
+    #[error("Line item already fulfilled: {line_item_id:?}")]
+    LineItemAlreadyFulfilled {
+        line_item_id: PurchaseOrderLineItemId,
+    },
+
+    #[error("Line item has a manual adjustment allocation: {line_item_id:?}")]
+    LineItemAllocated {
+        line_item_id: PurchaseOrderLineItemId,
+    },
+
     #[error("Repository error: {0}")]
     Repository(#[from] crate::errors::RepositoryError),
 }
@@ -80,6 +458,21 @@ pub enum GetOrderError {
     Repository(#[from] crate::errors::RepositoryError),
 }
 
+#[derive(Debug, thiserror::Error)]
+pub enum GetOrderEventsError {
+    #[error("Order not found")]
+    NotFound,
+
+    #[error("Repository error: {0}")]
+    Repository(#[from] crate::errors::RepositoryError),
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum ListOrderArrivalsError {
+    #[error("Repository error: {0}")]
+    Repository(#[from] crate::errors::RepositoryError),
+}
+
 #[derive(Debug, thiserror::Error)]
 pub enum ListOrdersError {
     #[error("Repository error: {0}")]
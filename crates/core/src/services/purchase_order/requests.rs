@@ -1,9 +1,16 @@
 use std::num::NonZeroU32;
 
+use chrono::NaiveDate;
+
 use crate::models::{
-    misc::{Address, Price},
+    misc::{Address, MediaId, Price},
     product::ProductVariantId,
-    purchase::{OrderRoleFilter, PurchaseOrderId, PurchaseOrderItemId, PurchaseOrderStatus},
+    purchase::{
+        AdjustmentAllocation, OrderRoleFilter, PurchaseOrderAdjustmentId,
+        PurchaseOrderAdjustmentKind, PurchaseOrderAttachmentId, PurchaseOrderAttachmentKind,
+        PurchaseOrderId, PurchaseOrderItemId, PurchaseOrderLineItemId, PurchaseOrderSchedule,
+        PurchaseOrderStatus,
+    },
     user::UserId,
 };
 
@@ -64,6 +71,181 @@ pub struct AddOrderItemRequest {
     pub unit_price: Option<Price>,
 }
 
+/// Request to remove an item from an order.
+pub struct RemoveOrderItemRequest {
+    /// The user performing this operation.
+    pub user_id: UserId,
+
+    /// The order containing the item.
+    pub order_id: PurchaseOrderId,
+
+    /// The order item to remove.
+    pub order_item_id: PurchaseOrderItemId,
+}
+
+/// Request to update an item in an order.
+///
+/// Fields left as `None` are kept unchanged.
+pub struct UpdateOrderItemRequest {
+    /// The user performing this operation.
+    pub user_id: UserId,
+
+    /// The order containing the item.
+    pub order_id: PurchaseOrderId,
+
+    /// The order item to update.
+    pub order_item_id: PurchaseOrderItemId,
+
+    /// New quantity to purchase.
+    /// Line items of regular items are regenerated to match.
+    pub quantity: Option<NonZeroU32>,
+
+    /// Corrected price at time of order.
+    pub unit_price: Option<Price>,
+}
+
+/// Request to change the owners of individual line items.
+pub struct ReassignLineItemOwnersRequest {
+    /// The user performing this operation.
+    pub user_id: UserId,
+
+    /// The order containing the line items.
+    pub order_id: PurchaseOrderId,
+
+    /// The new owner of each line item. Line items not listed keep their owner.
+    pub assignments: Vec<LineItemOwnerAssignment>,
+}
+
+/// A new owner for a single line item.
+pub struct LineItemOwnerAssignment {
+    /// The line item to reassign.
+    pub line_item_id: PurchaseOrderLineItemId,
+
+    /// The user who will own the instance created for this line item.
+    pub owner_id: UserId,
+}
+
+/// Request to split the quantity of an item among several owners.
+///
+/// The line items of the item are assigned to the owners in order,
+/// so the quantities must add up to the number of line items.
+pub struct SplitOrderItemOwnersRequest {
+    /// The user performing this operation.
+    pub user_id: UserId,
+
+    /// The order containing the item.
+    pub order_id: PurchaseOrderId,
+
+    /// The order item to split.
+    pub order_item_id: PurchaseOrderItemId,
+
+    /// How many line items each owner gets.
+    pub shares: Vec<OwnerShare>,
+}
+
+/// A number of line items of an item assigned to one owner.
+pub struct OwnerShare {
+    /// The user who will own these line items.
+    pub owner_id: UserId,
+
+    /// How many line items this owner gets.
+    pub quantity: NonZeroU32,
+}
+
+/// Request to update order-level details.
+///
+/// Fields left as `None` are kept unchanged.
+pub struct UpdateOrderRequest {
+    /// The user performing this operation.
+    pub user_id: UserId,
+
+    /// The order to update.
+    pub order_id: PurchaseOrderId,
+
+    /// New receiver of the shipment.
+    pub receiver_id: Option<UserId>,
+
+    /// New shipping/delivery address.
+    pub shipping_address: Option<Address>,
+}
+
+/// Request to set the expected dates and shipment tracking of an order.
+///
+/// The schedule is replaced as a whole; fields left as `None` are cleared.
+pub struct UpdateOrderScheduleRequest {
+    /// The user performing this operation.
+    pub user_id: UserId,
+
+    /// The order to update.
+    pub order_id: PurchaseOrderId,
+
+    /// The new schedule of the order.
+    pub schedule: PurchaseOrderSchedule,
+}
+
+/// Request to add an order-level adjustment (shipping, fee, tax, discount).
+pub struct AddOrderAdjustmentRequest {
+    /// The user performing this operation.
+    pub user_id: UserId,
+
+    /// The order to add the adjustment to.
+    pub order_id: PurchaseOrderId,
+
+    /// What the adjustment is for.
+    pub kind: PurchaseOrderAdjustmentKind,
+
+    /// The amount of the adjustment. Must be in the order currency.
+    pub amount: Price,
+
+    /// How the adjustment is spread over line items.
+    pub allocation: AdjustmentAllocation,
+
+    /// Optional note.
+    pub note: Option<String>,
+}
+
+/// Request to remove an order-level adjustment.
+pub struct RemoveOrderAdjustmentRequest {
+    /// The user performing this operation.
+    pub user_id: UserId,
+
+    /// The order containing the adjustment.
+    pub order_id: PurchaseOrderId,
+
+    /// The adjustment to remove.
+    pub adjustment_id: PurchaseOrderAdjustmentId,
+}
+
+/// Request to attach a receipt, invoice or photo to an order.
+pub struct AddOrderAttachmentRequest {
+    /// The user performing this operation.
+    pub user_id: UserId,
+
+    /// The order to attach the file to.
+    pub order_id: PurchaseOrderId,
+
+    /// The uploaded file.
+    pub media_id: MediaId,
+
+    /// What the file is.
+    pub kind: PurchaseOrderAttachmentKind,
+
+    /// Optional free-form note.
+    pub note: Option<String>,
+}
+
+/// Request to remove an attachment from an order.
+pub struct RemoveOrderAttachmentRequest {
+    /// The user performing this operation.
+    pub user_id: UserId,
+
+    /// The order containing the attachment.
+    pub order_id: PurchaseOrderId,
+
+    /// The attachment to remove.
+    pub attachment_id: PurchaseOrderAttachmentId,
+}
+
 /// Request to submit mystery box results.
 pub struct SubmitMysteryBoxResultsRequest {
     /// The user performing this operation.
@@ -82,6 +264,69 @@ pub struct SubmitMysteryBoxResultsRequest {
     pub received_variants: Vec<ProductVariantId>,
 }
 
+/// A single result from opening a mystery box.
+pub struct MysteryBoxResult {
+    /// The variant received.
+    pub variant_id: ProductVariantId,
+
+    /// The user who will own the instance created for this result.
+    /// Defaults to the order receiver.
+    pub owner_id: Option<UserId>,
+}
+
+/// Request to add mystery box results as boxes are opened.
+///
+/// The item moves to `Pending` once all `quantity * items_count` results are in.
+pub struct AddMysteryBoxResultsRequest {
+    /// The user performing this operation.
+    pub user_id: UserId,
+
+    /// The order containing the mystery box.
+    pub order_id: PurchaseOrderId,
+
+    /// The specific order item (mystery box).
+    pub order_item_id: PurchaseOrderItemId,
+
+    /// The results to add.
+    pub results: Vec<MysteryBoxResult>,
+}
+
+/// Request to correct or reassign a single unfulfilled mystery box result.
+pub struct UpdateMysteryBoxResultRequest {
+    /// The user performing this operation.
+    pub user_id: UserId,
+
+    /// The order containing the mystery box.
+    pub order_id: PurchaseOrderId,
+
+    /// The specific order item (mystery box).
+    pub order_item_id: PurchaseOrderItemId,
+
+    /// The line item created for the result.
+    pub line_item_id: PurchaseOrderLineItemId,
+
+    /// The corrected variant, if changed.
+    pub variant_id: Option<ProductVariantId>,
+
+    /// The new owner, if changed.
+    pub owner_id: Option<UserId>,
+}
+
+/// Request to remove a single unfulfilled mystery box result.
+pub struct RemoveMysteryBoxResultRequest {
+    /// The user performing this operation.
+    pub user_id: UserId,
+
+    /// The order containing the mystery box.
+    pub order_id: PurchaseOrderId,
+
+    /// The specific order item (mystery box).
+    pub order_item_id: PurchaseOrderItemId,
+
+    /// The line item created for the result.
+    pub line_item_id: PurchaseOrderLineItemId,
+}
+
 /// Request to get an order by ID.
 pub struct GetOrderRequest {
     /// The user requesting the order.
@@ -91,6 +336,28 @@ pub struct GetOrderRequest {
     pub order_id: PurchaseOrderId,
 }
 
+/// Request to get the event log of an order.
+pub struct GetOrderEventsRequest {
+    /// The user requesting the events.
+    pub user_id: UserId,
+
+    /// The order whose events to retrieve.
+    pub order_id: PurchaseOrderId,
+}
+
+/// Request to list pending orders of a user by their expected arrival date.
+pub struct ListOrderArrivalsRequest {
+    /// The user requesting the arrivals.
+    pub user_id: UserId,
+
+    /// The date to compare expected arrivals against, usually today in the user's time zone.
+    pub today: NaiveDate,
+
+    /// How many days after `today` count as arriving soon.
+    /// If `None`, all upcoming arrivals are included.
+    pub within_days: Option<u32>,
+}
+
 /// Request to list orders for a user.
 pub struct ListOrdersRequest {
     /// The user requesting the orders.
@@ -0,0 +1,28 @@
+use crate::models::{
+    product::MysteryBoxWarning,
+    purchase::{PurchaseOrder, PurchaseOrderView},
+};
+
+/// The order after submitting mystery box results, with any non-blocking problems found.
+#[derive(Debug, Clone, serde::Serialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct MysteryBoxResultsResponse {
+    /// The updated order.
+    pub order: PurchaseOrder,
+
+    /// Problems reported under the `Warn` validation policy of the mystery box.
+    pub warnings: Vec<MysteryBoxWarning>,
+}
+
+/// Pending orders of a user, grouped by their expected arrival date.
+///
+/// Each order is given as visible to that user.
+#[derive(Debug, Clone, serde::Serialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct OrderArrivalsResponse {
+    /// Orders whose expected arrival date has passed, oldest first.
+    pub overdue: Vec<PurchaseOrderView>,
+
+    /// Orders expected to arrive within the requested window, soonest first.
+    pub arriving_soon: Vec<PurchaseOrderView>,
+}
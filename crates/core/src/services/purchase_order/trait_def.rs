@@ -1,9 +1,22 @@
-use crate::models::purchase::{PurchaseOrder, PurchaseOrderItemId};
+use crate::models::purchase::{
+    PurchaseOrder, PurchaseOrderAdjustmentId, PurchaseOrderAttachment, PurchaseOrderEvent,
+    PurchaseOrderItemId, PurchaseOrderView,
+};
 
 use super::{
+    AddMysteryBoxResultsError, AddMysteryBoxResultsRequest, AddOrderAdjustmentError,
+    AddOrderAdjustmentRequest, AddOrderAttachmentError, AddOrderAttachmentRequest,
     AddOrderItemError, AddOrderItemRequest, CreateOrderError, CreateOrderRequest, GetOrderError,
-    GetOrderRequest, ListOrdersError, ListOrdersRequest, SubmitMysteryBoxResultsError,
-    SubmitMysteryBoxResultsRequest,
+    GetOrderEventsError, GetOrderEventsRequest, GetOrderRequest, ListOrderArrivalsError,
+    ListOrderArrivalsRequest, ListOrdersError, ListOrdersRequest, MysteryBoxResultsResponse,
+    OrderArrivalsResponse, ReassignLineItemOwnersError, ReassignLineItemOwnersRequest,
+    RemoveMysteryBoxResultError, RemoveMysteryBoxResultRequest, RemoveOrderAdjustmentError,
+    RemoveOrderAdjustmentRequest, RemoveOrderAttachmentError, RemoveOrderAttachmentRequest,
+    RemoveOrderItemError, RemoveOrderItemRequest, SplitOrderItemOwnersError,
+    SplitOrderItemOwnersRequest, SubmitMysteryBoxResultsError, SubmitMysteryBoxResultsRequest,
+    UpdateMysteryBoxResultError, UpdateMysteryBoxResultRequest, UpdateOrderError,
+    UpdateOrderItemError, UpdateOrderItemRequest, UpdateOrderRequest, UpdateOrderScheduleError,
+    UpdateOrderScheduleRequest,
 };
 
 /// Service for managing purchase orders (Port).
@@ -11,8 +24,12 @@ use super::{
 /// This service handles operations related to purchase order lifecycle:
 /// - Creating new orders
 /// - Adding items to orders
-/// - Submitting mystery box results
-/// - Querying orders
+/// - Editing incomplete orders
+/// - Managing order-level adjustments (shipping, fees, tax, discounts)
+/// - Keeping receipts, invoices and photos with orders
+/// - Submitting mystery box results, at once or box by box
+/// - Tracking expected arrival dates of pre-orders
+/// - Querying orders and their event logs
 pub trait PurchaseOrderService: Send + Sync + 'static {
     /// Create a new purchase order.
     fn create_order(
@@ -26,21 +43,132 @@ pub trait PurchaseOrderService: Send + Sync + 'static {
         req: AddOrderItemRequest,
     ) -> impl Future<Output = Result<PurchaseOrderItemId, AddOrderItemError>> + Send;
 
+    /// Remove an item from an incomplete order.
+    fn remove_order_item(
+        &self,
+        req: RemoveOrderItemRequest,
+    ) -> impl Future<Output = Result<PurchaseOrder, RemoveOrderItemError>> + Send;
+
+    /// Update the quantity or unit price of an item in an incomplete order.
+    fn update_order_item(
+        &self,
+        req: UpdateOrderItemRequest,
+    ) -> impl Future<Output = Result<PurchaseOrder, UpdateOrderItemError>> + Send;
+
+    /// Change the owners of individual line items of an incomplete order.
+    fn reassign_line_item_owners(
+        &self,
+        req: ReassignLineItemOwnersRequest,
+    ) -> impl Future<Output = Result<PurchaseOrder, ReassignLineItemOwnersError>> + Send;
+
+    /// Split the quantity of an item in an incomplete order among several owners.
+    fn split_order_item_owners(
+        &self,
+        req: SplitOrderItemOwnersRequest,
+    ) -> impl Future<Output = Result<PurchaseOrder, SplitOrderItemOwnersError>> + Send;
+
+    /// Update the receiver or shipping address of an incomplete order.
+    ///
+    /// Line items still owned by the previous receiver by default move to the new
+    /// receiver; line items assigned to it explicitly by a reassignment keep their owner.
+    fn update_order(
+        &self,
+        req: UpdateOrderRequest,
+    ) -> impl Future<Output = Result<PurchaseOrder, UpdateOrderError>> + Send;
+
+    /// Attach a receipt, invoice or photo to an order.
+    ///
+    /// Only the creator and the receiver can manage attachments, in any order status.
+    fn add_order_attachment(
+        &self,
+        req: AddOrderAttachmentRequest,
+    ) -> impl Future<Output = Result<PurchaseOrderAttachment, AddOrderAttachmentError>> + Send;
+
+    /// Remove an attachment from an order.
+    fn remove_order_attachment(
+        &self,
+        req: RemoveOrderAttachmentRequest,
+    ) -> impl Future<Output = Result<PurchaseOrder, RemoveOrderAttachmentError>> + Send;
+
+    /// Set the expected dates and shipment tracking of a pending order.
+    fn update_order_schedule(
+        &self,
+        req: UpdateOrderScheduleRequest,
+    ) -> impl Future<Output = Result<PurchaseOrder, UpdateOrderScheduleError>> + Send;
+
+    /// Add an order-level adjustment to an incomplete order.
+    fn add_order_adjustment(
+        &self,
+        req: AddOrderAdjustmentRequest,
+    ) -> impl Future<Output = Result<PurchaseOrderAdjustmentId, AddOrderAdjustmentError>> + Send;
+
+    /// Remove an order-level adjustment from an incomplete order.
+    fn remove_order_adjustment(
+        &self,
+        req: RemoveOrderAdjustmentRequest,
+    ) -> impl Future<Output = Result<PurchaseOrder, RemoveOrderAdjustmentError>> + Send;
+
     /// Submit mystery box results.
+    ///
+    /// Replaces all results of the item at once; see `add_mystery_box_results`
+    /// for recording boxes one by one.
+    ///
+    /// Results are checked against the box's `possible_variants` according to its
+    /// validation policy: strict boxes reject unexpected variants, warning boxes
+    /// report them in the response.
     fn submit_mystery_box_results(
         &self,
         req: SubmitMysteryBoxResultsRequest,
-    ) -> impl Future<Output = Result<(), SubmitMysteryBoxResultsError>> + Send;
+    ) -> impl Future<Output = Result<MysteryBoxResultsResponse, SubmitMysteryBoxResultsError>> + Send;
+
+    /// Add results to a mystery box item as boxes are opened.
+    ///
+    /// Results are validated like in `submit_mystery_box_results`.
+    fn add_mystery_box_results(
+        &self,
+        req: AddMysteryBoxResultsRequest,
+    ) -> impl Future<Output = Result<MysteryBoxResultsResponse, AddMysteryBoxResultsError>> + Send;
 
-    /// Get an order by ID.
+    /// Correct the variant or reassign the owner of an unfulfilled mystery box result.
+    ///
+    /// A corrected variant is validated like in `submit_mystery_box_results`.
+    fn update_mystery_box_result(
+        &self,
+        req: UpdateMysteryBoxResultRequest,
+    ) -> impl Future<Output = Result<MysteryBoxResultsResponse, UpdateMysteryBoxResultError>> + Send;
+
+    /// Remove an unfulfilled mystery box result.
+    fn remove_mystery_box_result(
+        &self,
+        req: RemoveMysteryBoxResultRequest,
+    ) -> impl Future<Output = Result<PurchaseOrder, RemoveMysteryBoxResultError>> + Send;
+
+    /// Get an order by ID, as visible to the requesting user.
+    ///
+    /// Participants who are neither the creator nor the receiver get a redacted view.
     fn get_order(
         &self,
         req: GetOrderRequest,
-    ) -> impl Future<Output = Result<PurchaseOrder, GetOrderError>> + Send;
+    ) -> impl Future<Output = Result<PurchaseOrderView, GetOrderError>> + Send;
+
+    /// Get the event log of an order, oldest first.
+    ///
+    /// Participants who are neither the creator nor the receiver only see events about the
+    /// order as a whole, their own line items and their own payments.
+    fn get_order_events(
+        &self,
+        req: GetOrderEventsRequest,
+    ) -> impl Future<Output = Result<Vec<PurchaseOrderEvent>, GetOrderEventsError>> + Send;
+
+    /// List pending orders of a user that are overdue or arriving soon.
+    fn list_order_arrivals(
+        &self,
+        req: ListOrderArrivalsRequest,
+    ) -> impl Future<Output = Result<OrderArrivalsResponse, ListOrderArrivalsError>> + Send;
 
-    /// List orders for a user.
+    /// List orders for a user, each as visible to that user.
     fn list_orders(
         &self,
         req: ListOrdersRequest,
-    ) -> impl Future<Output = Result<Vec<PurchaseOrder>, ListOrdersError>> + Send;
+    ) -> impl Future<Output = Result<Vec<PurchaseOrderView>, ListOrdersError>> + Send;
 }
@@ -1,4 +1,9 @@
-use crate::models::user::UserId;
+use crate::models::{
+    misc::Currency,
+    product::ProductInstanceId,
+    purchase::{PurchaseOrderItemId, PurchaseOrderLineItemId},
+    user::UserId,
+};
 
 #[derive(Debug, thiserror::Error)]
 pub enum FulfillOrderError {
@@ -14,6 +19,38 @@ pub enum FulfillOrderError {
     #[error("Order item not in pending status")]
     ItemNotPending,
 
+    #[error("Failed to create delivery transaction: {0}")]
+    Delivery(#[from] crate::services::CreateTransactionError),
+
+    #[error("Repository error: {0}")]
+    Repository(#[from] crate::errors::RepositoryError),
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum FulfillOrderItemsError {
+    #[error("Permission denied: user {user_id:?} cannot fulfill this order")]
+    PermissionDenied { user_id: UserId },
+
+    #[error("Order not found")]
+    OrderNotFound,
+
+    #[error("Order is cancelled and can not be fulfilled")]
+    OrderCancelled,
+
+    #[error("Order item not found: {order_item_id:?}")]
+    OrderItemNotFound { order_item_id: PurchaseOrderItemId },
+
+    #[error("Line item not found: {line_item_id:?}")]
+    LineItemNotFound {
+        line_item_id: PurchaseOrderLineItemId,
+    },
+
+    #[error("Order item not in pending status")]
+    ItemNotPending,
+
+    #[error("Failed to create delivery transaction: {0}")]
+    Delivery(#[from] crate::services::CreateTransactionError),
+
     #[error("Repository error: {0}")]
     Repository(#[from] crate::errors::RepositoryError),
 }
@@ -32,3 +69,48 @@ pub enum CancelOrderError {
     #[error("Repository error: {0}")]
     Repository(#[from] crate::errors::RepositoryError),
 }
+
+#[derive(Debug, thiserror::Error)]
+pub enum ReturnOrderItemsError {
+    #[error("Order not found")]
+    OrderNotFound,
+
+    #[error("Permission denied: user {user_id:?} cannot return items of this order")]
+    PermissionDenied { user_id: UserId },
+
+    #[error("Order has no fulfilled items to return")]
+    OrderNotFulfilled,
+
+    #[error("No line items selected")]
+    NoLineItems,
+
+    #[error("A return can not have both a refund and replacements")]
+    RefundWithReplacement,
+
+    #[error("Line item not found: {line_item_id:?}")]
+    LineItemNotFound {
+        line_item_id: PurchaseOrderLineItemId,
+    },
+
+    #[error("Line item not fulfilled: {line_item_id:?}")]
+    LineItemNotFulfilled {
+        line_item_id: PurchaseOrderLineItemId,
+    },
+
+    #[error("Line item already returned: {line_item_id:?}")]
+    LineItemAlreadyReturned {
+        line_item_id: PurchaseOrderLineItemId,
+    },
+
+    #[error("Instance {instance_id:?} is not active and can not be returned")]
+    InstanceNotReturnable { instance_id: ProductInstanceId },
+
+    #[error("Currency mismatch: expected {expected:?}, got {actual:?}")]
+    CurrencyMismatch {
+        expected: Currency,
+        actual: Currency,
+    },
+
+    #[error("Repository error: {0}")]
+    Repository(#[from] crate::errors::RepositoryError),
+}
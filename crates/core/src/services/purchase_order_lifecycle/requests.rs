@@ -1,4 +1,8 @@
-use crate::models::{purchase::PurchaseOrderId, user::UserId};
+use crate::models::{
+    misc::Price,
+    purchase::{PurchaseOrderId, PurchaseOrderItemId, PurchaseOrderLineItemId},
+    user::UserId,
+};
 
 /// Request to fulfill a purchase order.
 pub struct FulfillOrderRequest {
@@ -10,6 +14,24 @@ pub struct FulfillOrderRequest {
     pub order_id: PurchaseOrderId,
 }
 
+/// Request to fulfill part of a purchase order.
+///
+/// Whole items and individual line items can be selected together.
+pub struct FulfillOrderItemsRequest {
+    /// The user performing this operation.
+    /// Must be the creator or receiver of the order.
+    pub user_id: UserId,
+
+    /// The ID of the order to fulfill.
+    pub order_id: PurchaseOrderId,
+
+    /// Items whose line items should all be fulfilled.
+    pub item_ids: Vec<PurchaseOrderItemId>,
+
+    /// Individual line items to fulfill (e.g. part of a split shipment).
+    pub line_item_ids: Vec<PurchaseOrderLineItemId>,
+}
+
 /// Request to cancel a purchase order.
 pub struct CancelOrderRequest {
     /// The user performing this operation.
@@ -19,6 +41,30 @@ pub struct CancelOrderRequest {
     /// The ID of the order to cancel.
     pub order_id: PurchaseOrderId,
 
-    /// Optional reason for cancellation.
+    /// Optional reason for cancellation, recorded in the order's event log.
     pub reason: Option<String>,
 }
+
+/// Request to return fulfilled line items to the seller.
+pub struct ReturnOrderItemsRequest {
+    /// The user performing this operation.
+    /// Must be the creator of the order.
+    pub user_id: UserId,
+
+    /// The ID of the order the line items belong to.
+    pub order_id: PurchaseOrderId,
+
+    /// The fulfilled line items to return.
+    pub line_item_ids: Vec<PurchaseOrderLineItemId>,
+
+    /// The amount refunded by the seller, if any. Must be in the order currency.
+    pub refund: Option<Price>,
+
+    /// Optional reason (e.g. defective, wrong item).
+    pub reason: Option<String>,
+
+    /// Whether the seller sends replacements.
+    /// If true, a new unfulfilled line item is added for each returned one.
+    /// Can not be combined with a refund.
+    pub replace: bool,
+}
@@ -1,6 +1,9 @@
 use crate::models::purchase::PurchaseOrder;
 
-use super::{CancelOrderError, CancelOrderRequest, FulfillOrderError, FulfillOrderRequest};
+use super::{
+    CancelOrderError, CancelOrderRequest, FulfillOrderError, FulfillOrderItemsError,
+    FulfillOrderItemsRequest, FulfillOrderRequest, ReturnOrderItemsError, ReturnOrderItemsRequest,
+};
 
 /// Service for managing purchase order lifecycle and state transitions (Port).
 ///
@@ -8,16 +11,20 @@ use super::{CancelOrderError, CancelOrderRequest, FulfillOrderError, FulfillOrde
 /// This service handles order state transitions:
 /// - Fulfillment: Creating [ProductInstance]s from [PurchaseOrderLineItem]s
 /// - Cancellation: Cancelling incomplete orders (user input errors, etc.)
+/// - Returns: Sending fulfilled items back to the seller, with optional refund and replacement
 ///
 /// # State Transitions
 ///
 /// ```text
 /// Incomplete --fulfill--> Completed
+/// Incomplete --fulfill items--> PartiallyFulfilled --fulfill (rest)--> Completed
 /// Incomplete --cancel--> Cancelled
+/// PartiallyFulfilled --cancel (rest)--> Completed
+/// Completed --return with replacement--> PartiallyFulfilled
 /// ```
 ///
-/// Note: Completed orders CANNOT be cancelled. For returns/refunds of completed
-/// orders, a separate refund flow should be implemented in the future.
+/// Note: Completed orders CANNOT be cancelled. Use `return_order_items` for returns
+/// and refunds of fulfilled items instead.
 ///
 /// Responsibilities:
 /// - State validation before transitions
@@ -27,26 +34,49 @@ use super::{CancelOrderError, CancelOrderRequest, FulfillOrderError, FulfillOrde
 pub trait PurchaseOrderLifecycleService: Send + Sync + 'static {
     /// Fulfill a purchase order.
     ///
-    /// Transitions order from Incomplete to Completed.
+    /// Transitions order from Incomplete or PartiallyFulfilled to Completed.
     /// Creates ProductInstances and UserTransactions as needed.
     ///
     /// # Preconditions
     ///
-    /// - Order status must be Incomplete
-    /// - All order items must be in Pending status
+    /// - User must be the creator or the receiver of the order
+    /// - Order status must be Incomplete or PartiallyFulfilled
+    /// - All unfulfilled order items must be in Pending status
     fn fulfill_order(
         &self,
         req: &FulfillOrderRequest,
     ) -> impl Future<Output = Result<PurchaseOrder, FulfillOrderError>> + Send;
 
+    /// Fulfill selected items and line items of a purchase order.
+    ///
+    /// Creates ProductInstances for the selected line items only. An item becomes
+    /// Fulfilled once all its line items are fulfilled. The order becomes PartiallyFulfilled,
+    /// or Completed once no item is left awaiting input or delivery.
+    /// Items owned by other users are handed over through delivery UserTransactions.
+    ///
+    /// # Preconditions
+    ///
+    /// - User must be the creator or the receiver of the order
+    /// - Order status must be Incomplete or PartiallyFulfilled
+    /// - Selected items must be in Pending status (already fulfilled ones are skipped)
+    fn fulfill_order_items(
+        &self,
+        req: &FulfillOrderItemsRequest,
+    ) -> impl Future<Output = Result<PurchaseOrder, FulfillOrderItemsError>> + Send;
+
     /// Cancel an incomplete purchase order.
     ///
-    /// This operation is ONLY allowed for Incomplete orders.
-    /// Completed orders cannot be cancelled (use refund flow instead).
+    /// This operation is ONLY allowed for Incomplete and PartiallyFulfilled orders.
+    /// Completed orders cannot be cancelled (use `return_order_items` instead).
+    ///
+    /// For a PartiallyFulfilled order, only the remaining line items are cancelled and
+    /// the order is completed with what has been fulfilled. The total price no longer
+    /// includes cancelled items and line items, nor manual adjustment allocations to them.
+    /// Mystery boxes are paid per box, so a partially received box keeps its price.
     ///
     /// # Preconditions
     ///
-    /// - Order status must be Incomplete
+    /// - Order status must be Incomplete or PartiallyFulfilled
     ///
     /// # Use Cases
     ///
@@ -60,4 +90,23 @@ pub trait PurchaseOrderLifecycleService: Send + Sync + 'static {
         &self,
         req: &CancelOrderRequest,
     ) -> impl Future<Output = Result<PurchaseOrder, CancelOrderError>> + Send;
+
+    /// Return fulfilled line items to the seller.
+    ///
+    /// The instances of the returned line items become `Returned` (terminal state), and
+    /// the return is recorded on the order with an optional refund. If replacements are
+    /// requested, a new unfulfilled line item is added for each returned one, so the
+    /// affected items become Pending and the order PartiallyFulfilled until the
+    /// replacements are fulfilled.
+    ///
+    /// # Preconditions
+    ///
+    /// - Order status must be PartiallyFulfilled or Completed
+    /// - Line items must be fulfilled and not returned yet
+    /// - A refund and replacements are mutually exclusive
+    /// - Their instances must be Active (not locked in a pending transaction)
+    fn return_order_items(
+        &self,
+        req: &ReturnOrderItemsRequest,
+    ) -> impl Future<Output = Result<PurchaseOrder, ReturnOrderItemsError>> + Send;
 }
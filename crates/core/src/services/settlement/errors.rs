@@ -0,0 +1,68 @@
+use crate::models::{
+    misc::Currency,
+    purchase::{PurchaseOrderId, PurchaseOrderPaymentId},
+    user::UserId,
+};
+
+#[derive(Debug, thiserror::Error)]
+pub enum GetOrderSettlementError {
+    #[error("Order not found: {order_id:?}")]
+    OrderNotFound { order_id: PurchaseOrderId },
+
+    #[error("Repository error: {0}")]
+    Repository(#[from] crate::errors::RepositoryError),
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum RecordPaymentError {
+    #[error("Order not found: {order_id:?}")]
+    OrderNotFound { order_id: PurchaseOrderId },
+
+    #[error("Permission denied: user {user_id:?} cannot record this payment")]
+    PermissionDenied { user_id: UserId },
+
+    #[error("The order creator {payer_id:?} cannot pay themselves")]
+    PayerIsCreator { payer_id: UserId },
+
+    #[error("User {payer_id:?} does not take part in this order")]
+    PayerNotParticipant { payer_id: UserId },
+
+    #[error("Payment amount must be greater than zero")]
+    InvalidAmount,
+
+    #[error("Currency mismatch: expected {expected:?}, got {actual:?}")]
+    CurrencyMismatch {
+        expected: Currency,
+        actual: Currency,
+    },
+
+    #[error("Repository error: {0}")]
+    Repository(#[from] crate::errors::RepositoryError),
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum RemovePaymentError {
+    #[error("Order not found: {order_id:?}")]
+    OrderNotFound { order_id: PurchaseOrderId },
+
+    #[error("Permission denied: user {user_id:?} cannot remove this payment")]
+    PermissionDenied { user_id: UserId },
+
+    #[error("Payment not found: {payment_id:?}")]
+    PaymentNotFound { payment_id: PurchaseOrderPaymentId },
+
+    #[error("Repository error: {0}")]
+    Repository(#[from] crate::errors::RepositoryError),
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum ListBalancesError {
+    #[error("Repository error: {0}")]
+    Repository(#[from] crate::errors::RepositoryError),
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum GetNettingError {
+    #[error("Repository error: {0}")]
+    Repository(#[from] crate::errors::RepositoryError),
+}
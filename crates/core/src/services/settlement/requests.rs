@@ -0,0 +1,56 @@
+use crate::models::{
+    misc::Price,
+    purchase::{PurchaseOrderId, PurchaseOrderPaymentId},
+    user::UserId,
+};
+
+/// Request to get the settlement of an order.
+pub struct GetOrderSettlementRequest {
+    /// The user performing this operation.
+    pub user_id: UserId,
+
+    /// The order to settle.
+    pub order_id: PurchaseOrderId,
+}
+
+/// Request to record a payment from a participant to the order creator.
+pub struct RecordPaymentRequest {
+    /// The user performing this operation. Must be the order creator or the payer.
+    pub user_id: UserId,
+
+    /// The order the payment settles.
+    pub order_id: PurchaseOrderId,
+
+    /// The participant who paid.
+    pub payer_id: UserId,
+
+    /// The amount paid, in the order currency.
+    pub amount: Price,
+
+    /// Optional free-form note (e.g. payment method, reference).
+    pub note: Option<String>,
+}
+
+/// Request to remove a recorded payment.
+pub struct RemovePaymentRequest {
+    /// The user performing this operation. Must be the order creator or the payer.
+    pub user_id: UserId,
+
+    /// The order containing the payment.
+    pub order_id: PurchaseOrderId,
+
+    /// The payment to remove.
+    pub payment_id: PurchaseOrderPaymentId,
+}
+
+/// Request to list the outstanding balances of a user.
+pub struct ListBalancesRequest {
+    /// The user whose balances to list.
+    pub user_id: UserId,
+}
+
+/// Request to net the balances of all orders a user participates in.
+pub struct GetNettingRequest {
+    /// The user performing this operation.
+    pub user_id: UserId,
+}
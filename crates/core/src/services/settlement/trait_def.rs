@@ -0,0 +1,63 @@
+use crate::models::{
+    purchase::PurchaseOrderPayment,
+    settlement::{Balance, OrderSettlement},
+};
+
+use super::{
+    GetNettingError, GetNettingRequest, GetOrderSettlementError, GetOrderSettlementRequest,
+    ListBalancesError, ListBalancesRequest, RecordPaymentError, RecordPaymentRequest,
+    RemovePaymentError, RemovePaymentRequest,
+};
+
+/// Service for settling group-buy costs (Port).
+///
+/// In a group buy the order creator pays for everything, and every participant owes the
+/// landed cost of the line items they own. This service handles:
+/// - Calculating what each participant owes per order
+/// - Recording payments from participants to the creator
+/// - Outstanding balances of a user across all orders
+/// - Netting balances over many orders into "who owes whom"
+///
+/// Cancelled orders are included, so payments made before a cancellation show up as
+/// amounts owed back to the payer.
+pub trait SettlementService: Send + Sync + 'static {
+    /// Get what each participant of an order owes the creator.
+    ///
+    /// Participants who are neither the creator nor the receiver only see their own share.
+    fn get_order_settlement(
+        &self,
+        req: GetOrderSettlementRequest,
+    ) -> impl Future<Output = Result<OrderSettlement, GetOrderSettlementError>> + Send;
+
+    /// Record a payment from a participant to the order creator.
+    fn record_payment(
+        &self,
+        req: RecordPaymentRequest,
+    ) -> impl Future<Output = Result<PurchaseOrderPayment, RecordPaymentError>> + Send;
+
+    /// Remove a recorded payment, e.g. one recorded by mistake.
+    ///
+    /// Returns the updated settlement, as visible to the user (see `get_order_settlement`).
+    fn remove_payment(
+        &self,
+        req: RemovePaymentRequest,
+    ) -> impl Future<Output = Result<OrderSettlement, RemovePaymentError>> + Send;
+
+    /// List the outstanding balances between a user and everyone they share orders with.
+    ///
+    /// Balances are netted per counterparty and currency across all orders.
+    fn list_balances(
+        &self,
+        req: ListBalancesRequest,
+    ) -> impl Future<Output = Result<Vec<Balance>, ListBalancesError>> + Send;
+
+    /// Net all balances of the orders a user participates in into the fewest transfers.
+    ///
+    /// Unlike `list_balances`, this includes balances between other participants of the
+    /// orders the user created or receives, so debts can be settled through intermediaries
+    /// (A owes B, B owes C → A pays C). Of other orders, only the user's own balance counts.
+    fn get_netting(
+        &self,
+        req: GetNettingRequest,
+    ) -> impl Future<Output = Result<Vec<Balance>, GetNettingError>> + Send;
+}
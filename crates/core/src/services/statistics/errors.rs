@@ -0,0 +1,8 @@
+#[derive(Debug, thiserror::Error)]
+pub enum GetStatisticsError {
+    #[error("Invalid date range: start is after end")]
+    InvalidRange,
+
+    #[error("Repository error: {0}")]
+    Repository(#[from] crate::errors::RepositoryError),
+}
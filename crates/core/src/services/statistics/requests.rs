@@ -0,0 +1,14 @@
+use crate::models::{statistics::DateRange, user::UserId};
+
+pub struct GetStatisticsRequest {
+    pub user_id: UserId,
+    pub range: DateRange,
+}
+
+pub struct GetMostBoughtSeriesRequest {
+    pub user_id: UserId,
+    pub range: DateRange,
+
+    /// The maximum number of series to return
+    pub limit: usize,
+}
@@ -0,0 +1,50 @@
+use crate::models::statistics::{
+    MonthlySpending, MysteryBoxStatistics, ProductStatistics, SeriesPurchaseCount, StatusCount,
+    TagStatistics,
+};
+
+use super::{GetMostBoughtSeriesRequest, GetStatisticsError, GetStatisticsRequest};
+
+/// Service for a user's collection and spending statistics (Port).
+///
+/// Spending is the landed acquisition cost of what a user received from
+/// fulfilled orders, leaving out returned items. Amounts are never converted
+/// between currencies.
+pub trait StatisticsService: Send + Sync + 'static {
+    /// Get what the user spent per month and currency.
+    fn get_monthly_spending(
+        &self,
+        req: GetStatisticsRequest,
+    ) -> impl Future<Output = Result<Vec<MonthlySpending>, GetStatisticsError>> + Send;
+
+    /// Get how many of the user's instances are in each status.
+    fn get_status_counts(
+        &self,
+        req: GetStatisticsRequest,
+    ) -> impl Future<Output = Result<Vec<StatusCount>, GetStatisticsError>> + Send;
+
+    /// Get how many instances the user owns per tag, and what they cost.
+    fn get_tag_statistics(
+        &self,
+        req: GetStatisticsRequest,
+    ) -> impl Future<Output = Result<Vec<TagStatistics>, GetStatisticsError>> + Send;
+
+    /// Get how many instances the user owns per product, and what they cost.
+    fn get_product_statistics(
+        &self,
+        req: GetStatisticsRequest,
+    ) -> impl Future<Output = Result<Vec<ProductStatistics>, GetStatisticsError>> + Send;
+
+    /// Get the products the user bought the most items of.
+    fn get_most_bought_series(
+        &self,
+        req: GetMostBoughtSeriesRequest,
+    ) -> impl Future<Output = Result<Vec<SeriesPurchaseCount>, GetStatisticsError>> + Send;
+
+    /// Get what the user's mystery boxes yielded, and their average cost per
+    /// variant obtained.
+    fn get_mystery_box_statistics(
+        &self,
+        req: GetStatisticsRequest,
+    ) -> impl Future<Output = Result<Vec<MysteryBoxStatistics>, GetStatisticsError>> + Send;
+}
@@ -0,0 +1,8 @@
+mod errors;
+pub use errors::*;
+
+mod requests;
+pub use requests::*;
+
+mod trait_def;
+pub use trait_def::*;
@@ -0,0 +1,64 @@
+use crate::models::{product::ProductInstanceId, storage::StorageLocationId};
+
+#[derive(Debug, thiserror::Error)]
+pub enum CreateStorageLocationError {
+    #[error("Parent location not found: {parent_id:?}")]
+    ParentNotFound { parent_id: StorageLocationId },
+
+    #[error("Repository error: {0}")]
+    Repository(#[from] crate::errors::RepositoryError),
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum ListStorageLocationsError {
+    #[error("Repository error: {0}")]
+    Repository(#[from] crate::errors::RepositoryError),
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum UpdateStorageLocationError {
+    #[error("Location not found")]
+    NotFound,
+
+    #[error("Parent location not found: {parent_id:?}")]
+    ParentNotFound { parent_id: StorageLocationId },
+
+    #[error("A location cannot be moved inside itself")]
+    Cycle,
+
+    #[error("Repository error: {0}")]
+    Repository(#[from] crate::errors::RepositoryError),
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum DeleteStorageLocationError {
+    #[error("Location not found")]
+    NotFound,
+
+    #[error("Location still contains other locations or goods")]
+    NotEmpty,
+
+    #[error("Repository error: {0}")]
+    Repository(#[from] crate::errors::RepositoryError),
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum MoveProductInstancesError {
+    #[error("Location not found")]
+    LocationNotFound,
+
+    #[error("Product instance not found: {instance_id:?}")]
+    InstanceNotFound { instance_id: ProductInstanceId },
+
+    #[error("Product instance is not held by the user: {instance_id:?}")]
+    NotHeld { instance_id: ProductInstanceId },
+
+    #[error("Repository error: {0}")]
+    Repository(#[from] crate::errors::RepositoryError),
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum LocateProductInstancesError {
+    #[error("Repository error: {0}")]
+    Repository(#[from] crate::errors::RepositoryError),
+}
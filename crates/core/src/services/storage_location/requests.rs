@@ -0,0 +1,59 @@
+use crate::models::{
+    misc::NonEmptyString, product::ProductInstanceId, storage::StorageLocationId, user::UserId,
+};
+
+/// Request to create a storage location.
+pub struct CreateStorageLocationRequest {
+    /// The user the location belongs to.
+    pub user_id: UserId,
+
+    pub name: NonEmptyString,
+
+    /// The location containing the new one, or `None` for a top-level location.
+    pub parent_id: Option<StorageLocationId>,
+}
+
+/// Request to list the storage locations of a user.
+pub struct ListStorageLocationsRequest {
+    pub user_id: UserId,
+}
+
+/// Request to rename or move a storage location.
+pub struct UpdateStorageLocationRequest {
+    pub id: StorageLocationId,
+
+    /// The user attempting the update (must own the location).
+    pub user_id: UserId,
+
+    pub name: NonEmptyString,
+
+    /// The new parent location, or `None` to make it a top-level location.
+    pub parent_id: Option<StorageLocationId>,
+}
+
+/// Request to delete an empty storage location.
+pub struct DeleteStorageLocationRequest {
+    pub id: StorageLocationId,
+
+    /// The user attempting the deletion (must own the location).
+    pub user_id: UserId,
+}
+
+/// Request to move product instances into a storage location.
+pub struct MoveProductInstancesRequest {
+    /// The user moving the instances (must hold all of them).
+    pub user_id: UserId,
+
+    pub instance_ids: Vec<ProductInstanceId>,
+
+    /// The target location, or `None` to clear the location.
+    pub location_id: Option<StorageLocationId>,
+}
+
+/// Request to find where the instances of a variant are kept.
+pub struct LocateProductInstancesRequest {
+    pub user_id: UserId,
+
+    /// Part of the variant name to search for (case-insensitive).
+    pub query: String,
+}
@@ -0,0 +1,62 @@
+use crate::models::{
+    product::ProductInstance,
+    storage::{LocatedProductInstance, StorageLocation},
+};
+
+use super::{
+    CreateStorageLocationError, CreateStorageLocationRequest, DeleteStorageLocationError,
+    DeleteStorageLocationRequest, ListStorageLocationsError, ListStorageLocationsRequest,
+    LocateProductInstancesError, LocateProductInstancesRequest, MoveProductInstancesError,
+    MoveProductInstancesRequest, UpdateStorageLocationError, UpdateStorageLocationRequest,
+};
+
+/// Service for managing where goods are physically kept (Port).
+///
+/// This service handles the per-user hierarchy of storage locations:
+/// - Creating, renaming, moving and deleting locations
+/// - Moving product instances between locations
+/// - Finding where the instances of a variant are kept
+pub trait StorageLocationService: Send + Sync + 'static {
+    /// Create a storage location, optionally inside another one.
+    fn create_storage_location(
+        &self,
+        req: CreateStorageLocationRequest,
+    ) -> impl Future<Output = Result<StorageLocation, CreateStorageLocationError>> + Send;
+
+    /// List all storage locations of a user.
+    fn list_storage_locations(
+        &self,
+        req: ListStorageLocationsRequest,
+    ) -> impl Future<Output = Result<Vec<StorageLocation>, ListStorageLocationsError>> + Send;
+
+    /// Rename a storage location or move it into another one.
+    ///
+    /// Moving a location inside itself or one of its descendants is rejected.
+    fn update_storage_location(
+        &self,
+        req: UpdateStorageLocationRequest,
+    ) -> impl Future<Output = Result<StorageLocation, UpdateStorageLocationError>> + Send;
+
+    /// Delete a storage location.
+    ///
+    /// Only locations without nested locations and goods can be deleted.
+    fn delete_storage_location(
+        &self,
+        req: DeleteStorageLocationRequest,
+    ) -> impl Future<Output = Result<(), DeleteStorageLocationError>> + Send;
+
+    /// Move product instances held by the user into a location.
+    ///
+    /// Either all instances are moved, or none if any of them fails validation.
+    fn move_product_instances(
+        &self,
+        req: MoveProductInstancesRequest,
+    ) -> impl Future<Output = Result<Vec<ProductInstance>, MoveProductInstancesError>> + Send;
+
+    /// Find the instances owned or held by the user whose variant name matches the query,
+    /// with where each of them is kept.
+    fn locate_product_instances(
+        &self,
+        req: LocateProductInstancesRequest,
+    ) -> impl Future<Output = Result<Vec<LocatedProductInstance>, LocateProductInstancesError>> + Send;
+}
@@ -0,0 +1,31 @@
+#[derive(Debug, thiserror::Error)]
+pub enum SetTradeOptInError {
+    #[error("Repository error: {0}")]
+    Repository(#[from] crate::errors::RepositoryError),
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum FindTradeMatchesError {
+    #[error("User has not opted in to trade matching")]
+    NotOptedIn,
+
+    #[error("Repository error: {0}")]
+    Repository(#[from] crate::errors::RepositoryError),
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum ProposeTradeMatchError {
+    #[error("User has not opted in to trade matching")]
+    NotOptedIn,
+
+    /// The match is not (or no longer) among the matches found for the user,
+    /// e.g. because a spare was traded away or a want was satisfied meanwhile.
+    #[error("Trade match not available")]
+    MatchNotAvailable,
+
+    #[error(transparent)]
+    CreateTransaction(#[from] crate::services::CreateTransactionError),
+
+    #[error("Repository error: {0}")]
+    Repository(#[from] crate::errors::RepositoryError),
+}
@@ -0,0 +1,27 @@
+use crate::models::{transfer::TradeMatch, user::UserId};
+
+/// The longest cycle of users searched for trade matches.
+pub const MAX_TRADE_CYCLE_LENGTH: usize = 4;
+
+/// Request to take part in trade matching, or stop taking part.
+pub struct SetTradeOptInRequest {
+    pub user_id: UserId,
+    pub open_to_trades: bool,
+}
+
+/// Request to find trade matches involving a user.
+pub struct FindTradeMatchesRequest {
+    pub user_id: UserId,
+
+    /// The most users in a match, between 2 (pairs only) and `MAX_TRADE_CYCLE_LENGTH`.
+    pub max_cycle_length: usize,
+}
+
+/// Request to turn a trade match into transactions.
+pub struct ProposeTradeMatchRequest {
+    /// The user proposing the match, who must take part in it.
+    pub user_id: UserId,
+
+    /// A match previously found for the user.
+    pub trade_match: TradeMatch,
+}
@@ -0,0 +1,42 @@
+use crate::models::{
+    transfer::{TradeMatch, UserTransaction},
+    wishlist::Wishlist,
+};
+
+use super::{
+    FindTradeMatchesError, FindTradeMatchesRequest, ProposeTradeMatchError,
+    ProposeTradeMatchRequest, SetTradeOptInError, SetTradeOptInRequest,
+};
+
+/// Service for matching spares and wants between users (Port).
+///
+/// A user's wants are the unsatisfied items of their wishlist. Their spares are the
+/// `Active` instances they own and hold beyond the first of each variant, except
+/// for variants they still want themselves. Only users who opted in are matched.
+pub trait TradeMatchService: Send + Sync + 'static {
+    /// Take part in trade matching, or stop taking part.
+    fn set_trade_opt_in(
+        &self,
+        req: SetTradeOptInRequest,
+    ) -> impl Future<Output = Result<Wishlist, SetTradeOptInError>> + Send;
+
+    /// Find the cycles of users, starting with the given one, in which everyone
+    /// receives at least one wanted variant.
+    ///
+    /// Matches are ranked by how many wants they satisfy, shorter cycles first on ties.
+    fn find_trade_matches(
+        &self,
+        req: FindTradeMatchesRequest,
+    ) -> impl Future<Output = Result<Vec<TradeMatch>, FindTradeMatchesError>> + Send;
+
+    /// Create a transaction for every leg of a match.
+    ///
+    /// The proposer's own leg is pending, locking their spares. The other legs are
+    /// proposed to their senders, whose spares are only locked once they accept.
+    /// The match is checked against the current spares and wants first. Either all
+    /// transactions are created, or none.
+    fn propose_trade_match(
+        &self,
+        req: ProposeTradeMatchRequest,
+    ) -> impl Future<Output = Result<Vec<UserTransaction>, ProposeTradeMatchError>> + Send;
+}
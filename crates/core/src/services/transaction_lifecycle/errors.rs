@@ -9,6 +9,22 @@ pub enum CreateTransactionError {
     ItemNotOwned,
     #[error("One or more items are not in Active status")]
     ItemNotActive,
+    #[error("One or more items are not held by sender")]
+    ItemNotHeld,
+    #[error(transparent)]
+    Repository(#[from] RepositoryError),
+}
+
+#[derive(Debug, Error)]
+pub enum AcceptTransactionError {
+    #[error("Transaction not found")]
+    NotFound,
+    #[error("Transaction is not awaiting acceptance")]
+    NotProposed,
+    #[error("Permission denied")]
+    PermissionDenied,
+    #[error(transparent)]
+    Items(#[from] CreateTransactionError),
     #[error(transparent)]
     Repository(#[from] RepositoryError),
 }
@@ -17,6 +33,8 @@ pub enum CreateTransactionError {
 pub enum CompleteTransactionError {
     #[error("Transaction not found")]
     NotFound,
+    #[error("Transaction not accepted by the sender yet")]
+    NotAccepted,
     #[error("Transaction already completed")]
     AlreadyCompleted,
     #[error("Transaction cancelled")]
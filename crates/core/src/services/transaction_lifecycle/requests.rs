@@ -1,4 +1,5 @@
 use crate::models::{product::ProductInstanceId, transfer::UserTransactionId, user::UserId};
+use chrono::NaiveDate;
 
 /// Request to create a new transaction.
 pub struct CreateTransactionRequest {
@@ -7,10 +8,44 @@ pub struct CreateTransactionRequest {
     pub items: Vec<ProductInstanceId>,
 }
 
+/// Request to create a delivery transaction.
+///
+/// The sender holds the items and the receiver already owns them,
+/// e.g. a group-buy receiver handing items over to participants.
+pub struct CreateDeliveryTransactionRequest {
+    /// The current holder of the items
+    pub from_user_id: UserId,
+    /// The owner of the items
+    pub to_user_id: UserId,
+    pub items: Vec<ProductInstanceId>,
+}
+
+/// Request to create a lending transaction.
+///
+/// The sender owns and holds the items, and leaves them with the receiver,
+/// e.g. until the next meetup. Ownership does not change.
+pub struct CreateLendingTransactionRequest {
+    /// The owner and current holder of the items
+    pub from_user_id: UserId,
+    /// The new holder of the items
+    pub to_user_id: UserId,
+    pub items: Vec<ProductInstanceId>,
+    /// When the receiver should give the items back
+    pub return_by: Option<NaiveDate>,
+}
+
+/// Request to accept a proposed transaction.
+pub struct AcceptTransactionRequest {
+    pub transaction_id: UserTransactionId,
+    /// The user accepting the transaction (must be the sender)
+    pub user_id: UserId,
+}
+
 /// Request to complete a transaction.
 pub struct CompleteTransactionRequest {
     pub transaction_id: UserTransactionId,
-    /// The user attempting to complete the transaction (must be the receiver)
+    /// The user attempting to complete the transaction
+    /// (must be the receiver, or the manager of a placeholder receiver)
     pub user_id: UserId,
 }
 
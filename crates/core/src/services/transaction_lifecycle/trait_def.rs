@@ -5,13 +5,16 @@ use crate::models::transfer::UserTransaction;
 ///
 /// This service handles state transitions of transactions:
 /// - Creating transactions (locking items)
+/// - Creating delivery transactions (handing items over to their owners)
+/// - Creating lending transactions (leaving items with another user)
+/// - Accepting proposed transactions (locking items)
 /// - Completing transactions (transferring ownership)
 /// - Cancelling transactions
 pub trait TransactionLifecycleService: Send + Sync + 'static {
     /// Create a new transaction between users.
     ///
     /// This operation:
-    /// 1. Verifies all items belong to sender and are Active
+    /// 1. Verifies all items are owned and held by sender and Active
     /// 2. Locks all items (sets status to Locked)
     /// 3. Creates the transaction record in Pending status
     fn create_transaction(
@@ -19,12 +22,53 @@ pub trait TransactionLifecycleService: Send + Sync + 'static {
         req: CreateTransactionRequest,
     ) -> impl Future<Output = Result<UserTransaction, CreateTransactionError>> + Send;
 
+    /// Create a delivery transaction, handing items over to their owner.
+    ///
+    /// This operation:
+    /// 1. Verifies all items are held by sender, owned by receiver and Active
+    /// 2. Locks all items (sets status to Locked)
+    /// 3. Creates the transaction record in Pending status
+    ///
+    /// The receiver confirms receipt by completing the transaction.
+    fn create_delivery_transaction(
+        &self,
+        req: CreateDeliveryTransactionRequest,
+    ) -> impl Future<Output = Result<UserTransaction, CreateTransactionError>> + Send;
+
+    /// Create a lending transaction, leaving items with another user.
+    ///
+    /// This operation:
+    /// 1. Verifies all items are owned and held by sender and Active
+    /// 2. Locks all items (sets status to Locked)
+    /// 3. Creates the transaction record in Pending status
+    ///
+    /// The receiver confirms receipt by completing the transaction, and later gives the items
+    /// back with a delivery transaction.
+    fn create_lending_transaction(
+        &self,
+        req: CreateLendingTransactionRequest,
+    ) -> impl Future<Output = Result<UserTransaction, CreateTransactionError>> + Send;
+
+    /// Accept a transaction proposed to the sender.
+    ///
+    /// This operation:
+    /// 1. Verifies the transaction is proposed
+    /// 2. Verifies the user has permission (must be sender)
+    /// 3. Verifies and locks all items, as when creating the transaction
+    /// 4. Updates transaction status to Pending
+    fn accept_transaction(
+        &self,
+        req: AcceptTransactionRequest,
+    ) -> impl Future<Output = Result<UserTransaction, AcceptTransactionError>> + Send;
+
     /// Complete a transaction.
     ///
     /// This operation:
     /// 1. Verifies the transaction is pending
-    /// 2. Verifies the user has permission (must be receiver)
-    /// 3. Transfers ownership of all items to the receiver
+    /// 2. Verifies the user has permission (must be receiver, or the manager of a placeholder
+    ///    receiver)
+    /// 3. Transfers custody of all items to the receiver, and ownership unless delivering or
+    ///    lending, recording the transfer in each item's history
     /// 4. Updates transaction status to Completed
     fn complete_transaction(
         &self,
@@ -34,9 +78,9 @@ pub trait TransactionLifecycleService: Send + Sync + 'static {
     /// Cancel a transaction.
     ///
     /// This operation:
-    /// 1. Verifies the transaction is pending
+    /// 1. Verifies the transaction is proposed or pending
     /// 2. Verifies the user has permission (sender or receiver)
-    /// 3. Unlocks all items (sets status back to Active for sender)
+    /// 3. Unlocks all items of a pending transaction (sets status back to Active for sender)
     /// 4. Updates transaction status to Cancelled
     fn cancel_transaction(
         &self,
@@ -18,6 +18,8 @@ pub enum CreateUserError {
     Repository(#[from] RepositoryError),
     #[error("User already exists")]
     AlreadyExists,
+    #[error("Invalid invite code")]
+    InvalidInviteCode,
 }
 
 #[derive(Debug, Error)]
@@ -31,3 +33,47 @@ pub enum LoginError {
     #[error(transparent)]
     Repository(#[from] RepositoryError),
 }
+
+#[derive(Debug, Error)]
+pub enum CreatePlaceholderError {
+    #[error("User not found")]
+    UserNotFound,
+    #[error("Placeholders cannot manage other placeholders")]
+    PermissionDenied,
+    #[error(transparent)]
+    Repository(#[from] RepositoryError),
+}
+
+#[derive(Debug, Error)]
+pub enum ListPlaceholdersError {
+    #[error(transparent)]
+    Repository(#[from] RepositoryError),
+}
+
+#[derive(Debug, Error)]
+pub enum ClaimPlaceholderError {
+    #[error("User not found")]
+    UserNotFound,
+    #[error("Invalid invite code")]
+    InvalidInviteCode,
+    #[error("Placeholders cannot claim other placeholders")]
+    PermissionDenied,
+    #[error(transparent)]
+    Repository(#[from] RepositoryError),
+}
+
+#[derive(Debug, Error)]
+pub enum ResetCalendarTokenError {
+    #[error("User not found")]
+    UserNotFound,
+    #[error(transparent)]
+    Repository(#[from] RepositoryError),
+}
+
+#[derive(Debug, Error)]
+pub enum RevokeCalendarTokenError {
+    #[error("User not found")]
+    UserNotFound,
+    #[error(transparent)]
+    Repository(#[from] RepositoryError),
+}
@@ -6,6 +6,7 @@ pub enum GetUserRequest {
     ById(UserId),
     ByUsername(Username),
     ByEmail(Email),
+    ByCalendarToken(String),
 }
 
 /// Request to create a new user.
@@ -14,6 +15,8 @@ pub struct CreateUserRequest {
     pub username: Username,
     pub password: NonEmptyString,
     pub avatar: Option<MediaId>,
+    /// Invite code of a placeholder to claim with the new account
+    pub invite_code: Option<String>,
 }
 
 /// Request to login a user.
@@ -21,3 +24,36 @@ pub struct LoginRequest {
     pub username: Username,
     pub password: NonEmptyString,
 }
+
+/// Request to create a placeholder for a participant without an account.
+pub struct CreatePlaceholderRequest {
+    /// The user creating the placeholder, who will manage it
+    pub user_id: UserId,
+
+    /// The name shown for the placeholder
+    pub display_name: NonEmptyString,
+}
+
+/// Request to list the placeholders managed by a user.
+pub struct ListPlaceholdersRequest {
+    pub user_id: UserId,
+}
+
+/// Request to claim a placeholder, merging it into the user's account.
+pub struct ClaimPlaceholderRequest {
+    /// The user claiming the placeholder
+    pub user_id: UserId,
+
+    /// The invite code of the placeholder
+    pub invite_code: String,
+}
+
+/// Request to issue a new calendar feed token, revoking the previous one.
+pub struct ResetCalendarTokenRequest {
+    pub user_id: UserId,
+}
+
+/// Request to revoke the calendar feed token.
+pub struct RevokeCalendarTokenRequest {
+    pub user_id: UserId,
+}
@@ -6,6 +6,8 @@ use crate::models::user::User;
 /// This service handles user account operations:
 /// - Creating new users
 /// - Retrieving user profiles
+/// - Managing and claiming placeholders for participants without an account
+/// - Managing the token authenticating calendar feeds
 pub trait UserService: Send + Sync + 'static {
     /// Get a user by their ID.
     fn get_user(
@@ -24,4 +26,44 @@ pub trait UserService: Send + Sync + 'static {
         &self,
         req: CreateUserRequest,
     ) -> impl Future<Output = Result<User, CreateUserError>> + Send;
+
+    /// Create a placeholder user managed by the requesting user.
+    ///
+    /// The placeholder can own line items and instances like any other user, and gets an
+    /// invite code its participant can use to claim it once they have an account.
+    fn create_placeholder(
+        &self,
+        req: CreatePlaceholderRequest,
+    ) -> impl Future<Output = Result<User, CreatePlaceholderError>> + Send;
+
+    /// List the placeholders managed by a user.
+    fn list_placeholders(
+        &self,
+        req: ListPlaceholdersRequest,
+    ) -> impl Future<Output = Result<Vec<User>, ListPlaceholdersError>> + Send;
+
+    /// Claim a placeholder by its invite code.
+    ///
+    /// Every instance, order participation and transaction of the placeholder is transferred
+    /// to the claiming user, and the placeholder is deleted.
+    fn claim_placeholder(
+        &self,
+        req: ClaimPlaceholderRequest,
+    ) -> impl Future<Output = Result<User, ClaimPlaceholderError>> + Send;
+
+    /// Issue a new calendar feed token for a user, revoking the previous one.
+    ///
+    /// The token is a secret that authenticates the user's calendar feeds, which calendar
+    /// apps subscribe to without a session. Look the user up by it with
+    /// `GetUserRequest::ByCalendarToken`.
+    fn reset_calendar_token(
+        &self,
+        req: ResetCalendarTokenRequest,
+    ) -> impl Future<Output = Result<String, ResetCalendarTokenError>> + Send;
+
+    /// Revoke the calendar feed token of a user.
+    fn revoke_calendar_token(
+        &self,
+        req: RevokeCalendarTokenRequest,
+    ) -> impl Future<Output = Result<(), RevokeCalendarTokenError>> + Send;
 }
@@ -0,0 +1,47 @@
+use crate::models::{product::ProductVariantId, wishlist::WishlistItemId};
+
+#[derive(Debug, thiserror::Error)]
+pub enum GetWishlistError {
+    /// The wishlist of another user is not shared.
+    #[error("Wishlist not found")]
+    NotFound,
+
+    #[error("Repository error: {0}")]
+    Repository(#[from] crate::errors::RepositoryError),
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum AddWishlistItemError {
+    #[error("Variant not found: {variant_id:?}")]
+    VariantNotFound { variant_id: ProductVariantId },
+
+    #[error("Variant already in the wishlist: {variant_id:?}")]
+    AlreadyWishlisted { variant_id: ProductVariantId },
+
+    #[error("Repository error: {0}")]
+    Repository(#[from] crate::errors::RepositoryError),
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum UpdateWishlistItemError {
+    #[error("Wishlist item not found: {item_id:?}")]
+    ItemNotFound { item_id: WishlistItemId },
+
+    #[error("Repository error: {0}")]
+    Repository(#[from] crate::errors::RepositoryError),
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum RemoveWishlistItemError {
+    #[error("Wishlist item not found: {item_id:?}")]
+    ItemNotFound { item_id: WishlistItemId },
+
+    #[error("Repository error: {0}")]
+    Repository(#[from] crate::errors::RepositoryError),
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum SetWishlistVisibilityError {
+    #[error("Repository error: {0}")]
+    Repository(#[from] crate::errors::RepositoryError),
+}
@@ -0,0 +1,77 @@
+use std::num::NonZeroU32;
+
+use crate::models::{
+    misc::Price,
+    product::ProductVariantId,
+    user::UserId,
+    wishlist::{WishlistItemId, WishlistPriority},
+};
+
+/// Request to get the wishlist of a user.
+pub struct GetWishlistRequest {
+    /// The user whose wishlist to get.
+    pub user_id: UserId,
+
+    /// The user asking, if logged in. Only the owner can see a private wishlist.
+    pub viewer_id: Option<UserId>,
+}
+
+/// Request to add a variant to the wishlist.
+pub struct AddWishlistItemRequest {
+    /// The user whose wishlist to change.
+    pub user_id: UserId,
+
+    /// The wanted variant.
+    pub variant_id: ProductVariantId,
+
+    /// How much the user wants the variant.
+    pub priority: WishlistPriority,
+
+    /// How many instances the user wants.
+    pub desired_quantity: NonZeroU32,
+
+    /// The most the user is willing to pay per instance.
+    pub max_price: Option<Price>,
+
+    /// Free-form notes.
+    pub note: String,
+}
+
+/// Request to update an item of the wishlist.
+pub struct UpdateWishlistItemRequest {
+    /// The user whose wishlist to change.
+    pub user_id: UserId,
+
+    /// The item to update.
+    pub item_id: WishlistItemId,
+
+    /// New priority.
+    pub priority: Option<WishlistPriority>,
+
+    /// New desired quantity. The item is satisfied again once enough instances were obtained.
+    pub desired_quantity: Option<NonZeroU32>,
+
+    /// New maximum price per instance.
+    pub max_price: Option<Price>,
+
+    /// New notes.
+    pub note: Option<String>,
+}
+
+/// Request to remove an item from the wishlist.
+pub struct RemoveWishlistItemRequest {
+    /// The user whose wishlist to change.
+    pub user_id: UserId,
+
+    /// The item to remove.
+    pub item_id: WishlistItemId,
+}
+
+/// Request to share the wishlist publicly, or make it private again.
+pub struct SetWishlistVisibilityRequest {
+    /// The user whose wishlist to change.
+    pub user_id: UserId,
+
+    /// Whether other users can view the wishlist.
+    pub is_public: bool,
+}
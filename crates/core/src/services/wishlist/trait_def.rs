@@ -0,0 +1,48 @@
+use crate::models::wishlist::Wishlist;
+
+use super::{
+    AddWishlistItemError, AddWishlistItemRequest, GetWishlistError, GetWishlistRequest,
+    RemoveWishlistItemError, RemoveWishlistItemRequest, SetWishlistVisibilityError,
+    SetWishlistVisibilityRequest, UpdateWishlistItemError, UpdateWishlistItemRequest,
+};
+
+/// Service for managing wishlists (Port).
+///
+/// This service handles the per-user wishlist:
+/// - Adding, updating and removing wanted variants
+/// - Sharing the wishlist with other users
+///
+/// Items are satisfied automatically when orders are fulfilled or transactions completed.
+pub trait WishlistService: Send + Sync + 'static {
+    /// Get the wishlist of a user. Users who never added anything get an empty wishlist.
+    ///
+    /// Other users can only see the wishlist if it is public.
+    fn get_wishlist(
+        &self,
+        req: GetWishlistRequest,
+    ) -> impl Future<Output = Result<Wishlist, GetWishlistError>> + Send;
+
+    /// Add a variant to the wishlist.
+    fn add_wishlist_item(
+        &self,
+        req: AddWishlistItemRequest,
+    ) -> impl Future<Output = Result<Wishlist, AddWishlistItemError>> + Send;
+
+    /// Update the priority, quantity, price or notes of a wishlist item.
+    fn update_wishlist_item(
+        &self,
+        req: UpdateWishlistItemRequest,
+    ) -> impl Future<Output = Result<Wishlist, UpdateWishlistItemError>> + Send;
+
+    /// Remove an item from the wishlist.
+    fn remove_wishlist_item(
+        &self,
+        req: RemoveWishlistItemRequest,
+    ) -> impl Future<Output = Result<Wishlist, RemoveWishlistItemError>> + Send;
+
+    /// Share the wishlist publicly, or make it private again.
+    fn set_wishlist_visibility(
+        &self,
+        req: SetWishlistVisibilityRequest,
+    ) -> impl Future<Output = Result<Wishlist, SetWishlistVisibilityError>> + Send;
+}
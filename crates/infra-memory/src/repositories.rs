@@ -25,3 +25,15 @@ pub use media::*;
 
 mod tag;
 pub use tag::*;
+
+mod cart;
+pub use cart::*;
+
+mod storage_location;
+pub use storage_location::*;
+
+mod wishlist;
+pub use wishlist::*;
+
+mod statistics;
+pub use statistics::*;
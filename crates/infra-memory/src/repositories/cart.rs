@@ -0,0 +1,49 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, RwLock},
+};
+
+use sawa_core::{
+    errors::RepositoryError,
+    models::{cart::Cart, user::UserId},
+    repositories::CartRepository,
+};
+
+/// In-memory implementation of CartRepository.
+#[derive(Clone)]
+pub struct InMemoryCartRepository {
+    carts: Arc<RwLock<HashMap<UserId, Cart>>>,
+}
+
+impl InMemoryCartRepository {
+    pub fn new() -> Self {
+        Self {
+            carts: Arc::new(RwLock::new(HashMap::new())),
+        }
+    }
+}
+
+impl Default for InMemoryCartRepository {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CartRepository for InMemoryCartRepository {
+    async fn find_by_user(&self, user_id: &UserId) -> Result<Option<Cart>, RepositoryError> {
+        let carts = self.carts.read().unwrap();
+        Ok(carts.get(user_id).cloned())
+    }
+
+    async fn save(&self, cart: &Cart) -> Result<(), RepositoryError> {
+        let mut carts = self.carts.write().unwrap();
+        carts.insert(cart.user_id, cart.clone());
+        Ok(())
+    }
+
+    async fn delete(&self, user_id: &UserId) -> Result<(), RepositoryError> {
+        let mut carts = self.carts.write().unwrap();
+        carts.remove(user_id);
+        Ok(())
+    }
+}
@@ -0,0 +1,384 @@
+use std::{
+    cmp::Reverse,
+    collections::{BTreeMap, HashMap, HashSet},
+};
+
+use chrono::{DateTime, Datelike, Utc};
+use sawa_core::{
+    errors::RepositoryError,
+    models::{
+        misc::{Price, TagId},
+        product::{
+            ProductId, ProductInstance, ProductInstanceStatus, ProductVariant, ProductVariantId,
+        },
+        purchase::OrderRoleFilter,
+        statistics::{
+            CurrencyAmount, DateRange, MonthlySpending, MysteryBoxStatistics, ProductStatistics,
+            SeriesPurchaseCount, StatusCount, TagStatistics,
+        },
+        user::UserId,
+    },
+    repositories::{
+        ProductInstanceRepository, ProductRepository, ProductVariantRepository,
+        PurchaseOrderRepository, StatisticsRepository, TagRepository,
+    },
+};
+
+use super::{
+    InMemoryProductInstanceRepository, InMemoryProductRepository, InMemoryProductVariantRepository,
+    InMemoryPurchaseOrderRepository, InMemoryTagRepository,
+};
+
+/// A line item a user received from a fulfilled order.
+struct Purchase {
+    purchased_variant_id: ProductVariantId,
+    variant_id: ProductVariantId,
+    fulfilled_at: DateTime<Utc>,
+    cost: Option<Price>,
+}
+
+/// In-memory implementation of StatisticsRepository.
+///
+/// Computed on every call from the other in-memory repositories, so it must be
+/// given clones of the ones the service writes to.
+#[derive(Clone)]
+pub struct InMemoryStatisticsRepository {
+    orders: InMemoryPurchaseOrderRepository,
+    instances: InMemoryProductInstanceRepository,
+    variants: InMemoryProductVariantRepository,
+    products: InMemoryProductRepository,
+    tags: InMemoryTagRepository,
+}
+
+impl InMemoryStatisticsRepository {
+    pub fn new(
+        orders: InMemoryPurchaseOrderRepository,
+        instances: InMemoryProductInstanceRepository,
+        variants: InMemoryProductVariantRepository,
+        products: InMemoryProductRepository,
+        tags: InMemoryTagRepository,
+    ) -> Self {
+        Self {
+            orders,
+            instances,
+            variants,
+            products,
+            tags,
+        }
+    }
+
+    async fn purchases(
+        &self,
+        user_id: &UserId,
+        range: &DateRange,
+    ) -> Result<Vec<Purchase>, RepositoryError> {
+        let orders = self
+            .orders
+            .find_by_user(user_id, OrderRoleFilter::Participant, None)
+            .await?;
+
+        let mut purchases = Vec::new();
+        for order in orders {
+            for item in order.items {
+                for line_item in item.line_items {
+                    let Some(fulfilled_at) = line_item.fulfilled_at else {
+                        continue;
+                    };
+                    if line_item.owner_id != *user_id
+                        || line_item.returned_at.is_some()
+                        || !range.contains(fulfilled_at)
+                    {
+                        continue;
+                    }
+
+                    let cost = match line_item.instance_id {
+                        Some(instance_id) => self
+                            .instances
+                            .find_by_id(&instance_id)
+                            .await?
+                            .and_then(|instance| instance.acquisition_cost),
+                        None => None,
+                    };
+                    purchases.push(Purchase {
+                        purchased_variant_id: item.purchased_variant_id,
+                        variant_id: line_item.variant_id,
+                        fulfilled_at,
+                        cost,
+                    });
+                }
+            }
+        }
+        Ok(purchases)
+    }
+
+    /// The instances a user owns that were created within the range.
+    async fn owned_instances(
+        &self,
+        user_id: &UserId,
+        range: &DateRange,
+    ) -> Result<Vec<ProductInstance>, RepositoryError> {
+        let mut instances = self.instances.find_by_owner(user_id).await?;
+        instances.retain(|instance| range.contains(instance.created_at));
+        Ok(instances)
+    }
+
+    async fn load_variants(
+        &self,
+        ids: impl IntoIterator<Item = ProductVariantId>,
+    ) -> Result<HashMap<ProductVariantId, ProductVariant>, RepositoryError> {
+        let ids: Vec<_> = ids
+            .into_iter()
+            .collect::<HashSet<_>>()
+            .into_iter()
+            .collect();
+        Ok(self
+            .variants
+            .load_by_ids(&ids)
+            .await?
+            .into_iter()
+            .flatten()
+            .map(|variant| (variant.id, variant))
+            .collect())
+    }
+
+    async fn product_name(&self, product_id: &ProductId) -> Result<String, RepositoryError> {
+        let product = self
+            .products
+            .find_by_id(product_id)
+            .await?
+            .ok_or(RepositoryError::NotFound)?;
+        Ok(product.name.into())
+    }
+}
+
+impl StatisticsRepository for InMemoryStatisticsRepository {
+    async fn monthly_spending(
+        &self,
+        user_id: &UserId,
+        range: &DateRange,
+    ) -> Result<Vec<MonthlySpending>, RepositoryError> {
+        let mut totals: BTreeMap<_, CurrencyAmount> = BTreeMap::new();
+        for purchase in self.purchases(user_id, range).await? {
+            let Some(cost) = purchase.cost else {
+                continue;
+            };
+            let key = (
+                purchase.fulfilled_at.year(),
+                purchase.fulfilled_at.month(),
+                cost.currency.code(),
+            );
+            totals
+                .entry(key)
+                .or_insert(CurrencyAmount {
+                    currency: cost.currency,
+                    amount: 0,
+                })
+                .amount += u64::from(cost.amount);
+        }
+
+        Ok(totals
+            .into_iter()
+            .map(|((year, month, _), spent)| MonthlySpending { year, month, spent })
+            .collect())
+    }
+
+    async fn status_counts(
+        &self,
+        user_id: &UserId,
+        range: &DateRange,
+    ) -> Result<Vec<StatusCount>, RepositoryError> {
+        let mut counts: Vec<StatusCount> = Vec::new();
+        for instance in self.owned_instances(user_id, range).await? {
+            match counts
+                .iter_mut()
+                .find(|entry| entry.status == instance.status)
+            {
+                Some(entry) => entry.count += 1,
+                None => counts.push(StatusCount {
+                    status: instance.status,
+                    count: 1,
+                }),
+            }
+        }
+        Ok(counts)
+    }
+
+    async fn tag_statistics(
+        &self,
+        user_id: &UserId,
+        range: &DateRange,
+    ) -> Result<Vec<TagStatistics>, RepositoryError> {
+        let mut instances = self.owned_instances(user_id, range).await?;
+        instances.retain(|instance| instance.status != ProductInstanceStatus::Returned);
+        let variants = self
+            .load_variants(instances.iter().map(|instance| instance.variant_id))
+            .await?;
+
+        let mut by_tag: HashMap<TagId, (u64, Vec<CurrencyAmount>)> = HashMap::new();
+        for instance in &instances {
+            let Some(variant) = variants.get(&instance.variant_id) else {
+                continue;
+            };
+            for tag_id in &variant.tags {
+                let (count, spent) = by_tag.entry(*tag_id).or_default();
+                *count += 1;
+                if let Some(cost) = instance.acquisition_cost {
+                    CurrencyAmount::add_to(spent, cost.currency, cost.amount.into());
+                }
+            }
+        }
+
+        let mut statistics = Vec::with_capacity(by_tag.len());
+        for (tag_id, (instance_count, spent)) in by_tag {
+            let Some(tag) = self.tags.find_by_id(&tag_id).await? else {
+                continue;
+            };
+            statistics.push(TagStatistics {
+                tag_id,
+                name: tag.name.into(),
+                instance_count,
+                spent,
+            });
+        }
+        statistics.sort_by(|a, b| {
+            Reverse(a.instance_count)
+                .cmp(&Reverse(b.instance_count))
+                .then_with(|| a.name.cmp(&b.name))
+        });
+        Ok(statistics)
+    }
+
+    async fn product_statistics(
+        &self,
+        user_id: &UserId,
+        range: &DateRange,
+    ) -> Result<Vec<ProductStatistics>, RepositoryError> {
+        let mut instances = self.owned_instances(user_id, range).await?;
+        instances.retain(|instance| instance.status != ProductInstanceStatus::Returned);
+        let variants = self
+            .load_variants(instances.iter().map(|instance| instance.variant_id))
+            .await?;
+
+        let mut by_product: HashMap<ProductId, (u64, Vec<CurrencyAmount>)> = HashMap::new();
+        for instance in &instances {
+            let Some(variant) = variants.get(&instance.variant_id) else {
+                continue;
+            };
+            let (count, spent) = by_product.entry(variant.product_id).or_default();
+            *count += 1;
+            if let Some(cost) = instance.acquisition_cost {
+                CurrencyAmount::add_to(spent, cost.currency, cost.amount.into());
+            }
+        }
+
+        let mut statistics = Vec::with_capacity(by_product.len());
+        for (product_id, (instance_count, spent)) in by_product {
+            statistics.push(ProductStatistics {
+                product_id,
+                name: self.product_name(&product_id).await?,
+                instance_count,
+                spent,
+            });
+        }
+        statistics.sort_by(|a, b| {
+            Reverse(a.instance_count)
+                .cmp(&Reverse(b.instance_count))
+                .then_with(|| a.name.cmp(&b.name))
+        });
+        Ok(statistics)
+    }
+
+    async fn most_bought_series(
+        &self,
+        user_id: &UserId,
+        range: &DateRange,
+        limit: usize,
+    ) -> Result<Vec<SeriesPurchaseCount>, RepositoryError> {
+        let purchases = self.purchases(user_id, range).await?;
+        let variants = self
+            .load_variants(purchases.iter().map(|purchase| purchase.variant_id))
+            .await?;
+
+        let mut by_product: HashMap<ProductId, u64> = HashMap::new();
+        for purchase in &purchases {
+            if let Some(variant) = variants.get(&purchase.variant_id) {
+                *by_product.entry(variant.product_id).or_default() += 1;
+            }
+        }
+
+        let mut series = Vec::with_capacity(by_product.len());
+        for (product_id, purchased_count) in by_product {
+            series.push(SeriesPurchaseCount {
+                product_id,
+                name: self.product_name(&product_id).await?,
+                purchased_count,
+            });
+        }
+        series.sort_by(|a, b| {
+            Reverse(a.purchased_count)
+                .cmp(&Reverse(b.purchased_count))
+                .then_with(|| a.name.cmp(&b.name))
+        });
+        series.truncate(limit);
+        Ok(series)
+    }
+
+    async fn mystery_box_statistics(
+        &self,
+        user_id: &UserId,
+        range: &DateRange,
+    ) -> Result<Vec<MysteryBoxStatistics>, RepositoryError> {
+        let purchases = self.purchases(user_id, range).await?;
+        let boxes = self
+            .load_variants(
+                purchases
+                    .iter()
+                    .map(|purchase| purchase.purchased_variant_id),
+            )
+            .await?;
+
+        let mut by_box: HashMap<ProductVariantId, MysteryBoxStatistics> = HashMap::new();
+        let mut obtained: HashMap<ProductVariantId, HashSet<ProductVariantId>> = HashMap::new();
+        for purchase in &purchases {
+            let Some(mystery_box) = boxes
+                .get(&purchase.purchased_variant_id)
+                .filter(|variant| variant.mystery_box.is_some())
+            else {
+                continue;
+            };
+
+            let entry = by_box
+                .entry(mystery_box.id)
+                .or_insert_with(|| MysteryBoxStatistics {
+                    variant_id: mystery_box.id,
+                    name: mystery_box.name.to_string(),
+                    items_obtained: 0,
+                    distinct_variants_obtained: 0,
+                    spent: vec![],
+                    average_cost_per_variant: vec![],
+                });
+            entry.items_obtained += 1;
+            if let Some(cost) = purchase.cost {
+                CurrencyAmount::add_to(&mut entry.spent, cost.currency, cost.amount.into());
+            }
+            obtained
+                .entry(mystery_box.id)
+                .or_default()
+                .insert(purchase.variant_id);
+        }
+
+        let mut statistics: Vec<_> = by_box
+            .into_values()
+            .map(|mut entry| {
+                entry.distinct_variants_obtained = obtained[&entry.variant_id].len() as u64;
+                entry.with_averages()
+            })
+            .collect();
+        statistics.sort_by(|a, b| {
+            Reverse(a.items_obtained)
+                .cmp(&Reverse(b.items_obtained))
+                .then_with(|| a.name.cmp(&b.name))
+        });
+        Ok(statistics)
+    }
+}
@@ -0,0 +1,67 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, RwLock},
+};
+
+use sawa_core::{
+    errors::RepositoryError,
+    models::{
+        storage::{StorageLocation, StorageLocationId},
+        user::UserId,
+    },
+    repositories::StorageLocationRepository,
+};
+
+/// In-memory implementation of StorageLocationRepository.
+#[derive(Clone)]
+pub struct InMemoryStorageLocationRepository {
+    locations: Arc<RwLock<HashMap<StorageLocationId, StorageLocation>>>,
+}
+
+impl InMemoryStorageLocationRepository {
+    pub fn new() -> Self {
+        Self {
+            locations: Arc::new(RwLock::new(HashMap::new())),
+        }
+    }
+}
+
+impl Default for InMemoryStorageLocationRepository {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl StorageLocationRepository for InMemoryStorageLocationRepository {
+    async fn find_by_id(
+        &self,
+        id: &StorageLocationId,
+    ) -> Result<Option<StorageLocation>, RepositoryError> {
+        let locations = self.locations.read().unwrap();
+        Ok(locations.get(id).cloned())
+    }
+
+    async fn find_by_user(
+        &self,
+        user_id: &UserId,
+    ) -> Result<Vec<StorageLocation>, RepositoryError> {
+        let locations = self.locations.read().unwrap();
+        Ok(locations
+            .values()
+            .filter(|location| location.user_id == *user_id)
+            .cloned()
+            .collect())
+    }
+
+    async fn save(&self, location: &StorageLocation) -> Result<(), RepositoryError> {
+        let mut locations = self.locations.write().unwrap();
+        locations.insert(location.id, location.clone());
+        Ok(())
+    }
+
+    async fn delete(&self, id: &StorageLocationId) -> Result<(), RepositoryError> {
+        let mut locations = self.locations.write().unwrap();
+        locations.remove(id);
+        Ok(())
+    }
+}
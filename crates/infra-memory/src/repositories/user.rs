@@ -45,6 +45,48 @@ impl UserRepository for InMemoryUserRepository {
         Ok(users.values().find(|u| u.username.0 == username.0).cloned())
     }
 
+    async fn find_by_invite_code(
+        &self,
+        invite_code: &str,
+    ) -> Result<Option<User>, RepositoryError> {
+        let users = self.users.read().unwrap();
+        Ok(users
+            .values()
+            .find(|u| {
+                u.placeholder
+                    .as_ref()
+                    .is_some_and(|p| p.invite_code == invite_code)
+            })
+            .cloned())
+    }
+
+    async fn find_by_calendar_token(
+        &self,
+        calendar_token: &str,
+    ) -> Result<Option<User>, RepositoryError> {
+        let users = self.users.read().unwrap();
+        Ok(users
+            .values()
+            .find(|u| u.calendar_token.as_deref() == Some(calendar_token))
+            .cloned())
+    }
+
+    async fn find_placeholders_by_manager(
+        &self,
+        manager_id: &UserId,
+    ) -> Result<Vec<User>, RepositoryError> {
+        let users = self.users.read().unwrap();
+        Ok(users
+            .values()
+            .filter(|u| {
+                u.placeholder
+                    .as_ref()
+                    .is_some_and(|p| p.manager_id == *manager_id)
+            })
+            .cloned()
+            .collect())
+    }
+
     async fn create(&self, user: User) -> Result<User, RepositoryError> {
         let mut users = self.users.write().unwrap();
         if users.contains_key(&user.id) {
@@ -78,6 +120,8 @@ impl UserRepository for InMemoryUserRepository {
             email: user.email.unwrap_or(existing_user.email),
             password_hash: user.password_hash.unwrap_or(existing_user.password_hash),
             avatar: user.avatar,
+            placeholder: existing_user.placeholder,
+            calendar_token: user.calendar_token.unwrap_or(existing_user.calendar_token),
             created_at: existing_user.created_at,
         };
         users.insert(user.id, updated_user.clone());
@@ -0,0 +1,58 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, RwLock},
+};
+
+use sawa_core::{
+    errors::RepositoryError,
+    models::{user::UserId, wishlist::Wishlist},
+    repositories::WishlistRepository,
+};
+
+/// In-memory implementation of WishlistRepository.
+#[derive(Clone)]
+pub struct InMemoryWishlistRepository {
+    wishlists: Arc<RwLock<HashMap<UserId, Wishlist>>>,
+}
+
+impl InMemoryWishlistRepository {
+    pub fn new() -> Self {
+        Self {
+            wishlists: Arc::new(RwLock::new(HashMap::new())),
+        }
+    }
+}
+
+impl Default for InMemoryWishlistRepository {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl WishlistRepository for InMemoryWishlistRepository {
+    async fn find_by_user(&self, user_id: &UserId) -> Result<Option<Wishlist>, RepositoryError> {
+        let wishlists = self.wishlists.read().unwrap();
+        Ok(wishlists.get(user_id).cloned())
+    }
+
+    async fn find_open_to_trades(&self) -> Result<Vec<Wishlist>, RepositoryError> {
+        let wishlists = self.wishlists.read().unwrap();
+        Ok(wishlists
+            .values()
+            .filter(|wishlist| wishlist.open_to_trades)
+            .cloned()
+            .collect())
+    }
+
+    async fn save(&self, wishlist: &Wishlist) -> Result<(), RepositoryError> {
+        let mut wishlists = self.wishlists.write().unwrap();
+        wishlists.insert(wishlist.user_id, wishlist.clone());
+        Ok(())
+    }
+
+    async fn delete(&self, user_id: &UserId) -> Result<(), RepositoryError> {
+        let mut wishlists = self.wishlists.write().unwrap();
+        wishlists.remove(user_id);
+        Ok(())
+    }
+}
@@ -12,4 +12,7 @@ test_all_repositories! {
     user_transaction => InMemoryUserTransactionRepository::new(),
     media => InMemoryMediaRepository::new(),
     tag => InMemoryTagRepository::new(),
+    cart => InMemoryCartRepository::new(),
+    storage_location => InMemoryStorageLocationRepository::new(),
+    wishlist => InMemoryWishlistRepository::new(),
 }
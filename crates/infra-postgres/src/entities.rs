@@ -1,3 +1,5 @@
+pub mod cart;
+pub mod cart_line;
 pub mod media;
 pub mod product;
 pub mod product_instance;
@@ -6,14 +8,24 @@ pub mod product_instance_transfer_history;
 pub mod product_variant;
 pub mod product_variant_tag;
 pub mod purchase_order;
+pub mod purchase_order_adjustment;
+pub mod purchase_order_attachment;
+pub mod purchase_order_event;
 pub mod purchase_order_item;
 pub mod purchase_order_line_item;
+pub mod purchase_order_payment;
+pub mod purchase_order_return;
+pub mod storage_location;
 pub mod tag;
 pub mod user;
 pub mod user_transaction;
 pub mod user_transaction_item;
+pub mod wishlist;
+pub mod wishlist_item;
 
 pub mod prelude {
+    pub use super::cart::Entity as Cart;
+    pub use super::cart_line::Entity as CartLine;
     pub use super::media::Entity as Media;
     pub use super::product::Entity as Product;
     pub use super::product_instance::Entity as ProductInstance;
@@ -22,16 +34,26 @@ pub mod prelude {
     pub use super::product_variant::Entity as ProductVariant;
     pub use super::product_variant_tag::Entity as ProductVariantTag;
     pub use super::purchase_order::Entity as PurchaseOrder;
+    pub use super::purchase_order_adjustment::Entity as PurchaseOrderAdjustment;
+    pub use super::purchase_order_attachment::Entity as PurchaseOrderAttachment;
+    pub use super::purchase_order_event::Entity as PurchaseOrderEvent;
     pub use super::purchase_order_item::Entity as PurchaseOrderItem;
     pub use super::purchase_order_line_item::Entity as PurchaseOrderLineItem;
+    pub use super::purchase_order_payment::Entity as PurchaseOrderPayment;
+    pub use super::purchase_order_return::Entity as PurchaseOrderReturn;
+    pub use super::storage_location::Entity as StorageLocation;
     pub use super::tag::Entity as Tag;
     pub use super::user::Entity as User;
     pub use super::user_transaction::Entity as UserTransaction;
     pub use super::user_transaction_item::Entity as UserTransactionItem;
+    pub use super::wishlist::Entity as Wishlist;
+    pub use super::wishlist_item::Entity as WishlistItem;
 }
 
 pub async fn sync_schema(db: &sea_orm::DatabaseConnection) -> Result<(), sea_orm::DbErr> {
     db.get_schema_builder()
+        .register(prelude::Cart)
+        .register(prelude::CartLine)
         .register(prelude::Media)
         .register(prelude::Product)
         .register(prelude::ProductInstance)
@@ -40,12 +62,20 @@ pub async fn sync_schema(db: &sea_orm::DatabaseConnection) -> Result<(), sea_orm
         .register(prelude::ProductVariant)
         .register(prelude::ProductVariantTag)
         .register(prelude::PurchaseOrder)
+        .register(prelude::PurchaseOrderAdjustment)
+        .register(prelude::PurchaseOrderAttachment)
+        .register(prelude::PurchaseOrderEvent)
         .register(prelude::PurchaseOrderItem)
         .register(prelude::PurchaseOrderLineItem)
+        .register(prelude::PurchaseOrderPayment)
+        .register(prelude::PurchaseOrderReturn)
+        .register(prelude::StorageLocation)
         .register(prelude::Tag)
         .register(prelude::User)
         .register(prelude::UserTransaction)
         .register(prelude::UserTransactionItem)
+        .register(prelude::Wishlist)
+        .register(prelude::WishlistItem)
         .sync(db)
         .await
 }
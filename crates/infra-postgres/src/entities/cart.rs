@@ -0,0 +1,52 @@
+use crate::traits::TryIntoDomainModelSimple;
+use sawa_core::{errors::RepositoryError, models::cart::Cart};
+use sea_orm::{ActiveValue::Set, entity::prelude::*};
+
+///
+/// Cart entity
+///
+#[sea_orm::model]
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+#[sea_orm(table_name = "carts")]
+pub struct Model {
+    /// The user the cart belongs to
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub user_id: Uuid,
+    #[sea_orm(belongs_to, from = "user_id", to = "id", skip_fk)]
+    pub user: HasOne<super::user::Entity>,
+
+    /// The variants to order
+    #[sea_orm(has_many, skip_fk)]
+    pub lines: HasMany<super::cart_line::Entity>,
+
+    /// The timestamp when the cart was last changed.
+    pub updated_at: DateTimeUtc,
+}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+impl TryIntoDomainModelSimple<Cart> for ModelEx {
+    fn try_into_domain_model_simple(self) -> Result<Cart, RepositoryError> {
+        let mut lines = self
+            .lines
+            .into_iter()
+            .map(TryIntoDomainModelSimple::try_into_domain_model_simple)
+            .collect::<Result<Vec<_>, _>>()?;
+        lines.sort_by_key(|line| line.added_at);
+
+        Ok(Cart {
+            user_id: self.user_id.try_into()?,
+            lines,
+            updated_at: self.updated_at,
+        })
+    }
+}
+
+impl From<&Cart> for ActiveModel {
+    fn from(cart: &Cart) -> Self {
+        Self {
+            user_id: Set(Uuid::from(cart.user_id.0)),
+            updated_at: Set(cart.updated_at),
+        }
+    }
+}
@@ -0,0 +1,85 @@
+use crate::traits::TryIntoDomainModelSimple;
+use sawa_core::{
+    errors::RepositoryError,
+    models::{
+        cart::CartLine,
+        misc::{Currency, Price},
+    },
+};
+use sea_orm::{ActiveValue::Set, entity::prelude::*};
+use std::str::FromStr;
+
+///
+/// CartLine entity
+///
+#[sea_orm::model]
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+#[sea_orm(table_name = "cart_lines")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: Uuid,
+
+    /// The cart this line belongs to
+    pub cart_user_id: Uuid,
+    #[sea_orm(belongs_to, from = "cart_user_id", to = "user_id", skip_fk)]
+    pub cart: HasOne<super::cart::Entity>,
+
+    /// The variant to purchase
+    pub variant_id: Uuid,
+    #[sea_orm(belongs_to, from = "variant_id", to = "id", skip_fk)]
+    pub variant: HasOne<super::product_variant::Entity>,
+
+    /// Quantity to purchase
+    pub quantity: i64,
+
+    /// The user who will own the purchased instances
+    pub owner_id: Option<Uuid>,
+    #[sea_orm(belongs_to, from = "owner_id", to = "id", skip_fk)]
+    pub owner: HasOne<super::user::Entity>,
+
+    /// Expected price per unit
+    pub unit_price_currency: Option<String>,
+    pub unit_price_amount: Option<u32>,
+
+    /// The timestamp when the line was added.
+    pub added_at: DateTimeUtc,
+}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+impl TryIntoDomainModelSimple<CartLine> for ModelEx {
+    fn try_into_domain_model_simple(self) -> Result<CartLine, RepositoryError> {
+        Ok(CartLine {
+            id: self.id.try_into()?,
+            variant_id: self.variant_id.try_into()?,
+            quantity: (self.quantity as u32).try_into()?,
+            owner_id: self.owner_id.map(TryInto::try_into).transpose()?,
+            unit_price: match (self.unit_price_currency, self.unit_price_amount) {
+                (Some(currency), Some(amount)) => Some(Price {
+                    currency: Currency::from_str(&currency)?,
+                    amount,
+                }),
+                _ => None,
+            },
+            added_at: self.added_at,
+        })
+    }
+}
+
+impl From<(&CartLine, Uuid)> for ActiveModel {
+    fn from((line, cart_user_id): (&CartLine, Uuid)) -> Self {
+        Self {
+            id: Set(Uuid::from(line.id.0)),
+            cart_user_id: Set(cart_user_id),
+            variant_id: Set(Uuid::from(line.variant_id.0)),
+            quantity: Set(line.quantity.get() as i64),
+            owner_id: Set(line.owner_id.map(|id| Uuid::from(id.0))),
+            unit_price_currency: Set(line
+                .unit_price
+                .as_ref()
+                .map(|p| p.currency.code().to_string())),
+            unit_price_amount: Set(line.unit_price.as_ref().map(|p| p.amount)),
+            added_at: Set(line.added_at),
+        }
+    }
+}
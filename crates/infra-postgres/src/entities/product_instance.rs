@@ -1,9 +1,13 @@
 use crate::traits::TryIntoDomainModelSimple;
 use sawa_core::{
     errors::RepositoryResult,
-    models::product::{ProductInstance, ProductInstanceStatus},
+    models::{
+        misc::{Currency, Price},
+        product::{ProductInstance, ProductInstanceCondition, ProductInstanceStatus},
+    },
 };
 use sea_orm::{ActiveValue, entity::prelude::*};
+use std::str::FromStr;
 
 ///
 /// ProductInstance entity
@@ -42,9 +46,23 @@ pub struct Model {
     )]
     pub holder: HasOne<super::user::Entity>,
 
+    /// Where the holder keeps this instance
+    pub location_id: Option<Uuid>,
+    #[sea_orm(belongs_to, from = "location_id", to = "id", skip_fk)]
+    pub location: HasOne<super::storage_location::Entity>,
+
     /// Status of this instance
     pub status: DBProductInstanceStatus,
 
+    /// Physical condition of this copy, if graded
+    pub condition: Option<DBProductInstanceCondition>,
+
+    /// Private note about this copy
+    pub note: String,
+
+    /// Photos of this copy
+    pub medias: Vec<Uuid>,
+
     /// The line item that created this instance
     #[sea_orm(unique)]
     pub source_order_line_item_id: Uuid,
@@ -54,6 +72,10 @@ pub struct Model {
     /// When this instance was created (when order was fulfilled)
     pub created_at: DateTimeUtc,
 
+    /// Landed acquisition cost (snapshot at fulfillment)
+    pub acquisition_cost_currency: Option<String>,
+    pub acquisition_cost_amount: Option<u32>,
+
     /// Transfer history (optional, for auditing)
     #[sea_orm(has_many, skip_fk)]
     pub transfer_history: HasMany<super::product_instance_transfer_history::Entity>,
@@ -75,6 +97,7 @@ pub enum DBProductInstanceStatus {
     Consumed,
     NotFound,
     Destroyed,
+    Returned,
 }
 
 impl From<DBProductInstanceStatus> for ProductInstanceStatus {
@@ -85,6 +108,7 @@ impl From<DBProductInstanceStatus> for ProductInstanceStatus {
             DBProductInstanceStatus::Consumed => ProductInstanceStatus::Consumed,
             DBProductInstanceStatus::NotFound => ProductInstanceStatus::NotFound,
             DBProductInstanceStatus::Destroyed => ProductInstanceStatus::Destroyed,
+            DBProductInstanceStatus::Returned => ProductInstanceStatus::Returned,
         }
     }
 }
@@ -97,6 +121,45 @@ impl From<ProductInstanceStatus> for DBProductInstanceStatus {
             ProductInstanceStatus::Consumed => DBProductInstanceStatus::Consumed,
             ProductInstanceStatus::NotFound => DBProductInstanceStatus::NotFound,
             ProductInstanceStatus::Destroyed => DBProductInstanceStatus::Destroyed,
+            ProductInstanceStatus::Returned => DBProductInstanceStatus::Returned,
+        }
+    }
+}
+
+#[derive(EnumIter, DeriveActiveEnum, Debug, Clone, PartialEq, Eq)]
+#[sea_orm(
+    rs_type = "String",
+    db_type = "String(StringLen::None)",
+    rename_all = "snake_case"
+)]
+pub enum DBProductInstanceCondition {
+    Sealed,
+    Mint,
+    Good,
+    Fair,
+    Poor,
+}
+
+impl From<DBProductInstanceCondition> for ProductInstanceCondition {
+    fn from(db_condition: DBProductInstanceCondition) -> Self {
+        match db_condition {
+            DBProductInstanceCondition::Sealed => ProductInstanceCondition::Sealed,
+            DBProductInstanceCondition::Mint => ProductInstanceCondition::Mint,
+            DBProductInstanceCondition::Good => ProductInstanceCondition::Good,
+            DBProductInstanceCondition::Fair => ProductInstanceCondition::Fair,
+            DBProductInstanceCondition::Poor => ProductInstanceCondition::Poor,
+        }
+    }
+}
+
+impl From<ProductInstanceCondition> for DBProductInstanceCondition {
+    fn from(condition: ProductInstanceCondition) -> Self {
+        match condition {
+            ProductInstanceCondition::Sealed => DBProductInstanceCondition::Sealed,
+            ProductInstanceCondition::Mint => DBProductInstanceCondition::Mint,
+            ProductInstanceCondition::Good => DBProductInstanceCondition::Good,
+            ProductInstanceCondition::Fair => DBProductInstanceCondition::Fair,
+            ProductInstanceCondition::Poor => DBProductInstanceCondition::Poor,
         }
     }
 }
@@ -120,9 +183,24 @@ impl TryIntoDomainModelSimple<ProductInstance> for ModelEx {
             variant_id: self.variant_id.try_into()?,
             owner_id: self.owner_id.try_into()?,
             holder_id: self.holder_id.try_into()?,
+            location_id: self.location_id.map(TryInto::try_into).transpose()?,
             status: self.status.into(),
+            condition: self.condition.map(Into::into),
+            note: self.note,
+            medias: self
+                .medias
+                .into_iter()
+                .map(|id| id.try_into())
+                .collect::<Result<Vec<_>, _>>()?,
             source_order_line_item_id: self.source_order_line_item_id.try_into()?,
             created_at: self.created_at,
+            acquisition_cost: match (self.acquisition_cost_currency, self.acquisition_cost_amount) {
+                (Some(currency), Some(amount)) => Some(Price {
+                    currency: Currency::from_str(&currency)?,
+                    amount,
+                }),
+                _ => None,
+            },
             transfer_history,
             status_history,
         })
@@ -136,11 +214,24 @@ impl From<&ProductInstance> for crate::entities::product_instance::ActiveModel {
             variant_id: ActiveValue::Set(Uuid::from(instance.variant_id.0)),
             owner_id: ActiveValue::Set(Uuid::from(instance.owner_id.0)),
             holder_id: ActiveValue::Set(Uuid::from(instance.holder_id.0)),
+            location_id: ActiveValue::Set(instance.location_id.map(Into::into)),
             status: ActiveValue::Set(instance.status.into()),
+            condition: ActiveValue::Set(instance.condition.map(Into::into)),
+            note: ActiveValue::Set(instance.note.clone()),
+            medias: ActiveValue::Set(instance.medias.iter().map(|id| Uuid::from(id.0)).collect()),
             source_order_line_item_id: ActiveValue::Set(Uuid::from(
                 instance.source_order_line_item_id.0,
             )),
             created_at: ActiveValue::Set(instance.created_at),
+            acquisition_cost_currency: ActiveValue::Set(
+                instance
+                    .acquisition_cost
+                    .as_ref()
+                    .map(|p| p.currency.code().to_string()),
+            ),
+            acquisition_cost_amount: ActiveValue::Set(
+                instance.acquisition_cost.as_ref().map(|p| p.amount),
+            ),
         }
     }
 }
@@ -70,6 +70,9 @@ pub struct Model {
     /// Transfer reason
     pub reason: DBTransferReason,
 
+    /// When the new holder agreed to give the item back, for lending
+    pub return_by: Option<Date>,
+
     /// When the transfer happened
     pub transferred_at: DateTimeUtc,
 }
@@ -85,6 +88,7 @@ impl ActiveModelBehavior for ActiveModel {}
 pub enum DBTransferReason {
     Purchase,
     Delivery,
+    Lending,
     Trade,
     Gift,
     AdminTransfer,
@@ -95,6 +99,7 @@ impl From<TransferReason> for DBTransferReason {
         match reason {
             TransferReason::Purchase => DBTransferReason::Purchase,
             TransferReason::Delivery => DBTransferReason::Delivery,
+            TransferReason::Lending => DBTransferReason::Lending,
             TransferReason::Trade => DBTransferReason::Trade,
             TransferReason::Gift => DBTransferReason::Gift,
             TransferReason::AdminTransfer => DBTransferReason::AdminTransfer,
@@ -107,6 +112,7 @@ impl From<DBTransferReason> for TransferReason {
         match db_reason {
             DBTransferReason::Purchase => TransferReason::Purchase,
             DBTransferReason::Delivery => TransferReason::Delivery,
+            DBTransferReason::Lending => TransferReason::Lending,
             DBTransferReason::Trade => TransferReason::Trade,
             DBTransferReason::Gift => TransferReason::Gift,
             DBTransferReason::AdminTransfer => TransferReason::AdminTransfer,
@@ -123,6 +129,7 @@ impl TryIntoDomainModelSimple<ProductInstanceTransferHistory> for ModelEx {
             to_owner_id: self.to_owner_id.try_into()?,
             to_holder_id: self.to_holder_id.try_into()?,
             reason: self.reason.into(),
+            return_by: self.return_by,
             transferred_at: self.transferred_at,
         })
     }
@@ -140,6 +147,7 @@ impl From<(&ProductInstanceTransferHistory, ProductInstanceId)> for ActiveModel
             to_owner_id: ActiveValue::Set(transfer.to_owner_id.into()),
             to_holder_id: ActiveValue::Set(transfer.to_holder_id.into()),
             reason: ActiveValue::Set(transfer.reason.into()),
+            return_by: ActiveValue::Set(transfer.return_by),
             transferred_at: ActiveValue::Set(transfer.transferred_at),
         }
     }
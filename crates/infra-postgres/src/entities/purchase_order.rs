@@ -3,7 +3,7 @@ use sawa_core::{
     errors::RepositoryError,
     models::{
         misc::{Currency, Price},
-        purchase::{PurchaseOrder, PurchaseOrderStatus},
+        purchase::{PurchaseOrder, PurchaseOrderEvent, PurchaseOrderSchedule, PurchaseOrderStatus},
     },
 };
 use sea_orm::{ActiveValue::Set, entity::prelude::*};
@@ -46,10 +46,34 @@ pub struct Model {
     #[sea_orm(has_many, skip_fk)]
     pub items: HasMany<super::purchase_order_item::Entity>,
 
+    /// Order-level costs not belonging to any item
+    #[sea_orm(has_many, skip_fk)]
+    pub adjustments: HasMany<super::purchase_order_adjustment::Entity>,
+
+    /// Payments from participants to the creator
+    #[sea_orm(has_many, skip_fk)]
+    pub payments: HasMany<super::purchase_order_payment::Entity>,
+
+    /// Line items returned to the seller
+    #[sea_orm(has_many, skip_fk)]
+    pub returns: HasMany<super::purchase_order_return::Entity>,
+
+    /// Receipts, invoices and photos kept with this order
+    #[sea_orm(has_many, skip_fk)]
+    pub attachments: HasMany<super::purchase_order_attachment::Entity>,
+
+    /// Append-only log of changes to this order
+    #[sea_orm(has_many, skip_fk)]
+    pub events: HasMany<super::purchase_order_event::Entity>,
+
     /// Shipping/delivery address (if physical goods)
     #[sea_orm(column_type = "JsonBinary")]
     pub shipping_address: Option<DBAddress>,
 
+    /// Expected dates and shipment tracking (missing for orders saved before it was added)
+    #[sea_orm(column_type = "JsonBinary")]
+    pub schedule: Option<DBPurchaseOrderSchedule>,
+
     /// Total amount paid
     pub total_price_currency: String,
     pub total_price_amount: i64,
@@ -77,6 +101,7 @@ impl ActiveModelBehavior for ActiveModel {}
 )]
 pub enum DBPurchaseOrderStatus {
     Incomplete,
+    PartiallyFulfilled,
     Fulfilled,
     Cancelled,
 }
@@ -85,6 +110,7 @@ impl From<PurchaseOrderStatus> for DBPurchaseOrderStatus {
     fn from(status: PurchaseOrderStatus) -> Self {
         match status {
             PurchaseOrderStatus::Incomplete => DBPurchaseOrderStatus::Incomplete,
+            PurchaseOrderStatus::PartiallyFulfilled => DBPurchaseOrderStatus::PartiallyFulfilled,
             PurchaseOrderStatus::Fulfilled => DBPurchaseOrderStatus::Fulfilled,
             PurchaseOrderStatus::Cancelled => DBPurchaseOrderStatus::Cancelled,
         }
@@ -95,6 +121,7 @@ impl From<DBPurchaseOrderStatus> for PurchaseOrderStatus {
     fn from(db_status: DBPurchaseOrderStatus) -> Self {
         match db_status {
             DBPurchaseOrderStatus::Incomplete => PurchaseOrderStatus::Incomplete,
+            DBPurchaseOrderStatus::PartiallyFulfilled => PurchaseOrderStatus::PartiallyFulfilled,
             DBPurchaseOrderStatus::Fulfilled => PurchaseOrderStatus::Fulfilled,
             DBPurchaseOrderStatus::Cancelled => PurchaseOrderStatus::Cancelled,
         }
@@ -118,6 +145,9 @@ impl DBAddress {
     }
 }
 
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, FromJsonQueryResult)]
+pub struct DBPurchaseOrderSchedule(pub PurchaseOrderSchedule);
+
 impl TryIntoDomainModelSimple<PurchaseOrder> for ModelEx {
     fn try_into_domain_model_simple(self) -> Result<PurchaseOrder, RepositoryError> {
         let items = self
@@ -125,12 +155,45 @@ impl TryIntoDomainModelSimple<PurchaseOrder> for ModelEx {
             .into_iter()
             .map(TryIntoDomainModelSimple::try_into_domain_model_simple)
             .collect::<Result<Vec<_>, _>>()?;
+        let adjustments = self
+            .adjustments
+            .into_iter()
+            .map(TryIntoDomainModelSimple::try_into_domain_model_simple)
+            .collect::<Result<Vec<_>, _>>()?;
+        let payments = self
+            .payments
+            .into_iter()
+            .map(TryIntoDomainModelSimple::try_into_domain_model_simple)
+            .collect::<Result<Vec<_>, _>>()?;
+        let returns = self
+            .returns
+            .into_iter()
+            .map(TryIntoDomainModelSimple::try_into_domain_model_simple)
+            .collect::<Result<Vec<_>, _>>()?;
+        let attachments = self
+            .attachments
+            .into_iter()
+            .map(TryIntoDomainModelSimple::try_into_domain_model_simple)
+            .collect::<Result<Vec<_>, _>>()?;
+        let mut events = self
+            .events
+            .into_iter()
+            .map(TryIntoDomainModelSimple::try_into_domain_model_simple)
+            .collect::<Result<Vec<_>, _>>()?;
+        // Event ids are time-ordered (UUIDv7), so they break timestamp ties
+        events.sort_by_key(|event: &PurchaseOrderEvent| (event.created_at, event.id.0));
         Ok(PurchaseOrder {
             id: self.id.try_into()?,
             creator_id: self.creator_id.try_into()?,
             receiver_id: self.receiver_id.try_into()?,
             items,
+            adjustments,
+            payments,
+            returns,
+            attachments,
+            events,
             shipping_address: self.shipping_address.map(|addr| addr.into_inner()),
+            schedule: self.schedule.map(|schedule| schedule.0).unwrap_or_default(),
             total_price: Price {
                 currency: Currency::from_str(&self.total_price_currency)?,
                 amount: self.total_price_amount as u32,
@@ -153,6 +216,7 @@ impl From<&PurchaseOrder> for ActiveModel {
                 .shipping_address
                 .as_ref()
                 .map(|addr| DBAddress::new(addr.clone()))),
+            schedule: Set(Some(DBPurchaseOrderSchedule(order.schedule.clone()))),
             total_price_currency: Set(order.total_price.currency.code().to_string()),
             total_price_amount: Set(order.total_price.amount as i64),
             status: Set(order.status.into()),
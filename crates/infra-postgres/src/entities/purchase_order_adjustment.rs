@@ -0,0 +1,121 @@
+use crate::traits::TryIntoDomainModelSimple;
+use sawa_core::{
+    errors::RepositoryError,
+    models::{
+        misc::{Currency, Price},
+        purchase::{AdjustmentAllocation, PurchaseOrderAdjustment, PurchaseOrderAdjustmentKind},
+    },
+};
+use sea_orm::{ActiveValue::Set, entity::prelude::*};
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+///
+/// PurchaseOrderAdjustment entity
+///
+#[sea_orm::model]
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+#[sea_orm(table_name = "purchase_order_adjustments")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: Uuid,
+
+    /// The order this adjustment belongs to
+    pub purchase_order_id: Uuid,
+    #[sea_orm(belongs_to, from = "purchase_order_id", to = "id", skip_fk)]
+    pub purchase_order: HasOne<super::purchase_order::Entity>,
+
+    /// What this adjustment is for
+    pub kind: DBPurchaseOrderAdjustmentKind,
+
+    /// The amount of this adjustment
+    pub amount_currency: String,
+    pub amount: u32,
+
+    /// How this adjustment is spread over line items
+    #[sea_orm(column_type = "JsonBinary")]
+    pub allocation: DBAdjustmentAllocation,
+
+    /// Optional free-form note
+    pub note: Option<String>,
+}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+#[derive(EnumIter, DeriveActiveEnum, Debug, Clone, PartialEq, Eq)]
+#[sea_orm(
+    rs_type = "String",
+    db_type = "String(StringLen::None)",
+    rename_all = "snake_case"
+)]
+pub enum DBPurchaseOrderAdjustmentKind {
+    DomesticShipping,
+    InternationalShipping,
+    ServiceFee,
+    Tax,
+    Discount,
+}
+
+impl From<PurchaseOrderAdjustmentKind> for DBPurchaseOrderAdjustmentKind {
+    fn from(kind: PurchaseOrderAdjustmentKind) -> Self {
+        match kind {
+            PurchaseOrderAdjustmentKind::DomesticShipping => {
+                DBPurchaseOrderAdjustmentKind::DomesticShipping
+            }
+            PurchaseOrderAdjustmentKind::InternationalShipping => {
+                DBPurchaseOrderAdjustmentKind::InternationalShipping
+            }
+            PurchaseOrderAdjustmentKind::ServiceFee => DBPurchaseOrderAdjustmentKind::ServiceFee,
+            PurchaseOrderAdjustmentKind::Tax => DBPurchaseOrderAdjustmentKind::Tax,
+            PurchaseOrderAdjustmentKind::Discount => DBPurchaseOrderAdjustmentKind::Discount,
+        }
+    }
+}
+
+impl From<DBPurchaseOrderAdjustmentKind> for PurchaseOrderAdjustmentKind {
+    fn from(db_kind: DBPurchaseOrderAdjustmentKind) -> Self {
+        match db_kind {
+            DBPurchaseOrderAdjustmentKind::DomesticShipping => {
+                PurchaseOrderAdjustmentKind::DomesticShipping
+            }
+            DBPurchaseOrderAdjustmentKind::InternationalShipping => {
+                PurchaseOrderAdjustmentKind::InternationalShipping
+            }
+            DBPurchaseOrderAdjustmentKind::ServiceFee => PurchaseOrderAdjustmentKind::ServiceFee,
+            DBPurchaseOrderAdjustmentKind::Tax => PurchaseOrderAdjustmentKind::Tax,
+            DBPurchaseOrderAdjustmentKind::Discount => PurchaseOrderAdjustmentKind::Discount,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, FromJsonQueryResult)]
+pub struct DBAdjustmentAllocation(pub AdjustmentAllocation);
+
+impl TryIntoDomainModelSimple<PurchaseOrderAdjustment> for ModelEx {
+    fn try_into_domain_model_simple(self) -> Result<PurchaseOrderAdjustment, RepositoryError> {
+        Ok(PurchaseOrderAdjustment {
+            id: self.id.try_into()?,
+            kind: self.kind.into(),
+            amount: Price {
+                currency: Currency::from_str(&self.amount_currency)?,
+                amount: self.amount,
+            },
+            allocation: self.allocation.0,
+            note: self.note,
+        })
+    }
+}
+
+impl From<(&PurchaseOrderAdjustment, Uuid)> for ActiveModel {
+    fn from((adjustment, purchase_order_id): (&PurchaseOrderAdjustment, Uuid)) -> Self {
+        Self {
+            id: Set(Uuid::from(adjustment.id.0)),
+            purchase_order_id: Set(purchase_order_id),
+            kind: Set(adjustment.kind.into()),
+            amount_currency: Set(adjustment.amount.currency.code().to_string()),
+            amount: Set(adjustment.amount.amount),
+            allocation: Set(DBAdjustmentAllocation(adjustment.allocation.clone())),
+            note: Set(adjustment.note.clone()),
+        }
+    }
+}
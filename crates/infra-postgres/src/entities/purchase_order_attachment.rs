@@ -0,0 +1,102 @@
+use crate::traits::TryIntoDomainModelSimple;
+use sawa_core::{
+    errors::RepositoryError,
+    models::purchase::{PurchaseOrderAttachment, PurchaseOrderAttachmentKind},
+};
+use sea_orm::{ActiveValue::Set, entity::prelude::*};
+
+///
+/// PurchaseOrderAttachment entity
+///
+#[sea_orm::model]
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+#[sea_orm(table_name = "purchase_order_attachments")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: Uuid,
+
+    /// The order this attachment belongs to
+    pub purchase_order_id: Uuid,
+    #[sea_orm(belongs_to, from = "purchase_order_id", to = "id", skip_fk)]
+    pub purchase_order: HasOne<super::purchase_order::Entity>,
+
+    /// The uploaded file
+    pub media_id: Uuid,
+    #[sea_orm(belongs_to, from = "media_id", to = "id", skip_fk)]
+    pub media: HasOne<super::media::Entity>,
+
+    /// What the file is
+    pub kind: DBPurchaseOrderAttachmentKind,
+
+    /// Optional free-form note
+    pub note: Option<String>,
+
+    /// The user who attached the file
+    pub uploader_id: Uuid,
+    #[sea_orm(belongs_to, from = "uploader_id", to = "id", skip_fk)]
+    pub uploader: HasOne<super::user::Entity>,
+
+    /// The timestamp when the file was attached.
+    pub created_at: DateTimeUtc,
+}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+#[derive(EnumIter, DeriveActiveEnum, Debug, Clone, PartialEq, Eq)]
+#[sea_orm(
+    rs_type = "String",
+    db_type = "String(StringLen::None)",
+    rename_all = "snake_case"
+)]
+pub enum DBPurchaseOrderAttachmentKind {
+    Receipt,
+    Invoice,
+    Photo,
+}
+
+impl From<PurchaseOrderAttachmentKind> for DBPurchaseOrderAttachmentKind {
+    fn from(kind: PurchaseOrderAttachmentKind) -> Self {
+        match kind {
+            PurchaseOrderAttachmentKind::Receipt => DBPurchaseOrderAttachmentKind::Receipt,
+            PurchaseOrderAttachmentKind::Invoice => DBPurchaseOrderAttachmentKind::Invoice,
+            PurchaseOrderAttachmentKind::Photo => DBPurchaseOrderAttachmentKind::Photo,
+        }
+    }
+}
+
+impl From<DBPurchaseOrderAttachmentKind> for PurchaseOrderAttachmentKind {
+    fn from(db_kind: DBPurchaseOrderAttachmentKind) -> Self {
+        match db_kind {
+            DBPurchaseOrderAttachmentKind::Receipt => PurchaseOrderAttachmentKind::Receipt,
+            DBPurchaseOrderAttachmentKind::Invoice => PurchaseOrderAttachmentKind::Invoice,
+            DBPurchaseOrderAttachmentKind::Photo => PurchaseOrderAttachmentKind::Photo,
+        }
+    }
+}
+
+impl TryIntoDomainModelSimple<PurchaseOrderAttachment> for ModelEx {
+    fn try_into_domain_model_simple(self) -> Result<PurchaseOrderAttachment, RepositoryError> {
+        Ok(PurchaseOrderAttachment {
+            id: self.id.try_into()?,
+            media_id: self.media_id.try_into()?,
+            kind: self.kind.into(),
+            note: self.note,
+            uploader_id: self.uploader_id.try_into()?,
+            created_at: self.created_at,
+        })
+    }
+}
+
+impl From<(&PurchaseOrderAttachment, Uuid)> for ActiveModel {
+    fn from((attachment, purchase_order_id): (&PurchaseOrderAttachment, Uuid)) -> Self {
+        Self {
+            id: Set(Uuid::from(attachment.id.0)),
+            purchase_order_id: Set(purchase_order_id),
+            media_id: Set(Uuid::from(attachment.media_id.0)),
+            kind: Set(attachment.kind.into()),
+            note: Set(attachment.note.clone()),
+            uploader_id: Set(Uuid::from(attachment.uploader_id.0)),
+            created_at: Set(attachment.created_at),
+        }
+    }
+}
@@ -0,0 +1,63 @@
+use crate::traits::TryIntoDomainModelSimple;
+use sawa_core::{
+    errors::RepositoryError,
+    models::purchase::{PurchaseOrderEvent, PurchaseOrderEventKind},
+};
+use sea_orm::{ActiveValue::Set, entity::prelude::*};
+use serde::{Deserialize, Serialize};
+
+///
+/// PurchaseOrderEvent entity
+///
+#[sea_orm::model]
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+#[sea_orm(table_name = "purchase_order_events")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: Uuid,
+
+    /// The order this event belongs to
+    pub purchase_order_id: Uuid,
+    #[sea_orm(belongs_to, from = "purchase_order_id", to = "id", skip_fk)]
+    pub purchase_order: HasOne<super::purchase_order::Entity>,
+
+    /// The user who performed the change
+    pub actor_id: Uuid,
+    #[sea_orm(belongs_to, from = "actor_id", to = "id", skip_fk)]
+    pub actor: HasOne<super::user::Entity>,
+
+    /// What happened
+    #[sea_orm(column_type = "JsonBinary")]
+    pub kind: DBPurchaseOrderEventKind,
+
+    /// The timestamp when the event happened.
+    pub created_at: DateTimeUtc,
+}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, FromJsonQueryResult)]
+pub struct DBPurchaseOrderEventKind(pub PurchaseOrderEventKind);
+
+impl TryIntoDomainModelSimple<PurchaseOrderEvent> for ModelEx {
+    fn try_into_domain_model_simple(self) -> Result<PurchaseOrderEvent, RepositoryError> {
+        Ok(PurchaseOrderEvent {
+            id: self.id.try_into()?,
+            actor_id: self.actor_id.try_into()?,
+            kind: self.kind.0,
+            created_at: self.created_at,
+        })
+    }
+}
+
+impl From<(&PurchaseOrderEvent, Uuid)> for ActiveModel {
+    fn from((event, purchase_order_id): (&PurchaseOrderEvent, Uuid)) -> Self {
+        Self {
+            id: Set(Uuid::from(event.id.0)),
+            purchase_order_id: Set(purchase_order_id),
+            actor_id: Set(Uuid::from(event.actor_id.0)),
+            kind: Set(DBPurchaseOrderEventKind(event.kind.clone())),
+            created_at: Set(event.created_at),
+        }
+    }
+}
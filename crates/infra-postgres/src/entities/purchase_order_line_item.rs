@@ -34,6 +34,12 @@ pub struct Model {
 
     /// The timestamp when the instance was created
     pub fulfilled_at: Option<DateTimeUtc>,
+
+    /// The timestamp when the instance was returned to the seller
+    pub returned_at: Option<DateTimeUtc>,
+
+    /// The returned line item this one replaces
+    pub replaces: Option<Uuid>,
 }
 
 impl ActiveModelBehavior for ActiveModel {}
@@ -47,6 +53,8 @@ impl TryIntoDomainModelSimple<PurchaseOrderLineItem> for ModelEx {
             owner_id: self.owner_id.try_into()?,
             instance_id: self.instance_id.map(TryInto::try_into).transpose()?,
             fulfilled_at: self.fulfilled_at,
+            returned_at: self.returned_at,
+            replaces: self.replaces.map(TryInto::try_into).transpose()?,
         })
     }
 }
@@ -60,6 +68,8 @@ impl From<&PurchaseOrderLineItem> for ActiveModel {
             owner_id: Set(Uuid::from(line_item.owner_id.0)),
             instance_id: Set(line_item.instance_id.map(|id| Uuid::from(id.0))),
             fulfilled_at: Set(line_item.fulfilled_at),
+            returned_at: Set(line_item.returned_at),
+            replaces: Set(line_item.replaces.map(|id| Uuid::from(id.0))),
         }
     }
 }
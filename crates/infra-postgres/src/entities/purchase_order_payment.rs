@@ -0,0 +1,72 @@
+use crate::traits::TryIntoDomainModelSimple;
+use sawa_core::{
+    errors::RepositoryError,
+    models::{
+        misc::{Currency, Price},
+        purchase::PurchaseOrderPayment,
+    },
+};
+use sea_orm::{ActiveValue::Set, entity::prelude::*};
+use std::str::FromStr;
+
+///
+/// PurchaseOrderPayment entity
+///
+#[sea_orm::model]
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+#[sea_orm(table_name = "purchase_order_payments")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: Uuid,
+
+    /// The order this payment settles
+    pub purchase_order_id: Uuid,
+    #[sea_orm(belongs_to, from = "purchase_order_id", to = "id", skip_fk)]
+    pub purchase_order: HasOne<super::purchase_order::Entity>,
+
+    /// The participant who paid
+    pub payer_id: Uuid,
+    #[sea_orm(belongs_to, from = "payer_id", to = "id", skip_fk)]
+    pub payer: HasOne<super::user::Entity>,
+
+    /// The amount paid
+    pub amount_currency: String,
+    pub amount: u32,
+
+    /// Optional free-form note
+    pub note: Option<String>,
+
+    /// The timestamp when the payment was made.
+    pub paid_at: DateTimeUtc,
+}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+impl TryIntoDomainModelSimple<PurchaseOrderPayment> for ModelEx {
+    fn try_into_domain_model_simple(self) -> Result<PurchaseOrderPayment, RepositoryError> {
+        Ok(PurchaseOrderPayment {
+            id: self.id.try_into()?,
+            payer_id: self.payer_id.try_into()?,
+            amount: Price {
+                currency: Currency::from_str(&self.amount_currency)?,
+                amount: self.amount,
+            },
+            note: self.note,
+            paid_at: self.paid_at,
+        })
+    }
+}
+
+impl From<(&PurchaseOrderPayment, Uuid)> for ActiveModel {
+    fn from((payment, purchase_order_id): (&PurchaseOrderPayment, Uuid)) -> Self {
+        Self {
+            id: Set(Uuid::from(payment.id.0)),
+            purchase_order_id: Set(purchase_order_id),
+            payer_id: Set(Uuid::from(payment.payer_id.0)),
+            amount_currency: Set(payment.amount.currency.code().to_string()),
+            amount: Set(payment.amount.amount),
+            note: Set(payment.note.clone()),
+            paid_at: Set(payment.paid_at),
+        }
+    }
+}
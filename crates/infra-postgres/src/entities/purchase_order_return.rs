@@ -0,0 +1,81 @@
+use crate::traits::TryIntoDomainModelSimple;
+use sawa_core::{
+    errors::RepositoryError,
+    models::{
+        misc::{Currency, Price},
+        purchase::{PurchaseOrderLineItemId, PurchaseOrderReturn},
+    },
+};
+use sea_orm::{ActiveValue::Set, entity::prelude::*};
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+///
+/// PurchaseOrderReturn entity
+///
+#[sea_orm::model]
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+#[sea_orm(table_name = "purchase_order_returns")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: Uuid,
+
+    /// The order this return belongs to
+    pub purchase_order_id: Uuid,
+    #[sea_orm(belongs_to, from = "purchase_order_id", to = "id", skip_fk)]
+    pub purchase_order: HasOne<super::purchase_order::Entity>,
+
+    /// The returned line items
+    #[sea_orm(column_type = "JsonBinary")]
+    pub line_item_ids: DBLineItemIds,
+
+    /// The amount refunded by the seller
+    pub refund_currency: Option<String>,
+    pub refund_amount: Option<u32>,
+
+    /// Optional reason
+    pub reason: Option<String>,
+
+    /// The timestamp when the items were returned.
+    pub returned_at: DateTimeUtc,
+}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, FromJsonQueryResult)]
+pub struct DBLineItemIds(pub Vec<PurchaseOrderLineItemId>);
+
+impl TryIntoDomainModelSimple<PurchaseOrderReturn> for ModelEx {
+    fn try_into_domain_model_simple(self) -> Result<PurchaseOrderReturn, RepositoryError> {
+        Ok(PurchaseOrderReturn {
+            id: self.id.try_into()?,
+            line_item_ids: self.line_item_ids.0,
+            refund: match (self.refund_currency, self.refund_amount) {
+                (Some(currency), Some(amount)) => Some(Price {
+                    currency: Currency::from_str(&currency)?,
+                    amount,
+                }),
+                _ => None,
+            },
+            reason: self.reason,
+            returned_at: self.returned_at,
+        })
+    }
+}
+
+impl From<(&PurchaseOrderReturn, Uuid)> for ActiveModel {
+    fn from((order_return, purchase_order_id): (&PurchaseOrderReturn, Uuid)) -> Self {
+        Self {
+            id: Set(Uuid::from(order_return.id.0)),
+            purchase_order_id: Set(purchase_order_id),
+            line_item_ids: Set(DBLineItemIds(order_return.line_item_ids.clone())),
+            refund_currency: Set(order_return
+                .refund
+                .as_ref()
+                .map(|p| p.currency.code().to_string())),
+            refund_amount: Set(order_return.refund.as_ref().map(|p| p.amount)),
+            reason: Set(order_return.reason.clone()),
+            returned_at: Set(order_return.returned_at),
+        }
+    }
+}
@@ -0,0 +1,54 @@
+use crate::traits::TryIntoDomainModelSimple;
+use sawa_core::{errors::RepositoryError, models::storage::StorageLocation};
+use sea_orm::{ActiveValue::Set, entity::prelude::*};
+
+///
+/// StorageLocation entity
+///
+#[sea_orm::model]
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+#[sea_orm(table_name = "storage_locations")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: Uuid,
+
+    /// The user the location belongs to
+    pub user_id: Uuid,
+    #[sea_orm(belongs_to, from = "user_id", to = "id", skip_fk)]
+    pub user: HasOne<super::user::Entity>,
+
+    /// The name of the location
+    pub name: String,
+
+    /// The location containing this one
+    pub parent_id: Option<Uuid>,
+
+    /// The timestamp when the location was created.
+    pub created_at: DateTimeUtc,
+}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+impl TryIntoDomainModelSimple<StorageLocation> for Model {
+    fn try_into_domain_model_simple(self) -> Result<StorageLocation, RepositoryError> {
+        Ok(StorageLocation {
+            id: self.id.try_into()?,
+            user_id: self.user_id.try_into()?,
+            name: self.name.try_into()?,
+            parent_id: self.parent_id.map(TryInto::try_into).transpose()?,
+            created_at: self.created_at,
+        })
+    }
+}
+
+impl From<&StorageLocation> for ActiveModel {
+    fn from(location: &StorageLocation) -> Self {
+        Self {
+            id: Set(Uuid::from(location.id.0)),
+            user_id: Set(Uuid::from(location.user_id.0)),
+            name: Set(location.name.as_str().to_string()),
+            parent_id: Set(location.parent_id.map(Into::into)),
+            created_at: Set(location.created_at),
+        }
+    }
+}
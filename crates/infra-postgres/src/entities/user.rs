@@ -25,6 +25,20 @@ pub struct Model {
     #[sea_orm(belongs_to, from = "avatar_id", to = "id", skip_fk)]
     pub avatar: HasOne<super::media::Entity>,
 
+    /// The display name of a placeholder user.
+    pub display_name: Option<String>,
+
+    /// The user managing a placeholder user.
+    pub manager_id: Option<Uuid>,
+
+    /// The invite code to claim a placeholder user.
+    #[sea_orm(unique)]
+    pub invite_code: Option<String>,
+
+    /// The secret token authenticating the user's calendar feeds.
+    #[sea_orm(unique)]
+    pub calendar_token: Option<String>,
+
     /// The timestamp when the user was created.
     pub created_at: DateTimeUtc,
 }
@@ -41,6 +55,15 @@ impl TryFrom<Model> for User {
             email: Email(model.email),
             password_hash: model.password_hash.try_into()?,
             avatar: model.avatar_id.map(|id| id.try_into()).transpose()?.into(),
+            placeholder: match (model.display_name, model.manager_id, model.invite_code) {
+                (Some(display_name), Some(manager_id), Some(invite_code)) => Some(Placeholder {
+                    display_name,
+                    manager_id: manager_id.try_into()?,
+                    invite_code,
+                }),
+                _ => None,
+            },
+            calendar_token: model.calendar_token,
             created_at: model.created_at,
         })
     }
@@ -54,6 +77,12 @@ impl From<User> for crate::entities::user::ActiveModel {
             email: ActiveValue::Set(user.email.0),
             password_hash: ActiveValue::Set(user.password_hash.into_string()),
             avatar_id: ActiveValue::Set(user.avatar.map(Into::into)),
+            display_name: ActiveValue::Set(
+                user.placeholder.as_ref().map(|p| p.display_name.clone()),
+            ),
+            manager_id: ActiveValue::Set(user.placeholder.as_ref().map(|p| p.manager_id.into())),
+            invite_code: ActiveValue::Set(user.placeholder.map(|p| p.invite_code)),
+            calendar_token: ActiveValue::Set(user.calendar_token),
             created_at: ActiveValue::Set(user.created_at),
         }
     }
@@ -76,6 +105,13 @@ impl From<UserUpdate> for crate::entities::user::ActiveModel {
                 .map(|password_hash| ActiveValue::Set(password_hash.into_string()))
                 .unwrap_or(ActiveValue::NotSet),
             avatar_id: ActiveValue::Set(user.avatar.map(Into::into)),
+            display_name: ActiveValue::NotSet,
+            manager_id: ActiveValue::NotSet,
+            invite_code: ActiveValue::NotSet,
+            calendar_token: user
+                .calendar_token
+                .map(ActiveValue::Set)
+                .unwrap_or(ActiveValue::NotSet),
             created_at: ActiveValue::NotSet,
         }
     }
@@ -42,6 +42,12 @@ pub struct Model {
     #[sea_orm(has_many, skip_fk)]
     pub items: HasMany<super::user_transaction_item::Entity>,
 
+    /// Why the items are being transferred
+    pub reason: super::product_instance_transfer_history::DBTransferReason,
+
+    /// When the receiver should give the items back, for lending
+    pub return_by: Option<Date>,
+
     /// Transaction status
     pub status: DBUserTransactionStatus,
 
@@ -62,6 +68,7 @@ impl ActiveModelBehavior for ActiveModel {}
     rename_all = "snake_case"
 )]
 pub enum DBUserTransactionStatus {
+    Proposed,
     Pending,
     Completed,
     Cancelled,
@@ -70,6 +77,7 @@ pub enum DBUserTransactionStatus {
 impl From<UserTransactionStatus> for DBUserTransactionStatus {
     fn from(status: UserTransactionStatus) -> Self {
         match status {
+            UserTransactionStatus::Proposed => DBUserTransactionStatus::Proposed,
             UserTransactionStatus::Pending => DBUserTransactionStatus::Pending,
             UserTransactionStatus::Completed => DBUserTransactionStatus::Completed,
             UserTransactionStatus::Cancelled => DBUserTransactionStatus::Cancelled,
@@ -80,6 +88,7 @@ impl From<UserTransactionStatus> for DBUserTransactionStatus {
 impl From<DBUserTransactionStatus> for UserTransactionStatus {
     fn from(status: DBUserTransactionStatus) -> Self {
         match status {
+            DBUserTransactionStatus::Proposed => UserTransactionStatus::Proposed,
             DBUserTransactionStatus::Pending => UserTransactionStatus::Pending,
             DBUserTransactionStatus::Completed => UserTransactionStatus::Completed,
             DBUserTransactionStatus::Cancelled => UserTransactionStatus::Cancelled,
@@ -100,6 +109,8 @@ impl TryIntoDomainModelSimple<UserTransaction> for ModelEx {
             from_user_id: self.from_user_id.try_into()?,
             to_user_id: self.to_user_id.try_into()?,
             items,
+            reason: self.reason.into(),
+            return_by: self.return_by,
             status: self.status.into(),
             created_at: self.created_at,
             completed_at: self.completed_at,
@@ -114,6 +125,8 @@ impl From<&UserTransaction> for crate::entities::user_transaction::ActiveModel {
             id: ActiveValue::Set(Uuid::from(transaction.id.0)),
             from_user_id: ActiveValue::Set(Uuid::from(transaction.from_user_id.0)),
             to_user_id: ActiveValue::Set(Uuid::from(transaction.to_user_id.0)),
+            reason: ActiveValue::Set(transaction.reason.into()),
+            return_by: ActiveValue::Set(transaction.return_by),
             status: ActiveValue::Set(transaction.status.into()),
             created_at: ActiveValue::Set(transaction.created_at),
             completed_at: ActiveValue::Set(transaction.completed_at),
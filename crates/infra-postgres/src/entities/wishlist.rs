@@ -0,0 +1,62 @@
+use crate::traits::TryIntoDomainModelSimple;
+use sawa_core::{errors::RepositoryError, models::wishlist::Wishlist};
+use sea_orm::{ActiveValue::Set, entity::prelude::*};
+
+///
+/// Wishlist entity
+///
+#[sea_orm::model]
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+#[sea_orm(table_name = "wishlists")]
+pub struct Model {
+    /// The user the wishlist belongs to
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub user_id: Uuid,
+    #[sea_orm(belongs_to, from = "user_id", to = "id", skip_fk)]
+    pub user: HasOne<super::user::Entity>,
+
+    /// Whether other users can view the wishlist
+    pub is_public: bool,
+
+    /// Whether the user takes part in trade matching
+    pub open_to_trades: bool,
+
+    /// The wanted variants
+    #[sea_orm(has_many, skip_fk)]
+    pub items: HasMany<super::wishlist_item::Entity>,
+
+    /// The timestamp when the wishlist was last changed.
+    pub updated_at: DateTimeUtc,
+}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+impl TryIntoDomainModelSimple<Wishlist> for ModelEx {
+    fn try_into_domain_model_simple(self) -> Result<Wishlist, RepositoryError> {
+        let mut items = self
+            .items
+            .into_iter()
+            .map(TryIntoDomainModelSimple::try_into_domain_model_simple)
+            .collect::<Result<Vec<_>, _>>()?;
+        items.sort_by_key(|item| item.added_at);
+
+        Ok(Wishlist {
+            user_id: self.user_id.try_into()?,
+            is_public: self.is_public,
+            open_to_trades: self.open_to_trades,
+            items,
+            updated_at: self.updated_at,
+        })
+    }
+}
+
+impl From<&Wishlist> for ActiveModel {
+    fn from(wishlist: &Wishlist) -> Self {
+        Self {
+            user_id: Set(Uuid::from(wishlist.user_id.0)),
+            is_public: Set(wishlist.is_public),
+            open_to_trades: Set(wishlist.open_to_trades),
+            updated_at: Set(wishlist.updated_at),
+        }
+    }
+}
@@ -0,0 +1,130 @@
+use crate::traits::TryIntoDomainModelSimple;
+use sawa_core::{
+    errors::RepositoryError,
+    models::{
+        misc::{Currency, Price},
+        wishlist::{WishlistItem, WishlistPriority},
+    },
+};
+use sea_orm::{ActiveValue::Set, entity::prelude::*};
+use std::str::FromStr;
+
+///
+/// WishlistItem entity
+///
+#[sea_orm::model]
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+#[sea_orm(table_name = "wishlist_items")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: Uuid,
+
+    /// The wishlist this item belongs to
+    pub wishlist_user_id: Uuid,
+    #[sea_orm(belongs_to, from = "wishlist_user_id", to = "user_id", skip_fk)]
+    pub wishlist: HasOne<super::wishlist::Entity>,
+
+    /// The wanted variant
+    pub variant_id: Uuid,
+    #[sea_orm(belongs_to, from = "variant_id", to = "id", skip_fk)]
+    pub variant: HasOne<super::product_variant::Entity>,
+
+    /// How much the user wants the variant
+    pub priority: DBWishlistPriority,
+
+    /// How many instances the user wants
+    pub desired_quantity: i64,
+
+    /// How many instances the user obtained since adding the item
+    pub obtained_quantity: i64,
+
+    /// The most the user is willing to pay per instance
+    pub max_price_currency: Option<String>,
+    pub max_price_amount: Option<u32>,
+
+    /// Free-form notes
+    pub note: String,
+
+    /// When the user obtained the desired quantity
+    pub satisfied_at: Option<DateTimeUtc>,
+
+    /// The timestamp when the item was added.
+    pub added_at: DateTimeUtc,
+}
+
+#[derive(EnumIter, DeriveActiveEnum, Debug, Clone, PartialEq, Eq)]
+#[sea_orm(
+    rs_type = "String",
+    db_type = "String(StringLen::None)",
+    rename_all = "snake_case"
+)]
+pub enum DBWishlistPriority {
+    Low,
+    Normal,
+    High,
+}
+
+impl From<DBWishlistPriority> for WishlistPriority {
+    fn from(db_priority: DBWishlistPriority) -> Self {
+        match db_priority {
+            DBWishlistPriority::Low => WishlistPriority::Low,
+            DBWishlistPriority::Normal => WishlistPriority::Normal,
+            DBWishlistPriority::High => WishlistPriority::High,
+        }
+    }
+}
+
+impl From<WishlistPriority> for DBWishlistPriority {
+    fn from(priority: WishlistPriority) -> Self {
+        match priority {
+            WishlistPriority::Low => DBWishlistPriority::Low,
+            WishlistPriority::Normal => DBWishlistPriority::Normal,
+            WishlistPriority::High => DBWishlistPriority::High,
+        }
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+impl TryIntoDomainModelSimple<WishlistItem> for ModelEx {
+    fn try_into_domain_model_simple(self) -> Result<WishlistItem, RepositoryError> {
+        Ok(WishlistItem {
+            id: self.id.try_into()?,
+            variant_id: self.variant_id.try_into()?,
+            priority: self.priority.into(),
+            desired_quantity: (self.desired_quantity as u32).try_into()?,
+            obtained_quantity: self.obtained_quantity as u32,
+            max_price: match (self.max_price_currency, self.max_price_amount) {
+                (Some(currency), Some(amount)) => Some(Price {
+                    currency: Currency::from_str(&currency)?,
+                    amount,
+                }),
+                _ => None,
+            },
+            note: self.note,
+            satisfied_at: self.satisfied_at,
+            added_at: self.added_at,
+        })
+    }
+}
+
+impl From<(&WishlistItem, Uuid)> for ActiveModel {
+    fn from((item, wishlist_user_id): (&WishlistItem, Uuid)) -> Self {
+        Self {
+            id: Set(Uuid::from(item.id.0)),
+            wishlist_user_id: Set(wishlist_user_id),
+            variant_id: Set(Uuid::from(item.variant_id.0)),
+            priority: Set(item.priority.into()),
+            desired_quantity: Set(item.desired_quantity.get() as i64),
+            obtained_quantity: Set(item.obtained_quantity as i64),
+            max_price_currency: Set(item
+                .max_price
+                .as_ref()
+                .map(|p| p.currency.code().to_string())),
+            max_price_amount: Set(item.max_price.as_ref().map(|p| p.amount)),
+            note: Set(item.note.clone()),
+            satisfied_at: Set(item.satisfied_at),
+            added_at: Set(item.added_at),
+        }
+    }
+}
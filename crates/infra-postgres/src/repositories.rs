@@ -1,15 +1,23 @@
+mod cart;
 mod media;
 mod product;
 mod product_instance;
 mod purchase_order;
+mod statistics;
+mod storage_location;
 mod tag;
 mod user;
 mod user_transaction;
+mod wishlist;
 
+pub use cart::PostgresCartRepository;
 pub use media::PostgresMediaRepository;
 pub use product::{PostgresProductRepository, PostgresProductVariantRepository};
 pub use product_instance::PostgresProductInstanceRepository;
 pub use purchase_order::PostgresPurchaseOrderRepository;
+pub use statistics::PostgresStatisticsRepository;
+pub use storage_location::PostgresStorageLocationRepository;
 pub use tag::PostgresTagRepository;
 pub use user::PostgresUserRepository;
 pub use user_transaction::PostgresUserTransactionRepository;
+pub use wishlist::PostgresWishlistRepository;
@@ -0,0 +1,89 @@
+use crate::{cart_line, entities::cart, error::DatabaseError, traits::TryIntoDomainModelSimple};
+use sawa_core::{
+    errors::RepositoryError,
+    models::{cart::Cart, user::UserId},
+    repositories::CartRepository,
+};
+use sea_orm::{QueryFilter, TransactionTrait, prelude::*, sea_query::OnConflict};
+
+pub struct PostgresCartRepository {
+    db: DatabaseConnection,
+}
+
+impl PostgresCartRepository {
+    pub fn new(db: DatabaseConnection) -> Self {
+        Self { db }
+    }
+}
+
+impl CartRepository for PostgresCartRepository {
+    async fn find_by_user(&self, user_id: &UserId) -> Result<Option<Cart>, RepositoryError> {
+        let entity = cart::Entity::load()
+            .filter(cart::Column::UserId.eq(Uuid::from(user_id.0)))
+            .with(cart_line::Entity)
+            .one(&self.db)
+            .await
+            .map_err(DatabaseError)?;
+
+        entity
+            .map(TryIntoDomainModelSimple::try_into_domain_model_simple)
+            .transpose()
+    }
+
+    async fn save(&self, cart: &Cart) -> Result<(), RepositoryError> {
+        let user_id = Uuid::from(cart.user_id.0);
+        let cart_active_model: cart::ActiveModel = cart.into();
+
+        // Prepare line data outside the closure
+        let line_models: Vec<cart_line::ActiveModel> = cart
+            .lines
+            .iter()
+            .map(|line| (line, user_id).into())
+            .collect();
+
+        self.db
+            .transaction(|db| {
+                Box::pin(async move {
+                    // Save or update the cart
+                    cart::Entity::insert(cart_active_model)
+                        .on_conflict(
+                            OnConflict::column(cart::Column::UserId)
+                                .update_columns([cart::Column::UpdatedAt])
+                                .to_owned(),
+                        )
+                        .exec(db)
+                        .await?;
+
+                    // Replace lines
+                    cart_line::Entity::delete_many()
+                        .filter(cart_line::Column::CartUserId.eq(user_id))
+                        .exec(db)
+                        .await?;
+
+                    if !line_models.is_empty() {
+                        cart_line::Entity::insert_many(line_models).exec(db).await?;
+                    }
+
+                    Ok(())
+                })
+            })
+            .await
+            .map_err(DatabaseError::from)?;
+
+        Ok(())
+    }
+
+    async fn delete(&self, user_id: &UserId) -> Result<(), RepositoryError> {
+        cart::Entity::delete_by_id(Uuid::from(user_id.0))
+            .exec(&self.db)
+            .await
+            .map_err(DatabaseError)?;
+        cart_line::Entity::delete_many()
+            .filter(cart_line::Column::CartUserId.eq(Uuid::from(user_id.0)))
+            .exec(&self.db)
+            .await
+            .map_err(DatabaseError)?;
+
+        Ok(())
+    }
+}
@@ -7,13 +7,90 @@ use sawa_core::{
     },
     repositories::ProductInstanceRepository,
 };
-use sea_orm::{QueryFilter, TransactionTrait, prelude::*, sea_query::OnConflict};
+use sea_orm::{
+    DatabaseTransaction, QueryFilter, TransactionTrait, prelude::*, sea_query::OnConflict,
+};
 
 use crate::{
     error::DatabaseError, product_instance, product_instance_status_history,
     product_instance_transfer_history, traits::TryIntoDomainModelSimple,
 };
 
+/// The rows of a product instance and its history, prepared to be written.
+struct InstanceRows {
+    instance_id: Uuid,
+    instance: product_instance::ActiveModel,
+    transfer_history: Vec<product_instance_transfer_history::ActiveModel>,
+    status_history: Vec<product_instance_status_history::ActiveModel>,
+}
+
+impl From<&ProductInstance> for InstanceRows {
+    fn from(instance: &ProductInstance) -> Self {
+        Self {
+            instance_id: Uuid::from(instance.id.0),
+            instance: instance.into(),
+            transfer_history: instance
+                .transfer_history
+                .iter()
+                .map(|transfer| (transfer, instance.id).into())
+                .collect(),
+            status_history: instance
+                .status_history
+                .iter()
+                .map(|status| (status, instance.id).into())
+                .collect(),
+        }
+    }
+}
+
+impl InstanceRows {
+    /// Save or update the instance, replacing its history.
+    async fn write(self, db: &DatabaseTransaction) -> Result<(), DbErr> {
+        product_instance::Entity::insert(self.instance)
+            .on_conflict(
+                OnConflict::column(product_instance::Column::Id)
+                    .update_columns([
+                        product_instance::Column::OwnerId,
+                        product_instance::Column::HolderId,
+                        product_instance::Column::LocationId,
+                        product_instance::Column::Status,
+                        product_instance::Column::Condition,
+                        product_instance::Column::Note,
+                        product_instance::Column::Medias,
+                    ])
+                    .to_owned(),
+            )
+            .exec(db)
+            .await?;
+
+        // Replace the transfer history
+        product_instance_transfer_history::Entity::delete_many()
+            .filter(
+                product_instance_transfer_history::Column::ProductInstanceId.eq(self.instance_id),
+            )
+            .exec(db)
+            .await?;
+        if !self.transfer_history.is_empty() {
+            product_instance_transfer_history::Entity::insert_many(self.transfer_history)
+                .exec(db)
+                .await?;
+        }
+
+        // Replace the status history
+        product_instance_status_history::Entity::delete_many()
+            .filter(product_instance_status_history::Column::ProductInstanceId.eq(self.instance_id))
+            .exec(db)
+            .await?;
+        if !self.status_history.is_empty() {
+            product_instance_status_history::Entity::insert_many(self.status_history)
+                .exec(db)
+                .await?;
+        }
+
+        Ok(())
+    }
+}
+
 pub struct PostgresProductInstanceRepository {
     db: DatabaseConnection,
 }
@@ -180,73 +257,26 @@ impl ProductInstanceRepository for PostgresProductInstanceRepository {
     }
 
     async fn save(&self, instance: &ProductInstance) -> Result<(), RepositoryError> {
-        let instance_id = Uuid::from(instance.id.0);
-        let instance_active_model: product_instance::ActiveModel = instance.into();
-
-        // Prepare history data outside the closure
-        let transfer_history_models: Vec<product_instance_transfer_history::ActiveModel> = instance
-            .transfer_history
-            .iter()
-            .map(|transfer| (transfer, instance.id).into())
-            .collect();
-
-        let status_history_models: Vec<product_instance_status_history::ActiveModel> = instance
-            .status_history
-            .iter()
-            .map(|status| (status, instance.id).into())
-            .collect();
+        let rows = InstanceRows::from(instance);
+
+        self.db
+            .transaction(|db| Box::pin(async move { rows.write(db).await }))
+            .await
+            .map_err(DatabaseError::from)?;
+
+        Ok(())
+    }
+
+    async fn save_batch(&self, instances: &[ProductInstance]) -> Result<(), RepositoryError> {
+        let rows: Vec<InstanceRows> = instances.iter().map(InstanceRows::from).collect();
 
+        // All instances are saved in one transaction, so either all of them change or none
         self.db
             .transaction(|db| {
                 Box::pin(async move {
-                    // Save or update the instance
-                    product_instance::Entity::insert(instance_active_model)
-                        .on_conflict(
-                            OnConflict::column(product_instance::Column::Id)
-                                .update_columns([
-                                    product_instance::Column::OwnerId,
-                                    product_instance::Column::HolderId,
-                                    product_instance::Column::Status,
-                                ])
-                                .to_owned(),
-                        )
-                        .exec(db)
-                        .await?;
-
-                    // Delete existing transfer history
-                    product_instance_transfer_history::Entity::delete_many()
-                        .filter(
-                            product_instance_transfer_history::Column::ProductInstanceId
-                                .eq(instance_id),
-                        )
-                        .exec(db)
-                        .await?;
-
-                    // Insert new transfer history
-                    if !transfer_history_models.is_empty() {
-                        product_instance_transfer_history::Entity::insert_many(
-                            transfer_history_models,
-                        )
-                        .exec(db)
-                        .await?;
+                    for rows in rows {
+                        rows.write(db).await?;
                     }
-
-                    // Delete existing status history
-                    product_instance_status_history::Entity::delete_many()
-                        .filter(
-                            product_instance_status_history::Column::ProductInstanceId
-                                .eq(instance_id),
-                        )
-                        .exec(db)
-                        .await?;
-
-                    // Insert new status history
-                    if !status_history_models.is_empty() {
-                        product_instance_status_history::Entity::insert_many(status_history_models)
-                            .exec(db)
-                            .await?;
-                    }
-
                     Ok(())
                 })
             })
@@ -256,13 +286,6 @@ impl ProductInstanceRepository for PostgresProductInstanceRepository {
         Ok(())
     }
 
-    async fn save_batch(&self, instances: &[ProductInstance]) -> Result<(), RepositoryError> {
-        for instance in instances {
-            self.save(instance).await?;
-        }
-        Ok(())
-    }
-
     async fn delete(&self, id: &ProductInstanceId) -> Result<(), RepositoryError> {
         product_instance::Entity::delete_by_id(Uuid::from(id.0))
             .exec(&self.db)
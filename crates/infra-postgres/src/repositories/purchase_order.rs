@@ -1,6 +1,7 @@
 use crate::{
-    entities::purchase_order, error::DatabaseError, purchase_order_item, purchase_order_line_item,
-    traits::TryIntoDomainModelSimple,
+    entities::purchase_order, error::DatabaseError, purchase_order_adjustment,
+    purchase_order_attachment, purchase_order_event, purchase_order_item, purchase_order_line_item,
+    purchase_order_payment, purchase_order_return, traits::TryIntoDomainModelSimple,
 };
 use sawa_core::{
     errors::RepositoryError,
@@ -36,6 +37,11 @@ impl PurchaseOrderRepository for PostgresPurchaseOrderRepository {
                 purchase_order_item::Entity,
                 purchase_order_line_item::Entity,
             ))
+            .with(purchase_order_adjustment::Entity)
+            .with(purchase_order_payment::Entity)
+            .with(purchase_order_return::Entity)
+            .with(purchase_order_attachment::Entity)
+            .with(purchase_order_event::Entity)
             .filter(
                 Column::Id.eq(Uuid::from(id.0)).and(
                     Column::CreatorId.eq(Uuid::from(user_id.0)) // Creator can access the order
@@ -82,6 +88,11 @@ impl PurchaseOrderRepository for PostgresPurchaseOrderRepository {
                 purchase_order_item::Entity,
                 purchase_order_line_item::Entity,
             ))
+            .with(purchase_order_adjustment::Entity)
+            .with(purchase_order_payment::Entity)
+            .with(purchase_order_return::Entity)
+            .with(purchase_order_attachment::Entity)
+            .with(purchase_order_event::Entity)
             .filter(purchase_order::Column::Id.is_in(uuid_ids).and(
                     Column::CreatorId.eq(Uuid::from(user_id.0)) // Creator can access the order
                         .or(Column::ReceiverId.eq(Uuid::from(user_id.0))) // Receiver can access the order
@@ -169,6 +180,7 @@ impl PurchaseOrderRepository for PostgresPurchaseOrderRepository {
         if let Some(status) = status {
             let db_status = match status {
                 PurchaseOrderStatus::Incomplete => "incomplete",
+                PurchaseOrderStatus::PartiallyFulfilled => "partially_fulfilled",
                 PurchaseOrderStatus::Fulfilled => "fulfilled",
                 PurchaseOrderStatus::Cancelled => "cancelled",
             };
@@ -181,6 +193,11 @@ impl PurchaseOrderRepository for PostgresPurchaseOrderRepository {
                 purchase_order_item::Entity,
                 purchase_order_line_item::Entity,
             ))
+            .with(purchase_order_adjustment::Entity)
+            .with(purchase_order_payment::Entity)
+            .with(purchase_order_return::Entity)
+            .with(purchase_order_attachment::Entity)
+            .with(purchase_order_event::Entity)
             .all(&self.db)
             .await
             .map_err(|e| RepositoryError::Internal(e.to_string()))?;
@@ -213,6 +230,36 @@ impl PurchaseOrderRepository for PostgresPurchaseOrderRepository {
             })
             .collect();
 
+        let adjustment_models: Vec<purchase_order_adjustment::ActiveModel> = order
+            .adjustments
+            .iter()
+            .map(|adjustment| (adjustment, order_id).into())
+            .collect();
+
+        let payment_models: Vec<purchase_order_payment::ActiveModel> = order
+            .payments
+            .iter()
+            .map(|payment| (payment, order_id).into())
+            .collect();
+
+        let return_models: Vec<purchase_order_return::ActiveModel> = order
+            .returns
+            .iter()
+            .map(|order_return| (order_return, order_id).into())
+            .collect();
+
+        let attachment_models: Vec<purchase_order_attachment::ActiveModel> = order
+            .attachments
+            .iter()
+            .map(|attachment| (attachment, order_id).into())
+            .collect();
+
+        let event_models: Vec<purchase_order_event::ActiveModel> = order
+            .events
+            .iter()
+            .map(|event| (event, order_id).into())
+            .collect();
+
         self.db
             .transaction(|db| {
                 Box::pin(async move {
@@ -224,6 +271,7 @@ impl PurchaseOrderRepository for PostgresPurchaseOrderRepository {
                                     purchase_order::Column::CreatorId,
                                     purchase_order::Column::ReceiverId,
                                     purchase_order::Column::ShippingAddress,
+                                    purchase_order::Column::Schedule,
                                     purchase_order::Column::TotalPriceCurrency,
                                     purchase_order::Column::TotalPriceAmount,
                                     purchase_order::Column::Status,
@@ -271,6 +319,61 @@ impl PurchaseOrderRepository for PostgresPurchaseOrderRepository {
                         }
                     }
 
+                    // Replace adjustments
+                    purchase_order_adjustment::Entity::delete_many()
+                        .filter(purchase_order_adjustment::Column::PurchaseOrderId.eq(order_id))
+                        .exec(db)
+                        .await?;
+                    if !adjustment_models.is_empty() {
+                        purchase_order_adjustment::Entity::insert_many(adjustment_models)
+                            .exec(db)
+                            .await?;
+                    }
+
+                    // Replace payments
+                    purchase_order_payment::Entity::delete_many()
+                        .filter(purchase_order_payment::Column::PurchaseOrderId.eq(order_id))
+                        .exec(db)
+                        .await?;
+                    if !payment_models.is_empty() {
+                        purchase_order_payment::Entity::insert_many(payment_models)
+                            .exec(db)
+                            .await?;
+                    }
+
+                    // Replace returns
+                    purchase_order_return::Entity::delete_many()
+                        .filter(purchase_order_return::Column::PurchaseOrderId.eq(order_id))
+                        .exec(db)
+                        .await?;
+                    if !return_models.is_empty() {
+                        purchase_order_return::Entity::insert_many(return_models)
+                            .exec(db)
+                            .await?;
+                    }
+
+                    // Replace attachments
+                    purchase_order_attachment::Entity::delete_many()
+                        .filter(purchase_order_attachment::Column::PurchaseOrderId.eq(order_id))
+                        .exec(db)
+                        .await?;
+                    if !attachment_models.is_empty() {
+                        purchase_order_attachment::Entity::insert_many(attachment_models)
+                            .exec(db)
+                            .await?;
+                    }
+
+                    // Replace events
+                    purchase_order_event::Entity::delete_many()
+                        .filter(purchase_order_event::Column::PurchaseOrderId.eq(order_id))
+                        .exec(db)
+                        .await?;
+                    if !event_models.is_empty() {
+                        purchase_order_event::Entity::insert_many(event_models)
+                            .exec(db)
+                            .await?;
+                    }
+
                     Ok(())
                 })
             })
@@ -303,6 +406,31 @@ impl PurchaseOrderRepository for PostgresPurchaseOrderRepository {
                         .filter(purchase_order_item::Column::PurchaseOrderId.eq(id))
                         .exec(db)
                         .await?;
+                    // Delete adjustments based on order_id = id
+                    purchase_order_adjustment::Entity::delete_many()
+                        .filter(purchase_order_adjustment::Column::PurchaseOrderId.eq(id))
+                        .exec(db)
+                        .await?;
+                    // Delete payments based on order_id = id
+                    purchase_order_payment::Entity::delete_many()
+                        .filter(purchase_order_payment::Column::PurchaseOrderId.eq(id))
+                        .exec(db)
+                        .await?;
+                    // Delete returns based on order_id = id
+                    purchase_order_return::Entity::delete_many()
+                        .filter(purchase_order_return::Column::PurchaseOrderId.eq(id))
+                        .exec(db)
+                        .await?;
+                    // Delete attachments based on order_id = id
+                    purchase_order_attachment::Entity::delete_many()
+                        .filter(purchase_order_attachment::Column::PurchaseOrderId.eq(id))
+                        .exec(db)
+                        .await?;
+                    // Delete events based on order_id = id
+                    purchase_order_event::Entity::delete_many()
+                        .filter(purchase_order_event::Column::PurchaseOrderId.eq(id))
+                        .exec(db)
+                        .await?;
                     // Finally delete the order
                     purchase_order::Entity::delete_by_id(id).exec(db).await?;
 
@@ -0,0 +1,430 @@
+use crate::{entities::product_instance::DBProductInstanceStatus, error::DatabaseError};
+use chrono::{DateTime, NaiveDate, NaiveTime, Utc};
+use sawa_core::{
+    errors::RepositoryError,
+    models::{
+        misc::Currency,
+        statistics::{
+            CurrencyAmount, DateRange, MonthlySpending, MysteryBoxStatistics, ProductStatistics,
+            SeriesPurchaseCount, StatusCount, TagStatistics,
+        },
+        user::UserId,
+    },
+    repositories::StatisticsRepository,
+};
+use sea_orm::{FromQueryResult, prelude::*, raw_sql};
+use std::{
+    cmp::Reverse,
+    collections::{HashMap, HashSet, hash_map::Entry},
+    str::FromStr,
+};
+
+pub struct PostgresStatisticsRepository {
+    db: DatabaseConnection,
+}
+
+impl PostgresStatisticsRepository {
+    pub fn new(db: DatabaseConnection) -> Self {
+        Self { db }
+    }
+}
+
+/// The range as the timestamps `[start, end)`, with open ends replaced by far-away days.
+fn bounds(range: &DateRange) -> (DateTime<Utc>, DateTime<Utc>) {
+    let start = range
+        .from
+        .unwrap_or(NaiveDate::from_ymd_opt(1, 1, 1).unwrap());
+    let end = range
+        .to
+        .and_then(|to| to.succ_opt())
+        .unwrap_or(NaiveDate::from_ymd_opt(9999, 12, 31).unwrap());
+    (
+        start.and_time(NaiveTime::MIN).and_utc(),
+        end.and_time(NaiveTime::MIN).and_utc(),
+    )
+}
+
+/// Add a grouped sum to `spent`, skipping the group of instances without a cost.
+fn add_spent(
+    spent: &mut Vec<CurrencyAmount>,
+    currency: Option<String>,
+    amount: Option<i64>,
+) -> Result<(), RepositoryError> {
+    if let (Some(currency), Some(amount)) = (currency, amount) {
+        CurrencyAmount::add_to(spent, Currency::from_str(&currency)?, amount.try_into()?);
+    }
+    Ok(())
+}
+
+/// A count and cost sum of instances grouped by some key and currency.
+#[derive(FromQueryResult)]
+struct GroupedCost {
+    id: Uuid,
+    name: String,
+    currency: Option<String>,
+    count: i64,
+    amount: Option<i64>,
+}
+
+/// The currency groups of one key merged together.
+struct CostTotals {
+    id: Uuid,
+    name: String,
+    count: u64,
+    spent: Vec<CurrencyAmount>,
+}
+
+/// Merge the currency groups of each key, ordered by count, highest first.
+fn merge_grouped_costs(rows: Vec<GroupedCost>) -> Result<Vec<CostTotals>, RepositoryError> {
+    let mut merged: Vec<CostTotals> = Vec::new();
+    let mut positions = HashMap::new();
+    for row in rows {
+        let position = *positions.entry(row.id).or_insert_with(|| {
+            merged.push(CostTotals {
+                id: row.id,
+                name: row.name.clone(),
+                count: 0,
+                spent: vec![],
+            });
+            merged.len() - 1
+        });
+        let totals = &mut merged[position];
+        totals.count += u64::try_from(row.count)?;
+        add_spent(&mut totals.spent, row.currency, row.amount)?;
+    }
+    merged.sort_by(|a, b| {
+        Reverse(a.count)
+            .cmp(&Reverse(b.count))
+            .then_with(|| a.name.cmp(&b.name))
+    });
+    Ok(merged)
+}
+
+impl StatisticsRepository for PostgresStatisticsRepository {
+    async fn monthly_spending(
+        &self,
+        user_id: &UserId,
+        range: &DateRange,
+    ) -> Result<Vec<MonthlySpending>, RepositoryError> {
+        let user_id = Uuid::from(user_id.0);
+        let (start, end) = bounds(range);
+
+        #[derive(FromQueryResult)]
+        struct Data {
+            year: i32,
+            month: i32,
+            currency: String,
+            amount: i64,
+        }
+        let sql = raw_sql!(
+            Postgres,
+            r#"
+            SELECT
+              EXTRACT(YEAR FROM "li"."fulfilled_at" AT TIME ZONE 'UTC')::int4 AS "year",
+              EXTRACT(MONTH FROM "li"."fulfilled_at" AT TIME ZONE 'UTC')::int4 AS "month",
+              "pi"."acquisition_cost_currency" AS "currency",
+              SUM("pi"."acquisition_cost_amount")::int8 AS "amount"
+            FROM
+              "purchase_order_line_items" AS "li"
+              JOIN "product_instance" AS "pi" ON "pi"."id" = "li"."instance_id"
+            WHERE
+              "li"."owner_id" = {user_id}
+              AND "li"."returned_at" IS NULL
+              AND "li"."fulfilled_at" >= {start}
+              AND "li"."fulfilled_at" < {end}
+              AND "pi"."acquisition_cost_currency" IS NOT NULL
+            GROUP BY
+              "year", "month", "currency"
+            ORDER BY
+              "year", "month", "currency""#
+        );
+        Data::find_by_statement(sql)
+            .all(&self.db)
+            .await
+            .map_err(DatabaseError)?
+            .into_iter()
+            .map(|row| {
+                Ok(MonthlySpending {
+                    year: row.year,
+                    month: row.month.try_into()?,
+                    spent: CurrencyAmount {
+                        currency: Currency::from_str(&row.currency)?,
+                        amount: row.amount.try_into()?,
+                    },
+                })
+            })
+            .collect()
+    }
+
+    async fn status_counts(
+        &self,
+        user_id: &UserId,
+        range: &DateRange,
+    ) -> Result<Vec<StatusCount>, RepositoryError> {
+        let user_id = Uuid::from(user_id.0);
+        let (start, end) = bounds(range);
+
+        #[derive(FromQueryResult)]
+        struct Data {
+            status: DBProductInstanceStatus,
+            count: i64,
+        }
+        let sql = raw_sql!(
+            Postgres,
+            r#"
+            SELECT
+              "status",
+              COUNT(*) AS "count"
+            FROM
+              "product_instance"
+            WHERE
+              "owner_id" = {user_id}
+              AND "created_at" >= {start}
+              AND "created_at" < {end}
+            GROUP BY
+              "status""#
+        );
+        Data::find_by_statement(sql)
+            .all(&self.db)
+            .await
+            .map_err(DatabaseError)?
+            .into_iter()
+            .map(|row| {
+                Ok(StatusCount {
+                    status: row.status.into(),
+                    count: row.count.try_into()?,
+                })
+            })
+            .collect()
+    }
+
+    async fn tag_statistics(
+        &self,
+        user_id: &UserId,
+        range: &DateRange,
+    ) -> Result<Vec<TagStatistics>, RepositoryError> {
+        let user_id = Uuid::from(user_id.0);
+        let (start, end) = bounds(range);
+
+        let sql = raw_sql!(
+            Postgres,
+            r#"
+            SELECT
+              "tags"."id",
+              "tags"."name",
+              "pi"."acquisition_cost_currency" AS "currency",
+              COUNT(*) AS "count",
+              SUM("pi"."acquisition_cost_amount")::int8 AS "amount"
+            FROM
+              "product_instance" AS "pi"
+              JOIN "product_variant_tags" ON "product_variant_tags"."product_variant_id" = "pi"."variant_id"
+              JOIN "tags" ON "tags"."id" = "product_variant_tags"."tag_id"
+            WHERE
+              "pi"."owner_id" = {user_id}
+              AND "pi"."status" <> 'returned'
+              AND "pi"."created_at" >= {start}
+              AND "pi"."created_at" < {end}
+            GROUP BY
+              "tags"."id", "tags"."name", "currency""#
+        );
+        let rows = GroupedCost::find_by_statement(sql)
+            .all(&self.db)
+            .await
+            .map_err(DatabaseError)?;
+
+        merge_grouped_costs(rows)?
+            .into_iter()
+            .map(|totals| {
+                Ok(TagStatistics {
+                    tag_id: totals.id.try_into()?,
+                    name: totals.name,
+                    instance_count: totals.count,
+                    spent: totals.spent,
+                })
+            })
+            .collect()
+    }
+
+    async fn product_statistics(
+        &self,
+        user_id: &UserId,
+        range: &DateRange,
+    ) -> Result<Vec<ProductStatistics>, RepositoryError> {
+        let user_id = Uuid::from(user_id.0);
+        let (start, end) = bounds(range);
+
+        let sql = raw_sql!(
+            Postgres,
+            r#"
+            SELECT
+              "products"."id",
+              "products"."name",
+              "pi"."acquisition_cost_currency" AS "currency",
+              COUNT(*) AS "count",
+              SUM("pi"."acquisition_cost_amount")::int8 AS "amount"
+            FROM
+              "product_instance" AS "pi"
+              JOIN "product_variants" ON "product_variants"."id" = "pi"."variant_id"
+              JOIN "products" ON "products"."id" = "product_variants"."product_id"
+            WHERE
+              "pi"."owner_id" = {user_id}
+              AND "pi"."status" <> 'returned'
+              AND "pi"."created_at" >= {start}
+              AND "pi"."created_at" < {end}
+            GROUP BY
+              "products"."id", "products"."name", "currency""#
+        );
+        let rows = GroupedCost::find_by_statement(sql)
+            .all(&self.db)
+            .await
+            .map_err(DatabaseError)?;
+
+        merge_grouped_costs(rows)?
+            .into_iter()
+            .map(|totals| {
+                Ok(ProductStatistics {
+                    product_id: totals.id.try_into()?,
+                    name: totals.name,
+                    instance_count: totals.count,
+                    spent: totals.spent,
+                })
+            })
+            .collect()
+    }
+
+    async fn most_bought_series(
+        &self,
+        user_id: &UserId,
+        range: &DateRange,
+        limit: usize,
+    ) -> Result<Vec<SeriesPurchaseCount>, RepositoryError> {
+        let user_id = Uuid::from(user_id.0);
+        let (start, end) = bounds(range);
+        let limit = i64::try_from(limit)?;
+
+        #[derive(FromQueryResult)]
+        struct Data {
+            id: Uuid,
+            name: String,
+            count: i64,
+        }
+        let sql = raw_sql!(
+            Postgres,
+            r#"
+            SELECT
+              "products"."id",
+              "products"."name",
+              COUNT(*) AS "count"
+            FROM
+              "purchase_order_line_items" AS "li"
+              JOIN "product_variants" ON "product_variants"."id" = "li"."variant_id"
+              JOIN "products" ON "products"."id" = "product_variants"."product_id"
+            WHERE
+              "li"."owner_id" = {user_id}
+              AND "li"."returned_at" IS NULL
+              AND "li"."fulfilled_at" >= {start}
+              AND "li"."fulfilled_at" < {end}
+            GROUP BY
+              "products"."id", "products"."name"
+            ORDER BY
+              "count" DESC, "products"."name"
+            LIMIT {limit}"#
+        );
+        Data::find_by_statement(sql)
+            .all(&self.db)
+            .await
+            .map_err(DatabaseError)?
+            .into_iter()
+            .map(|row| {
+                Ok(SeriesPurchaseCount {
+                    product_id: row.id.try_into()?,
+                    name: row.name,
+                    purchased_count: row.count.try_into()?,
+                })
+            })
+            .collect()
+    }
+
+    async fn mystery_box_statistics(
+        &self,
+        user_id: &UserId,
+        range: &DateRange,
+    ) -> Result<Vec<MysteryBoxStatistics>, RepositoryError> {
+        let user_id = Uuid::from(user_id.0);
+        let (start, end) = bounds(range);
+
+        #[derive(FromQueryResult)]
+        struct Data {
+            id: Uuid,
+            name: String,
+            obtained_variant_id: Uuid,
+            currency: Option<String>,
+            count: i64,
+            amount: Option<i64>,
+        }
+        let sql = raw_sql!(
+            Postgres,
+            r#"
+            SELECT
+              "product_variants"."id",
+              "product_variants"."name",
+              "li"."variant_id" AS "obtained_variant_id",
+              "pi"."acquisition_cost_currency" AS "currency",
+              COUNT(*) AS "count",
+              SUM("pi"."acquisition_cost_amount")::int8 AS "amount"
+            FROM
+              "purchase_order_line_items" AS "li"
+              JOIN "purchase_order_items" AS "poi" ON "poi"."id" = "li"."purchase_order_item_id"
+              JOIN "product_variants" ON "product_variants"."id" = "poi"."purchased_variant_id"
+              LEFT JOIN "product_instance" AS "pi" ON "pi"."id" = "li"."instance_id"
+            WHERE
+              "li"."owner_id" = {user_id}
+              AND "li"."returned_at" IS NULL
+              AND "li"."fulfilled_at" >= {start}
+              AND "li"."fulfilled_at" < {end}
+              AND "product_variants"."mystery_box" IS NOT NULL
+            GROUP BY
+              "product_variants"."id", "product_variants"."name", "li"."variant_id", "currency""#
+        );
+        let rows = Data::find_by_statement(sql)
+            .all(&self.db)
+            .await
+            .map_err(DatabaseError)?;
+
+        let mut by_box: HashMap<Uuid, MysteryBoxStatistics> = HashMap::new();
+        let mut obtained: HashMap<Uuid, HashSet<Uuid>> = HashMap::new();
+        for row in rows {
+            let entry = match by_box.entry(row.id) {
+                Entry::Occupied(entry) => entry.into_mut(),
+                Entry::Vacant(entry) => entry.insert(MysteryBoxStatistics {
+                    variant_id: row.id.try_into()?,
+                    name: row.name,
+                    items_obtained: 0,
+                    distinct_variants_obtained: 0,
+                    spent: vec![],
+                    average_cost_per_variant: vec![],
+                }),
+            };
+            entry.items_obtained += u64::try_from(row.count)?;
+            add_spent(&mut entry.spent, row.currency, row.amount)?;
+            obtained
+                .entry(row.id)
+                .or_default()
+                .insert(row.obtained_variant_id);
+        }
+
+        let mut statistics: Vec<_> = by_box
+            .into_iter()
+            .map(|(id, mut entry)| {
+                entry.distinct_variants_obtained = obtained[&id].len() as u64;
+                entry.with_averages()
+            })
+            .collect();
+        statistics.sort_by(|a, b| {
+            Reverse(a.items_obtained)
+                .cmp(&Reverse(b.items_obtained))
+                .then_with(|| a.name.cmp(&b.name))
+        });
+        Ok(statistics)
+    }
+}
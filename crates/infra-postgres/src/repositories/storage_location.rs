@@ -0,0 +1,81 @@
+use crate::{
+    entities::storage_location::{Column, Entity},
+    error::DatabaseError,
+    traits::TryIntoDomainModelSimple,
+};
+use sawa_core::{
+    errors::RepositoryError,
+    models::{
+        storage::{StorageLocation, StorageLocationId},
+        user::UserId,
+    },
+    repositories::StorageLocationRepository,
+};
+use sea_orm::{QueryFilter, prelude::*, sea_query::OnConflict};
+
+pub struct PostgresStorageLocationRepository {
+    db: DatabaseConnection,
+}
+
+impl PostgresStorageLocationRepository {
+    pub fn new(db: DatabaseConnection) -> Self {
+        Self { db }
+    }
+}
+
+impl StorageLocationRepository for PostgresStorageLocationRepository {
+    async fn find_by_id(
+        &self,
+        id: &StorageLocationId,
+    ) -> Result<Option<StorageLocation>, RepositoryError> {
+        let entity = Entity::find_by_id(Uuid::from(id.0))
+            .one(&self.db)
+            .await
+            .map_err(DatabaseError)?;
+
+        entity
+            .map(TryIntoDomainModelSimple::try_into_domain_model_simple)
+            .transpose()
+    }
+
+    async fn find_by_user(
+        &self,
+        user_id: &UserId,
+    ) -> Result<Vec<StorageLocation>, RepositoryError> {
+        let entities = Entity::find()
+            .filter(Column::UserId.eq(Uuid::from(user_id.0)))
+            .all(&self.db)
+            .await
+            .map_err(DatabaseError)?;
+
+        entities
+            .into_iter()
+            .map(TryIntoDomainModelSimple::try_into_domain_model_simple)
+            .collect()
+    }
+
+    async fn save(&self, location: &StorageLocation) -> Result<(), RepositoryError> {
+        let active_model: crate::entities::storage_location::ActiveModel = location.into();
+
+        Entity::insert(active_model)
+            .on_conflict(
+                OnConflict::column(Column::Id)
+                    .update_columns([Column::Name, Column::ParentId])
+                    .to_owned(),
+            )
+            .exec(&self.db)
+            .await
+            .map_err(DatabaseError)?;
+
+        Ok(())
+    }
+
+    async fn delete(&self, id: &StorageLocationId) -> Result<(), RepositoryError> {
+        Entity::delete_by_id(Uuid::from(id.0))
+            .exec(&self.db)
+            .await
+            .map_err(DatabaseError)?;
+
+        Ok(())
+    }
+}
@@ -49,6 +49,45 @@ impl UserRepository for PostgresUserRepository {
         entity.map(|e| e.try_into()).transpose()
     }
 
+    async fn find_by_invite_code(
+        &self,
+        invite_code: &str,
+    ) -> Result<Option<User>, RepositoryError> {
+        let entity = Entity::find()
+            .filter(Column::InviteCode.eq(invite_code))
+            .one(&self.db)
+            .await
+            .map_err(DatabaseError)?;
+
+        entity.map(|e| e.try_into()).transpose()
+    }
+
+    async fn find_by_calendar_token(
+        &self,
+        calendar_token: &str,
+    ) -> Result<Option<User>, RepositoryError> {
+        let entity = Entity::find()
+            .filter(Column::CalendarToken.eq(calendar_token))
+            .one(&self.db)
+            .await
+            .map_err(DatabaseError)?;
+
+        entity.map(|e| e.try_into()).transpose()
+    }
+
+    async fn find_placeholders_by_manager(
+        &self,
+        manager_id: &UserId,
+    ) -> Result<Vec<User>, RepositoryError> {
+        let entities = Entity::find()
+            .filter(Column::ManagerId.eq(Uuid::from(manager_id.0)))
+            .all(&self.db)
+            .await
+            .map_err(DatabaseError)?;
+
+        entities.into_iter().map(|e| e.try_into()).collect()
+    }
+
     async fn create(&self, user: User) -> Result<User, RepositoryError> {
         let active_model: crate::entities::user::ActiveModel = user.into();
 
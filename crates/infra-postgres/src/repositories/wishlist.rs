@@ -0,0 +1,111 @@
+use crate::{
+    entities::wishlist, error::DatabaseError, traits::TryIntoDomainModelSimple, wishlist_item,
+};
+use sawa_core::{
+    errors::RepositoryError,
+    models::{user::UserId, wishlist::Wishlist},
+    repositories::WishlistRepository,
+};
+use sea_orm::{QueryFilter, TransactionTrait, prelude::*, sea_query::OnConflict};
+
+pub struct PostgresWishlistRepository {
+    db: DatabaseConnection,
+}
+
+impl PostgresWishlistRepository {
+    pub fn new(db: DatabaseConnection) -> Self {
+        Self { db }
+    }
+}
+
+impl WishlistRepository for PostgresWishlistRepository {
+    async fn find_by_user(&self, user_id: &UserId) -> Result<Option<Wishlist>, RepositoryError> {
+        let entity = wishlist::Entity::load()
+            .filter(wishlist::Column::UserId.eq(Uuid::from(user_id.0)))
+            .with(wishlist_item::Entity)
+            .one(&self.db)
+            .await
+            .map_err(DatabaseError)?;
+
+        entity
+            .map(TryIntoDomainModelSimple::try_into_domain_model_simple)
+            .transpose()
+    }
+
+    async fn find_open_to_trades(&self) -> Result<Vec<Wishlist>, RepositoryError> {
+        let entities = wishlist::Entity::load()
+            .filter(wishlist::Column::OpenToTrades.eq(true))
+            .with(wishlist_item::Entity)
+            .all(&self.db)
+            .await
+            .map_err(DatabaseError)?;
+
+        entities
+            .into_iter()
+            .map(TryIntoDomainModelSimple::try_into_domain_model_simple)
+            .collect()
+    }
+
+    async fn save(&self, wishlist: &Wishlist) -> Result<(), RepositoryError> {
+        let user_id = Uuid::from(wishlist.user_id.0);
+        let wishlist_active_model: wishlist::ActiveModel = wishlist.into();
+
+        // Prepare item data outside the closure
+        let item_models: Vec<wishlist_item::ActiveModel> = wishlist
+            .items
+            .iter()
+            .map(|item| (item, user_id).into())
+            .collect();
+
+        self.db
+            .transaction(|db| {
+                Box::pin(async move {
+                    // Save or update the wishlist
+                    wishlist::Entity::insert(wishlist_active_model)
+                        .on_conflict(
+                            OnConflict::column(wishlist::Column::UserId)
+                                .update_columns([
+                                    wishlist::Column::IsPublic,
+                                    wishlist::Column::OpenToTrades,
+                                    wishlist::Column::UpdatedAt,
+                                ])
+                                .to_owned(),
+                        )
+                        .exec(db)
+                        .await?;
+
+                    // Replace items
+                    wishlist_item::Entity::delete_many()
+                        .filter(wishlist_item::Column::WishlistUserId.eq(user_id))
+                        .exec(db)
+                        .await?;
+
+                    if !item_models.is_empty() {
+                        wishlist_item::Entity::insert_many(item_models)
+                            .exec(db)
+                            .await?;
+                    }
+
+                    Ok(())
+                })
+            })
+            .await
+            .map_err(DatabaseError::from)?;
+
+        Ok(())
+    }
+
+    async fn delete(&self, user_id: &UserId) -> Result<(), RepositoryError> {
+        wishlist::Entity::delete_by_id(Uuid::from(user_id.0))
+            .exec(&self.db)
+            .await
+            .map_err(DatabaseError)?;
+        wishlist_item::Entity::delete_many()
+            .filter(wishlist_item::Column::WishlistUserId.eq(Uuid::from(user_id.0)))
+            .exec(&self.db)
+            .await
+            .map_err(DatabaseError)?;
+
+        Ok(())
+    }
+}
@@ -30,4 +30,7 @@ test_all_repositories! {
     user_transaction => PostgresUserTransactionRepository::new(create_test_db().await),
     media => PostgresMediaRepository::new(create_test_db().await),
     tag => PostgresTagRepository::new(create_test_db().await),
+    cart => PostgresCartRepository::new(create_test_db().await),
+    storage_location => PostgresStorageLocationRepository::new(create_test_db().await),
+    wishlist => PostgresWishlistRepository::new(create_test_db().await),
 }
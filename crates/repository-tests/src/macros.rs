@@ -16,6 +16,9 @@
 ///     user_transaction => InMemoryUserTransactionRepository::new(),
 ///     media => InMemoryMediaRepository::new(),
 ///     tag => InMemoryTagRepository::new(),
+///     cart => InMemoryCartRepository::new(),
+///     storage_location => InMemoryStorageLocationRepository::new(),
+///     wishlist => InMemoryWishlistRepository::new(),
 /// }
 /// ```
 #[macro_export]
@@ -28,7 +31,10 @@ macro_rules! test_all_repositories {
         user => $user_repo:expr,
         user_transaction => $transaction_repo:expr,
         media => $media_repo:expr,
-        tag => $tag_repo:expr $(,)?
+        tag => $tag_repo:expr,
+        cart => $cart_repo:expr,
+        storage_location => $location_repo:expr,
+        wishlist => $wishlist_repo:expr $(,)?
     ) => {
         use sawa_repository_tests::tokio;
 
@@ -159,6 +165,36 @@ macro_rules! test_all_repositories {
                 $crate::suites::purchase_order::test_save_and_find_by_id(repo).await;
             }
 
+            #[$crate::tokio::test]
+            async fn save_with_adjustments() {
+                let repo = $order_repo;
+                $crate::suites::purchase_order::test_save_with_adjustments(repo).await;
+            }
+
+            #[$crate::tokio::test]
+            async fn save_with_payments() {
+                let repo = $order_repo;
+                $crate::suites::purchase_order::test_save_with_payments(repo).await;
+            }
+
+            #[$crate::tokio::test]
+            async fn save_with_events() {
+                let repo = $order_repo;
+                $crate::suites::purchase_order::test_save_with_events(repo).await;
+            }
+
+            #[$crate::tokio::test]
+            async fn save_with_schedule() {
+                let repo = $order_repo;
+                $crate::suites::purchase_order::test_save_with_schedule(repo).await;
+            }
+
+            #[$crate::tokio::test]
+            async fn save_with_attachments() {
+                let repo = $order_repo;
+                $crate::suites::purchase_order::test_save_with_attachments(repo).await;
+            }
+
             #[$crate::tokio::test]
             async fn find_by_user_without_status_filter() {
                 let repo = $order_repo;
@@ -253,6 +289,18 @@ macro_rules! test_all_repositories {
                 let repo = $user_repo;
                 $crate::suites::user::test_delete(repo).await;
             }
+
+            #[$crate::tokio::test]
+            async fn find_placeholders() {
+                let repo = $user_repo;
+                $crate::suites::user::test_find_placeholders(repo).await;
+            }
+
+            #[$crate::tokio::test]
+            async fn find_by_calendar_token() {
+                let repo = $user_repo;
+                $crate::suites::user::test_find_by_calendar_token(repo).await;
+            }
         }
 
         mod user_transaction_repository_tests {
@@ -356,5 +404,59 @@ macro_rules! test_all_repositories {
                 $crate::suites::tag::test_delete(repo).await;
             }
         }
+
+        mod cart_repository_tests {
+            use super::*;
+
+            #[$crate::tokio::test]
+            async fn save_and_find_by_user() {
+                let repo = $cart_repo;
+                $crate::suites::cart::test_save_and_find_by_user(repo).await;
+            }
+
+            #[$crate::tokio::test]
+            async fn delete() {
+                let repo = $cart_repo;
+                $crate::suites::cart::test_delete(repo).await;
+            }
+        }
+
+        mod storage_location_repository_tests {
+            use super::*;
+
+            #[$crate::tokio::test]
+            async fn save_and_find_by_id() {
+                let repo = $location_repo;
+                $crate::suites::storage_location::test_save_and_find_by_id(repo).await;
+            }
+
+            #[$crate::tokio::test]
+            async fn find_by_user() {
+                let repo = $location_repo;
+                $crate::suites::storage_location::test_find_by_user(repo).await;
+            }
+        }
+
+        mod wishlist_repository_tests {
+            use super::*;
+
+            #[$crate::tokio::test]
+            async fn save_and_find_by_user() {
+                let repo = $wishlist_repo;
+                $crate::suites::wishlist::test_save_and_find_by_user(repo).await;
+            }
+
+            #[$crate::tokio::test]
+            async fn delete() {
+                let repo = $wishlist_repo;
+                $crate::suites::wishlist::test_delete(repo).await;
+            }
+
+            #[$crate::tokio::test]
+            async fn find_open_to_trades() {
+                let repo = $wishlist_repo;
+                $crate::suites::wishlist::test_find_open_to_trades(repo).await;
+            }
+        }
     };
 }
@@ -1,10 +1,13 @@
 //! Test suites for each repository
 
+pub mod cart;
 pub mod media;
 pub mod product;
 pub mod product_instance;
 pub mod product_variant;
 pub mod purchase_order;
+pub mod storage_location;
 pub mod tag;
 pub mod user;
 pub mod user_transaction;
+pub mod wishlist;
@@ -0,0 +1,68 @@
+use chrono::Utc;
+use sawa_core::{
+    models::{
+        cart::{Cart, CartLine, CartLineId},
+        misc::{Currency, Price},
+        product::ProductVariantId,
+        user::UserId,
+    },
+    repositories::CartRepository,
+};
+use std::num::NonZeroU32;
+
+fn create_test_line(quantity: u32) -> CartLine {
+    CartLine {
+        id: CartLineId::new(),
+        variant_id: ProductVariantId::new(),
+        quantity: NonZeroU32::new(quantity).unwrap(),
+        owner_id: None,
+        unit_price: Some(Price {
+            currency: Currency::JPY,
+            amount: 1000,
+        }),
+        added_at: Utc::now(),
+    }
+}
+
+/// Test save and find_by_user, including replacing the lines.
+pub async fn test_save_and_find_by_user<R: CartRepository>(repo: R) {
+    let user_id = UserId::new();
+    assert!(repo.find_by_user(&user_id).await.unwrap().is_none());
+
+    let mut cart = Cart::empty(user_id);
+    cart.lines.push(create_test_line(1));
+    cart.lines.push(create_test_line(2));
+    repo.save(&cart).await.unwrap();
+
+    let found = repo.find_by_user(&user_id).await.unwrap().unwrap();
+    assert_eq!(found.user_id, user_id);
+    assert_eq!(found.lines.len(), 2);
+    assert_eq!(found.lines[0].id, cart.lines[0].id);
+    assert_eq!(found.lines[1].quantity.get(), 2);
+    assert_eq!(found.lines[1].unit_price.unwrap().amount, 1000);
+
+    // Saving again replaces the lines
+    let mut owned = create_test_line(3);
+    owned.owner_id = Some(UserId::new());
+    cart.lines = vec![owned.clone()];
+    repo.save(&cart).await.unwrap();
+
+    let found = repo.find_by_user(&user_id).await.unwrap().unwrap();
+    assert_eq!(found.lines.len(), 1);
+    assert_eq!(found.lines[0].id, owned.id);
+    assert_eq!(found.lines[0].owner_id, owned.owner_id);
+
+    // Clean up
+    repo.delete(&user_id).await.unwrap();
+}
+
+/// Test delete removes the cart and its lines.
+pub async fn test_delete<R: CartRepository>(repo: R) {
+    let user_id = UserId::new();
+    let mut cart = Cart::empty(user_id);
+    cart.lines.push(create_test_line(1));
+    repo.save(&cart).await.unwrap();
+
+    repo.delete(&user_id).await.unwrap();
+    assert!(repo.find_by_user(&user_id).await.unwrap().is_none());
+}
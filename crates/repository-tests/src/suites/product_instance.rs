@@ -14,9 +14,14 @@ fn create_test_instance(owner_id: UserId, variant_id: ProductVariantId) -> Produ
         variant_id,
         owner_id,
         holder_id: owner_id,
+        location_id: None,
         status: ProductInstanceStatus::Active,
+        condition: None,
+        note: String::new(),
+        medias: vec![],
         source_order_line_item_id: PurchaseOrderLineItemId::new(),
         created_at: chrono::Utc::now(),
+        acquisition_cost: None,
         transfer_history: vec![],
         status_history: vec![],
     }
@@ -1,11 +1,16 @@
+use chrono::NaiveDate;
 use sawa_core::{
     models::{
-        misc::{Currency, Price},
+        misc::{Currency, MediaId, Price},
         product::ProductVariantId,
         purchase::{
-            OrderRoleFilter, PurchaseOrder, PurchaseOrderId, PurchaseOrderItem,
+            AdjustmentAllocation, ManualAllocation, OrderRoleFilter, PurchaseOrder,
+            PurchaseOrderAdjustment, PurchaseOrderAdjustmentId, PurchaseOrderAdjustmentKind,
+            PurchaseOrderAttachment, PurchaseOrderAttachmentId, PurchaseOrderAttachmentKind,
+            PurchaseOrderEvent, PurchaseOrderEventKind, PurchaseOrderId, PurchaseOrderItem,
             PurchaseOrderItemId, PurchaseOrderItemStatus, PurchaseOrderLineItem,
-            PurchaseOrderStatus,
+            PurchaseOrderPayment, PurchaseOrderPaymentId, PurchaseOrderSchedule,
+            PurchaseOrderStatus, ShipmentTracking,
         },
         user::UserId,
     },
@@ -29,7 +34,13 @@ fn create_test_order(
         creator_id,
         receiver_id,
         items: vec![],
+        adjustments: vec![],
+        payments: vec![],
+        returns: vec![],
+        attachments: vec![],
+        events: vec![],
         shipping_address: None,
+        schedule: PurchaseOrderSchedule::default(),
         total_price: Price {
             currency: Currency::USD,
             amount: 1000,
@@ -91,6 +102,270 @@ pub async fn test_save_and_find_by_id<R: PurchaseOrderRepository>(repo: R) {
     repo.delete(&order_id).await.unwrap();
 }
 
+/// Test adjustments are saved and replaced along with the order.
+pub async fn test_save_with_adjustments<R: PurchaseOrderRepository>(repo: R) {
+    let mut order = create_test_order(
+        UserId::new(),
+        UserId::new(),
+        PurchaseOrderStatus::Incomplete,
+    );
+
+    let variant_id = ProductVariantId::new();
+    let item_id = PurchaseOrderItemId::new();
+    let line_item = PurchaseOrderLineItem::new(variant_id, item_id, order.creator_id);
+    let line_item_id = line_item.id;
+
+    order.items.push(PurchaseOrderItem {
+        id: item_id,
+        purchased_variant_id: variant_id,
+        line_items: vec![line_item],
+        status: PurchaseOrderItemStatus::Pending,
+        quantity: NonZeroU32::new(1).unwrap(),
+        unit_price: None,
+    });
+
+    let shipping = PurchaseOrderAdjustment {
+        id: PurchaseOrderAdjustmentId::new(),
+        kind: PurchaseOrderAdjustmentKind::InternationalShipping,
+        amount: Price {
+            currency: Currency::USD,
+            amount: 300,
+        },
+        allocation: AdjustmentAllocation::ByCount,
+        note: Some("EMS".to_string()),
+    };
+    let discount = PurchaseOrderAdjustment {
+        id: PurchaseOrderAdjustmentId::new(),
+        kind: PurchaseOrderAdjustmentKind::Discount,
+        amount: Price {
+            currency: Currency::USD,
+            amount: 100,
+        },
+        allocation: AdjustmentAllocation::Manual {
+            allocations: vec![ManualAllocation {
+                line_item_id,
+                amount: 100,
+            }],
+        },
+        note: None,
+    };
+    order.adjustments = vec![shipping.clone(), discount.clone()];
+    let order_id = order.id;
+
+    repo.save(&order).await.unwrap();
+
+    let found = repo
+        .find_by_id(&order_id, &order.creator_id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(found.adjustments.len(), 2);
+
+    let found_shipping = found
+        .adjustments
+        .iter()
+        .find(|a| a.id == shipping.id)
+        .unwrap();
+    assert_eq!(
+        found_shipping.kind,
+        PurchaseOrderAdjustmentKind::InternationalShipping
+    );
+    assert_eq!(found_shipping.amount.amount, 300);
+    assert_eq!(found_shipping.allocation, AdjustmentAllocation::ByCount);
+    assert_eq!(found_shipping.note.as_deref(), Some("EMS"));
+
+    let found_discount = found
+        .adjustments
+        .iter()
+        .find(|a| a.id == discount.id)
+        .unwrap();
+    assert_eq!(found_discount.allocation, discount.allocation);
+
+    // Removing an adjustment replaces the stored set
+    order.adjustments = vec![shipping.clone()];
+    repo.save(&order).await.unwrap();
+
+    let found = repo
+        .find_by_id(&order_id, &order.creator_id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(found.adjustments.len(), 1);
+    assert_eq!(found.adjustments[0].id, shipping.id);
+
+    // Clean up
+    repo.delete(&order_id).await.unwrap();
+}
+
+/// Test saving and loading an order with payments.
+pub async fn test_save_with_payments<R: PurchaseOrderRepository>(repo: R) {
+    let mut order = create_test_order(
+        UserId::new(),
+        UserId::new(),
+        PurchaseOrderStatus::Incomplete,
+    );
+
+    let payer_id = UserId::new();
+    let payment = PurchaseOrderPayment {
+        id: PurchaseOrderPaymentId::new(),
+        payer_id,
+        amount: Price {
+            currency: Currency::USD,
+            amount: 500,
+        },
+        note: Some("Bank transfer".to_string()),
+        paid_at: chrono::Utc::now(),
+    };
+    order.payments = vec![payment.clone()];
+    let order_id = order.id;
+
+    repo.save(&order).await.unwrap();
+
+    let found = repo
+        .find_by_id(&order_id, &order.creator_id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(found.payments.len(), 1);
+    assert_eq!(found.payments[0].id, payment.id);
+    assert_eq!(found.payments[0].payer_id, payer_id);
+    assert_eq!(found.payments[0].amount.amount, 500);
+    assert_eq!(found.payments[0].note.as_deref(), Some("Bank transfer"));
+
+    // Removing a payment replaces the stored set
+    order.payments = vec![];
+    repo.save(&order).await.unwrap();
+
+    let found = repo
+        .find_by_id(&order_id, &order.creator_id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(found.payments.is_empty());
+
+    // Clean up
+    repo.delete(&order_id).await.unwrap();
+}
+
+/// Test that the event log is persisted in order.
+pub async fn test_save_with_events<R: PurchaseOrderRepository>(repo: R) {
+    let mut order = create_test_order(
+        UserId::new(),
+        UserId::new(),
+        PurchaseOrderStatus::Incomplete,
+    );
+    let order_id = order.id;
+
+    order.events = vec![PurchaseOrderEvent::new(
+        order.creator_id,
+        PurchaseOrderEventKind::Created,
+    )];
+    repo.save(&order).await.unwrap();
+
+    // Appending keeps earlier events
+    order.events.push(PurchaseOrderEvent::new(
+        order.creator_id,
+        PurchaseOrderEventKind::Cancelled {
+            reason: Some("Out of stock".to_string()),
+        },
+    ));
+    repo.save(&order).await.unwrap();
+
+    let found = repo
+        .find_by_id(&order_id, &order.creator_id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(found.events.len(), 2);
+    assert_eq!(found.events[0].id, order.events[0].id);
+    assert_eq!(found.events[0].kind, PurchaseOrderEventKind::Created);
+    assert_eq!(found.events[1].actor_id, order.creator_id);
+    assert_eq!(
+        found.events[1].kind,
+        PurchaseOrderEventKind::Cancelled {
+            reason: Some("Out of stock".to_string()),
+        }
+    );
+
+    // Clean up
+    repo.delete(&order_id).await.unwrap();
+}
+
+/// Test that the schedule and shipment tracking are persisted.
+pub async fn test_save_with_schedule<R: PurchaseOrderRepository>(repo: R) {
+    let mut order = create_test_order(
+        UserId::new(),
+        UserId::new(),
+        PurchaseOrderStatus::Incomplete,
+    );
+    let order_id = order.id;
+
+    order.schedule = PurchaseOrderSchedule {
+        expected_release_date: NaiveDate::from_ymd_opt(2025, 3, 1),
+        expected_ship_date: None,
+        expected_arrival_date: NaiveDate::from_ymd_opt(2025, 3, 14),
+        tracking: Some(ShipmentTracking {
+            carrier: "Yamato".to_string(),
+            tracking_number: "1234-5678-9012".to_string(),
+        }),
+    };
+    repo.save(&order).await.unwrap();
+
+    let found = repo
+        .find_by_id(&order_id, &order.creator_id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(found.schedule, order.schedule);
+
+    // Clean up
+    repo.delete(&order_id).await.unwrap();
+}
+
+/// Test that attachments are persisted.
+pub async fn test_save_with_attachments<R: PurchaseOrderRepository>(repo: R) {
+    let mut order = create_test_order(UserId::new(), UserId::new(), PurchaseOrderStatus::Fulfilled);
+    let order_id = order.id;
+
+    let attachment = PurchaseOrderAttachment {
+        id: PurchaseOrderAttachmentId::new(),
+        media_id: MediaId::new(),
+        kind: PurchaseOrderAttachmentKind::Invoice,
+        note: Some("Customs invoice".to_string()),
+        uploader_id: order.creator_id,
+        created_at: chrono::Utc::now(),
+    };
+    order.attachments = vec![attachment.clone()];
+    repo.save(&order).await.unwrap();
+
+    let found = repo
+        .find_by_id(&order_id, &order.creator_id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(found.attachments.len(), 1);
+    assert_eq!(found.attachments[0].id, attachment.id);
+    assert_eq!(found.attachments[0].media_id, attachment.media_id);
+    assert_eq!(
+        found.attachments[0].kind,
+        PurchaseOrderAttachmentKind::Invoice
+    );
+    assert_eq!(found.attachments[0].note, attachment.note);
+
+    // Removing an attachment is persisted
+    order.attachments.clear();
+    repo.save(&order).await.unwrap();
+    let found = repo
+        .find_by_id(&order_id, &order.creator_id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(found.attachments.is_empty());
+
+    // Clean up
+    repo.delete(&order_id).await.unwrap();
+}
+
 /// Test find_by_user without status filter returns all statuses.
 pub async fn test_find_by_user_without_status_filter<R: PurchaseOrderRepository>(repo: R) {
     let user_id = UserId::new();
@@ -176,7 +451,13 @@ pub async fn test_find_by_user_permission_isolation<R: PurchaseOrderRepository>(
         creator_id: user_a,
         receiver_id: user_a,
         items: vec![],
+        adjustments: vec![],
+        payments: vec![],
+        returns: vec![],
+        attachments: vec![],
+        events: vec![],
         shipping_address: None,
+        schedule: PurchaseOrderSchedule::default(),
         total_price: Price {
             currency: Currency::USD,
             amount: 1000,
@@ -192,7 +473,13 @@ pub async fn test_find_by_user_permission_isolation<R: PurchaseOrderRepository>(
         creator_id: user_a,
         receiver_id: user_a,
         items: vec![],
+        adjustments: vec![],
+        payments: vec![],
+        returns: vec![],
+        attachments: vec![],
+        events: vec![],
         shipping_address: None,
+        schedule: PurchaseOrderSchedule::default(),
         total_price: Price {
             currency: Currency::USD,
             amount: 2000,
@@ -208,7 +495,13 @@ pub async fn test_find_by_user_permission_isolation<R: PurchaseOrderRepository>(
         creator_id: user_b,
         receiver_id: user_b,
         items: vec![],
+        adjustments: vec![],
+        payments: vec![],
+        returns: vec![],
+        attachments: vec![],
+        events: vec![],
         shipping_address: None,
+        schedule: PurchaseOrderSchedule::default(),
         total_price: Price {
             currency: Currency::USD,
             amount: 500,
@@ -264,7 +557,13 @@ pub async fn test_find_by_user_and_status_permission<R: PurchaseOrderRepository>
         creator_id: user_a,
         receiver_id: user_a,
         items: vec![],
+        adjustments: vec![],
+        payments: vec![],
+        returns: vec![],
+        attachments: vec![],
+        events: vec![],
         shipping_address: None,
+        schedule: PurchaseOrderSchedule::default(),
         total_price: Price {
             currency: Currency::USD,
             amount: 1000,
@@ -280,7 +579,13 @@ pub async fn test_find_by_user_and_status_permission<R: PurchaseOrderRepository>
         creator_id: user_b,
         receiver_id: user_b,
         items: vec![],
+        adjustments: vec![],
+        payments: vec![],
+        returns: vec![],
+        attachments: vec![],
+        events: vec![],
         shipping_address: None,
+        schedule: PurchaseOrderSchedule::default(),
         total_price: Price {
             currency: Currency::USD,
             amount: 1000,
@@ -345,7 +650,13 @@ pub async fn test_find_by_id_access_control<R: PurchaseOrderRepository>(repo: R)
         creator_id: creator,
         receiver_id: receiver,
         items: vec![item],
+        adjustments: vec![],
+        payments: vec![],
+        returns: vec![],
+        attachments: vec![],
+        events: vec![],
         shipping_address: None,
+        schedule: PurchaseOrderSchedule::default(),
         total_price: Price {
             currency: Currency::USD,
             amount: 1000,
@@ -422,7 +733,13 @@ pub async fn test_find_by_user_role_filter<R: PurchaseOrderRepository>(repo: R)
         creator_id: creator,
         receiver_id: receiver,
         items: vec![item],
+        adjustments: vec![],
+        payments: vec![],
+        returns: vec![],
+        attachments: vec![],
+        events: vec![],
         shipping_address: None,
+        schedule: PurchaseOrderSchedule::default(),
         total_price: Price {
             currency: Currency::USD,
             amount: 1000,
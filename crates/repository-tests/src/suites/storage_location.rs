@@ -0,0 +1,61 @@
+use sawa_core::{
+    models::{misc::NonEmptyString, storage::StorageLocation, user::UserId},
+    repositories::StorageLocationRepository,
+};
+
+fn create_test_location(user_id: UserId, name: &str) -> StorageLocation {
+    StorageLocation::new(
+        user_id,
+        NonEmptyString::new(name.to_string()).unwrap(),
+        None,
+    )
+}
+
+/// Test save and find_by_id, including updating a location.
+pub async fn test_save_and_find_by_id<R: StorageLocationRepository>(repo: R) {
+    let room = create_test_location(UserId::new(), "Room");
+    repo.save(&room).await.unwrap();
+
+    let mut shelf = create_test_location(room.user_id, "Shelf");
+    shelf.parent_id = Some(room.id);
+    repo.save(&shelf).await.unwrap();
+
+    let found = repo.find_by_id(&shelf.id).await.unwrap().unwrap();
+    assert_eq!(found.name.as_str(), "Shelf");
+    assert_eq!(found.parent_id, Some(room.id));
+
+    // Saving again updates the location
+    shelf.name = NonEmptyString::new("Top shelf".to_string()).unwrap();
+    shelf.parent_id = None;
+    repo.save(&shelf).await.unwrap();
+
+    let found = repo.find_by_id(&shelf.id).await.unwrap().unwrap();
+    assert_eq!(found.name.as_str(), "Top shelf");
+    assert_eq!(found.parent_id, None);
+
+    // Clean up
+    repo.delete(&shelf.id).await.unwrap();
+    repo.delete(&room.id).await.unwrap();
+}
+
+/// Test find_by_user only returns the user's locations.
+pub async fn test_find_by_user<R: StorageLocationRepository>(repo: R) {
+    let user_id = UserId::new();
+    let room = create_test_location(user_id, "Room");
+    let shelf = create_test_location(user_id, "Shelf");
+    let other = create_test_location(UserId::new(), "Other");
+    repo.save(&room).await.unwrap();
+    repo.save(&shelf).await.unwrap();
+    repo.save(&other).await.unwrap();
+
+    let found = repo.find_by_user(&user_id).await.unwrap();
+    assert_eq!(found.len(), 2);
+    assert!(found.iter().all(|location| location.user_id == user_id));
+
+    // Clean up
+    repo.delete(&room.id).await.unwrap();
+    repo.delete(&shelf.id).await.unwrap();
+    repo.delete(&other.id).await.unwrap();
+
+    assert!(repo.find_by_user(&user_id).await.unwrap().is_empty());
+}
@@ -1,5 +1,5 @@
 use sawa_core::{
-    models::user::{Email, User, UserId, Username},
+    models::user::{Email, Placeholder, User, UserId, Username},
     repositories::UserRepository,
 };
 
@@ -10,6 +10,8 @@ fn create_test_user(username: &str, email: &str) -> User {
         email: Email(email.to_string()),
         password_hash: "hash".try_into().unwrap(),
         avatar: None,
+        placeholder: None,
+        calendar_token: None,
         created_at: chrono::Utc::now(),
     }
 }
@@ -160,6 +162,7 @@ pub async fn test_duplicate_and_not_found<R: UserRepository>(repo: R) {
         email: None,
         password_hash: None,
         avatar: None,
+        calendar_token: None,
     };
     let result = repo.update(update).await;
     eprintln!("{:?}", result);
@@ -171,3 +174,111 @@ pub async fn test_duplicate_and_not_found<R: UserRepository>(repo: R) {
     // Clean up
     repo.delete(&user.id).await.unwrap();
 }
+
+/// Test placeholders can be found by invite code and by their manager.
+pub async fn test_find_placeholders<R: UserRepository>(repo: R) {
+    let manager = repo.create(create_random_test_user()).await.unwrap();
+    let other_manager = repo.create(create_random_test_user()).await.unwrap();
+
+    let mut placeholder = create_random_test_user();
+    let invite_code = uuid::Uuid::new_v4().simple().to_string();
+    placeholder.placeholder = Some(Placeholder {
+        display_name: "Alice".to_string(),
+        manager_id: manager.id,
+        invite_code: invite_code.clone(),
+    });
+    let placeholder = repo.create(placeholder).await.unwrap();
+
+    let found = repo
+        .find_by_invite_code(&invite_code)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(found.id, placeholder.id);
+    let details = found.placeholder.unwrap();
+    assert_eq!(details.display_name, "Alice");
+    assert_eq!(details.manager_id, manager.id);
+
+    assert!(repo.find_by_invite_code("unknown").await.unwrap().is_none());
+
+    let managed = repo
+        .find_placeholders_by_manager(&manager.id)
+        .await
+        .unwrap();
+    assert_eq!(managed.len(), 1);
+    assert_eq!(managed[0].id, placeholder.id);
+    assert!(
+        repo.find_placeholders_by_manager(&other_manager.id)
+            .await
+            .unwrap()
+            .is_empty()
+    );
+    assert!(
+        repo.find_placeholders_by_manager(&placeholder.id)
+            .await
+            .unwrap()
+            .is_empty()
+    );
+
+    // Clean up
+    repo.delete(&placeholder.id).await.unwrap();
+    repo.delete(&manager.id).await.unwrap();
+    repo.delete(&other_manager.id).await.unwrap();
+}
+
+/// Test finding a user by calendar token, and revoking it.
+pub async fn test_find_by_calendar_token<R: UserRepository>(repo: R) {
+    let mut user = create_random_test_user();
+    let calendar_token = uuid::Uuid::new_v4().simple().to_string();
+    user.calendar_token = Some(calendar_token.clone());
+    let user = repo.create(user).await.unwrap();
+
+    let found = repo
+        .find_by_calendar_token(&calendar_token)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(found.id, user.id);
+    assert!(
+        repo.find_by_calendar_token("unknown")
+            .await
+            .unwrap()
+            .is_none()
+    );
+
+    // Updates without a token keep it
+    let update = sawa_core::models::user::UserUpdate {
+        id: user.id,
+        username: None,
+        email: None,
+        password_hash: None,
+        avatar: None,
+        calendar_token: None,
+    };
+    let updated = repo.update(update).await.unwrap();
+    assert_eq!(
+        updated.calendar_token.as_deref(),
+        Some(calendar_token.as_str())
+    );
+
+    // Revoke
+    let update = sawa_core::models::user::UserUpdate {
+        id: user.id,
+        username: None,
+        email: None,
+        password_hash: None,
+        avatar: None,
+        calendar_token: Some(None),
+    };
+    let updated = repo.update(update).await.unwrap();
+    assert!(updated.calendar_token.is_none());
+    assert!(
+        repo.find_by_calendar_token(&calendar_token)
+            .await
+            .unwrap()
+            .is_none()
+    );
+
+    // Clean up
+    repo.delete(&user.id).await.unwrap();
+}
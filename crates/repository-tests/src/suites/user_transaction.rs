@@ -1,7 +1,7 @@
 use sawa_core::{
     models::{
         product::ProductInstanceId,
-        transfer::{UserTransaction, UserTransactionId, UserTransactionStatus},
+        transfer::{TransferReason, UserTransaction, UserTransactionId, UserTransactionStatus},
         user::UserId,
     },
     repositories::UserTransactionRepository,
@@ -23,6 +23,8 @@ fn create_test_transaction(
         from_user_id,
         to_user_id,
         items: vec![ProductInstanceId::new()],
+        reason: TransferReason::Trade,
+        return_by: None,
         status,
         created_at: chrono::Utc::now(),
         completed_at,
@@ -0,0 +1,105 @@
+use chrono::Utc;
+use sawa_core::{
+    models::{
+        misc::{Currency, Price},
+        product::ProductVariantId,
+        user::UserId,
+        wishlist::{Wishlist, WishlistItem, WishlistItemId, WishlistPriority},
+    },
+    repositories::WishlistRepository,
+};
+use std::num::NonZeroU32;
+
+fn create_test_item(desired_quantity: u32) -> WishlistItem {
+    WishlistItem {
+        id: WishlistItemId::new(),
+        variant_id: ProductVariantId::new(),
+        priority: WishlistPriority::Normal,
+        desired_quantity: NonZeroU32::new(desired_quantity).unwrap(),
+        obtained_quantity: 0,
+        max_price: Some(Price {
+            currency: Currency::JPY,
+            amount: 1000,
+        }),
+        note: String::new(),
+        satisfied_at: None,
+        added_at: Utc::now(),
+    }
+}
+
+/// Test save and find_by_user, including replacing the items.
+pub async fn test_save_and_find_by_user<R: WishlistRepository>(repo: R) {
+    let user_id = UserId::new();
+    assert!(repo.find_by_user(&user_id).await.unwrap().is_none());
+
+    let mut wishlist = Wishlist::empty(user_id);
+    wishlist.items.push(create_test_item(1));
+    wishlist.items.push(create_test_item(2));
+    repo.save(&wishlist).await.unwrap();
+
+    let found = repo.find_by_user(&user_id).await.unwrap().unwrap();
+    assert_eq!(found.user_id, user_id);
+    assert!(!found.is_public);
+    assert!(!found.open_to_trades);
+    assert_eq!(found.items.len(), 2);
+    assert_eq!(found.items[0].id, wishlist.items[0].id);
+    assert_eq!(found.items[1].desired_quantity.get(), 2);
+    assert_eq!(found.items[1].max_price.unwrap().amount, 1000);
+
+    // Saving again replaces the items and updates the visibility
+    let mut satisfied = create_test_item(1);
+    satisfied.priority = WishlistPriority::High;
+    satisfied.obtained_quantity = 1;
+    satisfied.satisfied_at = Some(Utc::now());
+    wishlist.items = vec![satisfied.clone()];
+    wishlist.is_public = true;
+    repo.save(&wishlist).await.unwrap();
+
+    let found = repo.find_by_user(&user_id).await.unwrap().unwrap();
+    assert!(found.is_public);
+    assert_eq!(found.items.len(), 1);
+    assert_eq!(found.items[0].id, satisfied.id);
+    assert_eq!(found.items[0].priority, WishlistPriority::High);
+    assert_eq!(found.items[0].obtained_quantity, 1);
+    assert!(found.items[0].is_satisfied());
+
+    // Clean up
+    repo.delete(&user_id).await.unwrap();
+}
+
+/// Test delete removes the wishlist and its items.
+pub async fn test_delete<R: WishlistRepository>(repo: R) {
+    let user_id = UserId::new();
+    let mut wishlist = Wishlist::empty(user_id);
+    wishlist.items.push(create_test_item(1));
+    repo.save(&wishlist).await.unwrap();
+
+    repo.delete(&user_id).await.unwrap();
+    assert!(repo.find_by_user(&user_id).await.unwrap().is_none());
+}
+
+/// Test find_open_to_trades only returns wishlists of users who opted in.
+pub async fn test_find_open_to_trades<R: WishlistRepository>(repo: R) {
+    let mut open = Wishlist::empty(UserId::new());
+    open.open_to_trades = true;
+    open.items.push(create_test_item(1));
+    let closed = Wishlist::empty(UserId::new());
+    repo.save(&open).await.unwrap();
+    repo.save(&closed).await.unwrap();
+
+    let found = repo.find_open_to_trades().await.unwrap();
+    assert!(
+        found
+            .iter()
+            .any(|wishlist| wishlist.user_id == open.user_id && wishlist.items.len() == 1)
+    );
+    assert!(
+        !found
+            .iter()
+            .any(|wishlist| wishlist.user_id == closed.user_id)
+    );
+
+    // Clean up
+    repo.delete(&open.user_id).await.unwrap();
+    repo.delete(&closed.user_id).await.unwrap();
+}